@@ -91,9 +91,10 @@ pub async fn run(opt: Opt) -> Result<()> {
         Command::Prepare {
             check,
             workspace,
+            analyze,
             connect_opts,
             args,
-        } => prepare::run(check, workspace, connect_opts, args).await?,
+        } => prepare::run(check, workspace, analyze, connect_opts, args).await?,
 
         #[cfg(feature = "completions")]
         Command::Completions { shell } => completions::run(shell),
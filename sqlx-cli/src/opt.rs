@@ -38,6 +38,16 @@ pub enum Command {
         #[clap(long)]
         workspace: bool,
 
+        /// Analyze the query plan of every prepared query and exit with 1 if any of them
+        /// performs a full table scan.
+        ///
+        /// Full scans of tables PostgreSQL estimates to have fewer than 1000 rows are only
+        /// warned about.
+        ///
+        /// Requires a database connection. Currently supported for PostgreSQL and SQLite.
+        #[clap(long)]
+        analyze: bool,
+
         /// Arguments to be passed to `cargo rustc ...`.
         #[clap(last = true)]
         args: Vec<String>,
@@ -14,6 +14,7 @@ use crate::opt::ConnectOpts;
 
 pub struct PrepareCtx {
     pub workspace: bool,
+    pub analyze: bool,
     pub cargo: OsString,
     pub cargo_args: Vec<String>,
     pub metadata: Metadata,
@@ -34,6 +35,7 @@ impl PrepareCtx {
 pub async fn run(
     check: bool,
     workspace: bool,
+    analyze: bool,
     connect_opts: ConnectOpts,
     cargo_args: Vec<String>,
 ) -> anyhow::Result<()> {
@@ -49,6 +51,7 @@ hint: This command only works in the manifest directory of a Cargo package or wo
     let metadata: Metadata = Metadata::from_current_directory(&cargo)?;
     let ctx = PrepareCtx {
         workspace,
+        analyze,
         cargo,
         cargo_args,
         metadata,
@@ -71,11 +74,15 @@ async fn prepare(ctx: &PrepareCtx) -> anyhow::Result<()> {
     run_prepare_step(ctx, &prepare_dir)?;
 
     // Warn if no queries were generated. Glob since the directory may contain unrelated files.
-    if glob_query_files(prepare_dir)?.is_empty() {
+    if glob_query_files(&prepare_dir)?.is_empty() {
         println!("{} no queries found", style("warning:").yellow());
         return Ok(());
     }
 
+    if ctx.analyze {
+        analyze_queries(&ctx.connect_opts, &prepare_dir).await?;
+    }
+
     if ctx.workspace {
         println!(
             "query data written to .sqlx in the workspace root; \
@@ -141,9 +148,245 @@ async fn prepare_check(ctx: &PrepareCtx) -> anyhow::Result<()> {
         }
     }
 
+    if ctx.analyze {
+        analyze_queries(&ctx.connect_opts, &cache_dir).await?;
+    }
+
+    Ok(())
+}
+
+/// Explain every query in `dir` against the database and fail if any performs a full table scan.
+///
+/// Full scans of tables the database estimates to be small are only warned about, as planners
+/// rightly prefer them there.
+async fn analyze_queries(connect_opts: &ConnectOpts, dir: &Path) -> anyhow::Result<()> {
+    let database_url = connect_opts.required_db_url()?;
+    let mut analyzer = QueryAnalyzer::default();
+    let mut num_full_scans = 0;
+
+    for path in glob_query_files(dir)? {
+        let json = load_json_file(&path)?;
+
+        let (Some(db_name), Some(query)) = (json["db_name"].as_str(), json["query"].as_str())
+        else {
+            bail!("invalid query data file: {}", path.display());
+        };
+
+        let scans = match analyzer
+            .full_scans(database_url, db_name, query, &json["describe"])
+            .await?
+        {
+            Some(scans) => scans,
+            None => {
+                println!(
+                    "{} cannot analyze queries for {}, skipping {}",
+                    style("warning:").yellow(),
+                    db_name,
+                    path.display()
+                );
+                continue;
+            }
+        };
+
+        let (small, large): (Vec<_>, Vec<_>) = scans.into_iter().partition(|scan| scan.small);
+
+        if !large.is_empty() {
+            num_full_scans += 1;
+            println!(
+                "{} query performs a full scan of {}:\n{}\n",
+                style("error:").red(),
+                FullScan::tables(&large),
+                query.trim()
+            );
+        } else if !small.is_empty() {
+            println!(
+                "{} query performs a full scan of the small table(s) {}:\n{}\n",
+                style("warning:").yellow(),
+                FullScan::tables(&small),
+                query.trim()
+            );
+        }
+    }
+
+    if num_full_scans > 0 {
+        bail!("prepare analyze failed: {num_full_scans} queries perform full table scans");
+    }
+
     Ok(())
 }
 
+/// A table that a query scans without an index.
+struct FullScan {
+    table: String,
+    /// Whether the database estimates the table to have fewer than `SMALL_TABLE_ROWS` rows.
+    small: bool,
+}
+
+impl FullScan {
+    fn tables(scans: &[FullScan]) -> String {
+        let tables: Vec<&str> = scans.iter().map(|scan| &*scan.table).collect();
+
+        tables.join(", ")
+    }
+}
+
+/// Tables with fewer rows than this are cheap to scan.
+#[cfg(feature = "postgres")]
+const SMALL_TABLE_ROWS: f64 = 1000.0;
+
+/// Lazily opened connections to explain queries with.
+#[derive(Default)]
+struct QueryAnalyzer {
+    #[cfg(feature = "postgres")]
+    postgres: Option<sqlx::PgConnection>,
+    #[cfg(feature = "sqlite")]
+    sqlite: Option<sqlx::SqliteConnection>,
+}
+
+impl QueryAnalyzer {
+    /// Returns the tables `query` fully scans, or `None` if `db_name` is not supported.
+    #[cfg(not(any(feature = "postgres", feature = "sqlite")))]
+    async fn full_scans(
+        &mut self,
+        _database_url: &str,
+        _db_name: &str,
+        _query: &str,
+        _describe: &serde_json::Value,
+    ) -> anyhow::Result<Option<Vec<FullScan>>> {
+        Ok(None)
+    }
+
+    /// Returns the tables `query` fully scans, or `None` if `db_name` is not supported.
+    #[cfg(any(feature = "postgres", feature = "sqlite"))]
+    async fn full_scans(
+        &mut self,
+        database_url: &str,
+        db_name: &str,
+        query: &str,
+        describe: &serde_json::Value,
+    ) -> anyhow::Result<Option<Vec<FullScan>>> {
+        match db_name {
+            #[cfg(feature = "postgres")]
+            "PostgreSQL" => {
+                use sqlx::Executor;
+
+                let conn = match &mut self.postgres {
+                    Some(conn) => conn,
+                    None => {
+                        let mut conn = sqlx::PgConnection::connect(database_url).await?;
+                        // plan queries without knowing their parameters
+                        conn.execute("SET plan_cache_mode = force_generic_plan")
+                            .await?;
+                        self.postgres.insert(conn)
+                    }
+                };
+
+                // bind parameters are not allowed in `EXPLAIN` directly, but they are in
+                // `EXPLAIN EXECUTE` of a prepared statement
+                let num_params = match &describe["parameters"] {
+                    serde_json::Value::Object(params) => params
+                        .get("Left")
+                        .and_then(|types| types.as_array())
+                        .map_or(0, Vec::len),
+                    _ => 0,
+                };
+
+                conn.execute(&*format!("PREPARE sqlx_analyze AS {query}"))
+                    .await?;
+
+                let execute = if num_params == 0 {
+                    "EXECUTE sqlx_analyze".to_owned()
+                } else {
+                    format!(
+                        "EXECUTE sqlx_analyze({})",
+                        vec!["NULL"; num_params].join(", ")
+                    )
+                };
+
+                let plan = conn.explain(&execute).await;
+                conn.execute("DEALLOCATE sqlx_analyze").await?;
+
+                let tables: BTreeSet<String> = plan?
+                    .iter()
+                    .filter(|node| node.node_type() == "Seq Scan")
+                    .filter_map(|node| node.relation_name().map(str::to_owned))
+                    .collect();
+
+                let mut scans = Vec::with_capacity(tables.len());
+
+                for table in tables {
+                    // `reltuples` is -1 for tables that were never analyzed
+                    let rows: Option<f64> = sqlx::query_scalar(
+                        "SELECT reltuples::float8 FROM pg_class \
+                         WHERE oid = to_regclass(quote_ident($1))",
+                    )
+                    .bind(&table)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+                    let small = matches!(rows, Some(rows) if rows < SMALL_TABLE_ROWS);
+
+                    scans.push(FullScan { table, small });
+                }
+
+                Ok(Some(scans))
+            }
+
+            #[cfg(feature = "sqlite")]
+            "SQLite" => {
+                let conn = match &mut self.sqlite {
+                    Some(conn) => conn,
+                    None => self
+                        .sqlite
+                        .insert(sqlx::SqliteConnection::connect(database_url).await?),
+                };
+
+                // SQLite has no row estimates to tell small tables apart
+                Ok(Some(
+                    sqlite_full_scans(conn, query)
+                        .await?
+                        .into_iter()
+                        .map(|table| FullScan {
+                            table,
+                            small: false,
+                        })
+                        .collect(),
+                ))
+            }
+
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Returns the tables `query` scans without an index.
+///
+/// Scans of CTEs, subqueries and `SCAN CONSTANT ROW` are not full table scans, so only scans of
+/// tables in the schema are reported. Tables that are scanned under an alias are reported by the
+/// plan under the alias and are missed.
+#[cfg(feature = "sqlite")]
+async fn sqlite_full_scans(
+    conn: &mut sqlx::SqliteConnection,
+    query: &str,
+) -> anyhow::Result<Vec<String>> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' \
+         UNION SELECT name FROM sqlite_temp_master WHERE type = 'table'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(conn
+        .explain(query)
+        .await?
+        .iter()
+        .filter(|node| node.detail().starts_with("SCAN ") && node.index().is_none())
+        .filter_map(|node| node.table())
+        .filter(|table| tables.iter().any(|name| name.eq_ignore_ascii_case(table)))
+        .map(str::to_owned)
+        .collect())
+}
+
 fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
     // Create and/or clean the directory.
     fs::create_dir_all(cache_dir).context(format!(
@@ -380,4 +623,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn sqlite_full_scans_only_reports_tables() -> anyhow::Result<()> {
+        use sqlx::{Connection, Executor};
+
+        let mut conn = sqlx::SqliteConnection::connect("sqlite::memory:").await?;
+        conn.execute(
+            "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, kind TEXT); \
+             CREATE INDEX items_name ON items (name);",
+        )
+        .await?;
+
+        assert_eq!(
+            sqlite_full_scans(&mut conn, "SELECT * FROM items").await?,
+            ["items"]
+        );
+        assert_eq!(
+            sqlite_full_scans(&mut conn, "SELECT * FROM items WHERE kind = ?").await?,
+            ["items"]
+        );
+        assert!(
+            sqlite_full_scans(&mut conn, "SELECT * FROM items WHERE name = ?")
+                .await?
+                .is_empty()
+        );
+        assert!(
+            sqlite_full_scans(&mut conn, "SELECT * FROM items WHERE id = ?")
+                .await?
+                .is_empty()
+        );
+
+        // `SCAN CONSTANT ROW`
+        assert!(sqlite_full_scans(&mut conn, "SELECT 1").await?.is_empty());
+
+        // a materialized CTE and a subquery, both filled through the index
+        assert!(sqlite_full_scans(
+            &mut conn,
+            "WITH named AS MATERIALIZED (SELECT * FROM items WHERE name = ?) \
+             SELECT * FROM named ORDER BY kind"
+        )
+        .await?
+        .is_empty());
+        assert!(sqlite_full_scans(
+            &mut conn,
+            "SELECT * FROM (SELECT DISTINCT kind FROM items WHERE name = ? LIMIT 5) ORDER BY kind"
+        )
+        .await?
+        .is_empty());
+
+        // the table scanned inside a CTE is still reported
+        assert_eq!(
+            sqlite_full_scans(
+                &mut conn,
+                "WITH all_items AS MATERIALIZED (SELECT * FROM items) SELECT * FROM all_items"
+            )
+            .await?,
+            ["items"]
+        );
+
+        Ok(())
+    }
 }
@@ -1,12 +1,17 @@
 use crate::any::value::AnyValueKind;
 use crate::any::Any;
-use crate::arguments::Arguments;
+use crate::arguments::{write_redacted, write_text, Arguments};
 use crate::encode::Encode;
 use crate::types::Type;
+use std::fmt::{self, Write};
 
 pub struct AnyArguments<'q> {
     #[doc(hidden)]
     pub values: AnyArgumentBuffer<'q>,
+
+    // indexes of the values to redact when logging
+    #[doc(hidden)]
+    pub sensitive: Vec<usize>,
 }
 
 impl<'q> Arguments<'q> for AnyArguments<'q> {
@@ -22,6 +27,33 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     {
         let _ = value.encode(&mut self.values);
     }
+
+    fn add_sensitive<T>(&mut self, value: T)
+    where
+        T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(value);
+        self.sensitive.push(self.values.0.len() - 1);
+    }
+
+    fn format_redacted<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write_redacted(
+            writer,
+            self.values.0.len(),
+            &self.sensitive,
+            |writer, i| match &self.values.0[i] {
+                AnyValueKind::Null => writer.write_str("NULL"),
+                AnyValueKind::Bool(value) => write!(writer, "{value}"),
+                AnyValueKind::SmallInt(value) => write!(writer, "{value}"),
+                AnyValueKind::Integer(value) => write!(writer, "{value}"),
+                AnyValueKind::BigInt(value) => write!(writer, "{value}"),
+                AnyValueKind::Real(value) => write!(writer, "{value}"),
+                AnyValueKind::Double(value) => write!(writer, "{value}"),
+                AnyValueKind::Text(text) => write_text(writer, text),
+                AnyValueKind::Blob(blob) => write!(writer, "<{} bytes>", blob.len()),
+            },
+        )
+    }
 }
 
 pub struct AnyArgumentBuffer<'q>(#[doc(hidden)] pub Vec<AnyValueKind<'q>>);
@@ -30,6 +62,7 @@ impl<'q> Default for AnyArguments<'q> {
     fn default() -> Self {
         AnyArguments {
             values: AnyArgumentBuffer(vec![]),
+            sensitive: Vec::new(),
         }
     }
 }
@@ -51,17 +84,29 @@ impl<'q> AnyArguments<'q> {
     {
         let mut out = A::default();
 
-        for arg in &self.values.0 {
+        for (i, arg) in self.values.0.iter().enumerate() {
+            let sensitive = self.sensitive.contains(&i);
+
+            macro_rules! add {
+                ($value:expr) => {
+                    if sensitive {
+                        out.add_sensitive($value)
+                    } else {
+                        out.add($value)
+                    }
+                };
+            }
+
             match arg {
-                AnyValueKind::Null => out.add(Option::<i32>::None),
-                AnyValueKind::Bool(b) => out.add(b),
-                AnyValueKind::SmallInt(i) => out.add(i),
-                AnyValueKind::Integer(i) => out.add(i),
-                AnyValueKind::BigInt(i) => out.add(i),
-                AnyValueKind::Real(r) => out.add(r),
-                AnyValueKind::Double(d) => out.add(d),
-                AnyValueKind::Text(t) => out.add(&**t),
-                AnyValueKind::Blob(b) => out.add(&**b),
+                AnyValueKind::Null => add!(Option::<i32>::None),
+                AnyValueKind::Bool(b) => add!(b),
+                AnyValueKind::SmallInt(i) => add!(i),
+                AnyValueKind::Integer(i) => add!(i),
+                AnyValueKind::BigInt(i) => add!(i),
+                AnyValueKind::Real(r) => add!(r),
+                AnyValueKind::Double(d) => add!(d),
+                AnyValueKind::Text(t) => add!(&**t),
+                AnyValueKind::Blob(b) => add!(&**b),
             }
         }
 
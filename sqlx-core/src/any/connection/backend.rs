@@ -1,4 +1,5 @@
 use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::database::{Database, DatabaseCapabilities};
 use crate::describe::Describe;
 use either::Either;
 use futures_core::future::BoxFuture;
@@ -9,6 +10,13 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
     fn name(&self) -> &str;
 
+    /// The SQL features supported by the backend, see [`Database::capabilities()`].
+    ///
+    /// [`Database::capabilities()`]: crate::database::Database::capabilities
+    fn capabilities(&self) -> DatabaseCapabilities {
+        <Any as Database>::capabilities()
+    }
+
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
@@ -47,6 +55,48 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Forward to [`Connection::prepare_cached()`].
+    ///
+    /// [`Connection::prepare_cached()`]: method@crate::connection::Connection::prepare_cached
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        let _ = statements;
+
+        Box::pin(async move { Ok(()) })
+    }
+
+    /// Forward to [`Connection::clear_table()`].
+    ///
+    /// [`Connection::clear_table()`]: method@crate::connection::Connection::clear_table
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        let _ = (table, restart_identity);
+
+        Box::pin(async move {
+            Err(crate::Error::Configuration(
+                "`clear_table()` is not supported by this driver".into(),
+            ))
+        })
+    }
+
+    /// Forward to [`Connection::reset()`].
+    ///
+    /// [`Connection::reset()`]: method@crate::connection::Connection::reset
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, crate::Result<()>> {
+        let _ = discard_session;
+
+        Box::pin(async move {
+            Err(crate::Error::Configuration(
+                "`reset()` is not supported by this driver".into(),
+            ))
+        })
+    }
+
     /// Forward to [`Connection::shrink_buffers()`].
     ///
     /// [`Connection::shrink_buffers()`]: method@crate::connection::Connection::shrink_buffers
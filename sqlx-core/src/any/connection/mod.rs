@@ -4,7 +4,7 @@ use crate::any::{Any, AnyConnectOptions};
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 
-use crate::database::Database;
+use crate::database::{Database, DatabaseCapabilities};
 pub use backend::AnyConnectionBackend;
 
 use crate::transaction::Transaction;
@@ -32,6 +32,13 @@ impl AnyConnection {
         self.backend.name()
     }
 
+    /// Returns the SQL features supported by the database backend in use.
+    ///
+    /// [`Database::capabilities()`] of [`Any`] only returns those supported by all of them.
+    pub fn capabilities(&self) -> DatabaseCapabilities {
+        self.backend.capabilities()
+    }
+
     pub(crate) fn connect(options: &AnyConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
         Box::pin(async {
             let driver = crate::any::driver::from_url(&options.database_url)?;
@@ -98,6 +105,25 @@ impl Connection for AnyConnection {
         self.backend.clear_cached_statements()
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        self.backend.prepare_cached(statements)
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        self.backend.clear_table(table, restart_identity)
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, crate::Result<()>> {
+        self.backend.reset(discard_session)
+    }
+
     fn shrink_buffers(&mut self) {
         self.backend.shrink_buffers()
     }
@@ -2,7 +2,9 @@ use crate::any::{
     AnyArgumentBuffer, AnyArguments, AnyColumn, AnyConnection, AnyQueryResult, AnyRow,
     AnyStatement, AnyTransactionManager, AnyTypeInfo, AnyValue, AnyValueRef,
 };
-use crate::database::{Database, HasArguments, HasStatement, HasStatementCache, HasValueRef};
+use crate::database::{
+    Database, DatabaseCapabilities, HasArguments, HasStatement, HasStatementCache, HasValueRef,
+};
 
 /// Opaque database driver. Capable of being used in place of any SQLx database driver. The actual
 /// driver used will be selected at runtime, from the connection url.
@@ -26,6 +28,17 @@ impl Database for Any {
     const NAME: &'static str = "Any";
 
     const URL_SCHEMES: &'static [&'static str] = &[];
+
+    // the lowest limit of the supported drivers, i.e. SQLite
+    const MAX_BIND_PARAMETERS: usize = 32766;
+
+    // the capabilities of the connection in use are returned by
+    // `AnyConnection::capabilities()`; these are supported by all drivers
+    fn capabilities() -> DatabaseCapabilities {
+        DatabaseCapabilities::new()
+            .multiple_statements(true)
+            .max_bind_parameters(Self::MAX_BIND_PARAMETERS)
+    }
 }
 
 impl<'r> HasValueRef<'r> for Any {
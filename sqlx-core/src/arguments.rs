@@ -18,9 +18,66 @@ pub trait Arguments<'q>: Send + Sized + Default {
     where
         T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>;
 
+    /// Add the value to the end of the arguments, to be shown as `<redacted>` by
+    /// [`format_redacted()`](Self::format_redacted), e.g. for passwords or tokens.
+    fn add_sensitive<T>(&mut self, value: T)
+    where
+        T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(value)
+    }
+
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         writer.write_str("?")
     }
+
+    /// Render the arguments for logging, e.g. `[42, "alice", <redacted>]`.
+    ///
+    /// Values added with [`add_sensitive()`](Self::add_sensitive) are shown as `<redacted>`, long
+    /// text is shortened and values of types the driver cannot render are shown by type name.
+    /// Drivers that do not support this write nothing.
+    fn format_redacted<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        let _ = writer;
+
+        Ok(())
+    }
+}
+
+/// Write `len` arguments as a list for [`Arguments::format_redacted()`], using `write_value` for
+/// every argument that is not at one of the `sensitive` indexes.
+#[doc(hidden)]
+pub fn write_redacted<W: Write>(
+    writer: &mut W,
+    len: usize,
+    sensitive: &[usize],
+    mut write_value: impl FnMut(&mut W, usize) -> fmt::Result,
+) -> fmt::Result {
+    writer.write_char('[')?;
+
+    for i in 0..len {
+        if i > 0 {
+            writer.write_str(", ")?;
+        }
+
+        if sensitive.contains(&i) {
+            writer.write_str("<redacted>")?;
+        } else {
+            write_value(writer, i)?;
+        }
+    }
+
+    writer.write_char(']')
+}
+
+/// Write a text argument quoted for [`Arguments::format_redacted()`], shortened if it is long.
+#[doc(hidden)]
+pub fn write_text<W: Write>(writer: &mut W, text: &str) -> fmt::Result {
+    const MAX_CHARS: usize = 64;
+
+    match text.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => write!(writer, "{:?}…", &text[..end]),
+        None => write!(writer, "{text:?}"),
+    }
 }
 
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
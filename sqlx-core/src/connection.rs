@@ -4,9 +4,12 @@ use crate::error::Error;
 use crate::transaction::Transaction;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
+use std::cmp;
 use std::fmt::Debug;
+use std::future::Future;
+use std::io;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use url::Url;
 
 /// Represents a single database connection.
@@ -112,6 +115,80 @@ pub trait Connection: Send {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Prepare `statements` and add them to the statement cache, so that executing them does not
+    /// have to wait for them to be prepared first.
+    ///
+    /// Statements that are already cached are skipped. Postgres prepares all statements in a
+    /// single round trip. See also
+    /// [`PoolOptions::prepare_statements()`][crate::pool::PoolOptions::prepare_statements].
+    ///
+    /// Statements are prepared without parameter types, as by
+    /// [`Executor::prepare()`][crate::executor::Executor::prepare], so Postgres infers them and
+    /// bound values must match the inferred types.
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, Result<(), Error>>
+    where
+        Self::Database: HasStatementCache,
+    {
+        let _ = statements;
+
+        Box::pin(async move { Ok(()) })
+    }
+
+    /// Delete all rows of `table` as fast as the database allows, e.g. to clean up after tests.
+    ///
+    /// `table` is inserted into the SQL as given. If `restart_identity` is set, generated ids
+    /// start over:
+    ///
+    /// * Postgres runs `TRUNCATE TABLE`, with `RESTART IDENTITY` to reset sequences owned by
+    ///   the table's columns. It fails if other tables have foreign keys referencing the table,
+    ///   unless they are cleared in the same statement, e.g. with `table` set to
+    ///   `"orders, order_items"`.
+    /// * MySQL runs `TRUNCATE TABLE`, which always resets `AUTO_INCREMENT`, and `DELETE FROM`
+    ///   otherwise. `TRUNCATE` fails for tables referenced by foreign keys of other tables.
+    /// * SQLite runs `DELETE FROM`, which it optimizes to drop all pages at once, and removes the
+    ///   table from `sqlite_sequence` to reset `AUTOINCREMENT` columns.
+    ///
+    /// See also [`ops::clear_table()`][crate::ops::clear_table].
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        let _ = (table, restart_identity);
+
+        Box::pin(async move {
+            Err(Error::Configuration(
+                "`clear_table()` is not supported by this driver".into(),
+            ))
+        })
+    }
+
+    /// Roll back any transaction left open on the connection, including ones begun with raw SQL,
+    /// and, if `discard_session` is set, discard the state of the session:
+    ///
+    /// * Postgres runs the equivalent of `DISCARD ALL`, except that the statements cached by the
+    ///   connection stay prepared.
+    /// * MySQL sends `COM_RESET_CONNECTION`, clears the statement cache and applies the session
+    ///   settings of its options again. Servers without `COM_RESET_CONNECTION`, such as MySQL
+    ///   before 5.7.3 and MariaDB before 10.2.4, keep user variables and temporary tables; the
+    ///   transaction is rolled back and the cached statements are closed instead.
+    /// * SQLite drops all temporary tables and views. `PRAGMA`s are settings of the connection,
+    ///   set by its options, and are kept.
+    ///
+    /// See also [`PoolOptions::reset_on_return()`][crate::pool::PoolOptions::reset_on_return].
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, Result<(), Error>> {
+        let _ = discard_session;
+
+        Box::pin(async move {
+            Err(Error::Configuration(
+                "`reset()` is not supported by this driver".into(),
+            ))
+        })
+    }
+
     /// Restore any buffers in the connection to their default capacity, if possible.
     ///
     /// Sending a large query or receiving a resultset with many columns can cause the connection
@@ -161,6 +238,7 @@ pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub log_arguments: bool,
 }
 
 impl Default for LogSettings {
@@ -169,6 +247,7 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            log_arguments: false,
         }
     }
 }
@@ -181,6 +260,9 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn log_arguments(&mut self, enabled: bool) {
+        self.log_arguments = enabled;
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -201,9 +283,208 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Include the bound arguments of executed statements in their log, rendered with
+    /// [`Arguments::format_redacted()`](crate::arguments::Arguments::format_redacted).
+    ///
+    /// Arguments bound with [`bind_sensitive()`](crate::query::Query::bind_sensitive) are shown
+    /// as `<redacted>`, all other values are logged as they are. Disabled by default.
+    ///
+    /// The default implementation ignores this, for connect options that cannot log arguments.
+    fn log_arguments(self, _enabled: bool) -> Self {
+        self
+    }
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
             .log_slow_statements(LevelFilter::Off, Duration::default())
     }
 }
+
+/// How long [`wait_for_database()`] sleeps between connection attempts.
+///
+/// The delay starts at `initial` and is doubled after every failed attempt, up to `max`.
+#[derive(Clone, Debug)]
+pub struct ConnectBackoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl ConnectBackoff {
+    /// Sleep for `initial` after the first failed attempt, doubling the delay every time
+    /// up to `max`.
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max: cmp::max(initial, max),
+        }
+    }
+
+    /// Sleep for `interval` between every attempt.
+    pub fn constant(interval: Duration) -> Self {
+        Self::exponential(interval, interval)
+    }
+}
+
+impl Default for ConnectBackoff {
+    /// Start at 100 milliseconds, capped at 5 seconds.
+    fn default() -> Self {
+        Self::exponential(Duration::from_millis(100), Duration::from_secs(5))
+    }
+}
+
+/// Wait until a connection can be established to the database at `url`.
+///
+/// This is intended for startup ordering, e.g. a service starting in a container next to
+/// its database. Connection attempts are retried with the given `backoff` while the errors
+/// look like the server is still coming up:
+///
+/// * the connection was refused, reset or aborted;
+/// * the server reported that it is starting up, shutting down or in recovery
+///   (currently only reported by the Postgres driver).
+///
+/// Any other error, like an invalid URL or bad credentials, is returned immediately.
+/// If `timeout` elapses first, the last error encountered is returned,
+/// or an [`Error::Io`] of kind [`TimedOut`][std::io::ErrorKind::TimedOut] if the connection
+/// attempt itself did not finish in time.
+///
+/// The connection that was successfully established is closed again before returning.
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use std::time::Duration;
+/// use sqlx::{ConnectBackoff, Postgres};
+///
+/// sqlx::wait_for_database::<Postgres>(
+///     "postgres://postgres:password@db/postgres",
+///     Duration::from_secs(30),
+///     ConnectBackoff::default(),
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn wait_for_database<DB: Database>(
+    url: &str,
+    timeout: Duration,
+    backoff: ConnectBackoff,
+) -> Result<(), Error> {
+    let options = <DB::Connection as Connection>::Options::from_str(url)?;
+
+    retry_connect(timeout, backoff, || options.connect())
+        .await?
+        .close()
+        .await
+}
+
+// calls `connect` until it succeeds, fails with a non-transient error or `timeout` elapses
+async fn retry_connect<T, F, Fut>(
+    timeout: Duration,
+    backoff: ConnectBackoff,
+    mut connect: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let deadline = Instant::now() + timeout;
+    let mut delay = backoff.initial;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        let error = match crate::rt::timeout(remaining, connect()).await {
+            Ok(Ok(conn)) => return Ok(conn),
+            Ok(Err(e)) if is_transient_connect_error(&e) => e,
+            Ok(Err(e)) => return Err(e),
+            Err(_) => return Err(io::Error::from(io::ErrorKind::TimedOut).into()),
+        };
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            return Err(error);
+        }
+
+        crate::rt::sleep(cmp::min(delay, remaining)).await;
+        delay = cmp::min(delay.saturating_mul(2), backoff.max);
+    }
+}
+
+/// Blocking version of [`wait_for_database()`].
+///
+/// This starts a temporary runtime, so it fails with [`Error::Configuration`] if called from
+/// within a Tokio runtime.
+pub fn wait_for_database_blocking<DB: Database>(
+    url: &str,
+    timeout: Duration,
+    backoff: ConnectBackoff,
+) -> Result<(), Error> {
+    crate::rt::block_on(wait_for_database::<DB>(url, timeout, backoff))?
+}
+
+fn is_transient_connect_error(error: &Error) -> bool {
+    match error {
+        Error::Io(e) => matches!(
+            e.kind(),
+            io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+        ),
+        Error::Database(e) => e.is_transient_in_connect_phase(),
+        _ => false,
+    }
+}
+
+#[test]
+#[cfg(feature = "_rt-tokio")]
+fn test_retry_connect() {
+    let refused = || Error::from(io::Error::from(io::ErrorKind::ConnectionRefused));
+    let backoff = ConnectBackoff::constant(Duration::from_millis(1));
+
+    // transient errors are retried
+    let mut attempts = 0;
+    let res = crate::rt::block_on(retry_connect(
+        Duration::from_secs(5),
+        backoff.clone(),
+        || {
+            attempts += 1;
+            let attempt = attempts;
+            async move { (attempt >= 3).then_some(attempt).ok_or_else(refused) }
+        },
+    ));
+    assert_eq!(res.unwrap().unwrap(), 3);
+
+    // others are not
+    let mut attempts = 0;
+    let res = crate::rt::block_on(retry_connect(
+        Duration::from_secs(5),
+        backoff.clone(),
+        || {
+            attempts += 1;
+            async { Err::<(), _>(Error::PoolClosed) }
+        },
+    ));
+    assert!(matches!(res.unwrap(), Err(Error::PoolClosed)));
+    assert_eq!(attempts, 1);
+
+    // the last error is returned once the timeout elapses
+    let res = crate::rt::block_on(retry_connect(
+        Duration::from_millis(20),
+        backoff,
+        || async { Err::<(), _>(refused()) },
+    ));
+    assert!(matches!(
+        res.unwrap(),
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::ConnectionRefused
+    ));
+
+    // a runtime cannot be blocked from within itself
+    crate::rt::block_on(async {
+        assert!(matches!(
+            crate::rt::block_on(async {}),
+            Err(Error::Configuration(_))
+        ));
+    })
+    .unwrap();
+}
@@ -105,6 +105,180 @@ pub trait Database:
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// The maximum number of bind parameters in a single statement.
+    ///
+    /// Used to chunk bulk statements, see
+    /// [`execute_values_chunked()`][crate::query_builder::execute_values_chunked].
+    const MAX_BIND_PARAMETERS: usize = u16::MAX as usize;
+
+    /// Render a cast of the SQL expression `expr` to `ty`.
+    ///
+    /// Used by [`QueryBuilder::push_cast()`][crate::query_builder::QueryBuilder::push_cast]. The
+    /// default is `CAST(<expr> AS <name>)` with the [name][crate::type_info::TypeInfo::name] of
+    /// `ty`.
+    fn cast_sql(expr: &str, ty: &Self::TypeInfo) -> String {
+        format!("CAST({expr} AS {})", ty.name())
+    }
+
+    /// The SQL features supported by this database, for generic code to branch on.
+    ///
+    /// The default supports savepoints but neither `RETURNING` nor multiple statements, with `?`
+    /// placeholders and [`MAX_BIND_PARAMETERS`](Self::MAX_BIND_PARAMETERS).
+    fn capabilities() -> DatabaseCapabilities {
+        DatabaseCapabilities::new().max_bind_parameters(Self::MAX_BIND_PARAMETERS)
+    }
+
+    /// Render the clause of an `INSERT` statement into `columns` that updates `update_columns`
+    /// of rows conflicting on `conflict_target`.
+    ///
+    /// Used by [`QueryBuilder::push_upsert()`][crate::query_builder::QueryBuilder::push_upsert].
+    /// The default is `ON CONFLICT (<conflict_target>) DO UPDATE SET <column> = excluded.<column>`,
+    /// or `DO NOTHING` if there are no `update_columns` or no `conflict_target`, which
+    /// `DO UPDATE` requires.
+    fn upsert_sql(_columns: &[&str], conflict_target: &[&str], update_columns: &[&str]) -> String {
+        let mut sql = String::from("ON CONFLICT ");
+
+        if !conflict_target.is_empty() {
+            sql.push('(');
+            sql.push_str(&conflict_target.join(", "));
+            sql.push_str(") ");
+        }
+
+        if update_columns.is_empty() || conflict_target.is_empty() {
+            sql.push_str("DO NOTHING");
+        } else {
+            sql.push_str("DO UPDATE SET ");
+
+            for (i, column) in update_columns.iter().enumerate() {
+                if i > 0 {
+                    sql.push_str(", ");
+                }
+
+                sql.push_str(&format!("{column} = excluded.{column}"));
+            }
+        }
+
+        sql
+    }
+}
+
+/// The SQL features supported by a database, see [`Database::capabilities()`].
+///
+/// ```rust,ignore
+/// fn placeholder<DB: Database>(n: usize) -> String {
+///     match DB::capabilities().get_placeholder_style() {
+///         PlaceholderStyle::DollarNumbered => format!("${n}"),
+///         _ => "?".into(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseCapabilities {
+    returning: bool,
+    savepoints: bool,
+    multiple_statements: bool,
+    multiple_statements_with_arguments: bool,
+    placeholder_style: PlaceholderStyle,
+    max_bind_parameters: usize,
+}
+
+/// The syntax of bind parameters in SQL, see [`DatabaseCapabilities::get_placeholder_style()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlaceholderStyle {
+    /// `?`, bound in the order of appearance.
+    QuestionMark,
+
+    /// `$1`, `$2`, etc.
+    DollarNumbered,
+}
+
+impl DatabaseCapabilities {
+    /// Supports savepoints but neither `RETURNING` nor multiple statements, with `?`
+    /// placeholders and up to 65535 bind parameters.
+    pub const fn new() -> Self {
+        Self {
+            returning: false,
+            savepoints: true,
+            multiple_statements: false,
+            multiple_statements_with_arguments: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+            max_bind_parameters: u16::MAX as usize,
+        }
+    }
+
+    /// Sets whether `INSERT`, `UPDATE` and `DELETE` statements support a `RETURNING` clause.
+    pub const fn returning(mut self, supported: bool) -> Self {
+        self.returning = supported;
+        self
+    }
+
+    /// Sets whether transactions can be nested with savepoints.
+    pub const fn savepoints(mut self, supported: bool) -> Self {
+        self.savepoints = supported;
+        self
+    }
+
+    /// Sets whether a query without arguments may contain multiple statements separated by `;`.
+    pub const fn multiple_statements(mut self, supported: bool) -> Self {
+        self.multiple_statements = supported;
+        self
+    }
+
+    /// Sets whether a query with arguments may contain multiple statements separated by `;`.
+    pub const fn multiple_statements_with_arguments(mut self, supported: bool) -> Self {
+        self.multiple_statements_with_arguments = supported;
+        self
+    }
+
+    /// Sets the syntax of bind parameters.
+    pub const fn placeholder_style(mut self, style: PlaceholderStyle) -> Self {
+        self.placeholder_style = style;
+        self
+    }
+
+    /// Sets the maximum number of bind parameters in a single statement.
+    pub const fn max_bind_parameters(mut self, max: usize) -> Self {
+        self.max_bind_parameters = max;
+        self
+    }
+
+    /// Whether `INSERT`, `UPDATE` and `DELETE` statements support a `RETURNING` clause.
+    pub const fn get_returning(&self) -> bool {
+        self.returning
+    }
+
+    /// Whether transactions can be nested with savepoints.
+    pub const fn get_savepoints(&self) -> bool {
+        self.savepoints
+    }
+
+    /// Whether a query without arguments may contain multiple statements separated by `;`.
+    pub const fn get_multiple_statements(&self) -> bool {
+        self.multiple_statements
+    }
+
+    /// Whether a query with arguments may contain multiple statements separated by `;`.
+    pub const fn get_multiple_statements_with_arguments(&self) -> bool {
+        self.multiple_statements_with_arguments
+    }
+
+    /// The syntax of bind parameters.
+    pub const fn get_placeholder_style(&self) -> PlaceholderStyle {
+        self.placeholder_style
+    }
+
+    /// The maximum number of bind parameters in a single statement.
+    pub const fn get_max_bind_parameters(&self) -> usize {
+        self.max_bind_parameters
+    }
+}
+
+impl Default for DatabaseCapabilities {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Associate [`Database`] with a [`ValueRef`](crate::value::ValueRef) of a generic lifetime.
@@ -78,6 +78,19 @@ pub enum Error {
         source: BoxDynError,
     },
 
+    /// Error occurred while binding the value of a specific parameter of a query.
+    ///
+    /// `index` is the 1-based position of the value in the arguments and `type_name` the Rust
+    /// type it was bound as.
+    #[error("error occurred while binding parameter {index} of type `{type_name}`: {source}")]
+    ArgumentBind {
+        index: usize,
+        type_name: &'static str,
+
+        #[source]
+        source: BoxDynError,
+    },
+
     /// Error occurred while decoding a value.
     #[error("error occurred while decoding: {0}")]
     Decode(#[source] BoxDynError),
@@ -100,6 +113,13 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// A query on a [`Pool`] exceeded a limit of its [`QueryGuard`].
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    /// [`QueryGuard`]: crate::pool::QueryGuard
+    #[error("query exceeded the {0} set for the pool")]
+    QueryLimitExceeded(crate::pool::QueryLimit),
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -179,6 +199,36 @@ pub enum ErrorKind {
     Other,
 }
 
+/// The details of a violated unique, primary key or foreign key constraint, see
+/// [`DatabaseError::constraint_violation()`].
+///
+/// The drivers parse these from the error reported by the database, so each field is only set if
+/// the database reports it, and may be missing if its messages are not in English.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConstraintViolation {
+    /// The name of the constraint, or of the unique index.
+    pub constraint: Option<String>,
+
+    /// The table the constraint is defined on.
+    pub table: Option<String>,
+
+    /// The columns of the violated key.
+    pub columns: Vec<String>,
+
+    /// The conflicting value of the key, as rendered by the database.
+    ///
+    /// For keys of several columns, this is the values of all columns as a single string, e.g.
+    /// `1, 2` in Postgres and `1-2` in MySQL.
+    pub value: Option<String>,
+
+    /// The table a foreign key references.
+    pub referenced_table: Option<String>,
+
+    /// The columns a foreign key references.
+    pub referenced_columns: Vec<String>,
+}
+
 /// An error that was returned from the database.
 pub trait DatabaseError: 'static + Send + Sync + StdError {
     /// The primary, human-readable error message.
@@ -220,6 +270,13 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         None
     }
 
+    /// Returns the details of the violated constraint if this is a unique or foreign key
+    /// violation, see [`is_unique_violation()`][Self::is_unique_violation] and
+    /// [`is_foreign_key_violation()`][Self::is_foreign_key_violation].
+    fn constraint_violation(&self) -> Option<ConstraintViolation> {
+        None
+    }
+
     /// Returns the kind of the error, if supported.
     ///
     /// ### Note
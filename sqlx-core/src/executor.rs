@@ -1,10 +1,11 @@
 use crate::database::{Database, HasArguments, HasStatement};
 use crate::describe::Describe;
 use crate::error::Error;
+use crate::types::NumericOverflow;
 
 use either::Either;
 use futures_core::future::BoxFuture;
-use futures_core::stream::BoxStream;
+use futures_core::stream::{BoxStream, Stream};
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
 
@@ -108,7 +109,9 @@ pub trait Executor<'c>: Send + Debug + Sized {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        self.fetch(query).try_collect().boxed()
+        let size_hint = query.size_hint();
+
+        collect_sized(self.fetch(query), size_hint).boxed()
     }
 
     /// Execute the query and returns exactly one row.
@@ -206,6 +209,51 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns the number of rows the query is expected to return, if known, so that
+    /// [`fetch_all()`](Executor::fetch_all) can allocate space for them up front.
+    ///
+    /// The actual number of rows may differ. The default is `None`.
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns how numeric values that do not fit into the requested Rust type are decoded for
+    /// this query, overriding the connection's setting.
+    ///
+    /// The default is `None`, which uses the connection's setting.
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        None
+    }
+}
+
+// collects `stream` into a `Vec` with space for `size_hint` items, for `fetch_all()`
+pub(crate) async fn collect_sized<T>(
+    stream: impl Stream<Item = Result<T, Error>> + Unpin,
+    size_hint: Option<usize>,
+) -> Result<Vec<T>, Error> {
+    let mut items = Vec::new();
+
+    extend_sized(stream, &mut items, size_hint).await?;
+
+    Ok(items)
+}
+
+// appends `stream` to `items` after reserving space for `size_hint` more, for `extend_into()`
+pub(crate) async fn extend_sized<T>(
+    mut stream: impl Stream<Item = Result<T, Error>> + Unpin,
+    items: &mut Vec<T>,
+    size_hint: Option<usize>,
+) -> Result<usize, Error> {
+    let len = items.len();
+
+    items.reserve(size_hint.unwrap_or(0));
+
+    while let Some(item) = stream.try_next().await? {
+        items.push(item);
+    }
+
+    Ok(items.len() - len)
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
@@ -253,3 +301,229 @@ impl<'q, DB: Database> Execute<'q, DB> for (&'q str, Option<<DB as HasArguments<
         true
     }
 }
+
+/// An object-safe subset of [`Executor`], so code can take `&mut dyn DynExecutor<DB>` instead of
+/// being generic over the executor.
+///
+/// This makes it possible to put query-running code behind trait objects, e.g. in repository
+/// traits that are mocked in unit tests:
+///
+/// ```rust,ignore
+/// use sqlx::{DynExecutor, Postgres};
+///
+/// async fn count_users(executor: &mut dyn DynExecutor<Postgres>) -> sqlx::Result<i64> {
+///     // `&mut dyn DynExecutor` implements `Executor`, so all the usual APIs work
+///     sqlx::query_scalar("SELECT count(*) FROM users")
+///         .fetch_one(executor)
+///         .await
+/// }
+///
+/// let mut conn = pool.acquire().await?;
+/// count_users(&mut *conn).await?;
+///
+/// let mut tx = pool.begin().await?;
+/// count_users(&mut *tx).await?;
+///
+/// count_users(&mut pool.clone()).await?;
+/// ```
+///
+/// Implemented for every type whose mutable reference is an [`Executor`], i.e. all connection
+/// types, and for [`Pool`](crate::pool::Pool).
+pub trait DynExecutor<DB: Database>: Send + Debug {
+    /// See [`Executor::fetch_many()`].
+    fn dyn_fetch_many<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>;
+
+    /// See [`Executor::fetch_optional()`].
+    fn dyn_fetch_optional<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>;
+
+    /// See [`Executor::prepare_with()`].
+    fn dyn_prepare_with<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>>;
+
+    /// See [`Executor::describe()`].
+    #[doc(hidden)]
+    fn dyn_describe<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<DB>, Error>>;
+}
+
+impl<DB, T> DynExecutor<DB> for T
+where
+    DB: Database,
+    T: Send + Debug,
+    for<'c> &'c mut T: Executor<'c, Database = DB>,
+{
+    fn dyn_fetch_many<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>> {
+        self.fetch_many(query)
+    }
+
+    fn dyn_fetch_optional<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>> {
+        self.fetch_optional(query)
+    }
+
+    fn dyn_prepare_with<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>> {
+        self.prepare_with(sql, parameters)
+    }
+
+    fn dyn_describe<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<DB>, Error>> {
+        self.describe(sql)
+    }
+}
+
+impl<'c, 'd, DB: Database> Executor<'c> for &'c mut (dyn DynExecutor<DB> + 'd) {
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, DB> + 'q,
+    {
+        self.dyn_fetch_many(DynQuery::new(query))
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, DB> + 'q,
+    {
+        self.dyn_fetch_optional(DynQuery::new(query))
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>>
+    where
+        'c: 'e,
+    {
+        self.dyn_prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>>
+    where
+        'c: 'e,
+    {
+        self.dyn_describe(sql)
+    }
+}
+
+/// A query passed to a [`DynExecutor`], which forwards every method of [`Execute`] to the
+/// original query.
+pub struct DynQuery<'q, DB: Database>(Box<dyn ErasedExecute<'q, DB> + 'q>);
+
+impl<'q, DB: Database> DynQuery<'q, DB> {
+    /// Erase the type of `query`.
+    pub fn new(query: impl Execute<'q, DB> + 'q) -> Self {
+        Self(Box::new(query))
+    }
+}
+
+// `Execute` is not object-safe as it requires `Sized`
+trait ErasedExecute<'q, DB: Database>: Send {
+    fn sql(&self) -> &'q str;
+
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement>;
+
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments>;
+
+    fn persistent(&self) -> bool;
+
+    fn size_hint(&self) -> Option<usize>;
+
+    fn numeric_overflow(&self) -> Option<NumericOverflow>;
+}
+
+impl<'q, DB: Database, E: Execute<'q, DB>> ErasedExecute<'q, DB> for E {
+    fn sql(&self) -> &'q str {
+        Execute::sql(self)
+    }
+
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        Execute::statement(self)
+    }
+
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        Execute::take_arguments(self)
+    }
+
+    fn persistent(&self) -> bool {
+        Execute::persistent(self)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Execute::size_hint(self)
+    }
+
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        Execute::numeric_overflow(self)
+    }
+}
+
+impl<'q, DB: Database> Execute<'q, DB> for DynQuery<'q, DB> {
+    #[inline]
+    fn sql(&self) -> &'q str {
+        self.0.sql()
+    }
+
+    #[inline]
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        self.0.statement()
+    }
+
+    #[inline]
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        self.0.take_arguments()
+    }
+
+    #[inline]
+    fn persistent(&self) -> bool {
+        self.0.persistent()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        self.0.numeric_overflow()
+    }
+}
+
+impl<DB: Database> Debug for DynQuery<'_, DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DynQuery").field(&self.0.sql()).finish()
+    }
+}
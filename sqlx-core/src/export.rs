@@ -0,0 +1,264 @@
+//! Write query results as CSV or TSV, e.g. for spreadsheets or bulk loading into other systems.
+//!
+//! ```rust,ignore
+//! let file = std::io::BufWriter::new(std::fs::File::create("users.csv")?);
+//! let mut conn = pool.acquire().await?;
+//!
+//! let rows = sqlx::export::write_csv(&mut *conn, sqlx::query("SELECT * FROM users"), file).await?;
+//! ```
+//!
+//! The first line holds the column names. Values are formatted the same for every database:
+//!
+//! * `NULL` is an empty field, while empty text is written as `""`.
+//! * Integers, floats and booleans are written as by Rust's `Display`, e.g. `42`, `1.5` and
+//!   `true`.
+//! * Binary values are written as hex prefixed with `\x`, as in Postgres.
+//! * Fields are quoted if they contain the delimiter, quotes or line breaks, and quotes are
+//!   doubled as in [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180). Lines end with `\n`.
+//!
+//! Values of other SQL types, e.g. timestamps or decimals, fail with [`Error::ColumnDecode`];
+//! cast them to text in the query.
+
+use std::io::Write;
+
+use crate::column::{Column, ColumnIndex};
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::executor::{DynExecutor, Execute, Executor};
+use crate::row::Row;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+use crate::value::ValueRef;
+use futures_util::TryStreamExt;
+
+/// Write the rows returned by `query` to `writer` as comma-separated values and return the
+/// number of rows written.
+///
+/// The `executor` is used to describe `query` if it returns no rows, to write the header.
+/// `writer` is written to once per row, so use a buffered writer for files and sockets.
+pub async fn write_csv<'q, DB, W>(
+    executor: &mut (dyn DynExecutor<DB> + '_),
+    query: impl Execute<'q, DB> + 'q,
+    writer: W,
+) -> Result<u64, Error>
+where
+    DB: Database,
+    W: Write,
+    usize: ColumnIndex<DB::Row>,
+    for<'r> i64: Decode<'r, DB> + Type<DB>,
+    for<'r> i32: Decode<'r, DB> + Type<DB>,
+    for<'r> i16: Decode<'r, DB> + Type<DB>,
+    for<'r> f64: Decode<'r, DB> + Type<DB>,
+    for<'r> f32: Decode<'r, DB> + Type<DB>,
+    for<'r> bool: Decode<'r, DB> + Type<DB>,
+    for<'r> String: Decode<'r, DB> + Type<DB>,
+    for<'r> Vec<u8>: Decode<'r, DB> + Type<DB>,
+{
+    write_delimited(executor, query, writer, b',').await
+}
+
+/// Like [`write_csv()`], but separating fields with tabs.
+pub async fn write_tsv<'q, DB, W>(
+    executor: &mut (dyn DynExecutor<DB> + '_),
+    query: impl Execute<'q, DB> + 'q,
+    writer: W,
+) -> Result<u64, Error>
+where
+    DB: Database,
+    W: Write,
+    usize: ColumnIndex<DB::Row>,
+    for<'r> i64: Decode<'r, DB> + Type<DB>,
+    for<'r> i32: Decode<'r, DB> + Type<DB>,
+    for<'r> i16: Decode<'r, DB> + Type<DB>,
+    for<'r> f64: Decode<'r, DB> + Type<DB>,
+    for<'r> f32: Decode<'r, DB> + Type<DB>,
+    for<'r> bool: Decode<'r, DB> + Type<DB>,
+    for<'r> String: Decode<'r, DB> + Type<DB>,
+    for<'r> Vec<u8>: Decode<'r, DB> + Type<DB>,
+{
+    write_delimited(executor, query, writer, b'\t').await
+}
+
+async fn write_delimited<'q, DB, W>(
+    executor: &mut (dyn DynExecutor<DB> + '_),
+    query: impl Execute<'q, DB> + 'q,
+    mut writer: W,
+    delimiter: u8,
+) -> Result<u64, Error>
+where
+    DB: Database,
+    W: Write,
+    usize: ColumnIndex<DB::Row>,
+    for<'r> i64: Decode<'r, DB> + Type<DB>,
+    for<'r> i32: Decode<'r, DB> + Type<DB>,
+    for<'r> i16: Decode<'r, DB> + Type<DB>,
+    for<'r> f64: Decode<'r, DB> + Type<DB>,
+    for<'r> f32: Decode<'r, DB> + Type<DB>,
+    for<'r> bool: Decode<'r, DB> + Type<DB>,
+    for<'r> String: Decode<'r, DB> + Type<DB>,
+    for<'r> Vec<u8>: Decode<'r, DB> + Type<DB>,
+{
+    let sql = query.sql();
+    let mut line = Vec::new();
+    let mut count = 0;
+
+    {
+        let mut rows = (&mut *executor).fetch(query);
+
+        while let Some(row) = rows.try_next().await? {
+            if count == 0 {
+                let names = row.columns().iter().map(|column| column.name());
+
+                write_header(&mut line, names, delimiter);
+                writer.write_all(&line)?;
+            }
+
+            line.clear();
+
+            for index in 0..row.columns().len() {
+                if index > 0 {
+                    line.push(delimiter);
+                }
+
+                write_value(&mut line, &row, index, delimiter)?;
+            }
+
+            line.push(b'\n');
+            writer.write_all(&line)?;
+
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        let describe = executor.dyn_describe(sql).await?;
+        let names = describe.columns().iter().map(|column| column.name());
+
+        write_header(&mut line, names, delimiter);
+        writer.write_all(&line)?;
+    }
+
+    writer.flush()?;
+
+    Ok(count)
+}
+
+fn write_header<'a>(line: &mut Vec<u8>, names: impl Iterator<Item = &'a str>, delimiter: u8) {
+    line.clear();
+
+    for (i, name) in names.enumerate() {
+        if i > 0 {
+            line.push(delimiter);
+        }
+
+        write_field(line, name.as_bytes(), delimiter);
+    }
+
+    line.push(b'\n');
+}
+
+fn write_value<R>(line: &mut Vec<u8>, row: &R, index: usize, delimiter: u8) -> Result<(), Error>
+where
+    R: Row,
+    usize: ColumnIndex<R>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i32: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i16: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> f64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> f32: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> Vec<u8>: Decode<'r, R::Database> + Type<R::Database>,
+{
+    let value = row.try_get_raw(index)?;
+
+    if value.is_null() {
+        return Ok(());
+    }
+
+    let ty = value.type_info().into_owned();
+
+    // integers go first as SQLite booleans are integers
+    if <i64 as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<i64, _>(index)?);
+    } else if <i32 as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<i32, _>(index)?);
+    } else if <i16 as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<i16, _>(index)?);
+    } else if <f64 as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<f64, _>(index)?);
+    } else if <f32 as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<f32, _>(index)?);
+    } else if <bool as Type<R::Database>>::compatible(&ty) {
+        write_display(line, row.try_get::<bool, _>(index)?);
+    } else if <String as Type<R::Database>>::compatible(&ty) {
+        let text = row.try_get::<String, _>(index)?;
+
+        if text.is_empty() {
+            line.extend_from_slice(b"\"\"");
+        } else {
+            write_field(line, text.as_bytes(), delimiter);
+        }
+    } else if <Vec<u8> as Type<R::Database>>::compatible(&ty) {
+        line.extend_from_slice(b"\\x");
+
+        for byte in row.try_get::<Vec<u8>, _>(index)? {
+            write!(line, "{byte:02x}").expect("writing to a Vec cannot fail");
+        }
+    } else {
+        return Err(Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source: format!(
+                "values of SQL type {} cannot be exported; cast them to text",
+                ty.name()
+            )
+            .into(),
+        });
+    }
+
+    Ok(())
+}
+
+fn write_display(line: &mut Vec<u8>, value: impl std::fmt::Display) {
+    write!(line, "{value}").expect("writing to a Vec cannot fail");
+}
+
+fn write_field(line: &mut Vec<u8>, field: &[u8], delimiter: u8) {
+    let needs_quotes = field
+        .iter()
+        .any(|&b| b == delimiter || matches!(b, b'"' | b'\n' | b'\r'));
+
+    if !needs_quotes {
+        line.extend_from_slice(field);
+        return;
+    }
+
+    line.push(b'"');
+
+    for &b in field {
+        if b == b'"' {
+            line.push(b'"');
+        }
+
+        line.push(b);
+    }
+
+    line.push(b'"');
+}
+
+#[test]
+fn test_write_field() {
+    let field = |text: &str, delimiter| {
+        let mut line = Vec::new();
+        write_field(&mut line, text.as_bytes(), delimiter);
+        String::from_utf8(line).unwrap()
+    };
+
+    assert_eq!(field("plain text", b','), "plain text");
+    assert_eq!(field("a,b", b','), "\"a,b\"");
+    assert_eq!(field("a,b", b'\t'), "a,b");
+    assert_eq!(field("a\tb", b'\t'), "\"a\tb\"");
+    assert_eq!(field("say \"hi\"", b','), "\"say \"\"hi\"\"\"");
+    assert_eq!(field("two\nlines", b','), "\"two\nlines\"");
+}
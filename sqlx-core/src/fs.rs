@@ -51,6 +51,12 @@ pub async fn remove_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
     rt::spawn_blocking(move || std::fs::remove_dir_all(path)).await
 }
 
+pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    let from = PathBuf::from(from.as_ref());
+    let to = PathBuf::from(to.as_ref());
+    rt::spawn_blocking(move || std::fs::rename(from, to)).await
+}
+
 pub async fn read_dir(path: PathBuf) -> io::Result<ReadDir> {
     let read_dir = rt::spawn_blocking(move || std::fs::read_dir(path)).await?;
 
@@ -0,0 +1,134 @@
+//! Rewrite the SQL of queries before a connection executes them.
+//!
+//! A query interceptor is installed per connection, through the connect options of a driver
+//! (e.g. `SqliteConnectOptions::query_interceptor()`), so every connection of a pool gets it, or
+//! directly on a connection. It is called with the SQL of every query executed through the
+//! connection's [`Executor`] impl and returns the SQL to run instead, or `None` to run the query
+//! unchanged.
+//!
+//! This is where cross-cutting query conventions can be enforced in one place, e.g. hiding
+//! soft-deleted rows and stamping audit columns:
+//!
+//! ```rust,ignore
+//! let options = SqliteConnectOptions::from_str("sqlite://app.db")?.query_interceptor(|sql| {
+//!     let sql = sql
+//!         // `active_documents` is a view of the rows `WHERE deleted_at IS NULL`
+//!         .replace("FROM documents", "FROM active_documents")
+//!         .replace(
+//!             "UPDATE documents SET ",
+//!             "UPDATE documents SET updated_at = CURRENT_TIMESTAMP, ",
+//!         );
+//!
+//!     Some(sql)
+//! });
+//! ```
+//!
+//! For more than a closure, e.g. to route the queries of each tenant to the tables of its shard
+//! depending on the bound arguments, implement [`QueryRewriter`] and install it with the
+//! `query_rewriter()` method of the connect options instead.
+//!
+//! The interceptor sees the SQL as written, with placeholders instead of bound arguments, as
+//! SQLx does not parse SQL. Statements prepared with [`Executor::prepare()`] are intercepted once,
+//! when they are prepared and before any arguments are bound, so a [`QueryRewriter`] sees `None`
+//! for their arguments; executing them does not intercept them again. Queries that are only
+//! described, e.g. by the query macros at compile time, are not intercepted at all.
+//!
+//! [`sqlcommenter`](crate::sqlcommenter) tags are appended to the rewritten SQL.
+//!
+//! [`Executor`]: crate::executor::Executor
+//! [`Executor::prepare()`]: crate::executor::Executor::prepare
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::common::DebugFn;
+use crate::database::{Database, HasArguments};
+use crate::sqlcommenter;
+
+/// Rewrite the SQL of the queries executed on a connection, see the
+/// [module documentation](self).
+///
+/// Implemented for closures taking the SQL, which are installed with the `query_interceptor()`
+/// method of the connect options.
+///
+/// ```rust,ignore
+/// // one shard per tenant, attached to the connection as `tenant_<id>`
+/// struct TenantShards;
+///
+/// impl QueryRewriter<Sqlite> for TenantShards {
+///     fn rewrite(&self, sql: &str, arguments: Option<&SqliteArguments<'_>>) -> Option<String> {
+///         // by convention, the first argument of every tenant query is the tenant ID
+///         let tenant = match arguments?.values().first()? {
+///             SqliteArgumentValue::Int64(tenant) => *tenant,
+///             _ => return None,
+///         };
+///
+///         Some(sql.replace("{tenant}.", &format!("tenant_{tenant}.")))
+///     }
+/// }
+/// ```
+pub trait QueryRewriter<DB: Database>: Send + Sync + 'static {
+    /// Return the SQL to execute instead of `sql`, or `None` to execute it unchanged.
+    ///
+    /// `arguments` are the arguments bound to the query, if any. They cannot be changed, so the
+    /// rewritten SQL has to keep their placeholders.
+    fn rewrite(
+        &self,
+        sql: &str,
+        arguments: Option<&<DB as HasArguments<'_>>::Arguments>,
+    ) -> Option<String>;
+}
+
+impl<DB, F> QueryRewriter<DB> for F
+where
+    DB: Database,
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    fn rewrite(
+        &self,
+        sql: &str,
+        _arguments: Option<&<DB as HasArguments<'_>>::Arguments>,
+    ) -> Option<String> {
+        self(sql)
+    }
+}
+
+/// A shared query interceptor, see the [module documentation](self).
+pub type QueryInterceptor<DB> = Arc<DebugFn<dyn QueryRewriter<DB>>>;
+
+/// Wrap `rewriter` to be installed on a connection.
+pub fn query_interceptor<DB, R>(rewriter: R) -> QueryInterceptor<DB>
+where
+    DB: Database,
+    R: QueryRewriter<DB>,
+{
+    Arc::new(DebugFn(rewriter))
+}
+
+/// Rewrite `sql` with `interceptor`, if any, for [`Executor::prepare()`].
+///
+/// [`Executor::prepare()`]: crate::executor::Executor::prepare
+pub fn rewrite<'q, DB: Database>(
+    interceptor: Option<&QueryInterceptor<DB>>,
+    sql: &'q str,
+) -> Cow<'q, str> {
+    match interceptor.and_then(|interceptor| interceptor.rewrite(sql, None)) {
+        Some(sql) => Cow::Owned(sql),
+        None => Cow::Borrowed(sql),
+    }
+}
+
+/// Rewrite `sql` with `interceptor`, if any, and append the [`sqlcommenter`] tags.
+///
+/// Called by the drivers for every executed query, with no `interceptor` for statements, which
+/// were already rewritten by [`rewrite()`] when they were prepared.
+pub fn apply<'q, DB: Database>(
+    interceptor: Option<&QueryInterceptor<DB>>,
+    sql: &'q str,
+    arguments: Option<&<DB as HasArguments<'_>>::Arguments>,
+) -> Cow<'q, str> {
+    match interceptor.and_then(|interceptor| interceptor.rewrite(sql, arguments)) {
+        Some(sql) => Cow::Owned(sqlcommenter::apply(&sql).into_owned()),
+        None => sqlcommenter::apply(sql),
+    }
+}
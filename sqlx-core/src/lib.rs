@@ -66,16 +66,22 @@ pub mod common;
 pub mod database;
 pub mod describe;
 pub mod executor;
+pub mod export;
 pub mod from_row;
 pub mod fs;
+pub mod interceptor;
 pub mod io;
 pub mod logger;
+pub mod named;
 pub mod net;
+pub mod ops;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
 pub mod row;
 pub mod rt;
+pub mod schema;
+pub mod sqlcommenter;
 pub mod sync;
 pub mod type_info;
 pub mod value;
@@ -83,6 +89,9 @@ pub mod value;
 #[cfg(feature = "migrate")]
 pub mod migrate;
 
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
 #[cfg(feature = "any")]
 pub mod any;
 
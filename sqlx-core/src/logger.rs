@@ -1,4 +1,6 @@
+use crate::arguments::Arguments;
 use crate::connection::LogSettings;
+use std::fmt;
 use std::time::Instant;
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
@@ -56,8 +58,11 @@ pub fn private_level_filter_to_levels(
 
 pub use sqlformat;
 
+type FormatArguments<'q> = Box<dyn Fn(&mut String) -> fmt::Result + Send + 'q>;
+
 pub struct QueryLogger<'q> {
     sql: &'q str,
+    arguments: Option<FormatArguments<'q>>,
     rows_returned: u64,
     rows_affected: u64,
     start: Instant,
@@ -68,6 +73,7 @@ impl<'q> QueryLogger<'q> {
     pub fn new(sql: &'q str, settings: LogSettings) -> Self {
         Self {
             sql,
+            arguments: None,
             rows_returned: 0,
             rows_affected: 0,
             start: Instant::now(),
@@ -75,6 +81,26 @@ impl<'q> QueryLogger<'q> {
         }
     }
 
+    /// Include the bound arguments in the log if
+    /// [`log_arguments`](LogSettings::log_arguments) is enabled, see
+    /// [`Arguments::format_redacted()`].
+    ///
+    /// `arguments` is only called if it is enabled, to take a copy of the arguments that is
+    /// rendered when the statement is logged.
+    pub fn arguments<'a, A, F>(&mut self, arguments: F)
+    where
+        A: Arguments<'a> + 'q,
+        F: FnOnce() -> A,
+    {
+        if !self.settings.log_arguments {
+            return;
+        }
+
+        let arguments = arguments();
+
+        self.arguments = Some(Box::new(move |buf| arguments.format_redacted(buf)));
+    }
+
     pub fn increment_rows_returned(&mut self) {
         self.rows_returned += 1;
     }
@@ -94,12 +120,8 @@ impl<'q> QueryLogger<'q> {
             self.settings.statements_level
         };
 
-        if let Some((tracing_level, log_level)) = private_level_filter_to_levels(lvl) {
-            // The enabled level could be set from either tracing world or log world, so check both
-            // to see if logging should be enabled for our level
-            let log_is_enabled = log::log_enabled!(target: "sqlx::query", log_level)
-                || private_tracing_dynamic_enabled!(target: "sqlx::query", tracing_level);
-            if log_is_enabled {
+        if let Some((tracing_level, _)) = private_level_filter_to_levels(lvl) {
+            if level_enabled(lvl) {
                 let mut summary = parse_query_summary(&self.sql);
 
                 let sql = if summary != self.sql {
@@ -116,12 +138,19 @@ impl<'q> QueryLogger<'q> {
                     String::new()
                 };
 
+                let mut arguments = String::new();
+
+                if let Some(format) = &self.arguments {
+                    format(&mut arguments).ok();
+                }
+
                 if was_slow {
                     private_tracing_dynamic_event!(
                         target: "sqlx::query",
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.arguments = arguments,
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         ?elapsed,
@@ -137,6 +166,7 @@ impl<'q> QueryLogger<'q> {
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.arguments = arguments,
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         ?elapsed,
@@ -147,6 +177,17 @@ impl<'q> QueryLogger<'q> {
     }
 }
 
+// The enabled level could be set from either tracing world or log world, so check both to see if
+// logging should be enabled for our level
+fn level_enabled(filter: log::LevelFilter) -> bool {
+    let Some((tracing_level, log_level)) = private_level_filter_to_levels(filter) else {
+        return false;
+    };
+
+    log::log_enabled!(target: "sqlx::query", log_level)
+        || private_tracing_dynamic_enabled!(target: "sqlx::query", tracing_level)
+}
+
 impl<'q> Drop for QueryLogger<'q> {
     fn drop(&mut self) {
         self.finish();
@@ -0,0 +1,204 @@
+//! Record metrics of queries and pools with the [`metrics`](https://docs.rs/metrics) crate.
+//!
+//! With the `metrics` feature enabled, every connection and pool records the following metrics
+//! to the recorder installed by the application, e.g. a Prometheus exporter:
+//!
+//! | Metric | Type | Labels | Description |
+//! | - | - | - | - |
+//! | `sqlx_queries_total` | counter | `database` | queries executed, including failed ones |
+//! | `sqlx_query_duration_seconds` | histogram | `database` | execution time of queries |
+//! | `sqlx_query_errors_total` | counter | `database`, `code` | failed queries |
+//! | `sqlx_pool_acquire_duration_seconds` | histogram | `database` | wait time of [`Pool::acquire()`] |
+//! | `sqlx_pool_acquire_timeouts_total` | counter | `database` | timed out [`Pool::acquire()`] calls |
+//! | `sqlx_statement_cache_hits_total` | counter | `database` | prepared statements reused |
+//! | `sqlx_statement_cache_misses_total` | counter | `database` | prepared statements not cached |
+//!
+//! `database` is the [name][crate::database::Database::NAME] of the driver, and `code` is the
+//! [code][crate::error::DatabaseError::code] of database errors, e.g. a SQLSTATE, or the kind of
+//! other errors, e.g. `io`.
+//!
+//! ```rust,ignore
+//! metrics_exporter_prometheus::PrometheusBuilder::new().install()?;
+//! sqlx::metrics::describe();
+//!
+//! let pool = SqlitePool::connect("sqlite://data.db").await?;
+//! ```
+//!
+//! [`Pool::acquire()`]: crate::pool::Pool::acquire
+
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::future::BoxFuture;
+use futures_core::stream::{BoxStream, Stream};
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+
+use crate::error::Error;
+
+/// Register the descriptions and units of the metrics with the installed recorder.
+///
+/// Metrics are recorded whether or not this is called; exporters may use the descriptions, e.g.
+/// for the `# HELP` lines of Prometheus.
+pub fn describe() {
+    describe_counter!(
+        "sqlx_queries_total",
+        "The number of queries executed, including failed ones."
+    );
+    describe_histogram!(
+        "sqlx_query_duration_seconds",
+        Unit::Seconds,
+        "The execution time of queries."
+    );
+    describe_counter!("sqlx_query_errors_total", "The number of failed queries.");
+    describe_histogram!(
+        "sqlx_pool_acquire_duration_seconds",
+        Unit::Seconds,
+        "The time spent waiting for a connection from a pool."
+    );
+    describe_counter!(
+        "sqlx_pool_acquire_timeouts_total",
+        "The number of times waiting for a connection from a pool timed out."
+    );
+    describe_counter!(
+        "sqlx_statement_cache_hits_total",
+        "The number of queries that reused a cached prepared statement."
+    );
+    describe_counter!(
+        "sqlx_statement_cache_misses_total",
+        "The number of queries that prepared a statement to cache."
+    );
+}
+
+/// Record the execution of the query producing `stream`.
+#[doc(hidden)]
+pub fn instrument_stream<'e, T: Send + 'e>(
+    database: &'static str,
+    stream: BoxStream<'e, Result<T, Error>>,
+) -> BoxStream<'e, Result<T, Error>> {
+    Box::pin(InstrumentedStream {
+        database,
+        stream,
+        start: Instant::now(),
+        finished: false,
+    })
+}
+
+/// Record the execution of the query producing `future`.
+#[doc(hidden)]
+pub fn instrument_future<'e, T: Send + 'e>(
+    database: &'static str,
+    future: BoxFuture<'e, Result<T, Error>>,
+) -> BoxFuture<'e, Result<T, Error>> {
+    Box::pin(async move {
+        let start = Instant::now();
+        let result = future.await;
+
+        if let Err(error) = &result {
+            query_failed(database, error);
+        }
+
+        query_finished(database, start.elapsed());
+
+        result
+    })
+}
+
+/// Record a lookup of a prepared statement in the statement cache of a connection.
+#[doc(hidden)]
+pub fn statement_cache_lookup(database: &'static str, hit: bool) {
+    if hit {
+        counter!("sqlx_statement_cache_hits_total", "database" => database).increment(1);
+    } else {
+        counter!("sqlx_statement_cache_misses_total", "database" => database).increment(1);
+    }
+}
+
+pub(crate) fn acquire_finished(database: &'static str, elapsed: Duration, timed_out: bool) {
+    histogram!("sqlx_pool_acquire_duration_seconds", "database" => database).record(elapsed);
+
+    if timed_out {
+        counter!("sqlx_pool_acquire_timeouts_total", "database" => database).increment(1);
+    }
+}
+
+fn query_finished(database: &'static str, elapsed: Duration) {
+    counter!("sqlx_queries_total", "database" => database).increment(1);
+    histogram!("sqlx_query_duration_seconds", "database" => database).record(elapsed);
+}
+
+fn query_failed(database: &'static str, error: &Error) {
+    counter!(
+        "sqlx_query_errors_total",
+        "database" => database,
+        "code" => error_code(error),
+    )
+    .increment(1);
+}
+
+fn error_code(error: &Error) -> Cow<'static, str> {
+    let kind = match error {
+        Error::Database(e) => {
+            return e
+                .code()
+                .map_or(Cow::Borrowed("database"), |code| code.into_owned().into())
+        }
+        Error::Configuration(_) => "configuration",
+        Error::Io(_) => "io",
+        Error::Tls(_) => "tls",
+        Error::Protocol(_) => "protocol",
+        Error::RowNotFound => "row_not_found",
+        Error::TypeNotFound { .. } => "type_not_found",
+        Error::ColumnIndexOutOfBounds { .. } => "column_index_out_of_bounds",
+        Error::ColumnNotFound(_) => "column_not_found",
+        Error::ColumnDecode { .. } | Error::Decode(_) => "decode",
+        Error::ArgumentBind { .. } => "argument_bind",
+        Error::PoolTimedOut => "pool_timed_out",
+        Error::PoolClosed => "pool_closed",
+        Error::QueryLimitExceeded(_) => "query_limit_exceeded",
+        Error::WorkerCrashed => "worker_crashed",
+        _ => "other",
+    };
+
+    Cow::Borrowed(kind)
+}
+
+struct InstrumentedStream<'e, T> {
+    database: &'static str,
+    stream: BoxStream<'e, Result<T, Error>>,
+    start: Instant,
+    finished: bool,
+}
+
+impl<T> InstrumentedStream<'_, T> {
+    // a query is finished when its stream ends or is dropped
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            query_finished(self.database, self.start.elapsed());
+        }
+    }
+}
+
+impl<T> Stream for InstrumentedStream<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = futures_core::ready!(self.stream.as_mut().poll_next(cx));
+
+        match &item {
+            Some(Err(error)) => query_failed(self.database, error),
+            Some(Ok(_)) => {}
+            None => self.finish(),
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+impl<T> Drop for InstrumentedStream<'_, T> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
@@ -0,0 +1,248 @@
+//! Named query parameters (`:name`) that work the same with every driver.
+//!
+//! [`NamedQuery`] rewrites named parameters to the placeholders of the database (`?` or `$1`)
+//! and binds their values from a map or a struct deriving [`BindNamed`], for use with
+//! [`query_with()`](crate::query::query_with) and its siblings:
+//!
+//! ```rust,ignore
+//! use sqlx::named::{BindNamed, NamedQuery};
+//!
+//! #[derive(BindNamed)]
+//! struct UserFilter {
+//!     name: String,
+//!     min_age: i32,
+//! }
+//!
+//! let query = NamedQuery::parse("SELECT * FROM users WHERE name = :name AND age >= :min_age");
+//!
+//! let filter = UserFilter { name: "alice".into(), min_age: 18 };
+//! let (sql, arguments) = query.bind::<Postgres, _>(&filter)?;
+//!
+//! let users = sqlx::query_with(&sql, arguments).fetch_all(&pool).await?;
+//! ```
+//!
+//! A name may be used several times and is bound for each use. Names are ASCII letters, digits
+//! and underscores, not starting with a digit. Colons within string literals, quoted
+//! identifiers, dollar-quoted strings and comments, as well as `::` casts, are left as they are.
+//! Backslash escapes in string literals are not recognized, so quotes in literals must be
+//! doubled (`'it''s'`).
+//!
+//! The derive binds each field under its name, subject to `rename` and `rename_all`, skips
+//! `#[sqlx(skip)]` fields, binds `#[sqlx(json)]` fields as [`Json`](crate::types::Json) and
+//! looks up names in `#[sqlx(flatten)]` fields after those of the struct itself.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasArguments};
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::types::Type;
+
+/// The values of named parameters, see the [module documentation](self).
+///
+/// Implemented for maps with string keys and by `#[derive(BindNamed)]` for structs.
+pub trait BindNamed<'q, DB: Database> {
+    /// Add the value of the parameter `name` to `arguments`, or return `false` if there is none.
+    fn bind_named(
+        &'q self,
+        name: &str,
+        arguments: &mut <DB as HasArguments<'q>>::Arguments,
+    ) -> bool;
+}
+
+/// SQL with named parameters, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedQuery {
+    // the SQL before each parameter, and after the last one
+    text: Vec<String>,
+    names: Vec<String>,
+}
+
+impl NamedQuery {
+    /// Find the named parameters in `sql`.
+    pub fn parse(sql: &str) -> Self {
+        let bytes = sql.as_bytes();
+
+        let mut text = Vec::new();
+        let mut names = Vec::new();
+
+        let mut start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                quote @ (b'\'' | b'"' | b'`') => {
+                    i = find(bytes, i + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+                }
+
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = find(bytes, i + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+                }
+
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = find(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                }
+
+                b'$' => i = skip_dollar_quoted(bytes, i),
+
+                // a cast, e.g. `'1'::int`
+                b':' if bytes.get(i + 1) == Some(&b':') => i += 2,
+
+                b':' if matches!(bytes.get(i + 1), Some(&b) if b.is_ascii_alphabetic() || b == b'_') =>
+                {
+                    let end = bytes[i + 1..]
+                        .iter()
+                        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_'))
+                        .map_or(bytes.len(), |len| i + 1 + len);
+
+                    text.push(sql[start..i].to_owned());
+                    names.push(sql[i + 1..end].to_owned());
+
+                    start = end;
+                    i = end;
+                }
+
+                _ => i += 1,
+            }
+        }
+
+        text.push(sql[start..].to_owned());
+
+        Self { text, names }
+    }
+
+    /// The names of the parameters in the order they appear in, including repetitions.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Rewrite the parameters to the placeholders of `DB` and bind their values from `params`,
+    /// returning the SQL and arguments for [`query_with()`](crate::query::query_with).
+    ///
+    /// Fails with [`Error::Configuration`] if `params` has no value for a parameter.
+    pub fn bind<'q, DB, P>(
+        &self,
+        params: &'q P,
+    ) -> Result<(String, <DB as HasArguments<'q>>::Arguments), Error>
+    where
+        DB: Database,
+        P: BindNamed<'q, DB> + ?Sized,
+    {
+        let mut sql = String::with_capacity(self.text.iter().map(String::len).sum());
+        let mut arguments = <DB as HasArguments<'q>>::Arguments::default();
+
+        arguments.reserve(self.names.len(), 0);
+
+        for (text, name) in self.text.iter().zip(&self.names) {
+            sql.push_str(text);
+
+            if !params.bind_named(name, &mut arguments) {
+                return Err(Error::Configuration(
+                    format!("no value for named parameter :{name}").into(),
+                ));
+            }
+
+            arguments
+                .format_placeholder(&mut sql)
+                .expect("error in format_placeholder");
+        }
+
+        if let Some(text) = self.text.last() {
+            sql.push_str(text);
+        }
+
+        Ok((sql, arguments))
+    }
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| from + i)
+}
+
+// skips a Postgres dollar-quoted string, e.g. `$$text$$` or `$tag$text$tag$`, starting at the
+// first `$`; positional parameters such as `$1` are skipped as is
+fn skip_dollar_quoted(bytes: &[u8], start: usize) -> usize {
+    let tag_len = bytes[start + 1..]
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_'))
+        .unwrap_or(bytes.len() - start - 1);
+
+    let tag_end = start + 1 + tag_len;
+
+    if bytes.get(tag_end) != Some(&b'$')
+        || matches!(bytes.get(start + 1), Some(b) if b.is_ascii_digit())
+    {
+        return tag_end;
+    }
+
+    let delimiter = &bytes[start..=tag_end];
+
+    find(bytes, tag_end + 1, delimiter).map_or(bytes.len(), |end| end + delimiter.len())
+}
+
+impl<'q, DB, K, V, S> BindNamed<'q, DB> for HashMap<K, V, S>
+where
+    DB: Database,
+    K: Borrow<str> + Hash + Eq,
+    V: 'q + Sync + Encode<'q, DB> + Type<DB>,
+    S: BuildHasher,
+{
+    fn bind_named(
+        &'q self,
+        name: &str,
+        arguments: &mut <DB as HasArguments<'q>>::Arguments,
+    ) -> bool {
+        match self.get(name) {
+            Some(value) => {
+                arguments.add(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'q, DB, K, V> BindNamed<'q, DB> for BTreeMap<K, V>
+where
+    DB: Database,
+    K: Borrow<str> + Ord,
+    V: 'q + Sync + Encode<'q, DB> + Type<DB>,
+{
+    fn bind_named(
+        &'q self,
+        name: &str,
+        arguments: &mut <DB as HasArguments<'q>>::Arguments,
+    ) -> bool {
+        match self.get(name) {
+            Some(value) => {
+                arguments.add(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn test_parse_named_query() {
+    let query = NamedQuery::parse(
+        "SELECT :a, ':b', \"c:d\", `:e`, x::int, $$:f$$, $tag$ $$ :g $tag$, $1 -- :h
+         /* :i */ FROM t WHERE y = :a AND z = :snake_case2",
+    );
+
+    assert_eq!(query.names().collect::<Vec<_>>(), ["a", "a", "snake_case2"]);
+    assert_eq!(query.text[0], "SELECT ");
+    assert_eq!(query.text[3], "");
+
+    let query = NamedQuery::parse("SELECT @x := 1, t[1:2], ':'");
+
+    assert_eq!(query.names().count(), 0);
+    assert_eq!(query.text, ["SELECT @x := 1, t[1:2], ':'"]);
+}
@@ -49,6 +49,16 @@ impl<S: Socket> BufferedSocket<S> {
         self.read_buf.read(len, &mut self.socket).await
     }
 
+    /// Wait until at least `len` bytes are buffered, and return them without consuming them.
+    ///
+    /// Like [`read_buffered()`](Self::read_buffered), this is cancel-safe: the bytes read are
+    /// kept in the buffer until they are consumed.
+    pub async fn peek(&mut self, len: usize) -> io::Result<&[u8]> {
+        self.read_buf.fill(len, &mut self.socket).await?;
+
+        Ok(&self.read_buf.read[..len])
+    }
+
     pub fn write_buffer(&self) -> &WriteBuffer {
         &self.write_buf
     }
@@ -222,6 +232,13 @@ impl WriteBuffer {
 
 impl ReadBuffer {
     async fn read(&mut self, len: usize, socket: &mut impl Socket) -> io::Result<BytesMut> {
+        self.fill(len, socket).await?;
+
+        Ok(self.drain(len))
+    }
+
+    // reads until at least `len` bytes are buffered
+    async fn fill(&mut self, len: usize, socket: &mut impl Socket) -> io::Result<()> {
         // Because of how `BytesMut` works, we should only be shifting capacity back and forth
         // between `read` and `available` unless we have to read an oversize message.
         while self.read.len() < len {
@@ -243,7 +260,7 @@ impl ReadBuffer {
             self.advance(read);
         }
 
-        Ok(self.drain(len))
+        Ok(())
     }
 
     fn reserve(&mut self, amt: usize) {
@@ -0,0 +1,23 @@
+//! Maintenance operations that need different SQL for each database.
+
+use crate::acquire::Acquire;
+use crate::connection::Connection;
+use crate::error::Error;
+
+/// Delete all rows of `table`, using `TRUNCATE` where the database supports it, e.g. to clean up
+/// after tests or to empty cache tables.
+///
+/// If `restart_identity` is set, generated ids start over. See
+/// [`Connection::clear_table()`] for the statements run for each database.
+///
+/// ```rust,ignore
+/// sqlx::ops::clear_table(&pool, "sessions", true).await?;
+/// ```
+pub async fn clear_table<'a, A>(conn: A, table: &str, restart_identity: bool) -> Result<(), Error>
+where
+    A: Acquire<'a>,
+{
+    let mut conn = conn.acquire().await?;
+
+    conn.clear_table(table, restart_identity).await
+}
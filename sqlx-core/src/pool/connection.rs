@@ -10,7 +10,7 @@ use crate::database::Database;
 use crate::error::Error;
 
 use super::inner::{DecrementSizeGuard, PoolInner};
-use crate::pool::options::PoolConnectionMetadata;
+use crate::pool::options::{PoolConnectionMetadata, ResetOnReturn};
 use std::future::Future;
 
 /// A connection managed by a [`Pool`][crate::pool::Pool].
@@ -257,6 +257,21 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             }
         }
 
+        let discard_session = match self.guard.pool.options.reset_on_return {
+            ResetOnReturn::Off => None,
+            ResetOnReturn::Rollback => Some(false),
+            ResetOnReturn::Full => Some(true),
+        };
+
+        if let Some(discard_session) = discard_session {
+            if let Err(error) = self.raw.reset(discard_session).await {
+                tracing::warn!(%error, "error occurred while resetting the connection on-release");
+
+                self.close_hard().await;
+                return false;
+            }
+        }
+
         // test the connection on-release to ensure it is still viable,
         // and flush anything time-sensitive like transaction rollbacks
         // if an Executor future/stream is dropped during an `.await` call, the connection
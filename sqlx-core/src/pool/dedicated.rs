@@ -0,0 +1,74 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::Error;
+
+use super::inner::PoolInner;
+
+/// A connection checked out with [`Pool::acquire_dedicated()`][crate::pool::Pool::acquire_dedicated].
+///
+/// Unlike a [`PoolConnection`][crate::pool::PoolConnection], this is not returned to the pool
+/// but closed on-drop. Prefer calling [`.close()`][Self::close] to close it gracefully.
+pub struct DedicatedConnection<DB: Database> {
+    raw: Option<DB::Connection>,
+    pool: Arc<PoolInner<DB>>,
+}
+
+const EXPECT_MSG: &str = "BUG: inner connection already taken!";
+
+impl<DB: Database> DedicatedConnection<DB> {
+    pub(super) fn new(raw: DB::Connection, pool: Arc<PoolInner<DB>>) -> Self {
+        Self {
+            raw: Some(raw),
+            pool,
+        }
+    }
+
+    /// Gracefully close this connection, allowing another dedicated connection to be acquired.
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.raw.take().expect(EXPECT_MSG).close().await
+    }
+}
+
+impl<DB: Database> Debug for DedicatedConnection<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedicatedConnection").finish()
+    }
+}
+
+impl<DB: Database> Deref for DedicatedConnection<DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw.as_ref().expect(EXPECT_MSG)
+    }
+}
+
+impl<DB: Database> DerefMut for DedicatedConnection<DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw.as_mut().expect(EXPECT_MSG)
+    }
+}
+
+impl<DB: Database> AsRef<DB::Connection> for DedicatedConnection<DB> {
+    fn as_ref(&self) -> &DB::Connection {
+        self
+    }
+}
+
+impl<DB: Database> AsMut<DB::Connection> for DedicatedConnection<DB> {
+    fn as_mut(&mut self) -> &mut DB::Connection {
+        self
+    }
+}
+
+impl<DB: Database> Drop for DedicatedConnection<DB> {
+    fn drop(&mut self) {
+        self.pool.num_dedicated.fetch_sub(1, Ordering::AcqRel);
+        self.pool.dedicated.release(1);
+    }
+}
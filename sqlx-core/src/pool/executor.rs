@@ -6,8 +6,9 @@ use futures_util::TryStreamExt;
 use crate::database::{Database, HasStatement};
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
-use crate::pool::Pool;
+use crate::executor::{DynExecutor, DynQuery, Execute, Executor};
+use crate::pool::{Pool, QueryGuardAction, QueryLimit};
+use std::time::Instant;
 
 impl<'p, DB: Database> Executor<'p> for &'_ Pool<DB>
 where
@@ -26,10 +27,57 @@ where
 
         Box::pin(try_stream! {
             let mut conn = pool.acquire().await?;
-            let mut s = conn.fetch_many(query);
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
+            let Some(guard) = pool.options().query_guard.clone() else {
+                let mut s = conn.fetch_many(query);
+
+                while let Some(v) = s.try_next().await? {
+                    r#yield!(v);
+                }
+
+                return Ok(());
+            };
+
+            let started = Instant::now();
+            let mut rows = 0;
+
+            let exceeded = {
+                let mut s = conn.fetch_many(query);
+
+                loop {
+                    let next = match guard.max_execution_time {
+                        Some(max) => {
+                            let remaining = max.saturating_sub(started.elapsed());
+
+                            match crate::rt::timeout(remaining, s.try_next()).await {
+                                Ok(next) => next?,
+                                Err(_) => break Some(QueryLimit::ExecutionTime(max)),
+                            }
+                        }
+                        None => s.try_next().await?,
+                    };
+
+                    let Some(v) = next else { break None };
+
+                    if v.is_right() {
+                        if let Some(max) = guard.max_rows.filter(|&max| rows >= max) {
+                            break Some(QueryLimit::Rows(max));
+                        }
+
+                        rows += 1;
+                    }
+
+                    r#yield!(v);
+                }
+            };
+
+            if let Some(limit) = exceeded {
+                // close the connection rather than reading the rest of the result on its next use
+                drop(conn.detach());
+
+                if guard.on_exceeded == QueryGuardAction::Reject {
+                    return Err(Error::QueryLimitExceeded(limit));
+                }
             }
 
             Ok(())
@@ -45,7 +93,26 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            let mut conn = pool.acquire().await?;
+
+            let Some(max) = pool
+                .options()
+                .query_guard
+                .as_ref()
+                .and_then(|guard| guard.max_execution_time)
+            else {
+                return conn.fetch_optional(query).await;
+            };
+
+            match crate::rt::timeout(max, conn.fetch_optional(query)).await {
+                Ok(row) => row,
+                Err(_) => {
+                    drop(conn.detach());
+                    Err(Error::QueryLimitExceeded(QueryLimit::ExecutionTime(max)))
+                }
+            }
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -140,3 +207,37 @@ where
 //         (**self).describe(sql)
 //     }
 // }
+
+impl<DB: Database> DynExecutor<DB> for Pool<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    fn dyn_fetch_many<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>> {
+        (&*self).fetch_many(query)
+    }
+
+    fn dyn_fetch_optional<'e, 'q: 'e>(
+        &'e mut self,
+        query: DynQuery<'q, DB>,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>> {
+        (&*self).fetch_optional(query)
+    }
+
+    fn dyn_prepare_with<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>> {
+        (&*self).prepare_with(sql, parameters)
+    }
+
+    fn dyn_describe<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<DB>, Error>> {
+        (&*self).describe(sql)
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// Limits for queries executed directly on a [`Pool`][super::Pool], to protect a shared database
+/// from accidental expensive queries such as a `SELECT *` on a huge table.
+///
+/// It is set with [`PoolOptions::query_guard()`][super::PoolOptions::query_guard]:
+///
+/// ```rust,ignore
+/// use sqlx::pool::{QueryGuard, QueryGuardAction};
+///
+/// let pool = PgPoolOptions::new()
+///     .query_guard(
+///         QueryGuard::new()
+///             .max_execution_time(Duration::from_secs(30))
+///             .max_rows(10_000)
+///             .on_exceeded(QueryGuardAction::Truncate),
+///     )
+///     .connect("postgres:// …")
+///     .await?;
+/// ```
+///
+/// The limits apply to each query executed with `&Pool` as the executor, not to queries on
+/// connections acquired from the pool or on transactions. The execution time is measured from
+/// when the query is sent to when its last row is received, excluding the wait for a connection.
+///
+/// A connection whose query exceeded a limit is closed instead of being returned to the pool,
+/// rather than reading the rest of the result when it is used next. Not all databases notice
+/// this before the query produces more output, so set a server-side timeout as well to cancel
+/// long-running queries reliably, such as Postgres' `statement_timeout`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryGuard {
+    pub(crate) max_execution_time: Option<Duration>,
+    pub(crate) max_rows: Option<u64>,
+    pub(crate) on_exceeded: QueryGuardAction,
+}
+
+/// What happens to a query that exceeds a limit of its [`QueryGuard`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryGuardAction {
+    /// The query fails with [`Error::QueryLimitExceeded`][crate::error::Error::QueryLimitExceeded].
+    /// This is the default.
+    #[default]
+    Reject,
+
+    /// The query ends early, returning the rows received within the limits.
+    ///
+    /// [`fetch_optional()`][crate::executor::Executor::fetch_optional] and
+    /// [`fetch_one()`][crate::executor::Executor::fetch_one] still fail if the execution time is
+    /// exceeded, as there would be no row to return.
+    Truncate,
+}
+
+/// A limit of a [`QueryGuard`] that was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryLimit {
+    ExecutionTime(Duration),
+    Rows(u64),
+}
+
+impl QueryGuard {
+    /// A guard without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail or end queries that take longer than `max`.
+    pub fn max_execution_time(mut self, max: impl Into<Option<Duration>>) -> Self {
+        self.max_execution_time = max.into();
+        self
+    }
+
+    /// Fail or end queries that return more than `max` rows.
+    pub fn max_rows(mut self, max: impl Into<Option<u64>>) -> Self {
+        self.max_rows = max.into();
+        self
+    }
+
+    /// Set what happens to queries that exceed a limit.
+    pub fn on_exceeded(mut self, action: QueryGuardAction) -> Self {
+        self.on_exceeded = action;
+        self
+    }
+
+    pub fn get_max_execution_time(&self) -> Option<Duration> {
+        self.max_execution_time
+    }
+
+    pub fn get_max_rows(&self) -> Option<u64> {
+        self.max_rows
+    }
+
+    pub fn get_on_exceeded(&self) -> QueryGuardAction {
+        self.on_exceeded
+    }
+}
+
+impl Display for QueryLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryLimit::ExecutionTime(max) => write!(f, "maximum execution time of {max:?}"),
+            QueryLimit::Rows(max) => write!(f, "maximum of {max} rows"),
+        }
+    }
+}
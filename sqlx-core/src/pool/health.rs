@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::pool::Pool;
+
+/// A snapshot of the health of a [`Pool`], as returned by [`Pool::health_check()`].
+///
+/// With the `serde` feature enabled this can be serialized directly into the response of a
+/// health check endpoint; `latency` is serialized as a number of (fractional) milliseconds.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct PoolHealth {
+    /// Whether a connection could be acquired from the pool and successfully pinged.
+    pub can_connect: bool,
+    /// How long it took to acquire and ping a connection, if that succeeded.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_latency"))]
+    pub latency: Option<Duration>,
+    /// The number of connections currently open, including idle connections.
+    pub size: u32,
+    /// The number of idle connections.
+    pub num_idle: usize,
+    /// The configured maximum number of connections.
+    pub max_connections: u32,
+    /// The error from the health check itself if it failed, otherwise the error of the most
+    /// recent failed attempt to open a connection, if the pool has not succeeded since.
+    pub last_error: Option<String>,
+}
+
+impl PoolHealth {
+    /// The fraction of [`max_connections`][Self::max_connections] currently in use,
+    /// from `0.0` to `1.0`.
+    pub fn utilization(&self) -> f64 {
+        if self.max_connections == 0 {
+            return 0.0;
+        }
+
+        let in_use = (self.size as usize).saturating_sub(self.num_idle);
+        in_use as f64 / self.max_connections as f64
+    }
+}
+
+impl<DB: Database> Pool<DB> {
+    /// Check whether the pool can serve connections, returning a report suitable for
+    /// health check endpoints (e.g. `/healthz`).
+    ///
+    /// This acquires a connection (waiting at most
+    /// [`acquire_timeout`][crate::pool::PoolOptions::acquire_timeout]) and pings it.
+    /// Failures are reported in the returned [`PoolHealth`] rather than as an error.
+    pub async fn health_check(&self) -> PoolHealth {
+        let started = Instant::now();
+
+        let res = match self.acquire().await {
+            Ok(mut conn) => {
+                let res = conn.ping().await;
+                // return the connection eagerly so it isn't counted as in use below
+                conn.return_to_pool().await;
+                res
+            }
+            Err(e) => Err(e),
+        };
+
+        let (latency, error) = match res {
+            Ok(()) => (Some(started.elapsed()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        PoolHealth {
+            can_connect: error.is_none(),
+            latency,
+            size: self.size(),
+            num_idle: self.num_idle(),
+            max_connections: self.options().get_max_connections(),
+            last_error: error.or_else(|| self.0.last_connect_error()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_latency<S>(latency: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match latency {
+        Some(latency) => serializer.serialize_some(&(latency.as_secs_f64() * 1000.0)),
+        None => serializer.serialize_none(),
+    }
+}
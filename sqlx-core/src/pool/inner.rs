@@ -11,7 +11,7 @@ use crate::sync::{AsyncSemaphore, AsyncSemaphoreReleaser};
 use std::cmp;
 use std::future::Future;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::task::Poll;
 
 use crate::pool::options::PoolConnectionMetadata;
@@ -23,9 +23,12 @@ pub(crate) struct PoolInner<DB: Database> {
     pub(super) connect_options: RwLock<Arc<<DB::Connection as Connection>::Options>>,
     pub(super) idle_conns: ArrayQueue<Idle<DB>>,
     pub(super) semaphore: AsyncSemaphore,
+    pub(super) dedicated: AsyncSemaphore,
+    pub(super) num_dedicated: AtomicU32,
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
+    last_connect_error: Mutex<Option<String>>,
     pub(super) on_closed: event_listener::Event,
     pub(super) options: PoolOptions<DB>,
 }
@@ -50,9 +53,15 @@ impl<DB: Database> PoolInner<DB> {
             connect_options: RwLock::new(Arc::new(connect_options)),
             idle_conns: ArrayQueue::new(capacity),
             semaphore: AsyncSemaphore::new(options.fair, semaphore_capacity),
+            dedicated: AsyncSemaphore::new(
+                options.fair,
+                options.max_dedicated_connections as usize,
+            ),
+            num_dedicated: AtomicU32::new(0),
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
+            last_connect_error: Mutex::new(None),
             on_closed: event_listener::Event::new(),
             options,
         };
@@ -81,6 +90,20 @@ impl<DB: Database> PoolInner<DB> {
         self.is_closed.load(Ordering::Acquire)
     }
 
+    pub(super) fn last_connect_error(&self) -> Option<String> {
+        self.last_connect_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn set_last_connect_error(&self, error: Option<&Error>) {
+        *self
+            .last_connect_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = error.map(Error::to_string);
+    }
+
     fn mark_closed(&self) {
         self.is_closed.store(true, Ordering::Release);
         self.on_closed.notify(usize::MAX);
@@ -241,9 +264,10 @@ impl<DB: Database> PoolInner<DB> {
             return Err(Error::PoolClosed);
         }
 
-        let deadline = Instant::now() + self.options.acquire_timeout;
+        let start = Instant::now();
+        let deadline = start + self.options.acquire_timeout;
 
-        crate::rt::timeout(
+        let acquired = crate::rt::timeout(
             self.options.acquire_timeout,
             async {
                 loop {
@@ -286,7 +310,12 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .map_err(|_| Error::PoolTimedOut);
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::acquire_finished(DB::NAME, start.elapsed(), acquired.is_err());
+
+        acquired?
     }
 
     pub(super) async fn connect(
@@ -294,6 +323,17 @@ impl<DB: Database> PoolInner<DB> {
         deadline: Instant,
         guard: DecrementSizeGuard<DB>,
     ) -> Result<Floating<DB, Live<DB>>, Error> {
+        let raw = self.connect_raw(deadline).await?;
+
+        Ok(Floating::new_live(raw, guard))
+    }
+
+    /// Open a connection with the connect options and callbacks of the pool, without counting it
+    /// against `max_connections`.
+    pub(super) async fn connect_raw(
+        self: &Arc<Self>,
+        deadline: Instant,
+    ) -> Result<DB::Connection, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
@@ -314,7 +354,13 @@ impl<DB: Database> PoolInner<DB> {
 
             // result here is `Result<Result<C, Error>, TimeoutError>`
             // if this block does not return, sleep for the backoff timeout and try again
-            match crate::rt::timeout(timeout, connect_options.connect()).await {
+            let res = crate::rt::timeout(timeout, connect_options.connect()).await;
+
+            if let Ok(res) = &res {
+                self.set_last_connect_error(res.as_ref().err());
+            }
+
+            match res {
                 // successfully established connection
                 Ok(Ok(mut raw)) => {
                     // See comment on `PoolOptions::after_connect`
@@ -323,16 +369,25 @@ impl<DB: Database> PoolInner<DB> {
                         idle_for: Duration::ZERO,
                     };
 
-                    let res = if let Some(callback) = &self.options.after_connect {
-                        callback(&mut raw, meta).await
+                    let mut res = if let Some(callback) = &self.options.after_connect {
+                        callback(&mut raw, meta).await.map_err(|error| {
+                            tracing::error!(%error, "error returned from after_connect");
+                            error
+                        })
                     } else {
                         Ok(())
                     };
 
+                    if let (Ok(()), Some(prepare)) = (&res, &self.options.prepare_statements) {
+                        res = prepare(&mut raw).await.map_err(|error| {
+                            tracing::error!(%error, "error preparing statements");
+                            error
+                        });
+                    }
+
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
-                        Err(error) => {
-                            tracing::error!(%error, "error returned from after_connect");
+                        Ok(()) => return Ok(raw),
+                        Err(_) => {
                             // The connection is broken, don't try to close nicely.
                             let _ = raw.close_hard().await;
 
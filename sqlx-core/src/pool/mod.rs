@@ -75,6 +75,7 @@ use futures_util::FutureExt;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
@@ -86,11 +87,19 @@ mod executor;
 pub mod maybe;
 
 mod connection;
+mod dedicated;
+mod guard;
+mod health;
 mod inner;
 mod options;
+mod replica;
 
 pub use self::connection::PoolConnection;
-pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::dedicated::DedicatedConnection;
+pub use self::guard::{QueryGuard, QueryGuardAction, QueryLimit};
+pub use self::health::PoolHealth;
+pub use self::options::{PoolConnectionMetadata, PoolOptions, ResetOnReturn};
+pub use self::replica::{is_read_only_sql, ReplicaPool};
 
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
@@ -374,6 +383,37 @@ impl<DB: Database> Pool<DB> {
         self.0.try_acquire().map(|conn| conn.into_live().reattach())
     }
 
+    /// Opens a connection for exclusive, long-running use, like `COPY`, `LISTEN` or backups.
+    ///
+    /// The connection is opened with the [connect options](Self::connect_options) and the
+    /// [`after_connect`][PoolOptions::after_connect] callback of the pool, but is not part of
+    /// it: it does not wait for or count against
+    /// [`max_connections`][PoolOptions::max_connections], and pool policies like
+    /// [`max_lifetime`][PoolOptions::max_lifetime] and [`idle_timeout`][PoolOptions::idle_timeout]
+    /// do not apply to it. It is closed when the returned [`DedicatedConnection`] is dropped.
+    ///
+    /// At most [`max_dedicated_connections`][PoolOptions::max_dedicated_connections] may be
+    /// open at a time, see [`num_dedicated()`](Self::num_dedicated); beyond that this waits for
+    /// one to be closed, up to [`acquire_timeout`][PoolOptions::acquire_timeout].
+    pub async fn acquire_dedicated(&self) -> Result<DedicatedConnection<DB>, Error> {
+        let deadline = Instant::now() + self.0.options.acquire_timeout;
+
+        let permit = crate::rt::timeout(
+            deadline_as_timeout::<DB>(deadline)?,
+            self.0.dedicated.acquire(1),
+        )
+        .await
+        .map_err(|_| Error::PoolTimedOut)?;
+
+        let raw = self.0.connect_raw(deadline).await?;
+
+        // released again by `DedicatedConnection` on drop
+        permit.disarm();
+        self.0.num_dedicated.fetch_add(1, Ordering::AcqRel);
+
+        Ok(DedicatedConnection::new(raw, Arc::clone(&self.0)))
+    }
+
     /// Retrieves a connection and immediately begins a new transaction.
     pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
         Ok(Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await?)
@@ -512,6 +552,13 @@ impl<DB: Database> Pool<DB> {
         self.0.num_idle()
     }
 
+    /// Returns the number of open connections acquired with
+    /// [`acquire_dedicated()`](Self::acquire_dedicated), which are not included in
+    /// [`size()`](Self::size).
+    pub fn num_dedicated(&self) -> u32 {
+        self.0.num_dedicated.load(Ordering::Acquire)
+    }
+
     /// Gets a clone of the connection options for this pool
     pub fn connect_options(&self) -> Arc<<DB::Connection as Connection>::Options> {
         self.0
@@ -1,8 +1,8 @@
 use crate::connection::Connection;
-use crate::database::Database;
+use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
 use crate::pool::inner::PoolInner;
-use crate::pool::Pool;
+use crate::pool::{Pool, QueryGuard};
 use futures_core::future::BoxFuture;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
@@ -43,6 +43,7 @@ use std::time::{Duration, Instant};
 /// the perspectives of both API designer and consumer.
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) reset_on_return: ResetOnReturn,
     pub(crate) after_connect: Option<
         Arc<
             dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
@@ -73,11 +74,18 @@ pub struct PoolOptions<DB: Database> {
                 + Sync,
         >,
     >,
+    pub(crate) prepare_statements: Option<
+        Arc<
+            dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>> + 'static + Send + Sync,
+        >,
+    >,
     pub(crate) max_connections: u32,
     pub(crate) acquire_timeout: Duration,
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) max_dedicated_connections: u32,
+    pub(crate) query_guard: Option<QueryGuard>,
     pub(crate) fair: bool,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
@@ -90,14 +98,18 @@ impl<DB: Database> Clone for PoolOptions<DB> {
     fn clone(&self) -> Self {
         PoolOptions {
             test_before_acquire: self.test_before_acquire,
+            reset_on_return: self.reset_on_return,
             after_connect: self.after_connect.clone(),
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
+            prepare_statements: self.prepare_statements.clone(),
             max_connections: self.max_connections,
             acquire_timeout: self.acquire_timeout,
             min_connections: self.min_connections,
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
+            max_dedicated_connections: self.max_dedicated_connections,
+            query_guard: self.query_guard.clone(),
             fair: self.fair,
             parent_pool: self.parent_pool.as_ref().map(Pool::clone),
         }
@@ -120,6 +132,24 @@ pub struct PoolConnectionMetadata {
     pub idle_for: Duration,
 }
 
+/// How a connection is reset when it is returned to the pool, see
+/// [`PoolOptions::reset_on_return`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResetOnReturn {
+    /// Return connections as they are.
+    ///
+    /// A [`Transaction`][crate::transaction::Transaction] that is dropped is still rolled back.
+    Off,
+
+    /// Roll back any transaction left open, see [`Connection::reset()`] with
+    /// `discard_session = false`.
+    Rollback,
+
+    /// Roll back any transaction left open and discard the state of the session, see
+    /// [`Connection::reset()`] with `discard_session = true`.
+    Full,
+}
+
 impl<DB: Database> Default for PoolOptions<DB> {
     fn default() -> Self {
         Self::new()
@@ -139,13 +169,17 @@ impl<DB: Database> PoolOptions<DB> {
             after_connect: None,
             before_acquire: None,
             after_release: None,
+            prepare_statements: None,
             test_before_acquire: true,
+            reset_on_return: ResetOnReturn::Off,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
             min_connections: 0,
             acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
+            max_dedicated_connections: 2,
+            query_guard: None,
             fair: true,
             parent_pool: None,
         }
@@ -260,6 +294,37 @@ impl<DB: Database> PoolOptions<DB> {
         self.idle_timeout
     }
 
+    /// Set the maximum number of connections that may be checked out with
+    /// [`Pool::acquire_dedicated()`] at the same time.
+    ///
+    /// Dedicated connections are not counted against [`max_connections`][Self::max_connections],
+    /// so this bounds how far the total number of connections opened by the pool may exceed it.
+    ///
+    /// Defaults to `2`.
+    pub fn max_dedicated_connections(mut self, max: u32) -> Self {
+        self.max_dedicated_connections = max;
+        self
+    }
+
+    /// Get the maximum number of connections that may be checked out with
+    /// [`Pool::acquire_dedicated()`] at the same time.
+    pub fn get_max_dedicated_connections(&self) -> u32 {
+        self.max_dedicated_connections
+    }
+
+    /// Set limits for queries executed directly on the pool, see [`QueryGuard`].
+    ///
+    /// Defaults to `None`, i.e. no limits.
+    pub fn query_guard(mut self, guard: impl Into<Option<QueryGuard>>) -> Self {
+        self.query_guard = guard.into();
+        self
+    }
+
+    /// Get the limits for queries executed directly on the pool.
+    pub fn get_query_guard(&self) -> Option<&QueryGuard> {
+        self.query_guard.as_ref()
+    }
+
     /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
     /// before returning the connection.
     ///
@@ -274,6 +339,23 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// Set how connections are reset when they are returned to the pool, so that state left
+    /// behind by one user, e.g. a transaction begun with raw SQL, cannot leak to the next.
+    ///
+    /// The reset runs after [`after_release`][Self::after_release]. If it fails, the connection
+    /// is closed instead of being returned.
+    ///
+    /// Defaults to [`ResetOnReturn::Off`].
+    pub fn reset_on_return(mut self, reset: ResetOnReturn) -> Self {
+        self.reset_on_return = reset;
+        self
+    }
+
+    /// Get how connections are reset when they are returned to the pool.
+    pub fn get_reset_on_return(&self) -> ResetOnReturn {
+        self.reset_on_return
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -343,6 +425,49 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Prepare `statements` on every new connection, after [`after_connect`][Self::after_connect],
+    /// so that latency-critical queries never have to wait for a statement to be prepared.
+    ///
+    /// The statements are added to the connection's statement cache with
+    /// [`Connection::prepare_cached()`], which for Postgres prepares all of them in a single
+    /// round trip. Later calls replace the list. If preparing fails, the connection is closed and
+    /// connecting is retried as if `after_connect` had failed.
+    ///
+    /// Statements beyond the capacity of the connection's statement cache are not prepared.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .prepare_statements([
+    ///         "SELECT * FROM users WHERE id = $1",
+    ///         "UPDATE users SET last_seen = now() WHERE id = $1",
+    ///     ])
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare_statements<I>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        DB: HasStatementCache,
+    {
+        let statements: Arc<[String]> = statements.into_iter().map(Into::into).collect();
+
+        self.prepare_statements = Some(Arc::new(move |conn| {
+            let statements = Arc::clone(&statements);
+
+            Box::pin(async move {
+                let statements: Vec<&str> = statements.iter().map(String::as_str).collect();
+
+                conn.prepare_cached(&statements).await
+            })
+        }));
+        self
+    }
+
     /// Perform an asynchronous action on a previously idle connection before giving it out.
     ///
     /// Alongside the connection, the closure gets [`PoolConnectionMetadata`] which contains
@@ -543,7 +668,10 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("connect_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
+            .field("max_dedicated_connections", &self.max_dedicated_connections)
+            .field("query_guard", &self.query_guard)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("reset_on_return", &self.reset_on_return)
             .finish()
     }
 }
@@ -0,0 +1,285 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::Pool;
+use crate::statement::Statement;
+
+/// Routes read-only queries to replica pools and all others to the primary pool.
+///
+/// `&ReplicaPool` is an [`Executor`] that decides for each query where to run it:
+///
+/// * Queries of a prepared [`Statement`] go to a replica if the driver reports the statement as
+///   read-only ([`Statement::is_read_only()`]) and not as controlling transactions
+///   ([`Statement::controls_transaction()`]), which only SQLite does.
+/// * Otherwise, queries go to a replica if [`is_read_only_sql()`] accepts their SQL.
+///
+/// Replicas are used in turn. Statements are prepared on the primary, and transactions are begun
+/// on it as well with [`begin()`](Self::begin).
+///
+/// Replicas may lag behind the primary, so use [`primary()`](Self::primary) to read data just
+/// written, as well as for queries that look read-only but are not, e.g. because they call a
+/// function that writes:
+///
+/// ```rust,ignore
+/// use sqlx::pool::ReplicaPool;
+///
+/// let pool = ReplicaPool::new(primary, [replica]);
+///
+/// // runs on the replica
+/// let users = sqlx::query("SELECT * FROM users").fetch_all(&pool).await?;
+///
+/// // runs on the primary
+/// sqlx::query("UPDATE users SET active = false").execute(&pool).await?;
+/// let id: i64 = sqlx::query_scalar("SELECT nextval('ids')").fetch_one(pool.primary()).await?;
+/// ```
+pub struct ReplicaPool<DB: Database> {
+    primary: Pool<DB>,
+    replicas: Arc<[Pool<DB>]>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<DB: Database> ReplicaPool<DB> {
+    /// Route writes to `primary` and reads to `replicas`, or to `primary` if there are none.
+    pub fn new(primary: Pool<DB>, replicas: impl IntoIterator<Item = Pool<DB>>) -> Self {
+        Self {
+            primary,
+            replicas: replicas.into_iter().collect(),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The pool of the primary database.
+    pub fn primary(&self) -> &Pool<DB> {
+        &self.primary
+    }
+
+    /// The pools of the replicas.
+    pub fn replicas(&self) -> &[Pool<DB>] {
+        &self.replicas
+    }
+
+    /// The pool of the replica to use next, or the primary if there are no replicas.
+    pub fn replica(&self) -> &Pool<DB> {
+        if self.replicas.is_empty() {
+            return &self.primary;
+        }
+
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+
+        &self.replicas[next % self.replicas.len()]
+    }
+
+    /// Begin a transaction on the primary.
+    pub async fn begin(&self) -> Result<crate::transaction::Transaction<'static, DB>, Error> {
+        self.primary.begin().await
+    }
+
+    /// Close the primary and all replica pools.
+    pub async fn close(&self) {
+        self.primary.close().await;
+
+        for replica in self.replicas.iter() {
+            replica.close().await;
+        }
+    }
+
+    fn route<'q, E: Execute<'q, DB>>(&self, query: &E) -> &Pool<DB> {
+        // `BEGIN`, `COMMIT` etc. are read-only, but must run on the connection of the
+        // transaction
+        let read_only = query
+            .statement()
+            .and_then(|statement| {
+                Some(statement.is_read_only()? && !statement.controls_transaction()?)
+            })
+            .unwrap_or_else(|| is_read_only_sql(query.sql()));
+
+        if read_only {
+            self.replica()
+        } else {
+            &self.primary
+        }
+    }
+}
+
+/// Whether `sql` looks like it only reads from the database, judging by its keywords.
+///
+/// This accepts single `SELECT`, `WITH`, `VALUES`, `TABLE`, `SHOW`, `EXPLAIN`, `DESCRIBE`
+/// and `DESC` statements that do not contain `INSERT`, `UPDATE`, `DELETE`, `MERGE`, `INTO`,
+/// `SHARE` or `ANALYZE` outside of literals, quoted identifiers and comments. So it rejects
+/// data-modifying common table expressions, `SELECT ... INTO`, locking reads such as
+/// `SELECT ... FOR UPDATE` and `EXPLAIN ANALYZE`. It cannot tell if a function called by the
+/// statement writes.
+pub fn is_read_only_sql(sql: &str) -> bool {
+    const READ: &[&str] = &[
+        "SELECT", "WITH", "VALUES", "TABLE", "SHOW", "EXPLAIN", "DESCRIBE", "DESC",
+    ];
+    const WRITE: &[&str] = &[
+        "INSERT", "UPDATE", "DELETE", "MERGE", "INTO", "SHARE", "ANALYZE",
+    ];
+
+    let bytes = sql.as_bytes();
+
+    let mut first = true;
+    let mut ended = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        let comment = matches!(bytes[i..], [b'-', b'-', ..] | [b'/', b'*', ..]);
+
+        if ended && !(comment || b.is_ascii_whitespace() || b == b';') {
+            // more than one statement
+            return false;
+        }
+
+        match b {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i = find(bytes, i + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = find(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+            }
+
+            b';' => {
+                ended = true;
+                i += 1;
+            }
+
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_'))
+                    .map_or(bytes.len(), |len| i + len);
+
+                let word = &sql[i..end];
+
+                if first && !READ.iter().any(|read| word.eq_ignore_ascii_case(read)) {
+                    return false;
+                }
+
+                if WRITE.iter().any(|write| word.eq_ignore_ascii_case(write)) {
+                    return false;
+                }
+
+                first = false;
+                i = end;
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    !first
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| from + i)
+}
+
+impl<DB: Database> Clone for ReplicaPool<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            primary: self.primary.clone(),
+            replicas: Arc::clone(&self.replicas),
+            next: Arc::clone(&self.next),
+        }
+    }
+}
+
+impl<DB: Database> std::fmt::Debug for ReplicaPool<DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplicaPool")
+            .field("primary", &self.primary)
+            .field("replicas", &self.replicas)
+            .finish()
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for &'_ ReplicaPool<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        self.route(&query).fetch_many(query)
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        self.route(&query).fetch_optional(query)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as HasStatement<'q>>::Statement, Error>> {
+        self.primary.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>> {
+        self.primary.describe(sql)
+    }
+}
+
+#[test]
+fn test_is_read_only_sql() {
+    assert!(is_read_only_sql("SELECT * FROM users"));
+    assert!(is_read_only_sql(
+        "  -- comment\n select 'into', \"update\" FROM t;"
+    ));
+    assert!(is_read_only_sql(
+        "WITH a AS (SELECT 1) SELECT * FROM a /* delete */"
+    ));
+    assert!(is_read_only_sql("EXPLAIN SELECT 1"));
+    assert!(is_read_only_sql("SHOW search_path"));
+    assert!(is_read_only_sql("SELECT 1; -- comment"));
+    assert!(is_read_only_sql("SELECT 1;\n/* comment */ ;"));
+
+    assert!(!is_read_only_sql(""));
+    assert!(!is_read_only_sql("INSERT INTO t VALUES (1)"));
+    assert!(!is_read_only_sql(
+        "WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d"
+    ));
+    assert!(!is_read_only_sql("SELECT * INTO backup FROM t"));
+    assert!(!is_read_only_sql("SELECT * FROM t FOR UPDATE"));
+    assert!(!is_read_only_sql("SELECT * FROM t LOCK IN SHARE MODE"));
+    assert!(!is_read_only_sql("EXPLAIN ANALYZE DELETE FROM t"));
+    assert!(!is_read_only_sql("SELECT 1; DROP TABLE t"));
+    assert!(!is_read_only_sql("SELECT 1; /* comment */ DELETE FROM t"));
+    assert!(!is_read_only_sql("SELECT 1; -- comment\nSELECT 2"));
+}
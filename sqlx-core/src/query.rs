@@ -8,9 +8,9 @@ use crate::arguments::{Arguments, IntoArguments};
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{collect_sized, extend_sized, Execute, Executor};
 use crate::statement::Statement;
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 
 /// Raw SQL query with bind parameters. Returned by [`query`][crate::query::query].
 #[must_use = "query must be executed to affect database"]
@@ -19,6 +19,8 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<A>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) size_hint: Option<usize>,
+    pub(crate) numeric_overflow: Option<NumericOverflow>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -65,6 +67,16 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.size_hint
+    }
+
+    #[inline]
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        self.numeric_overflow
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
@@ -83,6 +95,18 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
 
         self
     }
+
+    /// Bind a value like [`bind()`](Self::bind), but show it as `<redacted>` when the arguments
+    /// are logged, e.g. for passwords or tokens.
+    ///
+    /// See [`Arguments::format_redacted()`].
+    pub fn bind_sensitive<T: 'q + Send + Encode<'q, DB> + Type<DB>>(mut self, value: T) -> Self {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_sensitive(value);
+        }
+
+        self
+    }
 }
 
 impl<'q, DB, A> Query<'q, DB, A>
@@ -103,6 +127,27 @@ where
     }
 }
 
+impl<'q, DB: Database, A> Query<'q, DB, A> {
+    /// Set the number of rows the query is expected to return, so that `fetch_all()` allocates
+    /// space for them up front instead of growing its `Vec` as rows arrive.
+    ///
+    /// This is only an optimization; the query may return fewer or more rows.
+    pub fn size_hint(mut self, rows: usize) -> Self {
+        self.size_hint = Some(rows);
+        self
+    }
+
+    /// Set how numeric values that do not fit into the requested Rust type are decoded for the
+    /// rows of this query, overriding the connection's setting.
+    ///
+    /// Only the SQLite and MySQL drivers narrow numeric values; other drivers and queries run
+    /// through `Any` ignore this.
+    pub fn numeric_overflow(mut self, policy: NumericOverflow) -> Self {
+        self.numeric_overflow = Some(policy);
+        self
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -167,6 +212,21 @@ where
         executor.execute_many(self)
     }
 
+    /// Execute the query and return the rows it returned, e.g. with a `RETURNING` clause,
+    /// along with the total number of rows affected.
+    #[inline]
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(DB::QueryResult, Vec<DB::Row>), Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        collect_returning(executor.fetch_many(self)).await
+    }
+
     /// Execute the query and return the generated results as a stream.
     #[inline]
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<DB::Row, Error>>
@@ -204,6 +264,44 @@ where
         executor.fetch_all(self).await
     }
 
+    /// Execute the query and collect the generated results into `buffer`, after clearing it.
+    ///
+    /// This reuses the allocation of `buffer`, e.g. across calls in a loop, instead of
+    /// collecting a new [`Vec`] like [`fetch_all()`](Self::fetch_all). Returns the number of
+    /// results.
+    pub async fn fetch_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<DB::Row>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+    {
+        buffer.clear();
+
+        self.extend_into(executor, buffer).await
+    }
+
+    /// Execute the query and append the generated results to `buffer`, returning their number.
+    ///
+    /// If the query fails, the results generated before the error remain in `buffer`.
+    pub async fn extend_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<DB::Row>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+    {
+        let size_hint = self.size_hint;
+
+        extend_sized(executor.fetch(self), buffer, size_hint).await
+    }
+
     /// Execute the query and returns exactly one row.
     #[inline]
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<DB::Row, Error>
@@ -251,6 +349,16 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint
+    }
+
+    #[inline]
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        self.inner.numeric_overflow
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -298,6 +406,22 @@ where
         }
     }
 
+    /// Execute the query and return the mapped rows it returned, e.g. with a `RETURNING` clause,
+    /// along with the total number of rows affected.
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(DB::QueryResult, Vec<O>), Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        collect_returning(self.fetch_many(executor)).await
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -355,7 +479,49 @@ where
         F: 'e,
         O: 'e,
     {
-        self.fetch(executor).try_collect().await
+        let size_hint = self.inner.size_hint;
+
+        collect_sized(self.fetch(executor), size_hint).await
+    }
+
+    /// Execute the query and collect the generated results into `buffer`, after clearing it.
+    ///
+    /// See [`Query::fetch_into`](Query::fetch_into).
+    pub async fn fetch_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<O>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        buffer.clear();
+
+        self.extend_into(executor, buffer).await
+    }
+
+    /// Execute the query and append the generated results to `buffer`, returning their number.
+    ///
+    /// See [`Query::extend_into`](Query::extend_into).
+    pub async fn extend_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<O>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        let size_hint = self.inner.size_hint;
+
+        extend_sized(self.fetch(executor), buffer, size_hint).await
     }
 
     /// Execute the query and returns exactly one row.
@@ -406,6 +572,8 @@ where
         arguments: Some(Default::default()),
         statement: Either::Right(statement),
         persistent: true,
+        size_hint: None,
+        numeric_overflow: None,
     }
 }
 
@@ -423,9 +591,31 @@ where
         arguments: Some(arguments),
         statement: Either::Right(statement),
         persistent: true,
+        size_hint: None,
+        numeric_overflow: None,
     }
 }
 
+// the rows of `stream` along with the combined query results, for `execute_returning()`
+pub(crate) async fn collect_returning<R, O>(
+    mut stream: BoxStream<'_, Result<Either<R, O>, Error>>,
+) -> Result<(R, Vec<O>), Error>
+where
+    R: Default + Extend<R>,
+{
+    let mut result = R::default();
+    let mut rows = Vec::new();
+
+    while let Some(step) = stream.try_next().await? {
+        match step {
+            Either::Left(step) => result.extend(Some(step)),
+            Either::Right(row) => rows.push(row),
+        }
+    }
+
+    Ok((result, rows))
+}
+
 /// Make a SQL query.
 pub fn query<DB>(sql: &str) -> Query<'_, DB, <DB as HasArguments<'_>>::Arguments>
 where
@@ -436,6 +626,8 @@ where
         arguments: Some(Default::default()),
         statement: Either::Left(sql),
         persistent: true,
+        size_hint: None,
+        numeric_overflow: None,
     }
 }
 
@@ -450,5 +642,7 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        size_hint: None,
+        numeric_overflow: None,
     }
 }
@@ -1,12 +1,16 @@
 //! Runtime query-builder API.
 
+use std::cmp;
 use std::fmt::Display;
 use std::fmt::Write;
 use std::marker::PhantomData;
 
 use crate::arguments::{Arguments, IntoArguments};
+use crate::connection::Connection;
 use crate::database::{Database, HasArguments};
 use crate::encode::Encode;
+use crate::error::{DatabaseError, Error};
+use crate::executor::Executor;
 use crate::from_row::FromRow;
 use crate::query::Query;
 use crate::query_as::QueryAs;
@@ -28,6 +32,8 @@ where
     query: String,
     init_len: usize,
     arguments: Option<<DB as HasArguments<'args>>::Arguments>,
+    // pushed with `.push_bind()`, excluding arguments passed to `with_arguments()`
+    bind_count: usize,
 }
 
 impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
@@ -36,6 +42,7 @@ impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
             init_len: 0,
             query: String::default(),
             arguments: Some(Default::default()),
+            bind_count: 0,
         }
     }
 }
@@ -57,6 +64,7 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(Default::default()),
+            bind_count: 0,
         }
     }
 
@@ -75,6 +83,7 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(arguments.into_arguments()),
+            bind_count: 0,
         }
     }
 
@@ -161,9 +170,35 @@ where
             .format_placeholder(&mut self.query)
             .expect("error in format_placeholder");
 
+        self.bind_count += 1;
+
         self
     }
 
+    /// Push the SQL expression `expr`, cast to the SQL type of `T`.
+    ///
+    /// The cast is written as the driver expects it (see [`Database::cast_sql()`]), which helps
+    /// with result columns whose type differs between databases, e.g. aggregates or literals:
+    ///
+    /// ```rust
+    /// # use sqlx::{Execute, MySql, Postgres, QueryBuilder};
+    /// let mut pg = QueryBuilder::<Postgres>::new("SELECT ");
+    /// pg.push_cast::<i64>("COUNT(*)").push(" AS total FROM orders");
+    /// assert_eq!(pg.sql(), "SELECT CAST(COUNT(*) AS INT8) AS total FROM orders");
+    ///
+    /// let mut mysql = QueryBuilder::<MySql>::new("SELECT ");
+    /// mysql.push_cast::<i64>("COUNT(*)").push(" AS total FROM orders");
+    /// assert_eq!(mysql.sql(), "SELECT CAST(COUNT(*) AS SIGNED) AS total FROM orders");
+    /// ```
+    ///
+    /// The same warning about untrusted input as for [`.push()`][Self::push] applies to `expr`.
+    pub fn push_cast<T>(&mut self, expr: impl Display) -> &mut Self
+    where
+        T: Type<DB> + ?Sized,
+    {
+        self.push(DB::cast_sql(&expr.to_string(), &T::type_info()))
+    }
+
     /// Start a list separated by `separator`.
     ///
     /// The returned type exposes identical [`.push()`][Separated::push] and
@@ -326,6 +361,64 @@ where
         separated.query_builder
     }
 
+    /// Push an `INSERT` statement into `columns` of `table` with the `VALUES` pushed for
+    /// `tuples`, updating `update_columns` of existing rows that conflict on `conflict_target`.
+    ///
+    /// The conflict clause is written as the driver expects it (see
+    /// [`Database::upsert_sql()`]): `ON CONFLICT (...) DO UPDATE SET ...` for Postgres and SQLite
+    /// and `ON DUPLICATE KEY UPDATE ...` for MySQL, which checks all unique keys and ignores
+    /// `conflict_target`. Existing rows are left unchanged if `update_columns` is empty, and for
+    /// Postgres and SQLite also if `conflict_target` is empty, as `DO UPDATE` requires one.
+    ///
+    /// ```rust
+    /// # use sqlx::{Execute, MySql, Postgres, QueryBuilder};
+    /// let users = [(1, "alice@example.com"), (2, "bob@example.com")];
+    ///
+    /// let mut pg = QueryBuilder::<Postgres>::new("");
+    /// pg.push_upsert("users", &["id", "email"], &["id"], &["email"], users, |mut b, user| {
+    ///     b.push_bind(user.0).push_bind(user.1);
+    /// });
+    /// assert_eq!(
+    ///     pg.sql(),
+    ///     "INSERT INTO users (id, email) VALUES ($1, $2), ($3, $4) \
+    ///      ON CONFLICT (id) DO UPDATE SET email = excluded.email"
+    /// );
+    ///
+    /// let mut mysql = QueryBuilder::<MySql>::new("");
+    /// mysql.push_upsert("users", &["id", "email"], &["id"], &["email"], users, |mut b, user| {
+    ///     b.push_bind(user.0).push_bind(user.1);
+    /// });
+    /// assert_eq!(
+    ///     mysql.sql(),
+    ///     "INSERT INTO users (id, email) VALUES (?, ?), (?, ?) \
+    ///      ON DUPLICATE KEY UPDATE email = VALUES(email)"
+    /// );
+    /// ```
+    ///
+    /// The same caveats as for [`.push_values()`][Self::push_values] apply, and `table` and the
+    /// column names are pushed as-is, like with [`.push()`][Self::push].
+    pub fn push_upsert<I, F>(
+        &mut self,
+        table: &str,
+        columns: &[&str],
+        conflict_target: &[&str],
+        update_columns: &[&str],
+        tuples: I,
+        push_tuple: F,
+    ) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+    {
+        self.push(format_args!(
+            "INSERT INTO {table} ({}) ",
+            columns.join(", ")
+        ))
+        .push_values(tuples, push_tuple)
+        .push(' ')
+        .push(DB::upsert_sql(columns, conflict_target, update_columns))
+    }
+
     /// Creates `((a, b), (..)` statements, from `tuples`.
     ///
     /// This can be used to construct a bulk `SELECT` statement like this:
@@ -453,6 +546,8 @@ where
             arguments: self.arguments.take(),
             database: PhantomData,
             persistent: true,
+            size_hint: None,
+            numeric_overflow: None,
         }
     }
 
@@ -508,6 +603,7 @@ where
     pub fn reset(&mut self) -> &mut Self {
         self.query.truncate(self.init_len);
         self.arguments = Some(Default::default());
+        self.bind_count = 0;
 
         self
     }
@@ -594,6 +690,194 @@ where
     }
 }
 
+/// Insert `rows` in batches, isolating the rows that cause unique violations.
+///
+/// `rows` are split into batches of up to `batch_size` rows, each of which is passed to `build`
+/// to construct an `INSERT` statement, e.g. with [`QueryBuilder::push_values()`].
+/// Every batch is executed in its own transaction (or savepoint, if `conn` is already in a
+/// transaction). If a batch fails with a unique violation, it is rolled back and bisected
+/// until the offending rows are isolated; those are passed to `on_violation` together with the
+/// error, while all other rows are inserted.
+///
+/// Any other error is returned immediately. Batches inserted before then remain committed,
+/// unless `conn` is itself a transaction that is rolled back.
+///
+/// Returns the combined result of all successful batches.
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "postgres")]
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::query_builder::{insert_isolating_unique_violations, QueryBuilder};
+///
+/// let users = vec![(1, "alice"), (2, "bob"), (1, "alice again")];
+///
+/// insert_isolating_unique_violations(
+///     conn,
+///     &users,
+///     1000,
+///     |batch| {
+///         let mut qb = QueryBuilder::new("INSERT INTO users (id, name) ");
+///         qb.push_values(batch, |mut b, (id, name)| {
+///             b.push_bind(id).push_bind(name);
+///         });
+///         qb
+///     },
+///     |(id, _), error| eprintln!("skipping duplicate user {id}: {error}"),
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn insert_isolating_unique_violations<'r, C, T, B, V>(
+    conn: &mut C,
+    rows: &'r [T],
+    batch_size: usize,
+    mut build: B,
+    mut on_violation: V,
+) -> Result<<C::Database as Database>::QueryResult, Error>
+where
+    C: Connection,
+    B: for<'q> FnMut(&'q [T]) -> QueryBuilder<'q, C::Database>,
+    V: FnMut(&'r T, Box<dyn DatabaseError>),
+    for<'c> &'c mut <C::Database as Database>::Connection: Executor<'c, Database = C::Database>,
+{
+    let mut result = <C::Database as Database>::QueryResult::default();
+
+    // batches still to be inserted, in reverse order
+    let mut pending: Vec<&'r [T]> = rows.chunks(cmp::max(batch_size, 1)).rev().collect();
+
+    while let Some(batch) = pending.pop() {
+        // deconstruct the builder instead of calling `.build()` so it's not borrowed
+        // for the lifetime of the arguments
+        let QueryBuilder {
+            query: sql,
+            arguments,
+            ..
+        } = build(batch);
+        let arguments =
+            arguments.expect("QueryBuilder must be reset before reuse after `.build()`");
+
+        let mut tx = conn.begin().await?;
+
+        match tx.execute((sql.as_str(), Some(arguments))).await {
+            Ok(res) => {
+                tx.commit().await?;
+                result.extend(Some(res));
+            }
+
+            Err(Error::Database(e)) if e.is_unique_violation() => {
+                tx.rollback().await?;
+
+                if let [row] = batch {
+                    on_violation(row, e);
+                } else {
+                    let (first, second) = batch.split_at(batch.len() / 2);
+                    pending.push(second);
+                    pending.push(first);
+                }
+            }
+
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Execute `init` followed by a `VALUES` list of `rows`, split into as many statements as needed
+/// to stay within the driver's [bind parameter limit][Database::MAX_BIND_PARAMETERS].
+///
+/// Each row is pushed like with [`QueryBuilder::push_values()`]. The number of rows per
+/// statement is derived from the bind parameters of the first row, so all rows are expected to
+/// bind the same number of parameters. All statements are executed in one transaction (or
+/// savepoint, if `conn` is already in a transaction), so either all rows are inserted or none.
+///
+/// Returns the combined result of all statements. Does nothing if `rows` is empty.
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "sqlite")]
+/// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+/// use sqlx::query_builder::execute_values_chunked;
+///
+/// let users: Vec<(i64, String)> = (0..100_000)
+///     .map(|id| (id, format!("user{id}@example.com")))
+///     .collect();
+///
+/// // 200,000 parameters, split into statements of up to 32,766 parameters each
+/// let result = execute_values_chunked(
+///     conn,
+///     "INSERT INTO users (id, email) ",
+///     &users,
+///     |mut b, (id, email)| {
+///         b.push_bind(id).push_bind(email);
+///     },
+/// )
+/// .await?;
+///
+/// assert_eq!(result.rows_affected(), 100_000);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn execute_values_chunked<C, T, F>(
+    conn: &mut C,
+    init: &str,
+    rows: &[T],
+    mut push_tuple: F,
+) -> Result<<C::Database as Database>::QueryResult, Error>
+where
+    C: Connection,
+    F: for<'q> FnMut(Separated<'_, 'q, C::Database, &'static str>, &'q T),
+    for<'c> &'c mut <C::Database as Database>::Connection: Executor<'c, Database = C::Database>,
+{
+    let mut result = <C::Database as Database>::QueryResult::default();
+
+    if rows.is_empty() {
+        return Ok(result);
+    }
+
+    let mut remaining = rows;
+
+    // the first statement has a single row, to count its bind parameters
+    let mut rows_per_statement = 1;
+    let mut first = true;
+
+    let mut tx = conn.begin().await?;
+
+    while !remaining.is_empty() {
+        let (rows, rest) = remaining.split_at(cmp::min(rows_per_statement, remaining.len()));
+        remaining = rest;
+
+        // deconstruct the builder instead of calling `.build()` so it's not borrowed
+        // for the lifetime of the arguments
+        let QueryBuilder {
+            query: sql,
+            arguments,
+            bind_count,
+            ..
+        } = {
+            let mut qb = QueryBuilder::<C::Database>::new(init);
+            qb.push_values(rows, &mut push_tuple);
+            qb
+        };
+        let arguments =
+            arguments.expect("QueryBuilder must be reset before reuse after `.build()`");
+
+        if first {
+            first = false;
+            rows_per_statement = cmp::max(
+                <C::Database as Database>::MAX_BIND_PARAMETERS / cmp::max(bind_count, 1),
+                1,
+            );
+        }
+
+        result.extend(Some(tx.execute((sql.as_str(), Some(arguments))).await?));
+    }
+
+    tx.commit().await?;
+
+    Ok(result)
+}
+
 #[cfg(all(test, feature = "postgres"))]
 mod test {
     use crate::postgres::Postgres;
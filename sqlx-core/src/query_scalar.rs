@@ -6,12 +6,13 @@ use crate::arguments::IntoArguments;
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{collect_sized, extend_sized, Execute, Executor};
 use crate::from_row::FromRow;
+use crate::query::collect_returning;
 use crate::query_as::{
     query_as, query_as_with, query_statement_as, query_statement_as_with, QueryAs,
 };
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`] on `(O,)`.
 /// Returned from [`query_scalar`].
@@ -42,6 +43,16 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.inner.size_hint
+    }
+
+    #[inline]
+    fn numeric_overflow(&self) -> Option<NumericOverflow> {
+        self.inner.inner.numeric_overflow
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -52,6 +63,14 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Argum
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// Bind a value that is shown as `<redacted>` when the arguments are logged.
+    ///
+    /// See [`Query::bind_sensitive`](crate::query::Query::bind_sensitive).
+    pub fn bind_sensitive<T: 'q + Send + Encode<'q, DB> + Type<DB>>(mut self, value: T) -> Self {
+        self.inner = self.inner.bind_sensitive(value);
+        self
+    }
 }
 
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -72,6 +91,24 @@ where
     }
 }
 
+impl<'q, DB: Database, O, A> QueryScalar<'q, DB, O, A> {
+    /// Set the number of rows the query is expected to return.
+    ///
+    /// See [`Query::size_hint`](crate::query::Query::size_hint).
+    pub fn size_hint(mut self, rows: usize) -> Self {
+        self.inner = self.inner.size_hint(rows);
+        self
+    }
+
+    /// Set how numeric values that do not fit into the requested Rust type are decoded.
+    ///
+    /// See [`Query::numeric_overflow`](crate::query::Query::numeric_overflow).
+    pub fn numeric_overflow(mut self, policy: NumericOverflow) -> Self {
+        self.inner = self.inner.numeric_overflow(policy);
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -114,6 +151,22 @@ where
             .boxed()
     }
 
+    /// Execute the query and return the first column of the rows it returned, e.g. with a `RETURNING`
+    /// clause, along with the total number of rows affected.
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<(DB::QueryResult, Vec<O>), Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        A: 'e,
+        O: 'e,
+    {
+        collect_returning(self.fetch_many(executor)).await
+    }
+
     /// Execute the query and return all the generated results, collected into a [`Vec`].
     #[inline]
     pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<O>, Error>
@@ -124,11 +177,54 @@ where
         (O,): 'e,
         A: 'e,
     {
-        self.inner
-            .fetch(executor)
-            .map_ok(|it| it.0)
-            .try_collect()
-            .await
+        let size_hint = self.inner.inner.size_hint;
+
+        collect_sized(self.inner.fetch(executor).map_ok(|it| it.0), size_hint).await
+    }
+
+    /// Execute the query and collect the generated results into `buffer`, after clearing it.
+    ///
+    /// See [`Query::fetch_into`](crate::query::Query::fetch_into).
+    pub async fn fetch_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<O>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        (O,): 'e,
+        A: 'e,
+    {
+        buffer.clear();
+
+        self.extend_into(executor, buffer).await
+    }
+
+    /// Execute the query and append the generated results to `buffer`, returning their number.
+    ///
+    /// See [`Query::extend_into`](crate::query::Query::extend_into).
+    pub async fn extend_into<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        buffer: &mut Vec<O>,
+    ) -> Result<usize, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        (O,): 'e,
+        A: 'e,
+    {
+        let size_hint = self.inner.inner.size_hint;
+
+        extend_sized(
+            self.inner.fetch(executor).map_ok(|it| it.0),
+            buffer,
+            size_hint,
+        )
+        .await
     }
 
     /// Execute the query and returns exactly one row.
@@ -50,6 +50,18 @@ pub trait Row: Unpin + Send + Sync + 'static {
         Ok(&self.columns()[index.index(self)?])
     }
 
+    /// Gets the position of the column named `name` or a `ColumnNotFound` error.
+    ///
+    /// Rows look up columns by name in a map shared by all rows of the statement, which still
+    /// hashes the name on each call. In hot loops, look up the position once and index the
+    /// following rows by it.
+    fn column_index(&self, name: &str) -> Result<usize, Error>
+    where
+        for<'n> &'n str: ColumnIndex<Self>,
+    {
+        name.index(self)
+    }
+
     /// Gets all columns in this statement.
     fn columns(&self) -> &[<Self::Database as Database>::Column];
 
@@ -112,6 +112,41 @@ pub async fn yield_now() {
     missing_rt(())
 }
 
+/// Run `f` to completion on a temporary runtime, for blocking versions of async functions.
+///
+/// Fails instead of panicking if called from within a Tokio runtime, which cannot be blocked.
+// the `return`s are needed when several runtime features are enabled
+#[allow(clippy::needless_return)]
+pub fn block_on<F: Future>(f: F) -> Result<F::Output, crate::error::Error> {
+    #[cfg(feature = "_rt-tokio")]
+    {
+        if rt_tokio::available() {
+            return Err(crate::error::Error::Configuration(
+                "cannot block on a future from within a Tokio runtime".into(),
+            ));
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        return Ok(runtime.block_on(f));
+    }
+
+    #[cfg(all(feature = "_rt-async-std", not(feature = "_rt-tokio")))]
+    {
+        return Ok(async_std::task::block_on(f));
+    }
+
+    #[cfg(not(any(feature = "_rt-async-std", feature = "_rt-tokio")))]
+    {
+        drop(f);
+        Err(crate::error::Error::Configuration(
+            "at least one of the `runtime-*` features must be enabled".into(),
+        ))
+    }
+}
+
 #[track_caller]
 pub fn test_block_on<F: Future>(f: F) -> F::Output {
     #[cfg(feature = "_rt-tokio")]
@@ -0,0 +1,287 @@
+//! Verify at startup that the database schema matches what the application expects.
+//!
+//! Query macros check queries against the schema at compile time, but nothing stops the schema
+//! from drifting afterwards, e.g. because a migration was forgotten or applied to the wrong
+//! database. [`verify()`] checks the tables behind [`FromRow`](crate::from_row::FromRow)
+//! structs so the application can fail fast instead of with the first affected query:
+//!
+//! ```rust,ignore
+//! use sqlx::schema::{self, SchemaExpectations, TableExpectation};
+//!
+//! #[derive(sqlx::FromRow, SchemaExpectations)]
+//! struct User {
+//!     id: i64,
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let mut conn = pool.acquire().await?;
+//!
+//! schema::verify(&mut *conn, &[TableExpectation::for_row::<User>("users")]).await?;
+//! ```
+//!
+//! For each field, the table must have a column of the same name (subject to `rename` and
+//! `rename_all`) with a type [compatible](crate::types::Type::compatible) with the field type.
+//! Columns that may be `NULL` must map to `Option` fields. Fields with `#[sqlx(default)]` may be
+//! missing from the table, `#[sqlx(skip)]` fields are ignored and `#[sqlx(flatten)]` fields
+//! contribute the columns of the flattened struct.
+//!
+//! Columns are described with a `SELECT * FROM <table>` statement, so the nullability checks are
+//! only as good as the driver's inference for it; columns of unknown nullability pass.
+
+use std::any;
+use std::fmt::{self, Debug, Display};
+
+use crate::column::Column;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::DynExecutor;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+
+/// Implemented by `#[derive(SchemaExpectations)]` for [`FromRow`](crate::from_row::FromRow)
+/// structs, listing the columns [`from_row()`](crate::from_row::FromRow::from_row) reads.
+pub trait SchemaExpectations<DB: Database> {
+    fn columns() -> Vec<ColumnExpectation<DB>>;
+}
+
+/// The columns a table is expected to have, see [`verify()`].
+pub struct TableExpectation<DB: Database> {
+    table: String,
+    columns: Vec<ColumnExpectation<DB>>,
+}
+
+/// A column a table is expected to have, see [`verify()`].
+pub struct ColumnExpectation<DB: Database> {
+    name: &'static str,
+    rust_type: &'static str,
+    nullable: bool,
+    optional: bool,
+    compatible: fn(&DB::TypeInfo) -> bool,
+}
+
+/// The error returned by [`verify()`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SchemaError {
+    /// A table could not be described, e.g. because it does not exist.
+    #[error("error describing table {table:?}: {source}")]
+    Execute {
+        table: String,
+        #[source]
+        source: Error,
+    },
+
+    /// The tables do not match the expectations.
+    #[error("database schema does not match: {}", DisplayMismatches(.0))]
+    Mismatch(Vec<SchemaMismatch>),
+}
+
+/// A column that does not match its [`ColumnExpectation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    pub table: String,
+    pub column: String,
+    pub kind: SchemaMismatchKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaMismatchKind {
+    /// The table has no column of this name.
+    MissingColumn,
+
+    /// The column's SQL type is not compatible with the Rust type.
+    IncompatibleType {
+        rust_type: &'static str,
+        sql_type: String,
+    },
+
+    /// The column may be `NULL`, but the Rust type is not an `Option`.
+    Nullable { rust_type: &'static str },
+}
+
+impl<DB: Database> TableExpectation<DB> {
+    /// Expect `table` to have the columns read by `T`.
+    pub fn for_row<T: SchemaExpectations<DB>>(table: impl Into<String>) -> Self {
+        Self::new(table, T::columns())
+    }
+
+    /// Expect `table` to have `columns`.
+    pub fn new(table: impl Into<String>, columns: Vec<ColumnExpectation<DB>>) -> Self {
+        Self {
+            table: table.into(),
+            columns,
+        }
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn columns(&self) -> &[ColumnExpectation<DB>] {
+        &self.columns
+    }
+}
+
+impl<DB: Database> ColumnExpectation<DB> {
+    /// Expect a non-null column `name` which is compatible with `T`.
+    pub fn new<T: Type<DB>>(name: &'static str) -> Self {
+        Self {
+            name,
+            rust_type: any::type_name::<T>(),
+            nullable: false,
+            optional: false,
+            compatible: T::compatible,
+        }
+    }
+
+    /// Allow the column to contain `NULL`, i.e. `T` is an `Option`.
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Allow the column to be missing, as for `#[sqlx(default)]` fields.
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Check the described column, `None` if it is missing.
+    fn check(&self, column: Option<(&DB::TypeInfo, Option<bool>)>) -> Option<SchemaMismatchKind> {
+        let Some((type_info, nullable)) = column else {
+            return (!self.optional).then_some(SchemaMismatchKind::MissingColumn);
+        };
+
+        // no type to check if the driver could not infer one, e.g. for SQLite columns without a
+        // declared type
+        if !type_info.is_null() && !(self.compatible)(type_info) {
+            return Some(SchemaMismatchKind::IncompatibleType {
+                rust_type: self.rust_type,
+                sql_type: type_info.name().to_owned(),
+            });
+        }
+
+        if nullable == Some(true) && !self.nullable {
+            return Some(SchemaMismatchKind::Nullable {
+                rust_type: self.rust_type,
+            });
+        }
+
+        None
+    }
+}
+
+/// Check each table against its expectations.
+///
+/// Returns [`SchemaError::Mismatch`] listing every mismatched column of every table, or
+/// [`SchemaError::Execute`] for the first table that could not be described.
+pub async fn verify<DB: Database>(
+    executor: &mut (dyn DynExecutor<DB> + '_),
+    expectations: &[TableExpectation<DB>],
+) -> Result<(), SchemaError> {
+    let mut mismatches = Vec::new();
+
+    for expectation in expectations {
+        let sql = format!("SELECT * FROM {}", expectation.table);
+
+        let describe =
+            executor
+                .dyn_describe(&sql)
+                .await
+                .map_err(|source| SchemaError::Execute {
+                    table: expectation.table.clone(),
+                    source,
+                })?;
+
+        for column in &expectation.columns {
+            let described = describe
+                .columns()
+                .iter()
+                .position(|c| c.name() == column.name)
+                .map(|i| (describe.columns()[i].type_info(), describe.nullable(i)));
+
+            if let Some(kind) = column.check(described) {
+                mismatches.push(SchemaMismatch {
+                    table: expectation.table.clone(),
+                    column: column.name.to_owned(),
+                    kind,
+                });
+            }
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaError::Mismatch(mismatches))
+    }
+}
+
+impl<DB: Database> Debug for TableExpectation<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableExpectation")
+            .field("table", &self.table)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+impl<DB: Database> Debug for ColumnExpectation<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnExpectation")
+            .field("name", &self.name)
+            .field("rust_type", &self.rust_type)
+            .field("nullable", &self.nullable)
+            .field("optional", &self.optional)
+            .finish()
+    }
+}
+
+impl Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SchemaMismatch {
+            table,
+            column,
+            kind,
+        } = self;
+
+        match kind {
+            SchemaMismatchKind::MissingColumn => write!(f, "{table}.{column} does not exist"),
+            SchemaMismatchKind::IncompatibleType {
+                rust_type,
+                sql_type,
+            } => write!(
+                f,
+                "{table}.{column} is of SQL type {sql_type}, which is not compatible with Rust \
+                 type {rust_type}"
+            ),
+            SchemaMismatchKind::Nullable { rust_type } => write!(
+                f,
+                "{table}.{column} may be NULL, but is decoded as non-optional Rust type \
+                 {rust_type}"
+            ),
+        }
+    }
+}
+
+struct DisplayMismatches<'a>(&'a [SchemaMismatch]);
+
+impl Display for DisplayMismatches<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, mismatch) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            Display::fmt(mismatch, f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+//! Append [sqlcommenter]-style comments to executed SQL.
+//!
+//! APM tools and database-side query insights (e.g. Cloud SQL, `pg_stat_statements` viewers)
+//! can correlate database load with application traces if each query carries the trace context
+//! it was executed in, as a trailing comment like:
+//!
+//! ```sql
+//! SELECT * FROM users WHERE id = $1 /*route='%2Fusers%2F%3Aid',traceparent='00-...-01'*/
+//! ```
+//!
+//! This is opt-in: nothing is appended until a provider is installed with [`set_provider()`].
+//! The provider is called for every query executed through a connection's [`Executor`] impl,
+//! in the context (thread, task, span) the query is executed from, and fills in the tags to
+//! append:
+//!
+//! ```rust,ignore
+//! sqlx::sqlcommenter::set_provider(|tags| {
+//!     let context = opentelemetry::Context::current();
+//!     let span = context.span().span_context();
+//!
+//!     if span.is_valid() {
+//!         tags.traceparent(
+//!             u128::from_be_bytes(span.trace_id().to_bytes()),
+//!             u64::from_be_bytes(span.span_id().to_bytes()),
+//!             span.is_sampled(),
+//!         );
+//!     }
+//!
+//!     if let Some(route) = CURRENT_ROUTE.try_with(|route| route.clone()).ok() {
+//!         tags.insert("route", route);
+//!     }
+//! })?;
+//! ```
+//!
+//! Queries that already contain a comment are left alone, as required by the specification.
+//!
+//! Note that the comment is part of the query string, so queries that differ only by their
+//! comment are distinct statements for the prepared statement cache. Tags that change with every
+//! request, like `traceparent`, therefore effectively disable statement caching.
+//!
+//! [sqlcommenter]: https://google.github.io/sqlcommenter/spec/
+//! [`Executor`]: crate::executor::Executor
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use once_cell::sync::OnceCell;
+
+use crate::error::Error;
+
+type Provider = Box<dyn Fn(&mut SqlCommentTags) + Send + Sync + 'static>;
+
+static PROVIDER: OnceCell<Provider> = OnceCell::new();
+
+/// Install the process-wide provider of comment tags.
+///
+/// May only be called once; returns an error if a provider was already installed.
+pub fn set_provider<F>(provider: F) -> Result<(), Error>
+where
+    F: Fn(&mut SqlCommentTags) + Send + Sync + 'static,
+{
+    PROVIDER
+        .set(Box::new(provider))
+        .map_err(|_| Error::Configuration("a sqlcommenter provider is already installed".into()))
+}
+
+/// The key-value pairs of a sqlcommenter comment.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SqlCommentTags {
+    // sorted by key, as required by the specification
+    tags: BTreeMap<String, String>,
+}
+
+impl SqlCommentTags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tag `key` to `value`, replacing any previous value.
+    ///
+    /// Both are escaped when the comment is formatted.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the `traceparent` tag in the [W3C Trace Context] format.
+    ///
+    /// [W3C Trace Context]: https://www.w3.org/TR/trace-context/#traceparent-header
+    pub fn traceparent(&mut self, trace_id: u128, span_id: u64, sampled: bool) -> &mut Self {
+        self.insert(
+            "traceparent",
+            format!(
+                "00-{trace_id:032x}-{span_id:016x}-{:02x}",
+                u8::from(sampled)
+            ),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Format the tags as a comment, e.g. `/*action='run',route='%2Fjobs'*/`.
+    pub fn to_comment(&self) -> String {
+        let mut comment = String::from("/*");
+
+        for (i, (key, value)) in self.tags.iter().enumerate() {
+            if i > 0 {
+                comment.push(',');
+            }
+
+            url_encode_into(&mut comment, key);
+            comment.push_str("='");
+            url_encode_into(&mut comment, value);
+            comment.push('\'');
+        }
+
+        comment.push_str("*/");
+        comment
+    }
+
+    /// Append the tags as a comment to `sql`, before any trailing semicolon.
+    ///
+    /// Returns `sql` unchanged if there are no tags or it already contains a comment.
+    pub fn apply<'q>(&self, sql: &'q str) -> Cow<'q, str> {
+        if self.is_empty() || sql.contains("/*") || sql.contains("--") {
+            return Cow::Borrowed(sql);
+        }
+
+        let trimmed = sql.trim_end();
+        let (statement, terminator) = match trimmed.strip_suffix(';') {
+            Some(statement) => (statement.trim_end(), ";"),
+            None => (trimmed, ""),
+        };
+
+        Cow::Owned(format!("{statement} {}{terminator}", self.to_comment()))
+    }
+}
+
+/// Append the tags of the installed provider to `sql`, if any.
+///
+/// Called by the drivers for every executed query.
+pub fn apply(sql: &str) -> Cow<'_, str> {
+    let Some(provider) = PROVIDER.get() else {
+        return Cow::Borrowed(sql);
+    };
+
+    let mut tags = SqlCommentTags::new();
+    provider(&mut tags);
+
+    tags.apply(sql)
+}
+
+// percent-encodes everything but unreserved characters (RFC 3986), which also takes care of
+// the quotes the specification would otherwise require to be escaped
+fn url_encode_into(buf: &mut String, s: &str) {
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                buf.push(byte as char)
+            }
+            _ => {
+                let _ = write!(buf, "%{byte:02X}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_apply_tags() {
+    let mut tags = SqlCommentTags::new();
+
+    assert_eq!(tags.apply("SELECT 1"), "SELECT 1");
+
+    tags.insert("route", "/users/:id")
+        .insert("action", "it's")
+        .traceparent(0x4bf92f3577b34da6a3ce929d0e0e4736, 0x00f067aa0ba902b7, true);
+
+    assert_eq!(
+        tags.apply("SELECT 1;\n"),
+        "SELECT 1 /*action='it%27s',route='%2Fusers%2F%3Aid',\
+         traceparent='00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01'*/;"
+    );
+
+    assert_eq!(
+        tags.apply("SELECT 1 /* existing */"),
+        "SELECT 1 /* existing */"
+    );
+}
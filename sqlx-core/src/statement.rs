@@ -35,6 +35,20 @@ pub trait Statement<'q>: Send + Sync {
     /// Get the columns expected to be returned by executing this statement.
     fn columns(&self) -> &[<Self::Database as Database>::Column];
 
+    /// Whether executing this statement only reads from the database, if the driver can tell.
+    ///
+    /// Only SQLite reports this, from the prepared statement; other drivers return `None`.
+    fn is_read_only(&self) -> Option<bool> {
+        None
+    }
+
+    /// Whether this statement begins or ends a transaction or savepoint, if the driver can tell.
+    ///
+    /// Only SQLite reports this; other drivers return `None`.
+    fn controls_transaction(&self) -> Option<bool> {
+        None
+    }
+
     /// Gets the column information at `index`.
     ///
     /// A string index can be used to access a column by name and a `usize` index
@@ -11,6 +11,7 @@ use crate::error::Error;
 use crate::executor::Executor;
 use crate::migrate::{Migrate, Migrator};
 use crate::pool::{Pool, PoolConnection, PoolOptions};
+use crate::transaction::Transaction;
 
 mod fixtures;
 
@@ -68,6 +69,34 @@ pub struct TestContext<DB: Database> {
     pub pool_opts: PoolOptions<DB>,
     pub connect_opts: <DB::Connection as Connection>::Options,
     pub db_name: String,
+    migrations_applied: bool,
+}
+
+impl<DB: Database> TestContext<DB> {
+    pub fn new(
+        pool_opts: PoolOptions<DB>,
+        connect_opts: <DB::Connection as Connection>::Options,
+        db_name: String,
+    ) -> Self {
+        TestContext {
+            pool_opts,
+            connect_opts,
+            db_name,
+            migrations_applied: false,
+        }
+    }
+
+    /// Mark the migrations in [`TestArgs::migrator`] as already applied to the database,
+    /// e.g. because it was cloned from a template, so they are not run again.
+    pub fn with_migrations_applied(mut self, migrations_applied: bool) -> Self {
+        self.migrations_applied = migrations_applied;
+        self
+    }
+
+    /// Whether the migrations in [`TestArgs::migrator`] were already applied to the database.
+    pub fn migrations_applied(&self) -> bool {
+        self.migrations_applied
+    }
 }
 
 impl<DB, Fut> TestFn for fn(Pool<DB>) -> Fut
@@ -168,6 +197,35 @@ impl<T, E> TestTermination for Result<T, E> {
     }
 }
 
+/// Run `callback` in a transaction, or a savepoint if `conn` is already in one, that is always
+/// rolled back, even if `callback` panics.
+///
+/// Tests that only see their own changes can then share one migrated database instead of each
+/// creating one:
+///
+/// ```rust,ignore
+/// sqlx::testing::with_test_transaction(&mut conn, |tx| Box::pin(async move {
+///     sqlx::query("DELETE FROM users").execute(&mut **tx).await?;
+///     assert_eq!(count_users(&mut **tx).await?, 0);
+///     Ok::<_, sqlx::Error>(())
+/// }))
+/// .await??;
+/// ```
+///
+/// Returns the output of `callback`, or an error if the transaction could not be begun or
+/// rolled back.
+pub async fn with_test_transaction<C, F, R>(conn: &mut C, callback: F) -> Result<R, Error>
+where
+    C: Connection,
+    for<'c> F: FnOnce(&'c mut Transaction<'_, C::Database>) -> BoxFuture<'c, R>,
+{
+    let mut transaction = conn.begin().await?;
+    let ret = callback(&mut transaction).await;
+    transaction.rollback().await?;
+
+    Ok(ret)
+}
+
 fn run_test_with_pool<DB, F, Fut>(args: TestArgs, test_fn: F) -> Fut::Output
 where
     DB: TestSupport,
@@ -212,7 +270,7 @@ where
             .await
             .expect("failed to connect to setup test database");
 
-        setup_test_db::<DB>(&test_context.connect_opts, &args).await;
+        setup_test_db::<DB>(&test_context, &args).await;
 
         let res = test_fn(test_context.pool_opts, test_context.connect_opts).await;
 
@@ -229,19 +287,18 @@ where
     })
 }
 
-async fn setup_test_db<DB: Database>(
-    copts: &<DB::Connection as Connection>::Options,
-    args: &TestArgs,
-) where
+async fn setup_test_db<DB: Database>(test_context: &TestContext<DB>, args: &TestArgs)
+where
     DB::Connection: Migrate + Sized,
     for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
 {
-    let mut conn = copts
+    let mut conn = test_context
+        .connect_opts
         .connect()
         .await
         .expect("failed to connect to test database");
 
-    if let Some(migrator) = args.migrator {
+    if let Some(migrator) = args.migrator.filter(|_| !test_context.migrations_applied()) {
         migrator
             .run_direct(&mut conn)
             .await
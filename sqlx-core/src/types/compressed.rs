@@ -0,0 +1,256 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::error::BoxDynError;
+
+/// Store a large text or binary value compressed with the codec `C`, e.g. [`Zstd`] or [`Lz4`].
+///
+/// The value is compressed when it is bound and decompressed when it is decoded, so the column
+/// holds the compressed bytes and has to be binary, e.g. `BYTEA` in Postgres, `BLOB` in SQLite
+/// or `LONGBLOB` in MySQL. As the codec is part of the type, each column gets its own:
+///
+/// ```rust,ignore
+/// use sqlx::types::{Compressed, Lz4, Zstd};
+///
+/// #[derive(sqlx::FromRow)]
+/// struct Event {
+///     id: i64,
+///     // written once and kept for long, so worth the slower, denser codec
+///     payload: Compressed<String, Zstd>,
+///     // read back often
+///     trace: Compressed<Vec<u8>, Lz4>,
+/// }
+///
+/// sqlx::query("INSERT INTO events (payload, trace) VALUES (?, ?)")
+///     .bind(Compressed::<_, Zstd>::new(payload))
+///     .bind(Compressed::<_, Lz4>::new(trace))
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// The compressed bytes cannot be searched or compared in SQL. Values that decompress to more
+/// than [`MAX_DECOMPRESSED_LEN`] bytes fail to decode, so that a small, maliciously crafted
+/// value cannot exhaust memory.
+///
+/// ### Panics
+///
+/// Compressing a value only fails if memory runs out, but as the design of the `Encode` trait
+/// assumes encoding is infallible, this panics.
+pub struct Compressed<T, C> {
+    value: T,
+    codec: PhantomData<fn() -> C>,
+}
+
+/// The largest value a [`Compressed`] is decompressed to, 1 GiB.
+pub const MAX_DECOMPRESSED_LEN: usize = 1 << 30;
+
+/// A compression algorithm for [`Compressed`].
+pub trait Codec {
+    /// Compress `data`.
+    fn compress(data: &[u8]) -> Vec<u8>;
+
+    /// Decompress `data`, failing if it was not compressed by [`compress()`](Self::compress).
+    ///
+    /// Implementations must fail instead of decompressing more than [`MAX_DECOMPRESSED_LEN`]
+    /// bytes.
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, BoxDynError>;
+}
+
+/// A value that can be stored [`Compressed`].
+pub trait CompressedData: AsRef<[u8]> + Sized {
+    /// Convert the decompressed bytes back into the value.
+    fn from_decompressed(data: Vec<u8>) -> Result<Self, BoxDynError>;
+}
+
+/// The [Zstandard](https://facebook.github.io/zstd/) codec, which compresses well at a high
+/// speed.
+#[cfg(feature = "zstd")]
+#[derive(Debug)]
+pub enum Zstd {}
+
+/// The [LZ4](https://lz4.org/) codec, which compresses less than [`Zstd`] but decompresses
+/// faster.
+#[cfg(feature = "lz4")]
+#[derive(Debug)]
+pub enum Lz4 {}
+
+impl<T, C> Compressed<T, C> {
+    /// Compress `value` with `C` when it is bound.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            codec: PhantomData,
+        }
+    }
+
+    /// Extract the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: CompressedData, C: Codec> Compressed<T, C> {
+    /// Compress the value.
+    pub fn compress(&self) -> Vec<u8> {
+        C::compress(self.value.as_ref())
+    }
+
+    /// Decompress `data` into a value.
+    pub fn decompress(data: &[u8]) -> Result<Self, BoxDynError> {
+        T::from_decompressed(C::decompress(data)?).map(Self::new)
+    }
+}
+
+impl<T, C> From<T> for Compressed<T, C> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, C> Deref for Compressed<T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, C> DerefMut for Compressed<T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T: Debug, C> Debug for Compressed<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Compressed").field(&self.value).finish()
+    }
+}
+
+impl<T: Clone, C> Clone for Compressed<T, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Default, C> Default for Compressed<T, C> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for Compressed<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, C> Eq for Compressed<T, C> {}
+
+impl CompressedData for Vec<u8> {
+    fn from_decompressed(data: Vec<u8>) -> Result<Self, BoxDynError> {
+        Ok(data)
+    }
+}
+
+impl CompressedData for String {
+    fn from_decompressed(data: Vec<u8>) -> Result<Self, BoxDynError> {
+        Ok(String::from_utf8(data)?)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Codec for Zstd {
+    fn compress(data: &[u8]) -> Vec<u8> {
+        // 0 is the default level
+        zstd::bulk::compress(data, 0).expect("failed to compress with zstd")
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+        zstd_decompress(data, MAX_DECOMPRESSED_LEN)
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl Codec for Lz4 {
+    fn compress(data: &[u8]) -> Vec<u8> {
+        lz4_flex::compress_prepend_size(data)
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+        lz4_decompress(data, MAX_DECOMPRESSED_LEN)
+    }
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>, BoxDynError> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+
+    // one byte more than allowed tells an oversized value apart from one that fits exactly
+    zstd::stream::read::Decoder::new(data)?
+        .take(max_len as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+
+    if decompressed.len() > max_len {
+        return Err(too_large(max_len));
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(feature = "lz4")]
+fn lz4_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>, BoxDynError> {
+    // the prepended size is only trusted once it is checked
+    let (len, compressed) = lz4_flex::block::uncompressed_size(data)?;
+
+    if len > max_len {
+        return Err(too_large(max_len));
+    }
+
+    Ok(lz4_flex::decompress(compressed, len)?)
+}
+
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+fn too_large(max_len: usize) -> BoxDynError {
+    format!("compressed value is larger than {max_len} bytes when decompressed").into()
+}
+
+#[cfg(all(test, feature = "zstd", feature = "lz4"))]
+fn test_codec<C: Codec>() {
+    let text = "a large, repetitive payload ".repeat(100);
+
+    let compressed = Compressed::<_, C>::new(text.clone()).compress();
+    assert!(compressed.len() < text.len() / 10);
+
+    let decompressed = Compressed::<String, C>::decompress(&compressed).unwrap();
+    assert_eq!(decompressed.into_inner(), text);
+
+    assert!(Compressed::<String, C>::decompress(&[1]).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "lz4"))]
+fn test_compressed_roundtrip() {
+    test_codec::<Zstd>();
+    test_codec::<Lz4>();
+}
+
+#[test]
+#[cfg(all(feature = "zstd", feature = "lz4"))]
+fn test_decompressed_len_is_capped() {
+    let data = [0u8; 1000];
+
+    let compressed = Zstd::compress(&data);
+    assert_eq!(zstd_decompress(&compressed, 1000).unwrap().len(), 1000);
+    assert!(zstd_decompress(&compressed, 999).is_err());
+
+    let compressed = Lz4::compress(&data);
+    assert_eq!(lz4_decompress(&compressed, 1000).unwrap().len(), 1000);
+    assert!(lz4_decompress(&compressed, 999).is_err());
+
+    // a prepended size of 4 GiB is rejected before anything is allocated
+    assert!(Lz4::decompress(&[0xff, 0xff, 0xff, 0xff, 0]).is_err());
+}
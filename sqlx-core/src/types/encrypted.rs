@@ -0,0 +1,236 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+use crate::error::BoxDynError;
+
+// the length of the random nonce stored in front of the ciphertext
+const NONCE_LEN: usize = 24;
+
+/// A sensitive text or binary value stored encrypted with the key of `K`.
+///
+/// The value is encrypted with XChaCha20-Poly1305 by [`seal()`](Self::seal) and decrypted by
+/// [`open()`](Self::open), so the database only ever sees the ciphertext. The column has to be
+/// binary, e.g. `BYTEA` in Postgres, `BLOB` in SQLite or `VARBINARY` in MySQL.
+///
+/// The name of the column and the identity of the row, e.g. its primary key, are authenticated
+/// along with the value, so a ciphertext copied into another column or row does not open. Opening
+/// also fails if the ciphertext was modified or encrypted with another key:
+///
+/// ```rust,ignore
+/// use sqlx::types::{Encrypted, KeyProvider};
+///
+/// struct UserDataKey;
+///
+/// impl KeyProvider for UserDataKey {
+///     fn key() -> [u8; 32] {
+///         static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+///
+///         *KEY.get_or_init(|| load_key_from_vault("user-data"))
+///     }
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i64,
+///     email: Encrypted<String, UserDataKey>,
+/// }
+///
+/// sqlx::query("INSERT INTO users (id, email) VALUES (?, ?)")
+///     .bind(id)
+///     .bind(Encrypted::<_, UserDataKey>::seal(&email, "users.email", id.to_be_bytes())?)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let user: User = sqlx::query_as("SELECT id, email FROM users WHERE id = ?")
+///     .bind(id)
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// let email = user.email.open("users.email", user.id.to_be_bytes())?;
+/// ```
+///
+/// Databases that need separate keys use separate key providers.
+///
+/// Every encryption uses a new random nonce, so equal values have different ciphertexts and
+/// encrypted columns cannot be searched, compared or indexed in SQL.
+pub struct Encrypted<T, K> {
+    // the nonce followed by the ciphertext
+    data: Vec<u8>,
+    marker: PhantomData<fn() -> (T, K)>,
+}
+
+/// The key of an [`Encrypted`] column.
+pub trait KeyProvider {
+    /// The 256-bit key, called on every encryption and decryption.
+    fn key() -> [u8; 32];
+}
+
+/// A value that can be stored [`Encrypted`].
+pub trait EncryptedData: AsRef<[u8]> + Sized {
+    /// Convert the decrypted bytes back into the value.
+    fn from_decrypted(data: Vec<u8>) -> Result<Self, BoxDynError>;
+}
+
+impl<T, K> Encrypted<T, K> {
+    /// Wrap `data` from [`as_bytes()`](Self::as_bytes) without checking it; it is
+    /// authenticated by [`open()`](Self::open).
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            marker: PhantomData,
+        }
+    }
+
+    /// The nonce followed by the ciphertext, as stored in the database.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Extract the nonce followed by the ciphertext.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl<T: EncryptedData, K: KeyProvider> Encrypted<T, K> {
+    /// Encrypt `value` for the row identified by `row` in `column`.
+    ///
+    /// Fails only if the value is too large to encrypt.
+    pub fn seal(value: &T, column: &str, row: impl AsRef<[u8]>) -> Result<Self, BoxDynError> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(column, row.as_ref());
+
+        let ciphertext = cipher::<K>()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value.as_ref(),
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| "failed to encrypt value")?;
+
+        let mut data = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+
+        Ok(Self::from_bytes(data))
+    }
+
+    /// Decrypt the value, which must have been sealed for the same `column` and `row`.
+    pub fn open(&self, column: &str, row: impl AsRef<[u8]>) -> Result<T, BoxDynError> {
+        if self.data.len() < NONCE_LEN {
+            return Err("encrypted value is too short".into());
+        }
+
+        let (nonce, ciphertext) = self.data.split_at(NONCE_LEN);
+        let aad = associated_data(column, row.as_ref());
+
+        let plaintext = cipher::<K>()
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                "failed to decrypt value: wrong key, column or row, or modified ciphertext"
+            })?;
+
+        T::from_decrypted(plaintext)
+    }
+}
+
+fn cipher<K: KeyProvider>() -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(Key::from_slice(&K::key()))
+}
+
+// the column is length-prefixed, so that e.g. ("ab", "c") and ("a", "bc") differ
+fn associated_data(column: &str, row: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(8 + column.len() + row.len());
+    aad.extend_from_slice(&(column.len() as u64).to_be_bytes());
+    aad.extend_from_slice(column.as_bytes());
+    aad.extend_from_slice(row);
+    aad
+}
+
+// the ciphertext is not sensitive, but it is long and of no use in logs
+impl<T, K> Debug for Encrypted<T, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Encrypted(..)")
+    }
+}
+
+impl<T, K> Clone for Encrypted<T, K> {
+    fn clone(&self) -> Self {
+        Self::from_bytes(self.data.clone())
+    }
+}
+
+impl EncryptedData for Vec<u8> {
+    fn from_decrypted(data: Vec<u8>) -> Result<Self, BoxDynError> {
+        Ok(data)
+    }
+}
+
+impl EncryptedData for String {
+    fn from_decrypted(data: Vec<u8>) -> Result<Self, BoxDynError> {
+        Ok(String::from_utf8(data)?)
+    }
+}
+
+#[test]
+fn test_encrypted_roundtrip() {
+    struct TestKey;
+
+    impl KeyProvider for TestKey {
+        fn key() -> [u8; 32] {
+            [7; 32]
+        }
+    }
+
+    struct OtherKey;
+
+    impl KeyProvider for OtherKey {
+        fn key() -> [u8; 32] {
+            [8; 32]
+        }
+    }
+
+    let secret = String::from("alice@example.com");
+
+    let sealed =
+        Encrypted::<_, TestKey>::seal(&secret, "users.email", 1_i64.to_be_bytes()).unwrap();
+    let data = sealed.as_bytes();
+    assert_eq!(data.len(), NONCE_LEN + secret.len() + 16);
+    assert!(!data.windows(secret.len()).any(|w| w == secret.as_bytes()));
+
+    // a new nonce every time
+    let again = Encrypted::<_, TestKey>::seal(&secret, "users.email", 1_i64.to_be_bytes()).unwrap();
+    assert_ne!(data, again.as_bytes());
+
+    assert_eq!(
+        sealed.open("users.email", 1_i64.to_be_bytes()).unwrap(),
+        secret
+    );
+
+    // copied to another row or column
+    assert!(sealed.open("users.email", 2_i64.to_be_bytes()).is_err());
+    assert!(sealed.open("users.name", 1_i64.to_be_bytes()).is_err());
+    assert!(sealed.open("users.emai", b"l\0\0\0\0\0\0\0\x01").is_err());
+
+    let other = Encrypted::<String, OtherKey>::from_bytes(data.to_vec());
+    assert!(other.open("users.email", 1_i64.to_be_bytes()).is_err());
+
+    let mut modified = data.to_vec();
+    *modified.last_mut().unwrap() ^= 1;
+    let modified = Encrypted::<String, TestKey>::from_bytes(modified);
+    assert!(modified.open("users.email", 1_i64.to_be_bytes()).is_err());
+
+    let short = Encrypted::<String, TestKey>::from_bytes(data[..10].to_vec());
+    assert!(short.open("users.email", 1_i64.to_be_bytes()).is_err());
+}
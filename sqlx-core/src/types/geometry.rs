@@ -0,0 +1,163 @@
+use crate::error::BoxDynError;
+
+// set in the geometry type of Extended WKB if an SRID follows it
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// A geometry, e.g. a point or a polygon, as [Well-Known Binary] (WKB) with an optional
+/// spatial reference system identifier (SRID).
+///
+/// On Postgres, this is the `geometry` type of [PostGIS], including its SRID.
+///
+/// On SQLite, this is a `BLOB` holding the WKB, without the SRID. [SpatiaLite] stores geometries
+/// in a format of its own, so convert them with `GeomFromWKB()` and `AsBinary()`:
+///
+/// ```rust,ignore
+/// let options = SqliteConnectOptions::from_str("sqlite://places.db")?.extension("mod_spatialite");
+/// let mut conn = options.connect().await?;
+///
+/// sqlx::query("INSERT INTO places (name, location) VALUES (?, GeomFromWKB(?, 4326))")
+///     .bind("home")
+///     .bind(&location)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let location: Geometry = sqlx::query_scalar("SELECT AsBinary(location) FROM places")
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Only the header of the geometry is checked; reading its coordinates is left to crates that
+/// parse WKB, such as [`wkb`](https://crates.io/crates/wkb).
+///
+/// [Well-Known Binary]: https://libgeos.org/specifications/wkb/
+/// [PostGIS]: https://postgis.net/
+/// [SpatiaLite]: https://www.gaia-gis.it/fossil/libspatialite/index
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Geometry {
+    srid: Option<u32>,
+    wkb: Vec<u8>,
+}
+
+impl Geometry {
+    /// Wrap `wkb` after checking its header.
+    ///
+    /// Extended WKB (EWKB) as written by PostGIS is accepted as well; its SRID is moved to
+    /// [`srid()`](Self::srid).
+    pub fn from_wkb(wkb: impl Into<Vec<u8>>) -> Result<Self, BoxDynError> {
+        let mut wkb = wkb.into();
+
+        let ty = read_u32(&wkb, 1)?;
+
+        if ty & EWKB_SRID_FLAG == 0 {
+            return Ok(Self { srid: None, wkb });
+        }
+
+        let srid = read_u32(&wkb, 5)?;
+        let ty = ty & !EWKB_SRID_FLAG;
+
+        let ty = if wkb[0] == 1 {
+            ty.to_le_bytes()
+        } else {
+            ty.to_be_bytes()
+        };
+
+        wkb.splice(1..9, ty);
+
+        Ok(Self {
+            srid: Some(srid),
+            wkb,
+        })
+    }
+
+    /// Set the spatial reference system identifier, e.g. `4326` for WGS 84.
+    pub fn with_srid(mut self, srid: impl Into<Option<u32>>) -> Self {
+        self.srid = srid.into();
+        self
+    }
+
+    /// The spatial reference system identifier, if known.
+    pub fn srid(&self) -> Option<u32> {
+        self.srid
+    }
+
+    /// The geometry as WKB, without the SRID.
+    pub fn as_wkb(&self) -> &[u8] {
+        &self.wkb
+    }
+
+    /// Unwrap the WKB, dropping the SRID.
+    pub fn into_wkb(self) -> Vec<u8> {
+        self.wkb
+    }
+
+    /// The geometry as Extended WKB, which includes the SRID if there is one.
+    pub fn to_ewkb(&self) -> Vec<u8> {
+        let srid = match self.srid {
+            Some(srid) => srid,
+            None => return self.wkb.clone(),
+        };
+
+        let ty = read_u32(&self.wkb, 1).expect("header checked in from_wkb()") | EWKB_SRID_FLAG;
+
+        let mut ewkb = Vec::with_capacity(self.wkb.len() + 4);
+
+        ewkb.push(self.wkb[0]);
+
+        if self.wkb[0] == 1 {
+            ewkb.extend_from_slice(&ty.to_le_bytes());
+            ewkb.extend_from_slice(&srid.to_le_bytes());
+        } else {
+            ewkb.extend_from_slice(&ty.to_be_bytes());
+            ewkb.extend_from_slice(&srid.to_be_bytes());
+        }
+
+        ewkb.extend_from_slice(&self.wkb[5..]);
+        ewkb
+    }
+}
+
+// reads a `u32` at `offset` in the byte order given by the first byte
+fn read_u32(wkb: &[u8], offset: usize) -> Result<u32, BoxDynError> {
+    let bytes: [u8; 4] = wkb
+        .get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("WKB is too short")?;
+
+    match wkb[0] {
+        0 => Ok(u32::from_be_bytes(bytes)),
+        1 => Ok(u32::from_le_bytes(bytes)),
+        order => Err(format!("invalid WKB byte order {order}").into()),
+    }
+}
+
+#[test]
+fn test_geometry_ewkb() {
+    // POINT(1 2) in little endian WKB
+    let mut point = vec![1, 1, 0, 0, 0];
+    point.extend_from_slice(&1f64.to_le_bytes());
+    point.extend_from_slice(&2f64.to_le_bytes());
+
+    // SRID=4326;POINT(1 2)
+    let mut ewkb = vec![1, 1, 0, 0, 0x20, 0xe6, 0x10, 0, 0];
+    ewkb.extend_from_slice(&point[5..]);
+
+    let geometry = Geometry::from_wkb(ewkb.clone()).unwrap();
+    assert_eq!(geometry.srid(), Some(4326));
+    assert_eq!(geometry.as_wkb(), point);
+    assert_eq!(geometry.to_ewkb(), ewkb);
+
+    let geometry = Geometry::from_wkb(point.clone()).unwrap();
+    assert_eq!(geometry.srid(), None);
+    assert_eq!(geometry.to_ewkb(), point);
+
+    // big endian keeps its byte order
+    let big = Geometry::from_wkb([0, 0, 0, 0, 1].to_vec())
+        .unwrap()
+        .with_srid(3857);
+    assert_eq!(big.to_ewkb(), [0, 0x20, 0, 0, 1, 0, 0, 0x0f, 0x11]);
+    assert_eq!(Geometry::from_wkb(big.to_ewkb()).unwrap(), big);
+
+    assert!(Geometry::from_wkb(vec![1, 1, 0]).is_err());
+    assert!(Geometry::from_wkb(vec![2, 1, 0, 0, 0]).is_err());
+    assert!(Geometry::from_wkb(vec![1, 1, 0, 0, 0x20, 0xe6]).is_err());
+}
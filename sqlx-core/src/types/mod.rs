@@ -24,10 +24,23 @@ use crate::database::Database;
 #[cfg_attr(docsrs, doc(cfg(feature = "bstr")))]
 pub mod bstr;
 
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zstd", feature = "lz4"))))]
+mod compressed;
+
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+mod encrypted;
+
+#[cfg(feature = "geometry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "geometry")))]
+mod geometry;
+
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 mod json;
 
+mod numeric_overflow;
 mod text;
 
 #[cfg(feature = "uuid")]
@@ -40,7 +53,7 @@ pub use uuid::{self, Uuid};
 pub mod chrono {
     #[doc(no_inline)]
     pub use chrono::{
-        DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+        DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
 }
 
@@ -80,9 +93,25 @@ pub mod mac_address {
     pub use mac_address::MacAddress;
 }
 
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub use compressed::{Codec, Compressed, CompressedData, MAX_DECOMPRESSED_LEN};
+
+#[cfg(feature = "lz4")]
+pub use compressed::Lz4;
+
+#[cfg(feature = "zstd")]
+pub use compressed::Zstd;
+
+#[cfg(feature = "encryption")]
+pub use encrypted::{Encrypted, EncryptedData, KeyProvider};
+
+#[cfg(feature = "geometry")]
+pub use geometry::Geometry;
+
 #[cfg(feature = "json")]
 pub use json::{Json, JsonRawValue, JsonValue};
 
+pub use numeric_overflow::{Narrow, NumericOverflow};
 pub use text::Text;
 
 /// Indicates that a SQL type is supported for a database.
@@ -0,0 +1,199 @@
+use std::fmt::Display;
+
+use crate::error::BoxDynError;
+
+/// How to decode a numeric value that does not fit into the requested Rust type,
+/// e.g. a `BIGINT` of `2^40` into an `i32` or a large `DOUBLE` into an `f32`.
+///
+/// Configured per connection, see e.g. `SqliteConnectOptions::numeric_overflow()` or
+/// `MySqlConnectOptions::numeric_overflow()`, and per query with
+/// [`Query::numeric_overflow()`](crate::query::Query::numeric_overflow).
+///
+/// Loss of precision that stays within range (e.g. rounding a `DOUBLE` to the nearest `f32`)
+/// is not considered an overflow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NumericOverflow {
+    /// Decode the way the driver did before this setting existed. This is the default.
+    ///
+    /// SQLite converts `i32` and `f32` like [`Lossy`](Self::Lossy) and fails for every other
+    /// type like [`Error`](Self::Error). MySQL converts `f32` like `Lossy` and fails for
+    /// integers.
+    #[default]
+    DriverDefault,
+
+    /// Fail decoding with an error.
+    Error,
+
+    /// Clamp the value to the nearest representable value,
+    /// e.g. `i32::MAX` for integers or `f32::MAX` for floats.
+    Saturate,
+
+    /// Convert the value the same way an `as` cast would: integers are truncated to their
+    /// low bits and floats become infinite.
+    Lossy,
+}
+
+impl NumericOverflow {
+    /// `default` if this is [`DriverDefault`](Self::DriverDefault), `self` otherwise.
+    ///
+    /// Used by drivers to spell out what `DriverDefault` means for a type.
+    pub fn or(self, default: NumericOverflow) -> NumericOverflow {
+        match self {
+            NumericOverflow::DriverDefault => default,
+            policy => policy,
+        }
+    }
+
+    /// Convert `value` into `T`, applying this policy if it is out of range.
+    ///
+    /// [`DriverDefault`](Self::DriverDefault) behaves like [`Error`](Self::Error) here, see
+    /// [`or()`](Self::or).
+    pub fn narrow<S, T>(self, value: S) -> Result<T, BoxDynError>
+    where
+        S: Copy + Display,
+        T: Narrow<S>,
+    {
+        if let Some(narrowed) = T::checked_narrow(value) {
+            return Ok(narrowed);
+        }
+
+        match self {
+            NumericOverflow::DriverDefault | NumericOverflow::Error => Err(format!(
+                "value {value} is out of range for `{}`",
+                std::any::type_name::<T>()
+            )
+            .into()),
+
+            NumericOverflow::Saturate => Ok(T::saturating_narrow(value)),
+            NumericOverflow::Lossy => Ok(T::lossy_narrow(value)),
+        }
+    }
+}
+
+/// Conversion from a wider numeric type `S`, used by [`NumericOverflow::narrow()`].
+pub trait Narrow<S>: Sized {
+    /// Convert `value`, or return `None` if it is out of range.
+    fn checked_narrow(value: S) -> Option<Self>;
+
+    /// Convert `value`, clamping it to the range of `Self`.
+    fn saturating_narrow(value: S) -> Self;
+
+    /// Convert `value` like an `as` cast.
+    fn lossy_narrow(value: S) -> Self;
+}
+
+macro_rules! impl_narrow_int {
+    ($from:ty => $($to:ty),*) => {$(
+        impl Narrow<$from> for $to {
+            fn checked_narrow(value: $from) -> Option<Self> {
+                <$to>::try_from(value).ok()
+            }
+
+            fn saturating_narrow(value: $from) -> Self {
+                // every 64-bit integer fits into `i128`
+                i128::from(value).clamp(<$to>::MIN.into(), <$to>::MAX.into()) as $to
+            }
+
+            fn lossy_narrow(value: $from) -> Self {
+                value as $to
+            }
+        }
+    )*};
+}
+
+impl_narrow_int!(i64 => i8, i16, i32, i64, u8, u16, u32, u64);
+impl_narrow_int!(u64 => i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl Narrow<f64> for f32 {
+    fn checked_narrow(value: f64) -> Option<Self> {
+        let narrowed = value as f32;
+
+        // infinities and NaN are representable, finite values are only out of range if
+        // the cast turned them into infinities
+        (narrowed.is_finite() || !value.is_finite()).then_some(narrowed)
+    }
+
+    fn saturating_narrow(value: f64) -> Self {
+        value.clamp(f32::MIN.into(), f32::MAX.into()) as f32
+    }
+
+    fn lossy_narrow(value: f64) -> Self {
+        value as f32
+    }
+}
+
+#[test]
+fn test_narrow_integers() {
+    assert_eq!(NumericOverflow::Error.narrow::<i64, i32>(42).unwrap(), 42);
+    assert!(NumericOverflow::Error.narrow::<i64, i32>(1 << 40).is_err());
+    assert!(NumericOverflow::Error.narrow::<i64, u8>(-1).is_err());
+
+    assert_eq!(
+        NumericOverflow::Saturate
+            .narrow::<i64, i32>(1 << 40)
+            .unwrap(),
+        i32::MAX
+    );
+    assert_eq!(
+        NumericOverflow::Saturate.narrow::<i64, i8>(-1000).unwrap(),
+        i8::MIN
+    );
+    assert_eq!(NumericOverflow::Saturate.narrow::<i64, u64>(-1).unwrap(), 0);
+    assert_eq!(
+        NumericOverflow::Saturate
+            .narrow::<i64, u64>(i64::MAX)
+            .unwrap(),
+        i64::MAX as u64
+    );
+    assert_eq!(
+        NumericOverflow::Saturate
+            .narrow::<u64, i64>(u64::MAX)
+            .unwrap(),
+        i64::MAX
+    );
+
+    assert_eq!(
+        NumericOverflow::Lossy
+            .narrow::<i64, i32>((1 << 40) + 7)
+            .unwrap(),
+        7
+    );
+    assert_eq!(NumericOverflow::Lossy.narrow::<i64, u8>(-1).unwrap(), 255);
+}
+
+#[test]
+fn test_driver_default() {
+    assert_eq!(NumericOverflow::default(), NumericOverflow::DriverDefault);
+    assert_eq!(
+        NumericOverflow::DriverDefault.or(NumericOverflow::Lossy),
+        NumericOverflow::Lossy
+    );
+    assert_eq!(
+        NumericOverflow::Saturate.or(NumericOverflow::Lossy),
+        NumericOverflow::Saturate
+    );
+    assert!(NumericOverflow::DriverDefault
+        .narrow::<i64, i32>(1 << 40)
+        .is_err());
+}
+
+#[test]
+fn test_narrow_floats() {
+    assert_eq!(NumericOverflow::Error.narrow::<f64, f32>(0.5).unwrap(), 0.5);
+    assert!(NumericOverflow::Error
+        .narrow::<f64, f32>(f64::INFINITY)
+        .unwrap()
+        .is_infinite());
+    assert!(NumericOverflow::Error.narrow::<f64, f32>(1e300).is_err());
+
+    assert_eq!(
+        NumericOverflow::Saturate
+            .narrow::<f64, f32>(-1e300)
+            .unwrap(),
+        f32::MIN
+    );
+    assert_eq!(
+        NumericOverflow::Lossy.narrow::<f64, f32>(1e300).unwrap(),
+        f32::INFINITY
+    );
+}
@@ -37,6 +37,21 @@ pub trait DatabaseExt: Database {
     fn get_feature_gate(info: &Self::TypeInfo) -> Option<&'static str>;
 
     fn describe_blocking(query: &str, database_url: &str) -> sqlx_core::Result<Describe<Self>>;
+
+    /// Describe `query` in a new in-memory database, created by running the statements of
+    /// `schema`.
+    fn describe_blocking_with_schema(
+        _query: &str,
+        _schema: &str,
+    ) -> sqlx_core::Result<Describe<Self>> {
+        Err(sqlx_core::error::Error::Configuration(
+            format!(
+                "describing queries with a schema file is not supported for {}",
+                Self::NAME
+            )
+            .into(),
+        ))
+    }
 }
 
 #[allow(dead_code)]
@@ -84,6 +99,7 @@ macro_rules! impl_database_ext {
         feature-types: $ty_info:ident => $get_gate:expr,
         row: $row:path,
         $(describe-blocking: $describe:path,)?
+        $(describe-with-schema: $describe_with_schema:path,)?
     ) => {
         impl $crate::database::DatabaseExt for $database {
             const DATABASE_PATH: &'static str = stringify!($database);
@@ -123,6 +139,15 @@ macro_rules! impl_database_ext {
             }
 
             impl_describe_blocking!($database, $($describe)?);
+
+            $(
+                fn describe_blocking_with_schema(
+                    query: &str,
+                    schema: &str,
+                ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
+                    $describe_with_schema(query, schema)
+                }
+            )?
         }
     }
 }
@@ -74,6 +74,9 @@ impl_database_ext! {
         #[cfg(feature = "bit-vec")]
         sqlx::types::BitVec,
 
+        #[cfg(feature = "geometry")]
+        sqlx::types::Geometry,
+
         // Arrays
 
         Vec<bool> | &[bool],
@@ -32,6 +32,8 @@ impl_database_ext! {
 
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
+
+        std::time::Duration,
     },
     ParamChecking::Weak,
     feature-types: _info => None,
@@ -39,4 +41,5 @@ impl_database_ext! {
     // Since proc-macros don't benefit from async, we can make a describe call directly
     // which also ensures that the database is closed afterwards, regardless of errors.
     describe-blocking: sqlx_sqlite::describe_blocking,
+    describe-with-schema: sqlx_sqlite::describe_blocking_with_schema,
 }
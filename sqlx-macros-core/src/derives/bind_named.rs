@@ -0,0 +1,140 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Arm, Data, DataStruct, DeriveInput, Expr,
+    Field, Fields, FieldsNamed, Lifetime,
+};
+
+use super::{
+    attributes::{parse_child_attributes, parse_container_attributes},
+    rename_all,
+};
+
+pub fn expand_derive_bind_named(input: &DeriveInput) -> syn::Result<TokenStream> {
+    match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named, .. }),
+            ..
+        }) => expand_derive_bind_named_struct(input, named),
+
+        Data::Struct(_) => Err(syn::Error::new_spanned(
+            input,
+            "only structs with named fields are supported",
+        )),
+
+        Data::Enum(_) => Err(syn::Error::new_spanned(input, "enums are not supported")),
+
+        Data::Union(_) => Err(syn::Error::new_spanned(input, "unions are not supported")),
+    }
+}
+
+fn expand_derive_bind_named_struct(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let lifetime = Lifetime::new("'q", Span::call_site());
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .insert(0, parse_quote!(DB: ::sqlx::Database));
+    generics.params.insert(0, parse_quote!(#lifetime));
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    let container_attributes = parse_container_attributes(&input.attrs)?;
+
+    let mut arms: Vec<Arm> = Vec::new();
+    let mut flattened: Vec<Expr> = Vec::new();
+
+    for field in fields {
+        let Some(id) = &field.ident else { continue };
+        let ty = &field.ty;
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            continue;
+        }
+
+        if attributes.flatten {
+            predicates.push(parse_quote!(#ty: ::sqlx::named::BindNamed<#lifetime, DB>));
+            flattened.push(parse_quote!(
+                <#ty as ::sqlx::named::BindNamed<#lifetime, DB>>::bind_named(
+                    &self.#id,
+                    name,
+                    arguments,
+                )
+            ));
+            continue;
+        }
+
+        let name = attributes
+            .rename
+            .unwrap_or_else(|| id.to_string().trim_start_matches("r#").to_owned());
+
+        let name = match container_attributes.rename_all {
+            Some(pattern) => rename_all(&name, pattern),
+            None => name,
+        };
+
+        let value: Expr = if attributes.json {
+            predicates.push(parse_quote!(
+                ::sqlx::types::Json<&#lifetime #ty>: ::std::marker::Send
+                    + ::sqlx::Encode<#lifetime, DB>
+                    + ::sqlx::types::Type<DB>
+            ));
+            parse_quote!(::sqlx::types::Json(&self.#id))
+        } else {
+            predicates.push(parse_quote!(
+                #ty: #lifetime
+                    + ::std::marker::Sync
+                    + ::sqlx::Encode<#lifetime, DB>
+                    + ::sqlx::types::Type<DB>
+            ));
+            parse_quote!(&self.#id)
+        };
+
+        arms.push(parse_quote!(
+            #name => {
+                ::sqlx::Arguments::add(arguments, #value);
+                return true;
+            }
+        ));
+    }
+
+    let by_name = (!arms.is_empty()).then(|| {
+        quote!(
+            match name {
+                #(#arms)*
+                _ => {}
+            }
+        )
+    });
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::named::BindNamed<#lifetime, DB> for #ident #ty_generics #where_clause {
+            fn bind_named(
+                &#lifetime self,
+                name: &::std::primitive::str,
+                arguments: &mut <DB as ::sqlx::database::HasArguments<#lifetime>>::Arguments,
+            ) -> bool {
+                #by_name
+
+                #(
+                    if #flattened {
+                        return true;
+                    }
+                )*
+
+                false
+            }
+        }
+    ))
+}
@@ -1,13 +1,17 @@
 mod attributes;
+mod bind_named;
 mod decode;
 mod encode;
 mod row;
+mod schema;
 mod r#type;
 
+pub use bind_named::expand_derive_bind_named;
 pub use decode::expand_derive_decode;
 pub use encode::expand_derive_encode;
 pub use r#type::expand_derive_type;
 pub use row::expand_derive_from_row;
+pub use schema::expand_derive_schema_expectations;
 
 use self::attributes::RenameAll;
 use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
@@ -0,0 +1,124 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
+    Fields, FieldsNamed, Type,
+};
+
+use super::{
+    attributes::{parse_child_attributes, parse_container_attributes},
+    rename_all,
+};
+
+pub fn expand_derive_schema_expectations(input: &DeriveInput) -> syn::Result<TokenStream> {
+    match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named, .. }),
+            ..
+        }) => expand_derive_schema_expectations_struct(input, named),
+
+        Data::Struct(_) => Err(syn::Error::new_spanned(
+            input,
+            "only structs with named fields are supported",
+        )),
+
+        Data::Enum(_) => Err(syn::Error::new_spanned(input, "enums are not supported")),
+
+        Data::Union(_) => Err(syn::Error::new_spanned(input, "unions are not supported")),
+    }
+}
+
+fn expand_derive_schema_expectations_struct(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .insert(0, parse_quote!(DB: ::sqlx::Database));
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    let container_attributes = parse_container_attributes(&input.attrs)?;
+
+    let mut pushes: Vec<Expr> = Vec::new();
+
+    for field in fields {
+        let Some(id) = &field.ident else { continue };
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            continue;
+        }
+
+        // the type that is decoded from the row, see `expand_derive_from_row_struct()`
+        let ty: Type = match (attributes.try_from, attributes.json) {
+            (Some(try_from), true) => parse_quote!(::sqlx::types::Json<#try_from>),
+            (Some(try_from), false) => try_from,
+            (None, true) => {
+                let ty = &field.ty;
+                parse_quote!(::sqlx::types::Json<#ty>)
+            }
+            (None, false) => field.ty.clone(),
+        };
+
+        if attributes.flatten {
+            predicates.push(parse_quote!(#ty: ::sqlx::schema::SchemaExpectations<DB>));
+            pushes.push(parse_quote!(columns.extend(
+                <#ty as ::sqlx::schema::SchemaExpectations<DB>>::columns()
+            )));
+            continue;
+        }
+
+        let name = attributes
+            .rename
+            .unwrap_or_else(|| id.to_string().trim_start_matches("r#").to_owned());
+
+        let name = match container_attributes.rename_all {
+            Some(pattern) => rename_all(&name, pattern),
+            None => name,
+        };
+
+        let nullable = is_option(&field.ty);
+        let optional = attributes.default || container_attributes.default;
+
+        predicates.push(parse_quote!(#ty: ::sqlx::types::Type<DB>));
+        pushes.push(parse_quote!(columns.push(
+            ::sqlx::schema::ColumnExpectation::new::<#ty>(#name)
+                .nullable(#nullable)
+                .optional(#optional)
+        )));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::schema::SchemaExpectations<DB> for #ident #ty_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<::sqlx::schema::ColumnExpectation<DB>> {
+                let mut columns = ::std::vec::Vec::new();
+
+                #(#pushes;)*
+
+                columns
+            }
+        }
+    ))
+}
+
+// `Option<T>` fields may be decoded from nullable columns; this goes by the last path segment,
+// as type aliases cannot be resolved in a derive
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            matches!(path.path.segments.last(), Some(segment) if segment.ident == "Option")
+        }
+        Type::Group(group) => is_option(&group.elem),
+        Type::Paren(paren) => is_option(&paren.elem),
+        _ => false,
+    }
+}
@@ -15,6 +15,13 @@ pub fn quote_args<DB: DatabaseExt>(
 ) -> crate::Result<TokenStream> {
     let db_path = DB::db_path();
 
+    if let Some(args_object) = &input.args_object {
+        // `query_with()` checks that this is a `DB::Arguments`, its values are only known at runtime
+        return Ok(quote! {
+            let query_args = #args_object;
+        });
+    }
+
     if input.arg_exprs.is_empty() {
         return Ok(quote! {
             let query_args = <#db_path as ::sqlx::database::HasArguments>::Arguments::default();
@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::PathBuf;
 
 use proc_macro2::{Ident, Span};
 use syn::parse::{Parse, ParseStream};
@@ -16,9 +17,15 @@ pub struct QueryMacroInput {
 
     pub(super) arg_exprs: Vec<Expr>,
 
+    /// An arguments object passed instead of `arg_exprs`, by `query_checked_with!()`.
+    pub(super) args_object: Option<Expr>,
+
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    /// Files included by the query file with `-- include: <path>`, to be tracked by the compiler.
+    pub(super) include_paths: Vec<String>,
 }
 
 enum QuerySrc {
@@ -36,6 +43,7 @@ impl Parse for QueryMacroInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut query_src: Option<(QuerySrc, Span)> = None;
         let mut args: Option<Vec<Expr>> = None;
+        let mut args_object: Option<Expr> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
 
@@ -63,6 +71,8 @@ impl Parse for QueryMacroInput {
             } else if key == "args" {
                 let exprs = input.parse::<ExprArray>()?;
                 args = Some(exprs.elems.into_iter().collect())
+            } else if key == "args_object" {
+                args_object = Some(input.parse()?);
             } else if key == "record" {
                 if !matches!(record_type, RecordType::Generated) {
                     return Err(input.error("colliding `scalar` or `record` key"));
@@ -93,27 +103,38 @@ impl Parse for QueryMacroInput {
         let (src, src_span) =
             query_src.ok_or_else(|| input.error("expected `source` or `source_file` key"))?;
 
+        if args.is_some() && args_object.is_some() {
+            return Err(input.error("colliding `args` and `args_object` keys"));
+        }
+
         let arg_exprs = args.unwrap_or_default();
 
         let file_path = src.file_path(src_span)?;
+        let mut include_paths = Vec::new();
 
         Ok(QueryMacroInput {
-            sql: src.resolve(src_span)?,
+            sql: src.resolve(src_span, &mut include_paths)?,
             src_span,
             record_type,
             arg_exprs,
+            args_object,
             checked,
             file_path,
+            include_paths,
         })
     }
 }
 
 impl QuerySrc {
-    /// If the query source is a file, read it to a string. Otherwise return the query string.
-    fn resolve(self, source_span: Span) -> syn::Result<String> {
+    /// If the query source is a file, read it to a string, replacing its includes. Otherwise
+    /// return the query string.
+    fn resolve(self, source_span: Span, include_paths: &mut Vec<String>) -> syn::Result<String> {
         match self {
             QuerySrc::String(string) => Ok(string),
-            QuerySrc::File(file) => read_file_src(&file, source_span),
+            QuerySrc::File(file) => {
+                let mut stack = Vec::new();
+                read_file_src(&file, source_span, &mut stack, include_paths)
+            }
         }
     }
 
@@ -139,10 +160,20 @@ impl QuerySrc {
     }
 }
 
-fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
+/// Read the query file at `source`, replacing each line of the form `-- include: <path>` with the
+/// contents of the file at `<path>`, which may include further files.
+///
+/// Paths are resolved like the path of the query file itself. `stack` holds the files being read,
+/// to reject circular includes, and the paths of all included files are added to `include_paths`.
+fn read_file_src(
+    source: &str,
+    source_span: Span,
+    stack: &mut Vec<PathBuf>,
+    include_paths: &mut Vec<String>,
+) -> syn::Result<String> {
     let file_path = crate::common::resolve_path(source, source_span)?;
 
-    fs::read_to_string(&file_path).map_err(|e| {
+    let contents = fs::read_to_string(&file_path).map_err(|e| {
         syn::Error::new(
             source_span,
             format!(
@@ -151,5 +182,52 @@ fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
                 e
             ),
         )
-    })
+    })?;
+
+    let canonical_path = file_path
+        .canonicalize()
+        .map_err(|e| syn::Error::new(source_span, e))?;
+
+    if stack.contains(&canonical_path) {
+        return Err(syn::Error::new(
+            source_span,
+            format!("query file {} includes itself", file_path.display()),
+        ));
+    }
+
+    // the query file itself is tracked through `file_path`
+    if !stack.is_empty() {
+        let include_path = canonical_path.to_str().ok_or_else(|| {
+            syn::Error::new(
+                source_span,
+                "query file path cannot be represented as a string",
+            )
+        })?;
+
+        if !include_paths.iter().any(|path| path == include_path) {
+            include_paths.push(include_path.to_owned());
+        }
+    }
+
+    stack.push(canonical_path);
+
+    let mut sql = String::with_capacity(contents.len());
+
+    for line in contents.split_inclusive('\n') {
+        let Some(include) = line.trim().strip_prefix("-- include:") else {
+            sql.push_str(line);
+            continue;
+        };
+
+        let included = read_file_src(include.trim(), source_span, stack, include_paths)?;
+        sql.push_str(included.trim_end());
+
+        if line.ends_with('\n') {
+            sql.push('\n');
+        }
+    }
+
+    stack.pop();
+
+    Ok(sql)
 }
@@ -46,6 +46,10 @@ pub enum QueryDataSource<'a> {
         database_url: &'a str,
         database_url_parsed: Url,
     },
+    /// A new in-memory SQLite database, created by running the statements of `schema`.
+    SqliteSchema {
+        schema: String,
+    },
     Cached(DynQueryData),
 }
 
@@ -63,6 +67,7 @@ impl<'a> QueryDataSource<'a> {
                 database_url_parsed,
                 ..
             } => driver.url_schemes.contains(&database_url_parsed.scheme()),
+            Self::SqliteSchema { .. } => driver.db_name == "SQLite",
             Self::Cached(dyn_data) => dyn_data.db_name == driver.db_name,
         }
     }
@@ -73,6 +78,7 @@ struct Metadata {
     manifest_dir: PathBuf,
     offline: bool,
     database_url: Option<String>,
+    sqlite_schema: Option<PathBuf>,
     workspace_root: Arc<Mutex<Option<PathBuf>>>,
 }
 
@@ -141,10 +147,21 @@ static METADATA: Lazy<Metadata> = Lazy::new(|| {
 
     let database_url = env("DATABASE_URL").ok();
 
+    let sqlite_schema = env("SQLX_SQLITE_SCHEMA")
+        .ok()
+        .map(|path| manifest_dir.join(path));
+
+    // tell the compiler to watch the schema file for changes, if applicable
+    #[cfg(procmacro2_semver_exempt)]
+    if let Some(schema_path) = sqlite_schema.as_ref().and_then(|path| path.to_str()) {
+        proc_macro::tracked_path::path(schema_path);
+    }
+
     Metadata {
         manifest_dir,
         offline,
         database_url,
+        sqlite_schema,
         workspace_root: Arc::new(Mutex::new(None)),
     }
 });
@@ -160,6 +177,20 @@ pub fn expand_input<'a>(
             ..
         } => QueryDataSource::live(db_url)?,
 
+        Metadata {
+            offline: false,
+            database_url: None,
+            sqlite_schema: Some(schema_path),
+            ..
+        } => QueryDataSource::SqliteSchema {
+            schema: fs::read_to_string(schema_path).map_err(|e| {
+                format!(
+                    "failed to read `SQLX_SQLITE_SCHEMA` file at {}: {e}",
+                    schema_path.display()
+                )
+            })?,
+        },
+
         Metadata { offline, .. } => {
             // Try load the cached query metadata file.
             let filename = format!("query-{}.json", hash_string(&input.sql));
@@ -203,6 +234,9 @@ pub fn expand_input<'a>(
             "no database driver found matching URL scheme {:?}; the corresponding Cargo feature may need to be enabled", 
             database_url_parsed.scheme()
         ).into()),
+        QueryDataSource::SqliteSchema { .. } => Err(
+            "`SQLX_SQLITE_SCHEMA` is set but the `sqlite` feature of SQLx is not enabled".into(),
+        ),
         QueryDataSource::Cached(data) => {
             Err(format!(
                 "found cached data for database {:?} but no matching driver; the corresponding Cargo feature may need to be enabled",
@@ -225,6 +259,10 @@ where
             let describe = DB::describe_blocking(&input.sql, &database_url)?;
             (QueryData::from_describe(&input.sql, describe), false)
         }
+        QueryDataSource::SqliteSchema { schema } => {
+            let describe = DB::describe_blocking_with_schema(&input.sql, &schema)?;
+            (QueryData::from_describe(&input.sql, describe), false)
+        }
     };
 
     expand_with_data(input, query_data, offline)
@@ -254,7 +292,7 @@ where
         None => None,
     };
 
-    if let Some(num) = num_parameters {
+    if let (Some(num), None) = (num_parameters, &input.args_object) {
         if num != input.arg_exprs.len() {
             return Err(
                 format!("expected {} parameters, got {}", num, input.arg_exprs.len()).into(),
@@ -273,7 +311,7 @@ where
         .all(|it| it.type_info().is_void())
     {
         let db_path = DB::db_path();
-        let sql = &input.sql;
+        let sql = output::quote_sql(&input);
 
         quote! {
             ::sqlx::query_with::<#db_path, _>(#sql, #query_args)
@@ -163,13 +163,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
     let db_path = DB::db_path();
     let row_path = DB::row_path();
 
-    // if this query came from a file, use `include_str!()` to tell the compiler where it came from
-    let sql = if let Some(ref path) = &input.file_path {
-        quote::quote_spanned! { input.src_span => include_str!(#path) }
-    } else {
-        let sql = &input.sql;
-        quote! { #sql }
-    };
+    let sql = quote_sql(input);
 
     quote! {
         ::sqlx::query_with::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
@@ -212,13 +206,37 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     };
 
     let db = DB::db_path();
-    let query = &input.sql;
+    let query = quote_sql(input);
 
     Ok(quote! {
         ::sqlx::query_scalar_with::<#db, #ty, _>(#query, #bind_args)
     })
 }
 
+/// Quote the SQL of the query, telling the compiler which files it came from.
+pub(super) fn quote_sql(input: &QueryMacroInput) -> TokenStream {
+    let Some(path) = &input.file_path else {
+        let sql = &input.sql;
+        return quote! { #sql };
+    };
+
+    // without includes, `include_str!()` yields the query itself
+    if input.include_paths.is_empty() {
+        return quote::quote_spanned! { input.src_span => include_str!(#path) };
+    }
+
+    // otherwise, the preprocessed query is a literal and the files are only included to be tracked
+    let sql = &input.sql;
+    let paths = std::iter::once(path).chain(&input.include_paths);
+
+    quote::quote_spanned! { input.src_span =>
+        {
+            #(const _: &str = include_str!(#paths);)*
+            #sql
+        }
+    }
+}
+
 fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
     let type_info = &*column.type_info();
 
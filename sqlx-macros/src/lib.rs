@@ -58,6 +58,26 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(BindNamed, attributes(sqlx))]
+pub fn derive_bind_named(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match derives::expand_derive_bind_named(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(SchemaExpectations, attributes(sqlx))]
+pub fn derive_schema_expectations(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match derives::expand_derive_schema_expectations(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 #[cfg(feature = "migrate")]
 #[proc_macro]
 pub fn migrate(input: TokenStream) -> TokenStream {
@@ -12,7 +12,7 @@ use sqlx_core::any::{
     AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
 use sqlx_core::connection::Connection;
-use sqlx_core::database::Database;
+use sqlx_core::database::{Database, DatabaseCapabilities};
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::transaction::TransactionManager;
@@ -24,6 +24,10 @@ impl AnyConnectionBackend for MySqlConnection {
         <MySql as Database>::NAME
     }
 
+    fn capabilities(&self) -> DatabaseCapabilities {
+        <MySql as Database>::capabilities()
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -52,6 +56,25 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::start_rollback(self)
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::prepare_cached(self, statements)
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::clear_table(self, table, restart_identity)
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, discard_session)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -80,7 +103,7 @@ impl AnyConnectionBackend for MySqlConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(
-            self.run(query, args, persistent)
+            self.run(query, args, persistent, None)
                 .try_flatten_stream()
                 .map(|res| {
                     Ok(match res? {
@@ -100,7 +123,7 @@ impl AnyConnectionBackend for MySqlConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(async move {
-            let stream = self.run(query, args, persistent).await?;
+            let stream = self.run(query, args, persistent, None).await?;
             futures_util::pin_mut!(stream);
 
             if let Some(Either::Right(row)) = stream.try_next().await? {
@@ -1,7 +1,14 @@
+use std::fmt::{self, Write};
+
+use bytes::{Buf, Bytes};
+
 use crate::encode::{Encode, IsNull};
+use crate::io::MySqlBufExt;
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
 pub(crate) use sqlx_core::arguments::*;
+use sqlx_core::type_info::TypeInfo;
 
 /// Implementation of [`Arguments`] for MySQL.
 #[derive(Debug, Default, Clone)]
@@ -9,6 +16,9 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+
+    // indexes of the values to redact when logging
+    pub(crate) sensitive: Vec<usize>,
 }
 
 impl MySqlArguments {
@@ -47,4 +57,138 @@ impl<'q> Arguments<'q> for MySqlArguments {
     {
         self.add(value)
     }
+
+    fn add_sensitive<T>(&mut self, value: T)
+    where
+        T: Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(value);
+        self.sensitive.push(self.types.len() - 1);
+    }
+
+    fn format_redacted<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        let values = self.split_values();
+
+        write_redacted(
+            writer,
+            self.types.len(),
+            &self.sensitive,
+            |writer, i| match values.get(i) {
+                Some(Some(value)) => format_value(writer, &self.types[i], value.clone()),
+                Some(None) => writer.write_str("NULL"),
+                None => write!(writer, "<{}>", self.types[i].name()),
+            },
+        )
+    }
+}
+
+impl MySqlArguments {
+    // values are in the binary protocol, one after the other; NULL is only set in the bitmap
+    fn split_values(&self) -> Vec<Option<Bytes>> {
+        let mut buf = Bytes::copy_from_slice(&self.values);
+        let mut values = Vec::with_capacity(self.types.len());
+
+        for (i, ty) in self.types.iter().enumerate() {
+            if self.null_bitmap[i / 8] & (1 << (i % 8)) as u8 != 0 {
+                values.push(None);
+                continue;
+            }
+
+            if !buf.has_remaining() {
+                break;
+            }
+
+            let size = match ty.r#type {
+                ColumnType::LongLong | ColumnType::Double => 8,
+                ColumnType::Long | ColumnType::Int24 | ColumnType::Float => 4,
+                ColumnType::Short | ColumnType::Year => 2,
+                ColumnType::Tiny => 1,
+                ColumnType::Null => 0,
+
+                // prefixed with the length as a single byte
+                ColumnType::Time
+                | ColumnType::Timestamp
+                | ColumnType::Date
+                | ColumnType::Datetime => buf[0] as usize + 1,
+
+                _ => buf.get_uint_lenenc() as usize,
+            };
+
+            if size > buf.len() {
+                break;
+            }
+
+            values.push(Some(buf.split_to(size)));
+        }
+
+        values
+    }
+}
+
+fn format_value<W: Write>(writer: &mut W, ty: &MySqlTypeInfo, mut value: Bytes) -> fmt::Result {
+    let unsigned = ty.flags.contains(ColumnFlags::UNSIGNED);
+    // the `binary` character set
+    let binary = ty.char_set == 63;
+
+    match (ty.r#type, value.len()) {
+        (ColumnType::Tiny, 1) if unsigned => write!(writer, "{}", value.get_u8()),
+        (ColumnType::Tiny, 1) => write!(writer, "{}", value.get_i8()),
+        (ColumnType::Short | ColumnType::Year, 2) if unsigned => {
+            write!(writer, "{}", value.get_u16_le())
+        }
+        (ColumnType::Short | ColumnType::Year, 2) => write!(writer, "{}", value.get_i16_le()),
+        (ColumnType::Long | ColumnType::Int24, 4) if unsigned => {
+            write!(writer, "{}", value.get_u32_le())
+        }
+        (ColumnType::Long | ColumnType::Int24, 4) => write!(writer, "{}", value.get_i32_le()),
+        (ColumnType::LongLong, 8) if unsigned => write!(writer, "{}", value.get_u64_le()),
+        (ColumnType::LongLong, 8) => write!(writer, "{}", value.get_i64_le()),
+        (ColumnType::Float, 4) => write!(writer, "{}", value.get_f32_le()),
+        (ColumnType::Double, 8) => write!(writer, "{}", value.get_f64_le()),
+        (ColumnType::Decimal | ColumnType::NewDecimal, _) => {
+            write!(writer, "{}", String::from_utf8_lossy(&value))
+        }
+        (
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::Enum
+            | ColumnType::Set
+            | ColumnType::Json,
+            _,
+        ) if !binary => write_text(writer, &String::from_utf8_lossy(&value)),
+        (
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob,
+            len,
+        ) => write!(writer, "<{len} bytes>"),
+        _ => write!(writer, "<{}>", ty.name()),
+    }
+}
+
+#[test]
+fn test_format_redacted() {
+    let mut arguments = MySqlArguments::default();
+
+    Arguments::add(&mut arguments, 42_i64);
+    Arguments::add_sensitive(&mut arguments, "hunter2");
+    Arguments::add(&mut arguments, "alice");
+    Arguments::add(&mut arguments, None::<i32>);
+    Arguments::add(&mut arguments, u64::MAX);
+    Arguments::add(&mut arguments, 1.5_f32);
+    Arguments::add(&mut arguments, &b"raw"[..]);
+    Arguments::add(&mut arguments, -1_i8);
+
+    let mut rendered = String::new();
+    arguments.format_redacted(&mut rendered).unwrap();
+
+    assert_eq!(
+        rendered,
+        r#"[42, <redacted>, "alice", NULL, 18446744073709551615, 1.5, <3 bytes>, -1]"#
+    );
 }
@@ -2,7 +2,7 @@ use crate::error::Error;
 use std::str::FromStr;
 
 #[allow(non_camel_case_types)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) enum CharSet {
     armscii8,
     ascii,
@@ -197,7 +197,7 @@ impl FromStr for CharSet {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 #[allow(non_camel_case_types)]
 #[repr(u8)]
 pub(crate) enum Collation {
@@ -656,6 +656,307 @@ impl Collation {
             Collation::utf8mb4_vietnamese_ci => "utf8mb4_vietnamese_ci",
         }
     }
+
+    pub(crate) fn from_id(id: u16) -> Option<Self> {
+        Some(match id {
+            1 => Collation::big5_chinese_ci,
+            2 => Collation::latin2_czech_cs,
+            3 => Collation::dec8_swedish_ci,
+            4 => Collation::cp850_general_ci,
+            5 => Collation::latin1_german1_ci,
+            6 => Collation::hp8_english_ci,
+            7 => Collation::koi8r_general_ci,
+            8 => Collation::latin1_swedish_ci,
+            9 => Collation::latin2_general_ci,
+            10 => Collation::swe7_swedish_ci,
+            11 => Collation::ascii_general_ci,
+            12 => Collation::ujis_japanese_ci,
+            13 => Collation::sjis_japanese_ci,
+            14 => Collation::cp1251_bulgarian_ci,
+            15 => Collation::latin1_danish_ci,
+            16 => Collation::hebrew_general_ci,
+            18 => Collation::tis620_thai_ci,
+            19 => Collation::euckr_korean_ci,
+            20 => Collation::latin7_estonian_cs,
+            21 => Collation::latin2_hungarian_ci,
+            22 => Collation::koi8u_general_ci,
+            23 => Collation::cp1251_ukrainian_ci,
+            24 => Collation::gb2312_chinese_ci,
+            25 => Collation::greek_general_ci,
+            26 => Collation::cp1250_general_ci,
+            27 => Collation::latin2_croatian_ci,
+            28 => Collation::gbk_chinese_ci,
+            29 => Collation::cp1257_lithuanian_ci,
+            30 => Collation::latin5_turkish_ci,
+            31 => Collation::latin1_german2_ci,
+            32 => Collation::armscii8_general_ci,
+            33 => Collation::utf8_general_ci,
+            34 => Collation::cp1250_czech_cs,
+            35 => Collation::ucs2_general_ci,
+            36 => Collation::cp866_general_ci,
+            37 => Collation::keybcs2_general_ci,
+            38 => Collation::macce_general_ci,
+            39 => Collation::macroman_general_ci,
+            40 => Collation::cp852_general_ci,
+            41 => Collation::latin7_general_ci,
+            42 => Collation::latin7_general_cs,
+            43 => Collation::macce_bin,
+            44 => Collation::cp1250_croatian_ci,
+            45 => Collation::utf8mb4_general_ci,
+            46 => Collation::utf8mb4_bin,
+            47 => Collation::latin1_bin,
+            48 => Collation::latin1_general_ci,
+            49 => Collation::latin1_general_cs,
+            50 => Collation::cp1251_bin,
+            51 => Collation::cp1251_general_ci,
+            52 => Collation::cp1251_general_cs,
+            53 => Collation::macroman_bin,
+            54 => Collation::utf16_general_ci,
+            55 => Collation::utf16_bin,
+            56 => Collation::utf16le_general_ci,
+            57 => Collation::cp1256_general_ci,
+            58 => Collation::cp1257_bin,
+            59 => Collation::cp1257_general_ci,
+            60 => Collation::utf32_general_ci,
+            61 => Collation::utf32_bin,
+            62 => Collation::utf16le_bin,
+            63 => Collation::binary,
+            64 => Collation::armscii8_bin,
+            65 => Collation::ascii_bin,
+            66 => Collation::cp1250_bin,
+            67 => Collation::cp1256_bin,
+            68 => Collation::cp866_bin,
+            69 => Collation::dec8_bin,
+            70 => Collation::greek_bin,
+            71 => Collation::hebrew_bin,
+            72 => Collation::hp8_bin,
+            73 => Collation::keybcs2_bin,
+            74 => Collation::koi8r_bin,
+            75 => Collation::koi8u_bin,
+            76 => Collation::utf8_tolower_ci,
+            77 => Collation::latin2_bin,
+            78 => Collation::latin5_bin,
+            79 => Collation::latin7_bin,
+            80 => Collation::cp850_bin,
+            81 => Collation::cp852_bin,
+            82 => Collation::swe7_bin,
+            83 => Collation::utf8_bin,
+            84 => Collation::big5_bin,
+            85 => Collation::euckr_bin,
+            86 => Collation::gb2312_bin,
+            87 => Collation::gbk_bin,
+            88 => Collation::sjis_bin,
+            89 => Collation::tis620_bin,
+            90 => Collation::ucs2_bin,
+            91 => Collation::ujis_bin,
+            92 => Collation::geostd8_general_ci,
+            93 => Collation::geostd8_bin,
+            94 => Collation::latin1_spanish_ci,
+            95 => Collation::cp932_japanese_ci,
+            96 => Collation::cp932_bin,
+            97 => Collation::eucjpms_japanese_ci,
+            98 => Collation::eucjpms_bin,
+            99 => Collation::cp1250_polish_ci,
+            101 => Collation::utf16_unicode_ci,
+            102 => Collation::utf16_icelandic_ci,
+            103 => Collation::utf16_latvian_ci,
+            104 => Collation::utf16_romanian_ci,
+            105 => Collation::utf16_slovenian_ci,
+            106 => Collation::utf16_polish_ci,
+            107 => Collation::utf16_estonian_ci,
+            108 => Collation::utf16_spanish_ci,
+            109 => Collation::utf16_swedish_ci,
+            110 => Collation::utf16_turkish_ci,
+            111 => Collation::utf16_czech_ci,
+            112 => Collation::utf16_danish_ci,
+            113 => Collation::utf16_lithuanian_ci,
+            114 => Collation::utf16_slovak_ci,
+            115 => Collation::utf16_spanish2_ci,
+            116 => Collation::utf16_roman_ci,
+            117 => Collation::utf16_persian_ci,
+            118 => Collation::utf16_esperanto_ci,
+            119 => Collation::utf16_hungarian_ci,
+            120 => Collation::utf16_sinhala_ci,
+            121 => Collation::utf16_german2_ci,
+            122 => Collation::utf16_croatian_ci,
+            123 => Collation::utf16_unicode_520_ci,
+            124 => Collation::utf16_vietnamese_ci,
+            128 => Collation::ucs2_unicode_ci,
+            129 => Collation::ucs2_icelandic_ci,
+            130 => Collation::ucs2_latvian_ci,
+            131 => Collation::ucs2_romanian_ci,
+            132 => Collation::ucs2_slovenian_ci,
+            133 => Collation::ucs2_polish_ci,
+            134 => Collation::ucs2_estonian_ci,
+            135 => Collation::ucs2_spanish_ci,
+            136 => Collation::ucs2_swedish_ci,
+            137 => Collation::ucs2_turkish_ci,
+            138 => Collation::ucs2_czech_ci,
+            139 => Collation::ucs2_danish_ci,
+            140 => Collation::ucs2_lithuanian_ci,
+            141 => Collation::ucs2_slovak_ci,
+            142 => Collation::ucs2_spanish2_ci,
+            143 => Collation::ucs2_roman_ci,
+            144 => Collation::ucs2_persian_ci,
+            145 => Collation::ucs2_esperanto_ci,
+            146 => Collation::ucs2_hungarian_ci,
+            147 => Collation::ucs2_sinhala_ci,
+            148 => Collation::ucs2_german2_ci,
+            149 => Collation::ucs2_croatian_ci,
+            150 => Collation::ucs2_unicode_520_ci,
+            151 => Collation::ucs2_vietnamese_ci,
+            159 => Collation::ucs2_general_mysql500_ci,
+            160 => Collation::utf32_unicode_ci,
+            161 => Collation::utf32_icelandic_ci,
+            162 => Collation::utf32_latvian_ci,
+            163 => Collation::utf32_romanian_ci,
+            164 => Collation::utf32_slovenian_ci,
+            165 => Collation::utf32_polish_ci,
+            166 => Collation::utf32_estonian_ci,
+            167 => Collation::utf32_spanish_ci,
+            168 => Collation::utf32_swedish_ci,
+            169 => Collation::utf32_turkish_ci,
+            170 => Collation::utf32_czech_ci,
+            171 => Collation::utf32_danish_ci,
+            172 => Collation::utf32_lithuanian_ci,
+            173 => Collation::utf32_slovak_ci,
+            174 => Collation::utf32_spanish2_ci,
+            175 => Collation::utf32_roman_ci,
+            176 => Collation::utf32_persian_ci,
+            177 => Collation::utf32_esperanto_ci,
+            178 => Collation::utf32_hungarian_ci,
+            179 => Collation::utf32_sinhala_ci,
+            180 => Collation::utf32_german2_ci,
+            181 => Collation::utf32_croatian_ci,
+            182 => Collation::utf32_unicode_520_ci,
+            183 => Collation::utf32_vietnamese_ci,
+            192 => Collation::utf8_unicode_ci,
+            193 => Collation::utf8_icelandic_ci,
+            194 => Collation::utf8_latvian_ci,
+            195 => Collation::utf8_romanian_ci,
+            196 => Collation::utf8_slovenian_ci,
+            197 => Collation::utf8_polish_ci,
+            198 => Collation::utf8_estonian_ci,
+            199 => Collation::utf8_spanish_ci,
+            200 => Collation::utf8_swedish_ci,
+            201 => Collation::utf8_turkish_ci,
+            202 => Collation::utf8_czech_ci,
+            203 => Collation::utf8_danish_ci,
+            204 => Collation::utf8_lithuanian_ci,
+            205 => Collation::utf8_slovak_ci,
+            206 => Collation::utf8_spanish2_ci,
+            207 => Collation::utf8_roman_ci,
+            208 => Collation::utf8_persian_ci,
+            209 => Collation::utf8_esperanto_ci,
+            210 => Collation::utf8_hungarian_ci,
+            211 => Collation::utf8_sinhala_ci,
+            212 => Collation::utf8_german2_ci,
+            213 => Collation::utf8_croatian_ci,
+            214 => Collation::utf8_unicode_520_ci,
+            215 => Collation::utf8_vietnamese_ci,
+            223 => Collation::utf8_general_mysql500_ci,
+            224 => Collation::utf8mb4_unicode_ci,
+            225 => Collation::utf8mb4_icelandic_ci,
+            226 => Collation::utf8mb4_latvian_ci,
+            227 => Collation::utf8mb4_romanian_ci,
+            228 => Collation::utf8mb4_slovenian_ci,
+            229 => Collation::utf8mb4_polish_ci,
+            230 => Collation::utf8mb4_estonian_ci,
+            231 => Collation::utf8mb4_spanish_ci,
+            232 => Collation::utf8mb4_swedish_ci,
+            233 => Collation::utf8mb4_turkish_ci,
+            234 => Collation::utf8mb4_czech_ci,
+            235 => Collation::utf8mb4_danish_ci,
+            236 => Collation::utf8mb4_lithuanian_ci,
+            237 => Collation::utf8mb4_slovak_ci,
+            238 => Collation::utf8mb4_spanish2_ci,
+            239 => Collation::utf8mb4_roman_ci,
+            240 => Collation::utf8mb4_persian_ci,
+            241 => Collation::utf8mb4_esperanto_ci,
+            242 => Collation::utf8mb4_hungarian_ci,
+            243 => Collation::utf8mb4_sinhala_ci,
+            244 => Collation::utf8mb4_german2_ci,
+            245 => Collation::utf8mb4_croatian_ci,
+            246 => Collation::utf8mb4_unicode_520_ci,
+            247 => Collation::utf8mb4_vietnamese_ci,
+            248 => Collation::gb18030_chinese_ci,
+            249 => Collation::gb18030_bin,
+            250 => Collation::gb18030_unicode_520_ci,
+            255 => Collation::utf8mb4_0900_ai_ci,
+
+            _ => return None,
+        })
+    }
+
+    /// The character set this collation belongs to, e.g. `utf8mb4` for `utf8mb4_bin`.
+    pub(crate) fn charset(&self) -> CharSet {
+        let name = self.as_str();
+
+        // all collations but `binary` are prefixed by their character set
+        name.split('_')
+            .next()
+            .and_then(|charset| charset.parse().ok())
+            .unwrap_or(CharSet::binary)
+    }
+}
+
+/// Check that `collation` can be used with `charset`, before connecting.
+pub(crate) fn check_compatible(charset: CharSet, collation: Collation) -> Result<(), Error> {
+    if collation.charset().as_str() != charset.as_str() {
+        return Err(Error::Configuration(
+            format!(
+                "MySQL collation {} does not belong to charset {}",
+                collation.as_str(),
+                charset.as_str()
+            )
+            .into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that the server supports `collation`, once its version is known from the handshake.
+pub(crate) fn check_server_support(
+    collation: Collation,
+    server_version: (u16, u16, u16),
+    mariadb: bool,
+) -> Result<(), Error> {
+    let name = collation.as_str();
+
+    // MariaDB reports its version as e.g. `5.5.5-10.11.2-MariaDB`, so only check what it lacks
+    let required = if name.contains("_0900_") {
+        if mariadb {
+            return Err(Error::Configuration(
+                format!("MySQL collation {name} is not supported by MariaDB").into(),
+            ));
+        }
+
+        (8, 0, 0)
+    } else if mariadb {
+        return Ok(());
+    } else if name.contains("_520_") {
+        (5, 6, 0)
+    } else if matches!(collation.charset(), CharSet::utf8mb4) {
+        (5, 5, 3)
+    } else {
+        return Ok(());
+    };
+
+    if server_version < required {
+        let (major, minor, patch) = required;
+
+        return Err(Error::Configuration(
+            format!(
+                "MySQL collation {name} requires server version {major}.{minor}.{patch} or \
+                 newer, found {}.{}.{}; set a different charset or collation",
+                server_version.0, server_version.1, server_version.2
+            )
+            .into(),
+        ));
+    }
+
+    Ok(())
 }
 
 // Handshake packet have only 1 byte for collation_id.
@@ -898,3 +1199,27 @@ impl FromStr for Collation {
         })
     }
 }
+
+#[test]
+fn test_collation_support() {
+    assert_eq!(
+        Collation::from_id(45).unwrap().as_str(),
+        "utf8mb4_general_ci"
+    );
+    assert!(Collation::from_id(300).is_none());
+    assert_eq!(Collation::binary.charset().as_str(), "binary");
+
+    assert!(check_compatible(CharSet::utf8mb4, Collation::utf8mb4_bin).is_ok());
+    assert!(check_compatible(CharSet::utf8, Collation::utf8mb4_bin).is_err());
+    assert!(check_compatible(CharSet::utf16, Collation::utf16le_general_ci).is_err());
+
+    let utf8mb4 = Collation::utf8mb4_unicode_ci;
+    let utf8mb4_0900 = Collation::utf8mb4_0900_ai_ci;
+
+    assert!(check_server_support(utf8mb4, (5, 5, 3), false).is_ok());
+    assert!(check_server_support(utf8mb4, (5, 1, 73), false).is_err());
+    assert!(check_server_support(utf8mb4_0900, (8, 0, 34), false).is_ok());
+    assert!(check_server_support(utf8mb4_0900, (5, 7, 44), false).is_err());
+    assert!(check_server_support(utf8mb4_0900, (11, 2, 2), true).is_err());
+    assert!(check_server_support(Collation::utf8mb4_unicode_520_ci, (5, 5, 5), true).is_ok());
+}
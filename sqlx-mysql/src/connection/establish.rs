@@ -2,7 +2,7 @@ use bytes::buf::Buf;
 use bytes::Bytes;
 use futures_core::future::BoxFuture;
 
-use crate::collation::{CharSet, Collation};
+use crate::collation::{self, CharSet, Collation};
 use crate::common::StatementCache;
 use crate::connection::{tls, MySqlStream, MAX_PACKET_SIZE};
 use crate::error::Error;
@@ -29,6 +29,10 @@ impl MySqlConnection {
             transaction_depth: 0,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             log_settings: options.log_settings.clone(),
+            numeric_overflow: options.numeric_overflow,
+            invalid_utf8: options.invalid_utf8.clone(),
+            query_interceptor: options.query_interceptor.clone(),
+            session_setup: String::new(),
         })
     }
 }
@@ -49,6 +53,8 @@ impl<'a> DoHandshake<'a> {
             .transpose()?
             .unwrap_or_else(|| charset.default_collation());
 
+        collation::check_compatible(charset, collation)?;
+
         if options.enable_cleartext_plugin
             && matches!(
                 options.ssl_mode,
@@ -82,34 +88,21 @@ impl<'a> DoHandshake<'a> {
         let mut plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
 
-        // FIXME: server version parse is a bit ugly
-        // expecting MAJOR.MINOR.PATCH
-
-        let mut server_version = handshake.server_version.split('.');
+        stream.server_version = parse_server_version(&handshake.server_version);
 
-        let server_version_major: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
+        let mariadb = handshake.server_version.contains("MariaDB");
 
-        let server_version_minor: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
+        // MariaDB reports its version as e.g. `5.5.5-10.11.2-MariaDB`; `COM_RESET_CONNECTION`
+        // was added in MySQL 5.7.3 and MariaDB 10.2.4
+        stream.supports_reset_connection = if mariadb {
+            let version = handshake.server_version.trim_start_matches("5.5.5-");
 
-        let server_version_patch: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
+            parse_server_version(version) >= (10, 2, 4)
+        } else {
+            stream.server_version >= (5, 7, 3)
+        };
 
-        stream.server_version = (
-            server_version_major,
-            server_version_minor,
-            server_version_patch,
-        );
+        collation::check_server_support(collation, stream.server_version, mariadb)?;
 
         stream.capabilities &= handshake.server_capabilities;
         stream.capabilities |= Capabilities::PROTOCOL_41;
@@ -191,3 +184,28 @@ impl<'a> WithSocket for DoHandshake<'a> {
         Box::pin(self.do_handshake(socket))
     }
 }
+
+// expecting MAJOR.MINOR.PATCH, followed by anything, e.g. `8.0.36-0ubuntu0.22.04.1`
+fn parse_server_version(version: &str) -> (u16, u16, u16) {
+    let mut parts = version.split('.').map(|part| {
+        let digits = part
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(part.len());
+
+        part[..digits].parse().unwrap_or(0)
+    });
+
+    let mut next = || parts.next().unwrap_or(0);
+
+    (next(), next(), next())
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(parse_server_version("8.0.36"), (8, 0, 36));
+    assert_eq!(parse_server_version("8.0.36-0ubuntu0.22.04.1"), (8, 0, 36));
+    assert_eq!(parse_server_version("5.5.5-10.11.2-MariaDB"), (5, 5, 5));
+    assert_eq!(parse_server_version("10.11.2-MariaDB"), (10, 11, 2));
+    assert_eq!(parse_server_version("11"), (11, 0, 0));
+    assert_eq!(parse_server_version(""), (0, 0, 0));
+}
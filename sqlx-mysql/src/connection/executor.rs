@@ -12,6 +12,7 @@ use crate::protocol::statement::{
 };
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
+use crate::types::NumericOverflow;
 use crate::HashMap;
 use crate::{
     MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlTypeInfo,
@@ -22,15 +23,22 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
-use std::{borrow::Cow, sync::Arc};
+use sqlx_core::interceptor;
+use std::sync::Arc;
 
 impl MySqlConnection {
-    async fn get_or_prepare<'c>(
+    pub(super) async fn get_or_prepare<'c>(
         &mut self,
         sql: &str,
         persistent: bool,
     ) -> Result<(u32, MySqlStatementMetadata), Error> {
         if let Some(statement) = self.cache_statement.get_mut(sql) {
+            #[cfg(feature = "metrics")]
+            sqlx_core::metrics::statement_cache_lookup(
+                <MySql as sqlx_core::database::Database>::NAME,
+                true,
+            );
+
             // <MySqlStatementMetadata> is internally reference-counted
             return Ok((*statement).clone());
         }
@@ -73,6 +81,12 @@ impl MySqlConnection {
         };
 
         if persistent && self.cache_statement.is_enabled() {
+            #[cfg(feature = "metrics")]
+            sqlx_core::metrics::statement_cache_lookup(
+                <MySql as sqlx_core::database::Database>::NAME,
+                false,
+            );
+
             // in case of the cache being full, close the least recently used statement
             if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata.clone())) {
                 self.stream.send_packet(StmtClose { statement: id }).await?;
@@ -88,10 +102,17 @@ impl MySqlConnection {
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        numeric_overflow: Option<NumericOverflow>,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
 
+        if let Some(arguments) = &arguments {
+            logger.arguments(|| arguments.clone());
+        }
+
+        let numeric_overflow = numeric_overflow.unwrap_or(self.numeric_overflow);
+
         self.stream.wait_until_ready().await?;
         self.stream.waiting.push_back(Waiting::Result);
 
@@ -197,6 +218,8 @@ impl MySqlConnection {
                     let v = Either::Right(MySqlRow {
                         row,
                         format,
+                        numeric_overflow,
+                        invalid_utf8: self.invalid_utf8.clone(),
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
                     });
@@ -221,12 +244,18 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
+        // statements were intercepted when they were prepared
+        let interceptor = match query.statement() {
+            Some(_) => None,
+            None => self.query_interceptor.as_ref(),
+        };
         let arguments = query.take_arguments();
+        let sql = interceptor::apply(interceptor, query.sql(), arguments.as_ref());
         let persistent = query.persistent();
+        let numeric_overflow = query.numeric_overflow();
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
+        let stream = Box::pin(try_stream! {
+            let s = self.run(&sql, arguments, persistent, numeric_overflow).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -234,7 +263,16 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
             }
 
             Ok(())
-        })
+        });
+
+        // also records `fetch_optional()`, which is built on this
+        #[cfg(feature = "metrics")]
+        let stream = sqlx_core::metrics::instrument_stream(
+            <MySql as sqlx_core::database::Database>::NAME,
+            stream,
+        );
+
+        stream
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -269,10 +307,11 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         Box::pin(async move {
             self.stream.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, true).await?;
+            let sql = interceptor::rewrite(self.query_interceptor.as_ref(), sql);
+            let (_, metadata) = self.get_or_prepare(&sql, true).await?;
 
             Ok(MySqlStatement {
-                sql: Cow::Borrowed(sql),
+                sql,
                 // metadata has internal Arcs for expensive data structures
                 metadata: metadata.clone(),
             })
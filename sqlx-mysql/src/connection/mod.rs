@@ -7,11 +7,14 @@ pub(crate) use stream::{MySqlStream, Waiting};
 
 use crate::common::StatementCache;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
 use crate::statement::MySqlStatementMetadata;
 use crate::transaction::Transaction;
-use crate::{MySql, MySqlConnectOptions};
+use crate::types::NumericOverflow;
+use crate::{MySql, MySqlConnectOptions, MySqlDatabaseError, MySqlInvalidUtf8};
+use sqlx_core::interceptor::QueryInterceptor;
 
 mod auth;
 mod establish;
@@ -35,6 +38,79 @@ pub struct MySqlConnection {
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
     log_settings: LogSettings,
+
+    numeric_overflow: NumericOverflow,
+
+    invalid_utf8: MySqlInvalidUtf8,
+
+    pub(crate) query_interceptor: Option<QueryInterceptor<MySql>>,
+
+    // the `SET` statement run after connecting, run again after `COM_RESET_CONNECTION`
+    pub(crate) session_setup: String,
+}
+
+impl MySqlConnection {
+    // sends `COM_RESET_CONNECTION`, which also rolls back any transaction and deallocates all
+    // prepared statements; `false` if the server does not implement it
+    async fn reset_connection(&mut self) -> Result<bool, Error> {
+        if !self.stream.supports_reset_connection {
+            return Ok(false);
+        }
+
+        self.stream.send_packet(ResetConnection).await?;
+
+        match self.stream.recv_ok().await {
+            Ok(_) => {
+                self.cache_statement.clear();
+                Ok(true)
+            }
+
+            // `ER_UNKNOWN_COM_ERROR`, e.g. from a proxy in front of the server
+            Err(Error::Database(e))
+                if matches!(
+                    e.try_downcast_ref::<MySqlDatabaseError>(),
+                    Some(e) if e.number() == 1047
+                ) =>
+            {
+                self.stream.supports_reset_connection = false;
+                Ok(false)
+            }
+
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Change how out-of-range numerics are decoded for rows fetched from now on.
+    ///
+    /// See [`MySqlConnectOptions::numeric_overflow()`] for details.
+    pub fn set_numeric_overflow(&mut self, policy: NumericOverflow) {
+        self.numeric_overflow = policy;
+    }
+
+    /// The character set negotiated for this connection, see
+    /// [`MySqlConnectOptions::charset()`].
+    pub fn charset(&self) -> &'static str {
+        self.stream.charset.as_str()
+    }
+
+    /// The collation negotiated for this connection, see
+    /// [`MySqlConnectOptions::collation()`].
+    pub fn collation(&self) -> &'static str {
+        self.stream.collation.as_str()
+    }
+
+    /// Change how invalid UTF-8 is decoded for rows fetched from now on.
+    ///
+    /// See [`MySqlConnectOptions::invalid_utf8()`] for details.
+    pub fn set_invalid_utf8(&mut self, policy: MySqlInvalidUtf8) {
+        self.invalid_utf8 = policy;
+    }
+
+    /// Replace the query interceptor set by
+    /// [`MySqlConnectOptions::query_interceptor()`], or remove it with `None`.
+    pub fn set_query_interceptor(&mut self, interceptor: Option<QueryInterceptor<MySql>>) {
+        self.query_interceptor = interceptor;
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -83,6 +159,65 @@ impl Connection for MySqlConnection {
         self.cache_statement.len()
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            for sql in statements {
+                self.get_or_prepare(sql, true).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            // TRUNCATE always resets AUTO_INCREMENT
+            let sql = if restart_identity {
+                format!("TRUNCATE TABLE {table}")
+            } else {
+                format!("DELETE FROM {table}")
+            };
+
+            self.execute(&*sql).await?;
+
+            Ok(())
+        })
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            if discard_session {
+                if !self.reset_connection().await? {
+                    // the best we can do without `COM_RESET_CONNECTION`; user variables and
+                    // temporary tables are kept
+                    self.execute("ROLLBACK").await?;
+                    self.clear_cached_statements().await?;
+                }
+
+                let session_setup = self.session_setup.clone();
+                self.execute(&*session_setup).await?;
+            } else {
+                // without a transaction, `ROLLBACK` does nothing
+                self.execute("ROLLBACK").await?;
+            }
+
+            self.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             while let Some((statement_id, _)) = self.cache_statement.remove_lru() {
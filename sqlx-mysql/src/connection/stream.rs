@@ -16,6 +16,8 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    // whether the server implements `COM_RESET_CONNECTION`
+    pub(crate) supports_reset_connection: bool,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
@@ -61,6 +63,7 @@ impl<S: Socket> MySqlStream<S> {
             waiting: VecDeque::new(),
             capabilities,
             server_version: (0, 0, 0),
+            supports_reset_connection: false,
             sequence_id: 0,
             collation,
             charset,
@@ -217,6 +220,7 @@ impl<S: Socket> MySqlStream<S> {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            supports_reset_connection: self.supports_reset_connection,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
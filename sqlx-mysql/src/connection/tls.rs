@@ -10,6 +10,7 @@ use std::collections::VecDeque;
 
 struct MapStream {
     server_version: (u16, u16, u16),
+    supports_reset_connection: bool,
     capabilities: Capabilities,
     sequence_id: u8,
     waiting: VecDeque<Waiting>,
@@ -81,6 +82,7 @@ pub(super) async fn maybe_upgrade<S: Socket>(
         tls_config,
         MapStream {
             server_version: stream.server_version,
+            supports_reset_connection: stream.supports_reset_connection,
             capabilities: stream.capabilities,
             sequence_id: stream.sequence_id,
             waiting: stream.waiting,
@@ -98,6 +100,7 @@ impl WithSocket for MapStream {
         MySqlStream {
             socket: BufferedSocket::new(Box::new(socket)),
             server_version: self.server_version,
+            supports_reset_connection: self.supports_reset_connection,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
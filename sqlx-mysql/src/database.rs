@@ -4,7 +4,7 @@ use crate::{
     MySqlTransactionManager, MySqlTypeInfo,
 };
 pub(crate) use sqlx_core::database::{
-    Database, HasArguments, HasStatement, HasStatementCache, HasValueRef,
+    Database, DatabaseCapabilities, HasArguments, HasStatement, HasStatementCache, HasValueRef,
 };
 
 /// MySQL database driver.
@@ -29,6 +29,35 @@ impl Database for MySql {
     const NAME: &'static str = "MySQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["mysql", "mariadb"];
+
+    fn capabilities() -> DatabaseCapabilities {
+        // statements with arguments are prepared, which only supports a single statement
+        DatabaseCapabilities::new().multiple_statements(true)
+    }
+
+    fn cast_sql(expr: &str, ty: &MySqlTypeInfo) -> String {
+        format!("CAST({expr} AS {})", ty.cast_target())
+    }
+
+    // MySQL checks all unique keys for conflicts, so there is no conflict target; `VALUES()` is
+    // deprecated as of MySQL 8.0.20, but row aliases are not supported by MariaDB
+    fn upsert_sql(columns: &[&str], _conflict_target: &[&str], update_columns: &[&str]) -> String {
+        let assignments: Vec<String> = if update_columns.is_empty() {
+            // a no-op update, MySQL has no `DO NOTHING`
+            columns
+                .iter()
+                .take(1)
+                .map(|column| format!("{column} = {column}"))
+                .collect()
+        } else {
+            update_columns
+                .iter()
+                .map(|column| format!("{column} = VALUES({column})"))
+                .collect()
+        };
+
+        format!("ON DUPLICATE KEY UPDATE {}", assignments.join(", "))
+    }
 }
 
 impl<'r> HasValueRef<'r> for MySql {
@@ -52,3 +81,15 @@ impl<'q> HasStatement<'q> for MySql {
 }
 
 impl HasStatementCache for MySql {}
+
+#[test]
+fn test_upsert_sql() {
+    assert_eq!(
+        MySql::upsert_sql(&["id", "email", "name"], &["id"], &["email", "name"]),
+        "ON DUPLICATE KEY UPDATE email = VALUES(email), name = VALUES(name)"
+    );
+    assert_eq!(
+        MySql::upsert_sql(&["id", "email"], &["id"], &[]),
+        "ON DUPLICATE KEY UPDATE id = id"
+    );
+}
@@ -79,6 +79,14 @@ impl DatabaseError for MySqlDatabaseError {
         self
     }
 
+    fn constraint_violation(&self) -> Option<ConstraintViolation> {
+        match self.kind() {
+            ErrorKind::UniqueViolation => Some(parse_unique_violation(self.message())),
+            ErrorKind::ForeignKeyViolation => Some(parse_foreign_key_violation(self.message())),
+            _ => None,
+        }
+    }
+
     fn kind(&self) -> ErrorKind {
         match self.number() {
             error_codes::ER_DUP_KEY
@@ -105,6 +113,85 @@ impl DatabaseError for MySqlDatabaseError {
     }
 }
 
+// Parses the message of unique violations, where the key is qualified with the table since
+// MySQL 8.0.19:
+//
+// Duplicate entry '1-2' for key 'table.name'
+fn parse_unique_violation(message: &str) -> ConstraintViolation {
+    let mut violation = ConstraintViolation::default();
+
+    let Some((value, key)) = message
+        .strip_prefix("Duplicate entry '")
+        .and_then(|rest| rest.rsplit_once("' for key '"))
+    else {
+        return violation;
+    };
+
+    let key = key.strip_suffix('\'').unwrap_or(key);
+
+    violation.value = Some(value.to_owned());
+
+    match key.split_once('.') {
+        Some((table, constraint)) => {
+            violation.table = Some(table.to_owned());
+            violation.constraint = Some(constraint.to_owned());
+        }
+        None => violation.constraint = Some(key.to_owned()),
+    }
+
+    violation
+}
+
+// Parses the message of foreign key violations, which ends with the violated constraint:
+//
+// Cannot add or update a child row: a foreign key constraint fails (`db`.`child`, CONSTRAINT
+// `name` FOREIGN KEY (`parent_id`) REFERENCES `parent` (`id`))
+fn parse_foreign_key_violation(message: &str) -> ConstraintViolation {
+    let mut violation = ConstraintViolation::default();
+
+    let Some((_, constraint)) = message.split_once("a foreign key constraint fails (") else {
+        return violation;
+    };
+
+    let mut rest = constraint;
+
+    // the table is qualified with the database
+    if let Some((table, after)) = rest.split_once(", CONSTRAINT ") {
+        violation.table = table.rsplit('.').next().map(unquote_identifier);
+        rest = after;
+    }
+
+    if let Some((name, after)) = rest.split_once(" FOREIGN KEY (") {
+        violation.constraint = Some(unquote_identifier(name));
+        rest = after;
+    }
+
+    if let Some((columns, after)) = rest.split_once(") REFERENCES ") {
+        violation.columns = columns.split(", ").map(unquote_identifier).collect();
+        rest = after;
+    }
+
+    if let Some((table, after)) = rest.split_once(" (") {
+        violation.referenced_table = Some(unquote_identifier(table));
+
+        if let Some((columns, _)) = after.split_once(')') {
+            violation.referenced_columns = columns.split(", ").map(unquote_identifier).collect();
+        }
+    }
+
+    violation
+}
+
+fn unquote_identifier(identifier: &str) -> String {
+    match identifier
+        .strip_prefix('`')
+        .and_then(|identifier| identifier.strip_suffix('`'))
+    {
+        Some(quoted) => quoted.replace("``", "`"),
+        None => identifier.to_owned(),
+    }
+}
+
 /// The MySQL server uses SQLSTATEs as a generic error category,
 /// and returns a `error_code` instead within the error packet.
 ///
@@ -155,3 +242,40 @@ pub(crate) mod error_codes {
     /// Only available after 8.0.16.
     pub const ER_CHECK_CONSTRAINT_VIOLATED: u16 = 3819;
 }
+
+#[test]
+fn test_parse_constraint_violation() {
+    let violation = parse_unique_violation("Duplicate entry '1-2' for key 'users.users_a_b'");
+
+    assert_eq!(violation.constraint.as_deref(), Some("users_a_b"));
+    assert_eq!(violation.table.as_deref(), Some("users"));
+    assert_eq!(violation.value.as_deref(), Some("1-2"));
+
+    let violation = parse_unique_violation("Duplicate entry 'it's' for key 'PRIMARY'");
+
+    assert_eq!(violation.constraint.as_deref(), Some("PRIMARY"));
+    assert_eq!(violation.table, None);
+    assert_eq!(violation.value.as_deref(), Some("it's"));
+
+    let violation = parse_foreign_key_violation(
+        "Cannot add or update a child row: a foreign key constraint fails (`test`.`tweet_reply`, \
+         CONSTRAINT `tweet_reply_ibfk_1` FOREIGN KEY (`tweet_id`) REFERENCES `tweet` (`id`))",
+    );
+
+    assert_eq!(violation.constraint.as_deref(), Some("tweet_reply_ibfk_1"));
+    assert_eq!(violation.table.as_deref(), Some("tweet_reply"));
+    assert_eq!(violation.columns, ["tweet_id"]);
+    assert_eq!(violation.value, None);
+    assert_eq!(violation.referenced_table.as_deref(), Some("tweet"));
+    assert_eq!(violation.referenced_columns, ["id"]);
+
+    let violation = parse_foreign_key_violation(
+        "Cannot delete or update a parent row: a foreign key constraint fails (`test`.`child`, \
+         CONSTRAINT `fk` FOREIGN KEY (`a`, `b`) REFERENCES `parent` (`x`, `y`) ON DELETE \
+         RESTRICT)",
+    );
+
+    assert_eq!(violation.columns, ["a", "b"]);
+    assert_eq!(violation.referenced_table.as_deref(), Some("parent"));
+    assert_eq!(violation.referenced_columns, ["x", "y"]);
+}
@@ -62,6 +62,7 @@ impl ConnectOptions for MySqlConnectOptions {
             ));
 
             conn.execute(&*options).await?;
+            conn.session_setup = options;
 
             Ok(conn)
         })
@@ -76,4 +77,9 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_arguments(mut self, enabled: bool) -> Self {
+        self.log_settings.log_arguments(enabled);
+        self
+    }
 }
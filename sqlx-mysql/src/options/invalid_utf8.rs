@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::str::{from_utf8, Utf8Error};
+use std::sync::Arc;
+
+type OnInvalid = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+/// How text columns that are not valid UTF-8 are decoded, e.g. `latin1` data that was written
+/// into a `utf8mb4` column by a client with the wrong connection charset.
+///
+/// It is used by the [`invalid_utf8`](super::MySqlConnectOptions::invalid_utf8) method and
+/// applies to decoding `String`, `Box<str>` and `Cow<str>`. Decoding `&str` always fails for
+/// invalid UTF-8 as it cannot borrow repaired text.
+///
+/// ```rust,no_run
+/// use sqlx_mysql::{MySqlConnectOptions, MySqlInvalidUtf8};
+///
+/// let options = MySqlConnectOptions::new().invalid_utf8(
+///     MySqlInvalidUtf8::lossy().on_invalid(|bytes| {
+///         eprintln!("repaired invalid UTF-8: {:?}", String::from_utf8_lossy(bytes));
+///     }),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct MySqlInvalidUtf8 {
+    repair: Repair,
+    on_invalid: Option<OnInvalid>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Repair {
+    #[default]
+    Error,
+    Lossy,
+    Latin1,
+}
+
+impl MySqlInvalidUtf8 {
+    /// Fail to decode invalid UTF-8. This is the default.
+    pub fn error() -> Self {
+        Self::default()
+    }
+
+    /// Replace invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn lossy() -> Self {
+        Self {
+            repair: Repair::Lossy,
+            on_invalid: None,
+        }
+    }
+
+    /// Decode invalid UTF-8 as ISO-8859-1 (`latin1`), mapping each byte to one character.
+    ///
+    /// This recovers the original text of `latin1` data stored in UTF-8 columns without loss.
+    /// Values that are valid UTF-8 are decoded as such.
+    pub fn latin1() -> Self {
+        Self {
+            repair: Repair::Latin1,
+            on_invalid: None,
+        }
+    }
+
+    /// Call `callback` with the raw bytes of every value that is repaired, e.g. to log or count
+    /// them.
+    ///
+    /// Not called if invalid UTF-8 fails to decode.
+    pub fn on_invalid<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[u8]) + Send + Sync + 'static,
+    {
+        self.on_invalid = Some(Arc::new(callback));
+        self
+    }
+
+    pub(crate) fn decode<'r>(&self, bytes: &'r [u8]) -> Result<Cow<'r, str>, Utf8Error> {
+        let error = match from_utf8(bytes) {
+            Ok(text) => return Ok(Cow::Borrowed(text)),
+            Err(error) => error,
+        };
+
+        let text = match self.repair {
+            Repair::Error => return Err(error),
+            Repair::Lossy => String::from_utf8_lossy(bytes).into_owned(),
+            Repair::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+        };
+
+        if let Some(on_invalid) = &self.on_invalid {
+            on_invalid(bytes);
+        }
+
+        Ok(Cow::Owned(text))
+    }
+}
+
+impl Debug for MySqlInvalidUtf8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MySqlInvalidUtf8")
+            .field("repair", &self.repair)
+            .field("on_invalid", &self.on_invalid.is_some())
+            .finish()
+    }
+}
+
+#[test]
+fn test_decode_invalid_utf8() {
+    let invalid = b"caf\xe9";
+
+    assert!(MySqlInvalidUtf8::error().decode(invalid).is_err());
+    assert_eq!(
+        MySqlInvalidUtf8::lossy().decode(invalid).unwrap(),
+        "caf\u{fffd}"
+    );
+    assert_eq!(MySqlInvalidUtf8::latin1().decode(invalid).unwrap(), "café");
+    assert_eq!(
+        MySqlInvalidUtf8::latin1()
+            .decode("café".as_bytes())
+            .unwrap(),
+        "café"
+    );
+
+    let repaired = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = Arc::clone(&repaired);
+    let policy = MySqlInvalidUtf8::lossy().on_invalid(move |bytes| {
+        assert_eq!(bytes, b"caf\xe9");
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    });
+
+    policy.decode(b"cafe").unwrap();
+    policy.decode(invalid).unwrap();
+
+    assert_eq!(repaired.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
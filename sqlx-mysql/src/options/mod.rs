@@ -1,10 +1,15 @@
 use std::path::{Path, PathBuf};
 
 mod connect;
+mod invalid_utf8;
 mod parse;
 mod ssl_mode;
 
+use crate::types::NumericOverflow;
+use crate::MySql;
 use crate::{connection::LogSettings, net::tls::CertificateInput};
+pub use invalid_utf8::MySqlInvalidUtf8;
+use sqlx_core::interceptor::{self, QueryInterceptor, QueryRewriter};
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -77,6 +82,9 @@ pub struct MySqlConnectOptions {
     pub(crate) log_settings: LogSettings,
     pub(crate) pipes_as_concat: bool,
     pub(crate) enable_cleartext_plugin: bool,
+    pub(crate) numeric_overflow: NumericOverflow,
+    pub(crate) invalid_utf8: MySqlInvalidUtf8,
+    pub(crate) query_interceptor: Option<QueryInterceptor<MySql>>,
 }
 
 impl Default for MySqlConnectOptions {
@@ -105,6 +113,9 @@ impl MySqlConnectOptions {
             log_settings: Default::default(),
             pipes_as_concat: true,
             enable_cleartext_plugin: false,
+            numeric_overflow: NumericOverflow::DriverDefault,
+            invalid_utf8: MySqlInvalidUtf8::error(),
+            query_interceptor: None,
         }
     }
 
@@ -308,7 +319,8 @@ impl MySqlConnectOptions {
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
-    /// If you need to connect to an older version, we recommend you to change this to `utf8`.
+    /// If you need to connect to an older version, we recommend you to change this to `utf8`;
+    /// connecting to such a server with `utf8mb4` fails instead of garbling non-BMP characters.
     pub fn charset(mut self, charset: &str) -> Self {
         self.charset = charset.to_owned();
         self
@@ -318,6 +330,12 @@ impl MySqlConnectOptions {
     ///
     /// The default collation is derived from the `charset`. Normally, you should only have to set
     /// the `charset`.
+    ///
+    /// Connecting fails with [`Error::Configuration`][sqlx_core::error::Error::Configuration] if the
+    /// collation does not belong to the `charset`, e.g. `utf8mb4_bin` with `utf8`, or if the
+    /// server is too old to support it, e.g. `utf8mb4_0900_ai_ci` before MySQL 8.0 or on
+    /// MariaDB. The collation of result columns is available from
+    /// [`MySqlTypeInfo::collation()`][crate::MySqlTypeInfo::collation].
     pub fn collation(mut self, collation: &str) -> Self {
         self.collation = Some(collation.to_owned());
         self
@@ -347,4 +365,45 @@ impl MySqlConnectOptions {
         self.enable_cleartext_plugin = flag_val;
         self
     }
+
+    /// Sets how integers and floats that do not fit into the requested Rust type are decoded,
+    /// e.g. a `BIGINT` of `2^40` into an `i32` or a `DOUBLE` of `1e300` into an `f32`.
+    ///
+    /// Defaults to [`NumericOverflow::DriverDefault`], which fails for integers and converts
+    /// `f32` like an `as` cast. Can be changed for an open connection with
+    /// [`MySqlConnection::set_numeric_overflow()`][crate::MySqlConnection::set_numeric_overflow].
+    pub fn numeric_overflow(mut self, policy: NumericOverflow) -> Self {
+        self.numeric_overflow = policy;
+        self
+    }
+
+    /// Sets how text that is not valid UTF-8 is decoded, instead of failing to decode the
+    /// column.
+    ///
+    /// Defaults to [`MySqlInvalidUtf8::error()`]. Can be changed for an open connection with
+    /// [`MySqlConnection::set_invalid_utf8()`][crate::MySqlConnection::set_invalid_utf8].
+    pub fn invalid_utf8(mut self, policy: MySqlInvalidUtf8) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Rewrite the SQL of every query executed on the connections, e.g. to hide soft-deleted
+    /// rows. See the [`interceptor`](sqlx_core::interceptor) module for details.
+    pub fn query_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.query_interceptor = Some(interceptor::query_interceptor(interceptor));
+        self
+    }
+
+    /// Rewrite the SQL of every query executed on the connections with `rewriter`, which also
+    /// sees the bound arguments, e.g. to route the queries of a tenant to the tables of its
+    /// shard.
+    ///
+    /// Replaces the [`query_interceptor()`](Self::query_interceptor), if any.
+    pub fn query_rewriter<R: QueryRewriter<MySql>>(mut self, rewriter: R) -> Self {
+        self.query_interceptor = Some(interceptor::query_interceptor(rewriter));
+        self
+    }
 }
@@ -5,14 +5,17 @@ pub(crate) use sqlx_core::row::*;
 use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::types::NumericOverflow;
 use crate::HashMap;
-use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
+use crate::{protocol, MySql, MySqlColumn, MySqlInvalidUtf8, MySqlValueFormat, MySqlValueRef};
 
 /// Implementation of [`Row`] for MySQL.
 #[derive(Debug)]
 pub struct MySqlRow {
     pub(crate) row: protocol::Row,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) numeric_overflow: NumericOverflow,
+    pub(crate) invalid_utf8: MySqlInvalidUtf8,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
 }
@@ -34,6 +37,8 @@ impl Row for MySqlRow {
 
         Ok(MySqlValueRef {
             format: self.format,
+            numeric_overflow: self.numeric_overflow,
+            invalid_utf8: &self.invalid_utf8,
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
             value,
@@ -157,8 +157,8 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
 
     eprintln!("created database {new_db_name}");
 
-    Ok(TestContext {
-        pool_opts: PoolOptions::new()
+    Ok(TestContext::new(
+        PoolOptions::new()
             // Don't allow a single test to take all the connections.
             // Most tests shouldn't require more than 5 connections concurrently,
             // or else they're likely doing too much in one test.
@@ -166,13 +166,13 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
             // Close connections ASAP if left in the idle queue.
             .idle_timeout(Some(Duration::from_secs(1)))
             .parent(master_pool.clone()),
-        connect_opts: master_pool
+        master_pool
             .connect_options()
             .deref()
             .clone()
             .database(&new_db_name),
-        db_name: new_db_name,
-    })
+        new_db_name,
+    ))
 }
 
 async fn do_cleanup(conn: &mut MySqlConnection, created_before: Duration) -> Result<usize, Error> {
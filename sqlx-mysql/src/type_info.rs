@@ -2,6 +2,7 @@ use std::fmt::{self, Display, Formatter};
 
 pub(crate) use sqlx_core::type_info::*;
 
+use crate::collation::Collation;
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, ColumnType};
 
 /// Type information for a MySql type.
@@ -27,6 +28,82 @@ impl MySqlTypeInfo {
         }
     }
 
+    /// The collation of a column, e.g. `utf8mb4_unicode_ci`, or `binary` for non-string types.
+    ///
+    /// `None` if the collation is not known to SQLx, e.g. one of the collations with an ID above
+    /// 255 added in MySQL 8.0.
+    pub fn collation(&self) -> Option<&'static str> {
+        Collation::from_id(self.char_set).map(|collation| collation.as_str())
+    }
+
+    /// The character set of a column, e.g. `utf8mb4`, or `binary` for non-string types.
+    ///
+    /// `None` if the collation is not known to SQLx, see [`collation()`][Self::collation].
+    pub fn charset(&self) -> Option<&'static str> {
+        Collation::from_id(self.char_set).map(|collation| collation.charset().as_str())
+    }
+
+    // MySQL only accepts a few types in `CAST()`, and not always under their column type names
+    // https://dev.mysql.com/doc/refman/8.0/en/cast-functions.html#function_cast
+    pub(crate) fn cast_target(&self) -> &'static str {
+        let is_binary = self.char_set == 63;
+
+        match self.r#type {
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong
+            | ColumnType::Year
+            | ColumnType::Bit
+                if self.flags.contains(ColumnFlags::UNSIGNED) =>
+            {
+                "UNSIGNED"
+            }
+
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong
+            | ColumnType::Year
+            | ColumnType::Bit => "SIGNED",
+
+            ColumnType::Float => "FLOAT",
+            ColumnType::Double => "DOUBLE",
+            // without a precision, `DECIMAL` means `DECIMAL(10, 0)`
+            ColumnType::Decimal | ColumnType::NewDecimal => "DECIMAL(65, 30)",
+            ColumnType::Date => "DATE",
+            ColumnType::Time => "TIME(6)",
+            ColumnType::Timestamp | ColumnType::Datetime => "DATETIME(6)",
+            ColumnType::Json => "JSON",
+
+            ColumnType::Null | ColumnType::Geometry => "BINARY",
+
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+                if is_binary =>
+            {
+                "BINARY"
+            }
+
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Enum
+            | ColumnType::Set => "CHAR",
+        }
+    }
+
     #[doc(hidden)]
     pub const fn __enum() -> Self {
         Self {
@@ -113,3 +190,16 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
 }
 
 impl Eq for MySqlTypeInfo {}
+
+#[test]
+fn test_cast_target() {
+    use crate::types::Type;
+    use crate::MySql;
+
+    assert_eq!(<i64 as Type<MySql>>::type_info().cast_target(), "SIGNED");
+    assert_eq!(<u32 as Type<MySql>>::type_info().cast_target(), "UNSIGNED");
+    assert_eq!(<bool as Type<MySql>>::type_info().cast_target(), "UNSIGNED");
+    assert_eq!(<f64 as Type<MySql>>::type_info().cast_target(), "DOUBLE");
+    assert_eq!(<str as Type<MySql>>::type_info().cast_target(), "CHAR");
+    assert_eq!(<[u8] as Type<MySql>>::type_info().cast_target(), "BINARY");
+}
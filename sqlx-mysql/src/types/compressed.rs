@@ -0,0 +1,35 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Codec, Compressed, CompressedData, Type};
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+impl<T, C> Type<MySql> for Compressed<T, C> {
+    fn type_info() -> MySqlTypeInfo {
+        <Vec<u8> as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <Vec<u8> as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'q, T, C> Encode<'q, MySql> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <Vec<u8> as Encode<MySql>>::encode(self.compress(), buf)
+    }
+}
+
+impl<'r, T, C> Decode<'r, MySql> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        Self::decompress(<&[u8] as Decode<MySql>>::decode(value)?)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Encrypted, Type};
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+impl<T, K> Type<MySql> for Encrypted<T, K> {
+    fn type_info() -> MySqlTypeInfo {
+        <Vec<u8> as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <Vec<u8> as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'q, T, K> Encode<'q, MySql> for Encrypted<T, K> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <&[u8] as Encode<MySql>>::encode(self.as_bytes(), buf)
+    }
+}
+
+impl<'r, T, K> Decode<'r, MySql> for Encrypted<T, K> {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::from_bytes(
+            <&[u8] as Decode<MySql>>::decode(value)?.to_vec(),
+        ))
+    }
+}
@@ -4,7 +4,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::ColumnType;
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
 fn real_compatible(ty: &MySqlTypeInfo) -> bool {
@@ -55,14 +55,19 @@ impl Decode<'_, MySql> for f32 {
 
                 if buf.len() == 8 {
                     // MySQL can return 8-byte DOUBLE values for a FLOAT
-                    // We take and truncate to f32 as that's the same behavior as *in* MySQL
-                    LittleEndian::read_f64(buf) as f32
+                    value
+                        .numeric_overflow()
+                        .or(NumericOverflow::Lossy)
+                        .narrow(LittleEndian::read_f64(buf))?
                 } else {
                     LittleEndian::read_f32(buf)
                 }
             }
 
-            MySqlValueFormat::Text => value.as_str()?.parse()?,
+            MySqlValueFormat::Text => value
+                .numeric_overflow()
+                .or(NumericOverflow::Lossy)
+                .narrow(value.as_str()?.parse::<f64>()?)?,
         })
     }
 }
@@ -4,7 +4,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::{ColumnFlags, ColumnType};
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
 fn int_compatible(ty: &MySqlTypeInfo) -> bool {
@@ -102,19 +102,28 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
 
 impl Decode<'_, MySql> for i8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(int_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for i16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(int_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for i32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(int_decode(value)?)
     }
 }
 
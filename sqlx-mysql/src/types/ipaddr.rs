@@ -0,0 +1,42 @@
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::io::MySqlBufMutExt;
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+impl Type<MySql> for IpAddr {
+    fn type_info() -> MySqlTypeInfo {
+        <IpNetwork as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <IpNetwork as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for IpAddr {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_str_lenenc(&self.to_string());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for IpAddr {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let ipnetwork = IpNetwork::decode(value)?;
+
+        if ipnetwork.is_ipv4() && ipnetwork.prefix() != 32
+            || ipnetwork.is_ipv6() && ipnetwork.prefix() != 128
+        {
+            Err("lossy decode from network with prefix")?
+        }
+
+        Ok(ipnetwork.ip())
+    }
+}
@@ -0,0 +1,36 @@
+use ipnetwork::IpNetwork;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::io::MySqlBufMutExt;
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+impl Type<MySql> for IpNetwork {
+    fn type_info() -> MySqlTypeInfo {
+        <&str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for IpNetwork {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_str_lenenc(&self.to_string());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for IpNetwork {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        // delegate to the &str type to decode from MySQL
+        let text = <&str as Decode<MySql>>::decode(value)?;
+
+        // an address without a prefix is a single host
+        text.parse().map_err(Into::into)
+    }
+}
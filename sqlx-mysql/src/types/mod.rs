@@ -82,6 +82,26 @@
 //! | `uuid::fmt::Hyphenated`               | CHAR(36)                                             |
 //! | `uuid::fmt::Simple`                   | CHAR(32)                                             |
 //!
+//! ### [`ipnetwork`](https://crates.io/crates/ipnetwork)
+//!
+//! Requires the `ipnetwork` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL type(s)                                        |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `ipnetwork::IpNetwork`                | VARCHAR, CHAR, TEXT                                  |
+//! | [`std::net::IpAddr`]                  | VARCHAR, CHAR, TEXT                                  |
+//!
+//! Networks are stored in CIDR notation, e.g. `192.168.0.0/24`; an address without a prefix
+//! decodes as a single host. Decoding an `IpAddr` fails for a network that is not a single host.
+//!
+//! ### [`mac_address`](https://crates.io/crates/mac_address)
+//!
+//! Requires the `mac_address` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL type(s)                                        |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `mac_address::MacAddress`             | VARCHAR, CHAR, TEXT                                  |
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -110,6 +130,12 @@ mod uint;
 #[cfg(feature = "json")]
 mod json;
 
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod compressed;
+
+#[cfg(feature = "encryption")]
+mod encrypted;
+
 #[cfg(feature = "bigdecimal")]
 mod bigdecimal;
 
@@ -124,3 +150,12 @@ mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
+
+#[cfg(feature = "ipnetwork")]
+mod ipaddr;
+
+#[cfg(feature = "mac_address")]
+mod mac_address;
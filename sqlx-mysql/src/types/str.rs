@@ -80,7 +80,7 @@ impl Encode<'_, MySql> for Box<str> {
 
 impl<'r> Decode<'r, MySql> for Box<str> {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        <&str as Decode<MySql>>::decode(value).map(Box::from)
+        value.as_text().map(Box::from)
     }
 }
 
@@ -102,7 +102,7 @@ impl Encode<'_, MySql> for String {
 
 impl Decode<'_, MySql> for String {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        <&str as Decode<MySql>>::decode(value).map(ToOwned::to_owned)
+        value.as_text().map(Cow::into_owned)
     }
 }
 
@@ -127,6 +127,6 @@ impl Encode<'_, MySql> for Cow<'_, str> {
 
 impl<'r> Decode<'r, MySql> for Cow<'r, str> {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
-        value.as_str().map(Cow::Borrowed)
+        value.as_text()
     }
 }
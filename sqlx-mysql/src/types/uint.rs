@@ -2,7 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::{ColumnFlags, ColumnType};
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 use byteorder::{ByteOrder, LittleEndian};
 
@@ -126,19 +126,28 @@ fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
 
 impl Decode<'_, MySql> for u8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(uint_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for u16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(uint_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for u32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(uint_decode(value)?)
     }
 }
 
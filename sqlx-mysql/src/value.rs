@@ -6,7 +6,8 @@ pub(crate) use sqlx_core::value::*;
 
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
-use crate::{MySql, MySqlTypeInfo};
+use crate::types::NumericOverflow;
+use crate::{MySql, MySqlInvalidUtf8, MySqlTypeInfo};
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -21,6 +22,8 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    numeric_overflow: NumericOverflow,
+    invalid_utf8: MySqlInvalidUtf8,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -30,6 +33,8 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) numeric_overflow: NumericOverflow,
+    pub(crate) invalid_utf8: &'r MySqlInvalidUtf8,
 }
 
 impl<'r> MySqlValueRef<'r> {
@@ -37,6 +42,10 @@ impl<'r> MySqlValueRef<'r> {
         self.format
     }
 
+    pub(crate) fn numeric_overflow(&self) -> NumericOverflow {
+        self.numeric_overflow
+    }
+
     pub(crate) fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
         match &self.value {
             Some(v) => Ok(v),
@@ -47,6 +56,11 @@ impl<'r> MySqlValueRef<'r> {
     pub(crate) fn as_str(&self) -> Result<&'r str, BoxDynError> {
         Ok(from_utf8(self.as_bytes()?)?)
     }
+
+    /// Like [`as_str()`][Self::as_str], but repairs invalid UTF-8 as configured.
+    pub(crate) fn as_text(&self) -> Result<Cow<'r, str>, BoxDynError> {
+        Ok(self.invalid_utf8.decode(self.as_bytes()?)?)
+    }
 }
 
 impl Value for MySqlValue {
@@ -58,6 +72,8 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            numeric_overflow: self.numeric_overflow,
+            invalid_utf8: &self.invalid_utf8,
         }
     }
 
@@ -86,6 +102,8 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            numeric_overflow: self.numeric_overflow,
+            invalid_utf8: self.invalid_utf8.clone(),
         }
     }
 
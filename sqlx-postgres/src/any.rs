@@ -10,7 +10,7 @@ pub use sqlx_core::any::*;
 
 use crate::type_info::PgType;
 use sqlx_core::connection::Connection;
-use sqlx_core::database::Database;
+use sqlx_core::database::{Database, DatabaseCapabilities};
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
@@ -23,6 +23,10 @@ impl AnyConnectionBackend for PgConnection {
         <Postgres as Database>::NAME
     }
 
+    fn capabilities(&self) -> DatabaseCapabilities {
+        <Postgres as Database>::capabilities()
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -51,6 +55,25 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::start_rollback(self)
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::prepare_cached(self, statements)
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::clear_table(self, table, restart_identity)
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, discard_session)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
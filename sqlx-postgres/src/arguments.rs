@@ -1,13 +1,19 @@
-use std::fmt::{self, Write};
+use std::fmt::{self, Display, Write};
 use std::ops::{Deref, DerefMut};
 
+use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::type_info::PgType;
 use crate::types::Type;
-use crate::{PgConnection, PgTypeInfo, Postgres};
+use crate::value::PgValueFormat;
+use crate::{PgConnection, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::bytes::Buf;
+use sqlx_core::type_info::TypeInfo;
 
 pub(crate) use sqlx_core::arguments::Arguments;
+use sqlx_core::arguments::{write_redacted, write_text};
 
 // TODO: buf.patch(|| ...) is a poor name, can we think of a better name? Maybe `buf.lazy(||)` ?
 // TODO: Extend the patch system to support dynamic lengths
@@ -56,9 +62,25 @@ pub struct PgArguments {
 
     // Buffer of encoded bind parameters
     pub(crate) buffer: PgArgumentBuffer,
+
+    // Indexes of the parameters to redact when logging
+    pub(crate) sensitive: Vec<usize>,
 }
 
 impl PgArguments {
+    // a copy of the values to render in the statement log, without the pending patches
+    pub(crate) fn for_logging(&self) -> PgArguments {
+        PgArguments {
+            types: self.types.clone(),
+            buffer: PgArgumentBuffer {
+                buffer: self.buffer.buffer.clone(),
+                count: self.buffer.count,
+                ..Default::default()
+            },
+            sensitive: self.sensitive.clone(),
+        }
+    }
+
     pub(crate) fn add<'q, T>(&mut self, value: T)
     where
         T: Encode<'q, Postgres> + Type<Postgres>,
@@ -119,9 +141,85 @@ impl<'q> Arguments<'q> for PgArguments {
         self.add(value)
     }
 
+    fn add_sensitive<T>(&mut self, value: T)
+    where
+        T: Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(value);
+        self.sensitive.push(self.buffer.count - 1);
+    }
+
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         write!(writer, "${}", self.buffer.count)
     }
+
+    fn format_redacted<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        // each value is prefixed with its length, or -1 for NULL
+        let mut values = Vec::with_capacity(self.types.len());
+        let mut buf = &self.buffer[..];
+
+        while buf.remaining() >= 4 {
+            match usize::try_from(buf.get_i32()) {
+                Ok(len) if len <= buf.len() => {
+                    values.push(Some(&buf[..len]));
+                    buf.advance(len);
+                }
+                Ok(_) => break,
+                Err(_) => values.push(None),
+            }
+        }
+
+        write_redacted(
+            writer,
+            self.types.len(),
+            &self.sensitive,
+            |writer, i| match values.get(i) {
+                Some(Some(value)) => format_value(writer, &self.types[i], value),
+                Some(None) => writer.write_str("NULL"),
+                None => write!(writer, "<{}>", self.types[i].name()),
+            },
+        )
+    }
+}
+
+// arguments are always sent in the binary format
+fn format_value<W: Write>(writer: &mut W, type_info: &PgTypeInfo, value: &[u8]) -> fmt::Result {
+    let value = PgValueRef {
+        value: Some(value),
+        row: None,
+        type_info: type_info.clone(),
+        format: PgValueFormat::Binary,
+    };
+
+    match type_info.0 {
+        PgType::Bool => write_decoded::<bool, _>(writer, value),
+        PgType::Int2 => write_decoded::<i16, _>(writer, value),
+        PgType::Int4 => write_decoded::<i32, _>(writer, value),
+        PgType::Int8 => write_decoded::<i64, _>(writer, value),
+        PgType::Float4 => write_decoded::<f32, _>(writer, value),
+        PgType::Float8 => write_decoded::<f64, _>(writer, value),
+        PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+            match <&str as Decode<Postgres>>::decode(value) {
+                Ok(text) => write_text(writer, text),
+                Err(_) => write!(writer, "<{}>", type_info.name()),
+            }
+        }
+        PgType::Bytea => write!(writer, "<{} bytes>", value.value.map_or(0, <[u8]>::len)),
+        _ => write!(writer, "<{}>", type_info.name()),
+    }
+}
+
+fn write_decoded<'r, T, W>(writer: &mut W, value: PgValueRef<'r>) -> fmt::Result
+where
+    T: Decode<'r, Postgres> + Display,
+    W: Write,
+{
+    let name = value.type_info.name().to_owned();
+
+    match T::decode(value) {
+        Ok(value) => write!(writer, "{value}"),
+        Err(_) => write!(writer, "<{name}>"),
+    }
 }
 
 impl PgArgumentBuffer {
@@ -0,0 +1,401 @@
+use std::cmp;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_channel::{mpsc, oneshot};
+use futures_core::stream::Stream;
+use futures_util::future::{self, Either};
+use futures_util::task::AtomicWaker;
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use sqlx_core::HashMap;
+
+use crate::error::{BoxDynError, Error};
+use crate::PgListener;
+
+/// Fans out notifications received by a single [`PgListener`] to any number of typed
+/// subscriptions, each with its own bounded queue.
+///
+/// The listener is driven by a background task that is spawned on construction and keeps
+/// running until the router is dropped, which ends all [`PgSubscription`]s. Channels are
+/// `LISTEN`ed to when the first subscription for them is created and `UNLISTEN`ed once the last
+/// one is dropped; dropped subscriptions are noticed with the next notification on any channel.
+///
+/// If the listener fails, it is reconnected with an exponential backoff; notifications sent
+/// while it was disconnected are not delivered.
+///
+/// ```rust,ignore
+/// use futures_util::StreamExt;
+/// use sqlx::postgres::{PgChannelRouter, PgListener, PgOverflowPolicy};
+///
+/// #[derive(serde::Deserialize)]
+/// struct OrderPlaced {
+///     order_id: i64,
+/// }
+///
+/// let router = PgChannelRouter::new(PgListener::connect("postgres:// ...").await?);
+///
+/// let mut orders = router
+///     .subscribe_json::<OrderPlaced>("orders", 1024, PgOverflowPolicy::DropOldest)
+///     .await?;
+///
+/// let mut cache_invalidations = router
+///     .subscribe("cache", 16, PgOverflowPolicy::DropNewest, |payload| Ok(payload.to_owned()))
+///     .await?;
+///
+/// while let Some(order) = orders.next().await {
+///     println!("order placed: {}", order?.order_id);
+/// }
+/// ```
+pub struct PgChannelRouter {
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+/// What to do when a notification arrives for a [`PgSubscription`] whose queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgOverflowPolicy {
+    /// Discard the oldest queued notification to make room.
+    DropOldest,
+
+    /// Discard the new notification.
+    DropNewest,
+
+    /// End the subscription; its stream yields the queued notifications and then `None`.
+    Close,
+}
+
+/// A typed stream of notifications from a single channel, see [`PgChannelRouter`].
+pub struct PgSubscription<T> {
+    queue: Arc<Queue<T>>,
+}
+
+enum Command {
+    Subscribe {
+        channel: String,
+        subscriber: Box<dyn Deliver>,
+        subscribed: oneshot::Sender<Result<(), Error>>,
+    },
+}
+
+impl PgChannelRouter {
+    /// Spawn a task which routes the notifications received by `listener`.
+    ///
+    /// Channels the listener is already listening to receive no subscriptions and are
+    /// `UNLISTEN`ed the first time a subscription for them is dropped.
+    ///
+    /// # Panics
+    /// If no async runtime is available.
+    pub fn new(listener: PgListener) -> Self {
+        let (commands, rx) = mpsc::unbounded();
+
+        sqlx_core::rt::spawn(route(listener, rx));
+
+        Self { commands }
+    }
+
+    /// Subscribe to `channel`, decoding each payload with `decode`.
+    ///
+    /// Up to `capacity` notifications are queued for the subscription; see [`PgOverflowPolicy`]
+    /// for what happens beyond that. Payloads that fail to decode are yielded as
+    /// [`Error::Decode`].
+    pub async fn subscribe<T, D>(
+        &self,
+        channel: &str,
+        capacity: usize,
+        overflow: PgOverflowPolicy,
+        decode: D,
+    ) -> Result<PgSubscription<T>, Error>
+    where
+        T: Send + 'static,
+        D: Fn(&str) -> Result<T, BoxDynError> + Send + 'static,
+    {
+        let queue = Arc::new(Queue {
+            state: Mutex::new(QueueState {
+                items: VecDeque::with_capacity(cmp::min(capacity, 1024)),
+                dropped: 0,
+                closed: false,
+            }),
+            waker: AtomicWaker::new(),
+            capacity: cmp::max(capacity, 1),
+            overflow,
+        });
+
+        let (subscribed, rx) = oneshot::channel();
+
+        self.commands
+            .unbounded_send(Command::Subscribe {
+                channel: channel.to_owned(),
+                subscriber: Box::new(Subscriber {
+                    queue: Arc::clone(&queue),
+                    decode,
+                }),
+                subscribed,
+            })
+            .map_err(|_| Error::WorkerCrashed)?;
+
+        rx.await.map_err(|_| Error::WorkerCrashed)??;
+
+        Ok(PgSubscription { queue })
+    }
+
+    /// Subscribe to `channel`, decoding each payload as JSON.
+    ///
+    /// See [`subscribe()`][Self::subscribe] for details.
+    pub async fn subscribe_json<T>(
+        &self,
+        channel: &str,
+        capacity: usize,
+        overflow: PgOverflowPolicy,
+    ) -> Result<PgSubscription<T>, Error>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.subscribe(channel, capacity, overflow, |payload| {
+            serde_json::from_str(payload).map_err(Into::into)
+        })
+        .await
+    }
+}
+
+impl<T> PgSubscription<T> {
+    /// Receive the next notification, or `None` if the subscription has ended.
+    ///
+    /// A subscription ends if it was closed by [`PgOverflowPolicy::Close`], if the
+    /// [`PgChannelRouter`] was dropped, or if the pool of the listener was closed.
+    pub async fn recv(&mut self) -> Option<Result<T, Error>> {
+        self.next().await
+    }
+
+    /// The number of notifications discarded so far because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.queue.lock().dropped
+    }
+}
+
+impl<T> Stream for PgSubscription<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let queue = &self.queue;
+
+        // register first so a notification delivered in between wakes us
+        queue.waker.register(cx.waker());
+
+        let mut state = queue.lock();
+
+        match state.items.pop_front() {
+            Some(item) => Poll::Ready(Some(item)),
+            None if state.closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for PgSubscription<T> {
+    fn drop(&mut self) {
+        // lets the router forget about this subscription with the next notification
+        self.queue.lock().closed = true;
+    }
+}
+
+impl Debug for PgChannelRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgChannelRouter").finish()
+    }
+}
+
+impl<T> Debug for PgSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgSubscription")
+            .field("overflow", &self.queue.overflow)
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+struct Queue<T> {
+    state: Mutex<QueueState<T>>,
+    waker: AtomicWaker,
+    capacity: usize,
+    overflow: PgOverflowPolicy,
+}
+
+struct QueueState<T> {
+    items: VecDeque<Result<T, Error>>,
+    dropped: u64,
+    closed: bool,
+}
+
+impl<T> Queue<T> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState<T>> {
+        // the lock is never held across user code that could panic
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.waker.wake();
+    }
+}
+
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// The type-erased sending half of a subscription.
+trait Deliver: Send {
+    /// Decode and queue `payload`; returns `false` if the subscription has ended.
+    fn deliver(&self, payload: &str) -> bool;
+
+    fn is_closed(&self) -> bool;
+
+    fn close(&self);
+}
+
+struct Subscriber<T, D> {
+    queue: Arc<Queue<T>>,
+    decode: D,
+}
+
+impl<T, D> Deliver for Subscriber<T, D>
+where
+    T: Send,
+    D: Fn(&str) -> Result<T, BoxDynError> + Send,
+{
+    fn deliver(&self, payload: &str) -> bool {
+        if self.queue.lock().closed {
+            return false;
+        }
+
+        let item = (self.decode)(payload).map_err(Error::Decode);
+
+        let mut state = self.queue.lock();
+
+        if state.items.len() >= self.queue.capacity {
+            match self.queue.overflow {
+                PgOverflowPolicy::DropOldest => {
+                    state.items.pop_front();
+                    state.dropped += 1;
+                }
+                PgOverflowPolicy::DropNewest => {
+                    state.dropped += 1;
+                    return true;
+                }
+                PgOverflowPolicy::Close => {
+                    state.dropped += 1;
+                    state.closed = true;
+                    drop(state);
+
+                    self.queue.waker.wake();
+                    return false;
+                }
+            }
+        }
+
+        state.items.push_back(item);
+        drop(state);
+
+        self.queue.waker.wake();
+        true
+    }
+
+    fn is_closed(&self) -> bool {
+        self.queue.lock().closed
+    }
+
+    fn close(&self) {
+        self.queue.close();
+    }
+}
+
+async fn route(mut listener: PgListener, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let mut subscribers: HashMap<String, Vec<Box<dyn Deliver>>> = HashMap::default();
+    let mut backoff = MIN_BACKOFF;
+
+    loop {
+        // `PgListener::recv()` is cancel-safe, so no notification is lost if a command wins
+        let event = match future::select(Box::pin(listener.recv()), commands.next()).await {
+            Either::Left((notification, _)) => Either::Left(notification),
+            Either::Right((command, _)) => Either::Right(command),
+        };
+
+        match event {
+            Either::Left(Ok(notification)) => {
+                backoff = MIN_BACKOFF;
+
+                if let Some(channel_subscribers) = subscribers.get_mut(notification.channel()) {
+                    channel_subscribers
+                        .retain(|subscriber| subscriber.deliver(notification.payload()));
+                }
+
+                prune(&mut listener, &mut subscribers).await;
+            }
+
+            Either::Left(Err(Error::PoolClosed)) => break,
+
+            Either::Left(Err(error)) => {
+                tracing::warn!(
+                    %error,
+                    ?backoff,
+                    "PgChannelRouter: listener failed, reconnecting"
+                );
+
+                listener.disconnect();
+                sqlx_core::rt::sleep(backoff).await;
+                backoff = cmp::min(backoff.saturating_mul(2), MAX_BACKOFF);
+            }
+
+            Either::Right(Some(Command::Subscribe {
+                channel,
+                subscriber,
+                subscribed,
+            })) => {
+                let result = if subscribers.contains_key(&channel) {
+                    Ok(())
+                } else {
+                    listener.listen(&channel).await
+                };
+
+                if result.is_ok() {
+                    subscribers.entry(channel).or_default().push(subscriber);
+                }
+
+                let _ = subscribed.send(result);
+            }
+
+            // the router was dropped
+            Either::Right(None) => break,
+        }
+    }
+
+    for subscriber in subscribers.into_values().flatten() {
+        subscriber.close();
+    }
+}
+
+/// Forget ended subscriptions and `UNLISTEN` the channels left without any.
+async fn prune(
+    listener: &mut PgListener,
+    subscribers: &mut HashMap<String, Vec<Box<dyn Deliver>>>,
+) {
+    let mut unused = Vec::new();
+
+    subscribers.retain(|channel, channel_subscribers| {
+        channel_subscribers.retain(|subscriber| !subscriber.is_closed());
+
+        if channel_subscribers.is_empty() {
+            unused.push(channel.clone());
+        }
+
+        !channel_subscribers.is_empty()
+    });
+
+    for channel in unused {
+        if let Err(error) = listener.unlisten(&channel).await {
+            tracing::warn!(%error, channel, "PgChannelRouter: failed to UNLISTEN");
+        }
+    }
+}
@@ -161,15 +161,32 @@ impl PgConnection {
             return Ok(info.clone());
         }
 
+        // then the cache shared with other connections to the same database
+        if let Some(info) = self.shared_type_cache.type_info(oid) {
+            self.cache_type_info.insert(oid, info.clone());
+            self.cache_type_oid
+                .insert(info.0.name().to_string().into(), oid);
+
+            return Ok(info);
+        }
+
         // fallback to asking the database directly for a type name
         if should_fetch {
-            let info = self.fetch_type_by_oid(oid).await?;
+            let info = match self.fetch_type_by_oid(oid).await {
+                Ok(info) => info,
+                Err(error) => {
+                    // the types changed since the cached ones were resolved
+                    self.clear_type_caches();
+                    return Err(error);
+                }
+            };
 
             // cache the type name <-> oid relationship in a paired hashmap
             // so we don't come down this road again
             self.cache_type_info.insert(oid, info.clone());
             self.cache_type_oid
                 .insert(info.0.name().to_string().into(), oid);
+            self.shared_type_cache.insert_type_info(oid, info.clone());
 
             Ok(info)
         } else {
@@ -338,11 +355,23 @@ WHERE rngtypid = $1
         })
     }
 
+    // forget the resolved types, e.g. after the server reported that it does not know one of them
+    pub(crate) fn clear_type_caches(&mut self) {
+        self.cache_type_info.clear();
+        self.cache_type_oid.clear();
+        self.shared_type_cache.clear();
+    }
+
     pub(crate) async fn fetch_type_id_by_name(&mut self, name: &str) -> Result<Oid, Error> {
         if let Some(oid) = self.cache_type_oid.get(name) {
             return Ok(*oid);
         }
 
+        if let Some(oid) = self.shared_type_cache.type_oid(name) {
+            self.cache_type_oid.insert(name.to_string().into(), oid);
+            return Ok(oid);
+        }
+
         // language=SQL
         let (oid,): (Oid,) = query_as("SELECT $1::regtype::oid")
             .bind(name)
@@ -353,6 +382,8 @@ WHERE rngtypid = $1
             })?;
 
         self.cache_type_oid.insert(name.to_string().into(), oid);
+        self.shared_type_cache
+            .insert_type_oid(name.to_string().into(), oid);
         Ok(oid)
     }
 
@@ -8,7 +8,7 @@ use crate::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgConnection};
+use crate::{PgClientEncoding, PgConnectOptions, PgConnection};
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
@@ -27,7 +27,7 @@ impl PgConnection {
             ("DateStyle", "ISO, MDY"),
             // Sets the client-side encoding (character set).
             // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
-            ("client_encoding", "UTF8"),
+            ("client_encoding", options.client_encoding.as_str()),
             // Sets the time zone for displaying and interpreting time stamps.
             ("TimeZone", "UTC"),
         ];
@@ -135,6 +135,22 @@ impl PgConnection {
             }
         }
 
+        // poolers and other implementations of the protocol may ignore the startup parameter,
+        // and decoding text would fail later on with a far less helpful error; some do not
+        // report the parameter at all, in which case the requested encoding is assumed
+        if let Some(client_encoding) = stream.parameter_statuses.get("client_encoding") {
+            if client_encoding.parse::<PgClientEncoding>().ok() != Some(options.client_encoding) {
+                return Err(Error::Configuration(
+                    format!(
+                        "server is using client_encoding {:?} instead of the requested {:?}",
+                        client_encoding,
+                        options.client_encoding.as_str(),
+                    )
+                    .into(),
+                ));
+            }
+        }
+
         Ok(PgConnection {
             stream,
             process_id,
@@ -146,7 +162,9 @@ impl PgConnection {
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
+            shared_type_cache: options.type_cache.for_database(options),
             log_settings: options.log_settings.clone(),
+            query_interceptor: options.query_interceptor.clone(),
         })
     }
 }
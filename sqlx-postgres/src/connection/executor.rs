@@ -17,8 +17,9 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use sqlx_core::interceptor;
 use sqlx_core::Either;
-use std::{borrow::Cow, sync::Arc};
+use std::sync::Arc;
 
 async fn prepare(
     conn: &mut PgConnection,
@@ -63,10 +64,17 @@ async fn prepare(
     conn.stream.flush().await?;
 
     // indicates that the SQL query string is now successfully parsed and has semantic validity
-    let _ = conn
-        .stream
-        .recv_expect(MessageFormat::ParseComplete)
-        .await?;
+    let parsed: Result<(), Error> = conn.stream.recv_expect(MessageFormat::ParseComplete).await;
+
+    if let Err(error) = parsed {
+        // a parameter type resolved by name may have been dropped since, or its database
+        // recreated, so that its cached OID is stale
+        if is_unknown_type_oid(&error) {
+            conn.clear_type_caches();
+        }
+
+        return Err(error);
+    }
 
     let metadata = if let Some(metadata) = metadata {
         // each SYNC produces one READY FOR QUERY
@@ -100,6 +108,21 @@ async fn prepare(
     Ok((id, metadata))
 }
 
+// the error of the server for a type OID it does not know
+fn is_unknown_type_oid(error: &Error) -> bool {
+    let Some(error) = error.as_database_error() else {
+        return false;
+    };
+
+    let message = error.message();
+
+    match error.code().as_deref() {
+        Some("XX000") => message.starts_with("cache lookup failed for type"),
+        Some("42704") => message.starts_with("type with OID"),
+        _ => false,
+    }
+}
+
 async fn recv_desc_params(conn: &mut PgConnection) -> Result<ParameterDescription, Error> {
     conn.stream
         .recv_expect(MessageFormat::ParameterDescription)
@@ -153,6 +176,138 @@ impl PgConnection {
         Ok(())
     }
 
+    // like `get_or_prepare()` for each statement, but with a single SYNC
+    pub(super) async fn prepare_pipelined(&mut self, statements: &[&str]) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+
+        let mut pending: Vec<&str> = Vec::new();
+
+        for &sql in statements {
+            // preparing more than fit into the cache would only evict them again
+            if pending.len() == self.cache_statement.capacity() {
+                break;
+            }
+
+            if !self.cache_statement.contains_key(sql) && !pending.contains(&sql) {
+                pending.push(sql);
+            }
+        }
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut ids = Vec::with_capacity(pending.len());
+
+        for sql in &pending {
+            let id = self.next_statement_id;
+            self.next_statement_id.incr_one();
+
+            self.stream.write(Parse {
+                param_types: &[],
+                query: sql,
+                statement: id,
+            });
+            self.stream.write(message::Describe::Statement(id));
+
+            ids.push(id);
+        }
+
+        self.write_sync();
+        self.stream.flush().await?;
+
+        // if any statement fails to parse, the server skips the rest until the SYNC;
+        // named statements outlive the failed SYNC, so the ones parsed before it are closed
+        let mut descriptions = Vec::with_capacity(pending.len());
+        let mut parsed = 0;
+
+        let received: Result<(), Error> = async {
+            for _ in &pending {
+                self.stream
+                    .recv_expect::<()>(MessageFormat::ParseComplete)
+                    .await?;
+
+                parsed += 1;
+
+                let parameters = recv_desc_params(self).await?;
+                let rows = recv_desc_rows(self).await?;
+
+                descriptions.push((parameters, rows));
+            }
+
+            self.recv_ready_for_query().await
+        }
+        .await;
+
+        if let Err(error) = received {
+            // the original error is more useful than one from the cleanup
+            let _ = self.close_statements(&ids[..parsed]).await;
+
+            return Err(error);
+        }
+
+        for (i, (parameters, rows)) in descriptions.into_iter().enumerate() {
+            let (sql, id) = (pending[i], ids[i]);
+
+            let described: Result<_, Error> = async {
+                let parameters = self.handle_parameter_description(parameters).await?;
+                let (columns, column_names) = self.handle_row_description(rows, true).await?;
+
+                self.wait_until_ready().await?;
+
+                Ok((parameters, columns, column_names))
+            }
+            .await;
+
+            let (parameters, columns, column_names) = match described {
+                Ok(described) => described,
+                Err(error) => {
+                    // this and the following statements were never cached
+                    let _ = self.close_statements(&ids[i..]).await;
+
+                    return Err(error);
+                }
+            };
+
+            let metadata = Arc::new(PgStatementMetadata {
+                parameters,
+                columns,
+                column_names: Arc::new(column_names),
+            });
+
+            // evicts the least recently used statements if the cache was not empty
+            if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata)) {
+                self.stream.write(Close::Statement(id));
+                self.write_sync();
+
+                self.stream.flush().await?;
+
+                self.wait_for_close_complete(1).await?;
+                self.recv_ready_for_query().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn close_statements(&mut self, ids: &[Oid]) -> Result<(), Error> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.wait_until_ready().await?;
+
+        for &id in ids {
+            self.stream.write(Close::Statement(id));
+        }
+
+        self.write_sync();
+        self.stream.flush().await?;
+
+        self.wait_for_close_complete(ids.len()).await?;
+        self.recv_ready_for_query().await
+    }
+
     pub(crate) fn write_sync(&mut self) {
         self.stream.write(message::Sync);
 
@@ -171,12 +326,24 @@ impl PgConnection {
         metadata: Option<Arc<PgStatementMetadata>>,
     ) -> Result<(Oid, Arc<PgStatementMetadata>), Error> {
         if let Some(statement) = self.cache_statement.get_mut(sql) {
+            #[cfg(feature = "metrics")]
+            sqlx_core::metrics::statement_cache_lookup(
+                <Postgres as sqlx_core::database::Database>::NAME,
+                true,
+            );
+
             return Ok((*statement).clone());
         }
 
         let statement = prepare(self, sql, parameters, metadata).await?;
 
         if store_to_cache && self.cache_statement.is_enabled() {
+            #[cfg(feature = "metrics")]
+            sqlx_core::metrics::statement_cache_lookup(
+                <Postgres as sqlx_core::database::Database>::NAME,
+                false,
+            );
+
             if let Some((id, _)) = self.cache_statement.insert(sql, statement.clone()) {
                 self.stream.write(Close::Statement(id));
                 self.write_sync();
@@ -201,6 +368,10 @@ impl PgConnection {
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
+        if let Some(arguments) = &arguments {
+            logger.arguments(|| arguments.for_logging());
+        }
+
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
@@ -368,13 +539,18 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        // statements were intercepted when they were prepared
+        let interceptor = match query.statement() {
+            Some(_) => None,
+            None => self.query_interceptor.as_ref(),
+        };
         let arguments = query.take_arguments();
+        let sql = interceptor::apply(interceptor, query.sql(), arguments.as_ref());
         let persistent = query.persistent();
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+        let stream = Box::pin(try_stream! {
+            let s = self.run(&sql, arguments, 0, persistent, metadata).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -382,7 +558,15 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             }
 
             Ok(())
-        })
+        });
+
+        #[cfg(feature = "metrics")]
+        let stream = sqlx_core::metrics::instrument_stream(
+            <Postgres as sqlx_core::database::Database>::NAME,
+            stream,
+        );
+
+        stream
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -393,13 +577,18 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        // statements were intercepted when they were prepared
+        let interceptor = match query.statement() {
+            Some(_) => None,
+            None => self.query_interceptor.as_ref(),
+        };
         let arguments = query.take_arguments();
+        let sql = interceptor::apply(interceptor, query.sql(), arguments.as_ref());
         let persistent = query.persistent();
 
-        Box::pin(async move {
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+        let future = Box::pin(async move {
+            let s = self.run(&sql, arguments, 1, persistent, metadata).await?;
             pin_mut!(s);
 
             while let Some(s) = s.try_next().await? {
@@ -409,7 +598,15 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             }
 
             Ok(None)
-        })
+        });
+
+        #[cfg(feature = "metrics")]
+        let future = sqlx_core::metrics::instrument_future(
+            <Postgres as sqlx_core::database::Database>::NAME,
+            future,
+        );
+
+        future
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -423,12 +620,10 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            let sql = interceptor::rewrite(self.query_interceptor.as_ref(), sql);
+            let (_, metadata) = self.get_or_prepare(&sql, parameters, true, None).await?;
 
-            Ok(PgStatement {
-                sql: Cow::Borrowed(sql),
-                metadata,
-            })
+            Ok(PgStatement { sql, metadata })
         })
     }
 
@@ -6,7 +6,9 @@ use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 
 use crate::common::StatementCache;
+use crate::connection::type_cache::TypeCache;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::Decode;
 use crate::message::{
@@ -16,6 +18,7 @@ use crate::statement::PgStatementMetadata;
 use crate::transaction::Transaction;
 use crate::types::Oid;
 use crate::{PgConnectOptions, PgTypeInfo, Postgres};
+use sqlx_core::interceptor::QueryInterceptor;
 
 pub(crate) use sqlx_core::connection::*;
 
@@ -27,6 +30,7 @@ mod executor;
 mod sasl;
 mod stream;
 mod tls;
+pub(crate) mod type_cache;
 
 /// A connection to a PostgreSQL database.
 pub struct PgConnection {
@@ -56,6 +60,10 @@ pub struct PgConnection {
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
 
+    // user-defined types resolved by any connection to the same database,
+    // shared through the connect options
+    shared_type_cache: Arc<TypeCache>,
+
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
@@ -64,6 +72,8 @@ pub struct PgConnection {
     pub(crate) transaction_depth: usize,
 
     log_settings: LogSettings,
+
+    pub(crate) query_interceptor: Option<QueryInterceptor<Postgres>>,
 }
 
 impl PgConnection {
@@ -72,6 +82,12 @@ impl PgConnection {
         self.stream.server_version_num
     }
 
+    /// Replace the query interceptor set by
+    /// [`PgConnectOptions::query_interceptor()`], or remove it with `None`.
+    pub fn set_query_interceptor(&mut self, interceptor: Option<QueryInterceptor<Postgres>>) {
+        self.query_interceptor = interceptor;
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.write_buffer_mut().is_empty() {
@@ -174,9 +190,62 @@ impl Connection for PgConnection {
         self.cache_statement.len()
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(self.prepare_pipelined(statements))
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let mut sql = format!("TRUNCATE TABLE {table}");
+
+            if restart_identity {
+                sql.push_str(" RESTART IDENTITY");
+            }
+
+            self.execute(&*sql).await?;
+
+            Ok(())
+        })
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // also catches transactions begun with a raw `BEGIN`, which are not counted
+            if self.transaction_depth > 0
+                || !matches!(self.transaction_status, TransactionStatus::Idle)
+            {
+                self.execute("ROLLBACK").await?;
+            }
+
+            self.transaction_depth = 0;
+
+            if discard_session {
+                // `DISCARD ALL` without `DEALLOCATE ALL`, which would invalidate the statement cache
+                self.execute(
+                    "CLOSE ALL; SET SESSION AUTHORIZATION DEFAULT; RESET ALL; UNLISTEN *; \
+                     SELECT pg_advisory_unlock_all(); DISCARD PLANS; DISCARD TEMP; \
+                     DISCARD SEQUENCES",
+                )
+                .await?;
+            }
+
+            Ok(())
+        })
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.cache_type_oid.clear();
+            self.shared_type_cache.clear();
 
             let mut cleared = 0_usize;
 
@@ -5,14 +5,14 @@ use std::str::FromStr;
 use futures_channel::mpsc::UnboundedSender;
 use futures_util::SinkExt;
 use log::Level;
-use sqlx_core::bytes::{Buf, Bytes};
+use sqlx_core::bytes::Buf;
 
 use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
 use crate::io::{Decode, Encode};
 use crate::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
 use crate::net::{self, BufferedSocket, Socket};
-use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
+use crate::{PgClientEncoding, PgConnectOptions, PgDatabaseError, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
 // [PgConnection] is a **valid** connection to postgres
@@ -85,12 +85,19 @@ impl PgStream {
     pub(crate) async fn recv_unchecked(&mut self) -> Result<Message, Error> {
         // all packets in postgres start with a 5-byte header
         // this header contains the message type and the total length of the message
-        let mut header: Bytes = self.inner.read(5).await?;
+        // the header is only consumed along with the contents, so that a cancelled call does
+        // not leave the stream in the middle of a message
+        let mut header = self.inner.peek(5).await?;
 
         let format = MessageFormat::try_from_u8(header.get_u8())?;
         let size = (header.get_u32() - 4) as usize;
 
-        let contents = self.inner.read(size).await?;
+        let contents = self
+            .inner
+            .read_buffered(5 + size)
+            .await?
+            .split_off(5)
+            .freeze();
 
         Ok(Message { format, contents })
     }
@@ -121,12 +128,27 @@ impl PgStream {
                     // setting of backend parameters
 
                     let ParameterStatus { name, value } = message.decode()?;
-                    // TODO: handle `client_encoding`, `DateStyle` change
+                    // TODO: handle `DateStyle` change
 
                     match name.as_str() {
                         "server_version" => {
                             self.server_version_num = parse_server_version(&value);
                         }
+                        "client_encoding" => {
+                            // the encoding requested at startup is verified in `establish()`,
+                            // but it may still be changed with `SET client_encoding`
+                            if self.parameter_statuses.contains_key(&name)
+                                && value.parse::<PgClientEncoding>().is_err()
+                            {
+                                tracing::warn!(
+                                    client_encoding = %value,
+                                    "client_encoding changed to an encoding SQLx does not \
+                                     support; text may fail to decode"
+                                );
+                            }
+
+                            self.parameter_statuses.insert(name, value);
+                        }
                         _ => {
                             self.parameter_statuses.insert(name, value);
                         }
@@ -0,0 +1,160 @@
+use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::ext::ustr::UStr;
+use crate::types::Oid;
+use crate::{HashMap, PgConnectOptions, PgTypeInfo};
+
+/// Cache of resolved user-defined types shared by all connections opened with the same
+/// [`PgConnectOptions`] (and its clones, which includes every connection of a pool).
+///
+/// Entries are keyed by the identity of the database they were resolved against, as OIDs of
+/// user-defined types are only meaningful within a single database. Only names qualified with
+/// their schema are shared, as unqualified names resolve against the `search_path` of each
+/// connection, which may change at any time.
+///
+/// A type may be dropped and created again, or its database recreated, while its OID is cached.
+/// Connections clear the cache when the server does not know an OID they sent it.
+#[derive(Clone, Default)]
+pub(crate) struct SharedTypeCache {
+    databases: Arc<Mutex<HashMap<DatabaseKey, Arc<TypeCache>>>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DatabaseKey {
+    host: String,
+    port: u16,
+    socket: Option<PathBuf>,
+    username: String,
+    database: Option<String>,
+    options: Option<String>,
+}
+
+/// The user-defined types of a single database.
+#[derive(Default)]
+pub(crate) struct TypeCache {
+    inner: Mutex<TypeCacheInner>,
+}
+
+#[derive(Default)]
+struct TypeCacheInner {
+    type_info: HashMap<Oid, PgTypeInfo>,
+    // only schema-qualified names
+    type_oid: HashMap<UStr, Oid>,
+}
+
+impl SharedTypeCache {
+    /// Get the cache for the database `options` connect to, creating it if necessary.
+    pub(crate) fn for_database(&self, options: &PgConnectOptions) -> Arc<TypeCache> {
+        let key = DatabaseKey {
+            host: options.host.clone(),
+            port: options.port,
+            socket: options.socket.clone(),
+            username: options.username.clone(),
+            database: options.database.clone(),
+            options: options.options.clone(),
+        };
+
+        let mut databases = self
+            .databases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        Arc::clone(databases.entry(key).or_default())
+    }
+}
+
+impl Debug for SharedTypeCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedTypeCache").finish_non_exhaustive()
+    }
+}
+
+impl TypeCache {
+    pub(crate) fn type_info(&self, oid: Oid) -> Option<PgTypeInfo> {
+        self.lock().type_info.get(&oid).cloned()
+    }
+
+    pub(crate) fn type_oid(&self, name: &str) -> Option<Oid> {
+        if !is_qualified(name) {
+            return None;
+        }
+
+        self.lock().type_oid.get(name).copied()
+    }
+
+    pub(crate) fn insert_type_info(&self, oid: Oid, info: PgTypeInfo) {
+        self.lock().type_info.insert(oid, info);
+    }
+
+    pub(crate) fn insert_type_oid(&self, name: UStr, oid: Oid) {
+        if is_qualified(&name) {
+            self.lock().type_oid.insert(name, oid);
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut inner = self.lock();
+        inner.type_info.clear();
+        inner.type_oid.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TypeCacheInner> {
+        // the maps are always left in a consistent state, so a poisoned lock can be ignored
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// e.g. `public.my_enum`, which does not depend on the `search_path`
+fn is_qualified(name: &str) -> bool {
+    name.contains('.')
+}
+
+#[test]
+fn test_shared_type_cache_is_keyed_by_database() {
+    let options = PgConnectOptions::new_without_pgpass()
+        .host("localhost")
+        .database("a");
+
+    let cache = options.type_cache.for_database(&options);
+    cache.insert_type_oid("public.my_enum".into(), Oid(10_000));
+
+    // clones (e.g. held by a pool) share the cache
+    let cloned = options.clone();
+    assert_eq!(
+        cloned
+            .type_cache
+            .for_database(&cloned)
+            .type_oid("public.my_enum"),
+        Some(Oid(10_000))
+    );
+
+    // but a different database on the same server does not
+    let other = options.clone().database("b");
+    assert_eq!(
+        other
+            .type_cache
+            .for_database(&other)
+            .type_oid("public.my_enum"),
+        None
+    );
+
+    cache.clear();
+    assert_eq!(cache.type_oid("public.my_enum"), None);
+}
+
+#[test]
+fn test_shared_type_cache_ignores_unqualified_names() {
+    let cache = TypeCache::default();
+
+    // resolves differently depending on the `search_path`
+    cache.insert_type_oid("my_enum".into(), Oid(10_000));
+    assert_eq!(cache.type_oid("my_enum"), None);
+
+    cache.insert_type_oid("app.my_enum".into(), Oid(10_001));
+    assert_eq!(cache.type_oid("app.my_enum"), Some(Oid(10_001)));
+    assert_eq!(cache.type_oid("other.my_enum"), None);
+}
@@ -1,6 +1,7 @@
 use futures_core::future::BoxFuture;
 use std::borrow::Cow;
 use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
 
 use futures_core::stream::BoxStream;
 use sqlx_core::bytes::{BufMut, Bytes};
@@ -138,6 +139,26 @@ impl PgPoolCopyExt for Pool<Postgres> {
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponse,
+    started_at: Instant,
+    bytes_written: u64,
+    rows_written: u64,
+    on_progress: Option<ProgressCallback>,
+    max_bytes_per_second: Option<u64>,
+}
+
+type ProgressCallback = Box<dyn FnMut(&PgCopyProgress) + Send + 'static>;
+
+/// Progress of a `COPY FROM STDIN` operation, passed to the callback set with
+/// [`PgCopyIn::on_progress()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgCopyProgress {
+    /// The number of bytes of `COPY` data sent so far.
+    pub bytes_written: u64,
+    /// The number of rows sent so far; see [`PgCopyIn::rows_written()`] for caveats.
+    pub rows_written: u64,
+    /// The time since the `COPY` was started.
+    pub elapsed: Duration,
 }
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
@@ -156,6 +177,11 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(PgCopyIn {
             conn: Some(conn),
             response,
+            started_at: Instant::now(),
+            bytes_written: 0,
+            rows_written: 0,
+            on_progress: None,
+            max_bytes_per_second: None,
         })
     }
 
@@ -182,10 +208,53 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         self.response.format_codes[column] == 0
     }
 
+    /// Returns the number of bytes of `COPY` data sent so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns the number of rows sent so far.
+    ///
+    /// This is determined by counting line breaks in the data, so it is only available for text
+    /// and CSV formats (it is always `0` for binary), and CSV values containing quoted line breaks
+    /// or a header line are counted as additional rows.
+    /// The exact number of rows copied is returned by [`.finish()`][Self::finish].
+    pub fn rows_written(&self) -> u64 {
+        self.rows_written
+    }
+
+    /// Set a callback to invoke with the progress of the operation after every chunk of data
+    /// is sent.
+    pub fn on_progress<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnMut(&PgCopyProgress) + Send + 'static,
+    {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Limit the rate at which data is sent to the server, averaged over the whole operation.
+    ///
+    /// Before each chunk of data is sent, [`.send()`][Self::send] and
+    /// [`.read_from()`][Self::read_from] sleep for as long as the data already sent puts the
+    /// operation ahead of the given rate. This can be used to avoid saturating a server during
+    /// huge loads.
+    ///
+    /// `None` or `0` disables the limit, which is the default.
+    pub fn max_bytes_per_second(&mut self, limit: impl Into<Option<u64>>) -> &mut Self {
+        self.max_bytes_per_second = limit.into().filter(|&limit| limit > 0);
+        self
+    }
+
     /// Send a chunk of `COPY` data.
     ///
     /// If you're copying data from an `AsyncRead`, maybe consider [Self::read_from] instead.
     pub async fn send(&mut self, data: impl Deref<Target = [u8]>) -> Result<&mut Self> {
+        let len = data.len();
+        let rows = self.count_rows(&data);
+
+        self.throttle().await;
+
         self.conn
             .as_deref_mut()
             .expect("send_data: conn taken")
@@ -193,6 +262,8 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .send(CopyData(data))
             .await?;
 
+        self.record_progress(len, rows);
+
         Ok(self)
     }
 
@@ -220,8 +291,10 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             }
         }
 
-        let conn: &mut PgConnection = self.conn.as_deref_mut().expect("copy_from: conn taken");
+        let textual = self.is_textual();
+
         loop {
+            let conn: &mut PgConnection = self.conn.as_deref_mut().expect("copy_from: conn taken");
             let buf = conn.stream.write_buffer_mut();
 
             // CopyData format code and reserved space for length
@@ -248,7 +321,23 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
 
             (&mut buf.get_mut()[1..]).put_u32(read32 + 4);
 
-            conn.stream.flush().await?;
+            let data = buf.get();
+            let rows = if textual {
+                count_line_breaks(&data[data.len() - read..])
+            } else {
+                0
+            };
+
+            self.throttle().await;
+
+            self.conn
+                .as_deref_mut()
+                .expect("copy_from: conn taken")
+                .stream
+                .flush()
+                .await?;
+
+            self.record_progress(read, rows);
         }
 
         Ok(self)
@@ -318,6 +407,53 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     }
 }
 
+impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
+    fn count_rows(&self, data: &[u8]) -> u64 {
+        if self.is_textual() {
+            count_line_breaks(data)
+        } else {
+            0
+        }
+    }
+
+    fn record_progress(&mut self, bytes: usize, rows: u64) {
+        self.bytes_written += bytes as u64;
+        self.rows_written += rows;
+
+        if let Some(on_progress) = &mut self.on_progress {
+            on_progress(&PgCopyProgress {
+                bytes_written: self.bytes_written,
+                rows_written: self.rows_written,
+                elapsed: self.started_at.elapsed(),
+            });
+        }
+    }
+
+    async fn throttle(&self) {
+        let Some(limit) = self.max_bytes_per_second else {
+            return;
+        };
+
+        if let Some(delay) = throttle_delay(self.bytes_written, limit, self.started_at.elapsed()) {
+            sqlx_core::rt::sleep(delay).await;
+        }
+    }
+}
+
+// How long to wait before sending more data, if sending `bytes_written` bytes in `elapsed` is
+// ahead of `limit` bytes per second.
+fn throttle_delay(bytes_written: u64, limit: u64, elapsed: Duration) -> Option<Duration> {
+    let scheduled = Duration::from_secs_f64(bytes_written as f64 / limit as f64);
+
+    scheduled
+        .checked_sub(elapsed)
+        .filter(|ahead| !ahead.is_zero())
+}
+
+fn count_line_breaks(data: &[u8]) -> u64 {
+    data.iter().filter(|&&b| b == b'\n').count() as u64
+}
+
 impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     fn drop(&mut self) {
         if let Some(mut conn) = self.conn.take() {
@@ -358,3 +494,46 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
 
     Ok(Box::pin(stream))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::throttle_delay;
+    use std::time::Duration;
+
+    #[test]
+    fn test_throttle_delay() {
+        assert_eq!(throttle_delay(0, 1000, Duration::ZERO), None);
+        assert_eq!(
+            throttle_delay(500, 1000, Duration::from_millis(200)),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(throttle_delay(500, 1000, Duration::from_millis(500)), None);
+        assert_eq!(throttle_delay(500, 1000, Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn test_throttle_rate() {
+        // a fake clock where sending a chunk takes 1ms and only sleeping advances it otherwise
+        let limit = 10_000;
+        let chunk = 4096;
+        let mut now = Duration::ZERO;
+        let mut bytes_written = 0;
+
+        for _ in 0..100 {
+            if let Some(delay) = throttle_delay(bytes_written, limit, now) {
+                now += delay;
+            }
+
+            now += Duration::from_millis(1);
+            bytes_written += chunk;
+
+            // never more than one chunk ahead of the limit
+            let rate_limited = (bytes_written - chunk) as f64 / now.as_secs_f64();
+            assert!(rate_limited <= limit as f64, "{rate_limited} > {limit}");
+        }
+
+        // the last chunk was sent as soon as the ones before it were on schedule
+        let scheduled = Duration::from_secs_f64((bytes_written - chunk) as f64 / limit as f64);
+        assert_eq!(now, scheduled + Duration::from_millis(1));
+    }
+}
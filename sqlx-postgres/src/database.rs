@@ -6,7 +6,8 @@ use crate::{
 };
 
 pub(crate) use sqlx_core::database::{
-    Database, HasArguments, HasStatement, HasStatementCache, HasValueRef,
+    Database, DatabaseCapabilities, HasArguments, HasStatement, HasStatementCache, HasValueRef,
+    PlaceholderStyle,
 };
 
 /// PostgreSQL database driver.
@@ -31,6 +32,15 @@ impl Database for Postgres {
     const NAME: &'static str = "PostgreSQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["postgres", "postgresql"];
+
+    fn capabilities() -> DatabaseCapabilities {
+        // statements with arguments use the extended query protocol, which only supports a
+        // single statement
+        DatabaseCapabilities::new()
+            .returning(true)
+            .multiple_statements(true)
+            .placeholder_style(PlaceholderStyle::DollarNumbered)
+    }
 }
 
 impl<'r> HasValueRef<'r> for Postgres {
@@ -208,6 +208,26 @@ impl DatabaseError for PgDatabaseError {
         self.table()
     }
 
+    fn constraint_violation(&self) -> Option<ConstraintViolation> {
+        if !matches!(
+            self.kind(),
+            ErrorKind::UniqueViolation | ErrorKind::ForeignKeyViolation
+        ) {
+            return None;
+        }
+
+        let mut violation = ConstraintViolation::default();
+        violation.constraint = self.constraint().map(str::to_owned);
+        violation.table = self.table().map(str::to_owned);
+
+        // the detail is omitted if the user may not see the row, e.g. because of row security
+        if let Some(detail) = self.detail() {
+            parse_key_detail(detail, self.message(), &mut violation);
+        }
+
+        Some(violation)
+    }
+
     fn kind(&self) -> ErrorKind {
         match self.code() {
             error_codes::UNIQUE_VIOLATION => ErrorKind::UniqueViolation,
@@ -219,6 +239,50 @@ impl DatabaseError for PgDatabaseError {
     }
 }
 
+// Parses the detail of unique and foreign key violations, which is one of:
+//
+// Key (a, b)=(1, 2) already exists.
+// Key (parent_id)=(1) is not present in table "parent".
+// Key (id)=(1) is still referenced from table "child".
+fn parse_key_detail(detail: &str, message: &str, violation: &mut ConstraintViolation) {
+    let Some((columns, rest)) = detail
+        .strip_prefix("Key (")
+        .and_then(|rest| rest.split_once(")=("))
+    else {
+        return;
+    };
+
+    let columns = columns.split(", ").map(unquote_identifier).collect();
+
+    if let Some((value, _)) = rest.rsplit_once(") already exists.") {
+        violation.columns = columns;
+        violation.value = Some(value.to_owned());
+    } else if let Some((value, table)) = rest.rsplit_once(") is not present in table ") {
+        violation.columns = columns;
+        violation.value = Some(value.to_owned());
+        violation.referenced_table = Some(unquote_identifier(table.trim_end_matches('.')));
+    } else if let Some((value, _)) = rest.rsplit_once(") is still referenced from table ") {
+        // the key is that of the referenced row which is updated or deleted, whose table is
+        // only named in the message: update or delete on table "parent" violates ...
+        violation.referenced_columns = columns;
+        violation.value = Some(value.to_owned());
+        violation.referenced_table = message
+            .strip_prefix("update or delete on table \"")
+            .and_then(|rest| rest.split_once("\" violates "))
+            .map(|(table, _)| table.replace("\"\"", "\""));
+    }
+}
+
+fn unquote_identifier(identifier: &str) -> String {
+    match identifier
+        .strip_prefix('"')
+        .and_then(|identifier| identifier.strip_suffix('"'))
+    {
+        Some(quoted) => quoted.replace("\"\"", "\""),
+        None => identifier.to_owned(),
+    }
+}
+
 /// For reference: <https://www.postgresql.org/docs/current/errcodes-appendix.html>
 pub(crate) mod error_codes {
     /// Caused when a unique or primary key is violated.
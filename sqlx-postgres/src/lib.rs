@@ -7,6 +7,7 @@ use crate::executor::Executor;
 
 mod advisory_lock;
 mod arguments;
+mod channel_router;
 mod column;
 mod connection;
 mod copy;
@@ -16,6 +17,7 @@ mod io;
 mod listener;
 mod message;
 mod options;
+mod query_plan;
 mod query_result;
 mod row;
 mod statement;
@@ -37,14 +39,16 @@ pub(crate) use sqlx_core::driver_prelude::*;
 
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
+pub use channel_router::{PgChannelRouter, PgOverflowPolicy, PgSubscription};
 pub use column::PgColumn;
 pub use connection::PgConnection;
-pub use copy::PgCopyIn;
+pub use copy::{PgCopyIn, PgCopyProgress};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use options::{PgClientEncoding, PgConnectOptions, PgSslMode};
+pub use query_plan::{PgQueryPlan, PgQueryPlanNode};
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
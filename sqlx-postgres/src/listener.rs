@@ -155,18 +155,27 @@ impl PgListener {
     async fn connect_if_needed(&mut self) -> Result<(), Error> {
         if self.connection.is_none() {
             let mut connection = self.pool.acquire().await?;
-            connection.stream.notifications = self.buffer_tx.take();
+            // keep our sender until the connection is established, in case we are cancelled
+            connection.stream.notifications = self.buffer_tx.clone();
 
             connection
                 .execute(&*build_listen_all_query(&self.channels))
                 .await?;
 
+            self.buffer_tx = None;
             self.connection = Some(connection);
         }
 
         Ok(())
     }
 
+    /// Drop the connection, reconnecting on next use.
+    pub(crate) fn disconnect(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            self.buffer_tx = connection.stream.notifications.take();
+        }
+    }
+
     #[inline]
     async fn connection(&mut self) -> Result<&mut PgConnection, Error> {
         // Ensure we have an active connection to work with.
@@ -185,6 +194,11 @@ impl PgListener {
     /// be returned. If you'd prefer the reconnection to be explicit and have a chance to
     /// do something before, please see [`try_recv`](Self::try_recv).
     ///
+    /// # Cancel Safety
+    ///
+    /// This method is cancel-safe: if it is dropped before it completes, no notification is
+    /// lost and the next call picks up where it left off.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -216,6 +230,10 @@ impl PgListener {
     /// If the connection to PostgreSQL is lost, `None` is returned, and the connection is
     /// reconnected on the next call to `try_recv()`.
     ///
+    /// # Cancel Safety
+    ///
+    /// This method is cancel-safe, see [`recv`](Self::recv).
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -266,8 +284,7 @@ impl PgListener {
                     if (err.kind() == io::ErrorKind::ConnectionAborted
                         || err.kind() == io::ErrorKind::UnexpectedEof) =>
                 {
-                    self.buffer_tx = self.connection().await?.stream.notifications.take();
-                    self.connection = None;
+                    self.disconnect();
 
                     // lost connection
                     return Ok(None);
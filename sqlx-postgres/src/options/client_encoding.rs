@@ -0,0 +1,80 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// The `client_encoding` requested when connecting, i.e. the encoding Postgres converts text to
+/// and from.
+///
+/// It is used by the [`client_encoding`](super::PgConnectOptions::client_encoding) method.
+/// After startup, the connection checks that the server accepted it and fails with
+/// [`Error::Configuration`] otherwise.
+///
+/// See [the Postgres manual, 24.3][PG.24.3] for details on character set support.
+///
+/// [PG.24.3]: https://www.postgresql.org/docs/current/multibyte.html
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgClientEncoding {
+    /// Text is converted to UTF-8. This is the default.
+    ///
+    /// Note that databases created with encoding `SQL_ASCII` do not validate or convert text,
+    /// so values stored in them may still fail to decode as `String`.
+    #[default]
+    Utf8,
+
+    /// No encoding conversion is performed; text is passed through as stored in the database.
+    ///
+    /// This is intended for legacy `SQL_ASCII` databases whose text is in an unknown or mixed
+    /// encoding. Decode such values as [`PgRawText`](crate::types::PgRawText) to get their
+    /// bytes; decoding text that is not valid UTF-8 as `String` or `&str` fails.
+    SqlAscii,
+}
+
+impl PgClientEncoding {
+    /// The name of the encoding as reported by Postgres.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PgClientEncoding::Utf8 => "UTF8",
+            PgClientEncoding::SqlAscii => "SQL_ASCII",
+        }
+    }
+}
+
+impl FromStr for PgClientEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        // Postgres ignores case, dashes and underscores in encoding names
+        let name: String = s
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        Ok(match &*name {
+            "utf8" | "unicode" => PgClientEncoding::Utf8,
+            "sqlascii" => PgClientEncoding::SqlAscii,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unsupported value {s:?} for `client_encoding`").into(),
+                ));
+            }
+        })
+    }
+}
+
+#[test]
+fn test_parse_client_encoding() {
+    assert_eq!(
+        "UTF8".parse::<PgClientEncoding>().unwrap(),
+        PgClientEncoding::Utf8
+    );
+    assert_eq!(
+        "utf-8".parse::<PgClientEncoding>().unwrap(),
+        PgClientEncoding::Utf8
+    );
+    assert_eq!(
+        "SQL_ASCII".parse::<PgClientEncoding>().unwrap(),
+        PgClientEncoding::SqlAscii
+    );
+    assert!("LATIN1".parse::<PgClientEncoding>().is_err());
+}
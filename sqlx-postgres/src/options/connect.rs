@@ -29,4 +29,9 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_arguments(mut self, enabled: bool) -> Self {
+        self.log_settings.log_arguments(enabled);
+        self
+    }
 }
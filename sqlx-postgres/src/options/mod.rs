@@ -3,10 +3,14 @@ use std::env::var;
 use std::fmt::{Display, Write};
 use std::path::{Path, PathBuf};
 
+pub use client_encoding::PgClientEncoding;
 pub use ssl_mode::PgSslMode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::type_cache::SharedTypeCache;
+use crate::{connection::LogSettings, net::tls::CertificateInput, Postgres};
+use sqlx_core::interceptor::{self, QueryInterceptor, QueryRewriter};
 
+mod client_encoding;
 mod connect;
 mod parse;
 mod pgpass;
@@ -41,6 +45,7 @@ mod ssl_mode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `client_encoding` | `UTF8` | The encoding text is converted to, `UTF8` or `SQL_ASCII`. See [`PgClientEncoding`]. |
 ///
 /// The URL scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URL parts is optional.
@@ -102,6 +107,9 @@ pub struct PgConnectOptions {
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) client_encoding: PgClientEncoding,
+    pub(crate) type_cache: SharedTypeCache,
+    pub(crate) query_interceptor: Option<QueryInterceptor<Postgres>>,
 }
 
 impl Default for PgConnectOptions {
@@ -168,6 +176,9 @@ impl PgConnectOptions {
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            client_encoding: PgClientEncoding::default(),
+            type_cache: Default::default(),
+            query_interceptor: None,
         }
     }
 
@@ -547,6 +558,48 @@ impl PgConnectOptions {
         self
     }
 
+    /// Rewrite the SQL of every query executed on the connections, e.g. to hide soft-deleted
+    /// rows. See the [`interceptor`](sqlx_core::interceptor) module for details.
+    pub fn query_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.query_interceptor = Some(interceptor::query_interceptor(interceptor));
+        self
+    }
+
+    /// Rewrite the SQL of every query executed on the connections with `rewriter`, which also
+    /// sees the bound arguments, e.g. to route the queries of a tenant to the tables of its
+    /// shard.
+    ///
+    /// Replaces the [`query_interceptor()`](Self::query_interceptor), if any.
+    pub fn query_rewriter<R: QueryRewriter<Postgres>>(mut self, rewriter: R) -> Self {
+        self.query_interceptor = Some(interceptor::query_interceptor(rewriter));
+        self
+    }
+
+    /// Sets the `client_encoding` requested at connection start.
+    ///
+    /// SQLx assumes UTF-8 by default; [`PgClientEncoding::SqlAscii`] disables encoding
+    /// conversion for legacy `SQL_ASCII` databases. The connection fails with
+    /// [`Error::Configuration`] if the server reports a different encoding, e.g. because a
+    /// connection pooler or an alternative implementation of the protocol ignores it. If the
+    /// server does not report its encoding at all, the requested one is assumed.
+    ///
+    /// [`Error::Configuration`]: crate::error::Error::Configuration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgClientEncoding, PgConnectOptions};
+    /// let options = PgConnectOptions::new()
+    ///     .client_encoding(PgClientEncoding::SqlAscii);
+    /// ```
+    pub fn client_encoding(mut self, client_encoding: PgClientEncoding) -> Self {
+        self.client_encoding = client_encoding;
+        self
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
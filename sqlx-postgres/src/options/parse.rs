@@ -85,6 +85,10 @@ impl PgConnectOptions {
 
                 "application_name" => options = options.application_name(&*value),
 
+                "client_encoding" => {
+                    options = options.client_encoding(value.parse()?);
+                }
+
                 "options" => {
                     if let Some(options) = options.options.as_mut() {
                         options.push(' ');
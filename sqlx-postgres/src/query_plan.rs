@@ -0,0 +1,201 @@
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::{PgConnection, PgRow};
+use sqlx_core::executor::Executor;
+use sqlx_core::row::Row;
+
+/// The parsed output of `EXPLAIN (FORMAT JSON)`, as returned by [`PgConnection::explain()`].
+///
+/// See [the Postgres manual](https://www.postgresql.org/docs/current/using-explain.html)
+/// for how to interpret the plan.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PgQueryPlan {
+    #[serde(rename = "Plan")]
+    root: PgQueryPlanNode,
+}
+
+/// A single node of a [`PgQueryPlan`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PgQueryPlanNode {
+    #[serde(rename = "Node Type")]
+    node_type: String,
+    #[serde(rename = "Relation Name")]
+    relation_name: Option<String>,
+    #[serde(rename = "Index Name")]
+    index_name: Option<String>,
+    #[serde(rename = "Startup Cost")]
+    startup_cost: f64,
+    #[serde(rename = "Total Cost")]
+    total_cost: f64,
+    #[serde(rename = "Plan Rows")]
+    plan_rows: f64,
+    #[serde(rename = "Plans", default)]
+    children: Vec<PgQueryPlanNode>,
+    #[serde(flatten)]
+    properties: serde_json::Map<String, serde_json::Value>,
+}
+
+impl PgConnection {
+    /// Run `EXPLAIN (FORMAT JSON)` for `query` and parse the output into a tree.
+    ///
+    /// The query is planned but not executed. It must not contain bind parameters.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let plan = conn.explain("SELECT * FROM users WHERE email = 'foo@example.com'").await?;
+    /// assert!(plan.uses_index("users_email_idx"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain(&mut self, query: &str) -> Result<PgQueryPlan, Error> {
+        let row: PgRow = self
+            .fetch_one(&*format!("EXPLAIN (FORMAT JSON) {query}"))
+            .await?;
+
+        // the column is of type `json`, whose binary format is the same as its text format
+        let json: String = row.try_get_unchecked(0)?;
+
+        PgQueryPlan::from_json(&json)
+    }
+}
+
+impl PgQueryPlan {
+    fn from_json(json: &str) -> Result<Self, Error> {
+        // the output is an array with a single element per statement
+        let plans: Vec<PgQueryPlan> = serde_json::from_str(json)
+            .map_err(|e| err_protocol!("failed to parse EXPLAIN output: {}", e))?;
+
+        plans
+            .into_iter()
+            .next()
+            .ok_or_else(|| err_protocol!("EXPLAIN returned no plan"))
+    }
+
+    /// The top-level node of the plan.
+    pub fn root(&self) -> &PgQueryPlanNode {
+        &self.root
+    }
+
+    /// Iterate over all nodes of the plan, depth-first.
+    pub fn iter(&self) -> impl Iterator<Item = &PgQueryPlanNode> {
+        self.root.iter()
+    }
+
+    /// Returns `true` if any node of the plan uses the index with the given name.
+    pub fn uses_index(&self, index: &str) -> bool {
+        self.iter().any(|node| node.index_name() == Some(index))
+    }
+
+    /// Returns `true` if any node of the plan is a sequential scan of the given table.
+    pub fn scans_table(&self, table: &str) -> bool {
+        self.iter()
+            .any(|node| node.node_type == "Seq Scan" && node.relation_name() == Some(table))
+    }
+}
+
+impl PgQueryPlanNode {
+    /// The kind of this node, e.g. `Seq Scan`, `Index Scan` or `Hash Join`.
+    pub fn node_type(&self) -> &str {
+        &self.node_type
+    }
+
+    /// The table this node reads from, if any.
+    pub fn relation_name(&self) -> Option<&str> {
+        self.relation_name.as_deref()
+    }
+
+    /// The index this node uses, if any.
+    pub fn index_name(&self) -> Option<&str> {
+        self.index_name.as_deref()
+    }
+
+    /// The estimated cost before the first row can be returned.
+    pub fn startup_cost(&self) -> f64 {
+        self.startup_cost
+    }
+
+    /// The estimated cost to return all rows.
+    pub fn total_cost(&self) -> f64 {
+        self.total_cost
+    }
+
+    /// The estimated number of rows returned by this node.
+    pub fn plan_rows(&self) -> f64 {
+        self.plan_rows
+    }
+
+    /// The child nodes of this node.
+    pub fn children(&self) -> &[PgQueryPlanNode] {
+        &self.children
+    }
+
+    /// Any other properties of this node not exposed by a dedicated method,
+    /// e.g. `Filter`, `Index Cond` or `Join Type`.
+    pub fn properties(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.properties
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &PgQueryPlanNode> + '_> {
+        Box::new(std::iter::once(self).chain(self.children.iter().flat_map(Self::iter)))
+    }
+}
+
+#[test]
+fn test_parse_query_plan() {
+    let plan = PgQueryPlan::from_json(
+        r#"[
+          {
+            "Plan": {
+              "Node Type": "Nested Loop",
+              "Parallel Aware": false,
+              "Join Type": "Inner",
+              "Startup Cost": 0.29,
+              "Total Cost": 16.34,
+              "Plan Rows": 1,
+              "Plan Width": 68,
+              "Plans": [
+                {
+                  "Node Type": "Seq Scan",
+                  "Parent Relationship": "Outer",
+                  "Relation Name": "orders",
+                  "Alias": "o",
+                  "Startup Cost": 0.00,
+                  "Total Cost": 8.00,
+                  "Plan Rows": 1,
+                  "Plan Width": 36
+                },
+                {
+                  "Node Type": "Index Scan",
+                  "Parent Relationship": "Inner",
+                  "Scan Direction": "Forward",
+                  "Index Name": "users_pkey",
+                  "Relation Name": "users",
+                  "Alias": "u",
+                  "Startup Cost": 0.29,
+                  "Total Cost": 8.31,
+                  "Plan Rows": 1,
+                  "Plan Width": 36,
+                  "Index Cond": "(id = o.user_id)"
+                }
+              ]
+            }
+          }
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(plan.root().node_type(), "Nested Loop");
+    assert_eq!(plan.root().total_cost(), 16.34);
+    assert_eq!(plan.root().properties()["Join Type"], "Inner");
+    assert_eq!(plan.iter().count(), 3);
+
+    assert!(plan.uses_index("users_pkey"));
+    assert!(!plan.uses_index("orders_pkey"));
+    assert!(plan.scans_table("orders"));
+    assert!(!plan.scans_table("users"));
+
+    let index_scan = &plan.root().children()[1];
+    assert_eq!(index_scan.relation_name(), Some("users"));
+    assert_eq!(index_scan.properties()["Index Cond"], "(id = o.user_id)");
+}
@@ -164,8 +164,8 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<Postgres>, Error> {
     conn.execute(&format!("create database {new_db_name:?}")[..])
         .await?;
 
-    Ok(TestContext {
-        pool_opts: PoolOptions::new()
+    Ok(TestContext::new(
+        PoolOptions::new()
             // Don't allow a single test to take all the connections.
             // Most tests shouldn't require more than 5 connections concurrently,
             // or else they're likely doing too much in one test.
@@ -173,13 +173,13 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<Postgres>, Error> {
             // Close connections ASAP if left in the idle queue.
             .idle_timeout(Some(Duration::from_secs(1)))
             .parent(master_pool.clone()),
-        connect_opts: master_pool
+        master_pool
             .connect_options()
             .deref()
             .clone()
             .database(&new_db_name),
-        db_name: new_db_name,
-    })
+        new_db_name,
+    ))
 }
 
 async fn do_cleanup(conn: &mut PgConnection, created_before: Duration) -> Result<usize, Error> {
@@ -0,0 +1,36 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::types::{Codec, Compressed, CompressedData};
+
+impl<T, C> Type<Postgres> for Compressed<T, C> {
+    fn type_info() -> PgTypeInfo {
+        <Vec<u8> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<u8> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'q, T, C> Encode<'q, Postgres> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <Vec<u8> as Encode<Postgres>>::encode(self.compress(), buf)
+    }
+}
+
+impl<'r, T, C> Decode<'r, Postgres> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Self::decompress(<&[u8] as Decode<Postgres>>::decode(value)?)
+    }
+}
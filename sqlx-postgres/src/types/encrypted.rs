@@ -0,0 +1,30 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::types::Encrypted;
+
+impl<T, K> Type<Postgres> for Encrypted<T, K> {
+    fn type_info() -> PgTypeInfo {
+        <Vec<u8> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<u8> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'q, T, K> Encode<'q, Postgres> for Encrypted<T, K> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&[u8] as Encode<Postgres>>::encode(self.as_bytes(), buf)
+    }
+}
+
+impl<'r, T, K> Decode<'r, Postgres> for Encrypted<T, K> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::from_bytes(
+            <&[u8] as Decode<Postgres>>::decode(value)?.to_vec(),
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use sqlx_core::types::Geometry;
+
+impl Type<Postgres> for Geometry {
+    fn type_info() -> PgTypeInfo {
+        // Since `geometry` is enabled by the PostGIS extension, it does not have a stable OID.
+        PgTypeInfo::with_name("geometry")
+    }
+}
+
+impl PgHasArrayType for Geometry {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_geometry")
+    }
+}
+
+impl Encode<'_, Postgres> for Geometry {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // the binary format is Extended WKB
+        buf.extend(self.to_ewkb());
+
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Geometry {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Geometry::from_wkb(value.as_bytes()?),
+            // the text format is Extended WKB in hex
+            PgValueFormat::Text => Geometry::from_wkb(hex::decode(value.as_str()?)?),
+        }
+    }
+}
@@ -17,6 +17,20 @@ pub struct PgInterval {
     pub microseconds: i64,
 }
 
+impl PgInterval {
+    // the length of the interval, if it has no months
+    fn fixed_microseconds(&self) -> Result<i64, BoxDynError> {
+        if self.months != 0 {
+            return Err("`INTERVAL` with months cannot be decoded as a duration".into());
+        }
+
+        i64::from(self.days)
+            .checked_mul(86_400_000_000)
+            .and_then(|days| days.checked_add(self.microseconds))
+            .ok_or_else(|| "overflow decoding `INTERVAL` as a duration".into())
+    }
+}
+
 impl Type<Postgres> for PgInterval {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INTERVAL
@@ -67,8 +81,9 @@ impl Encode<'_, Postgres> for PgInterval {
     }
 }
 
-// We then implement Encode + Type for std Duration, chrono Duration, and time Duration
-// This is to enable ease-of-use for encoding when its simple
+// We then implement Encode + Decode + Type for std Duration, chrono Duration, and time Duration
+// This is to enable ease-of-use when its simple; decoding fails for intervals with months as
+// their length is not fixed, while a day is taken to be 24 hours
 
 impl Type<Postgres> for std::time::Duration {
     fn type_info() -> PgTypeInfo {
@@ -94,6 +109,18 @@ impl Encode<'_, Postgres> for std::time::Duration {
     }
 }
 
+impl<'de> Decode<'de, Postgres> for std::time::Duration {
+    fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
+        let microseconds = PgInterval::decode(value)?.fixed_microseconds()?;
+
+        Ok(std::time::Duration::from_micros(
+            microseconds
+                .try_into()
+                .map_err(|_| "negative `INTERVAL` cannot be decoded as `std::time::Duration`")?,
+        ))
+    }
+}
+
 impl TryFrom<std::time::Duration> for PgInterval {
     type Error = BoxDynError;
 
@@ -140,6 +167,15 @@ impl Encode<'_, Postgres> for chrono::Duration {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl<'de> Decode<'de, Postgres> for chrono::Duration {
+    fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
+        let microseconds = PgInterval::decode(value)?.fixed_microseconds()?;
+
+        Ok(chrono::Duration::microseconds(microseconds))
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl TryFrom<chrono::Duration> for PgInterval {
     type Error = BoxDynError;
@@ -202,6 +238,15 @@ impl Encode<'_, Postgres> for time::Duration {
     }
 }
 
+#[cfg(feature = "time")]
+impl<'de> Decode<'de, Postgres> for time::Duration {
+    fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
+        let microseconds = PgInterval::decode(value)?.fixed_microseconds()?;
+
+        Ok(time::Duration::microseconds(microseconds))
+    }
+}
+
 #[cfg(feature = "time")]
 impl TryFrom<time::Duration> for PgInterval {
     type Error = BoxDynError;
@@ -303,6 +348,23 @@ fn test_encode_interval() {
     buf.clear();
 }
 
+#[test]
+fn test_pginterval_fixed_microseconds() {
+    let interval = PgInterval {
+        months: 0,
+        days: 1,
+        microseconds: -1,
+    };
+    assert_eq!(interval.fixed_microseconds().unwrap(), 86_399_999_999);
+
+    let interval = PgInterval {
+        months: 1,
+        days: 0,
+        microseconds: 0,
+    };
+    assert!(interval.fixed_microseconds().is_err());
+}
+
 #[test]
 fn test_pginterval_std() {
     // Case for positive duration
@@ -15,16 +15,27 @@
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
+//! | `std::time::Duration`                 | INTERVAL (without months)                            |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
+//! | [`PgRawText`]                         | VARCHAR, CHAR(N), TEXT, NAME, CITEXT<sup>3</sup>     |
+//! | [`PgTimestampExt<T>`](PgTimestampExt) | TIMESTAMP, TIMESTAMPTZ, DATE<sup>2</sup>             |
+//! | [`PgNumericExt<T>`](PgNumericExt)     | NUMERIC<sup>2</sup>                                  |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> Wraps one of the `chrono`, `time`, `bigdecimal` or `rust_decimal` types below
+//! to additionally decode and encode `'infinity'`, `'-infinity'` and (for `NUMERIC`) `'NaN'`,
+//! which those types cannot represent.
+//!
+//! <sup>3</sup> The bytes of the text without UTF-8 validation, for `SQL_ASCII` databases; see
+//! [`PgClientEncoding::SqlAscii`](crate::PgClientEncoding::SqlAscii).
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -50,6 +61,7 @@
 //! | `chrono::NaiveDateTime`               | TIMESTAMP                                            |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
+//! | `chrono::Duration`                    | INTERVAL (without months)                            |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
 //! ### [`time`](https://crates.io/crates/time)
@@ -62,6 +74,7 @@
 //! | `time::OffsetDateTime`                | TIMESTAMPTZ                                          |
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
+//! | `time::Duration`                      | INTERVAL (without months)                            |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
 //! ### [`uuid`](https://crates.io/crates/uuid)
@@ -103,6 +116,14 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
 //!
+//! ### Geometry
+//!
+//! Requires the `geometry` Cargo feature flag and the [PostGIS](https://postgis.net/) extension.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `sqlx::types::Geometry`               | GEOMETRY                                             |
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -189,11 +210,14 @@ mod ltree;
 // Not behind a Cargo feature because we require JSON in the driver implementation.
 mod json;
 mod money;
+mod numeric_ext;
 mod oid;
 mod range;
+mod raw_text;
 mod record;
 mod str;
 mod text;
+mod timestamp_ext;
 mod tuple;
 mod void;
 
@@ -230,6 +254,15 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+#[cfg(feature = "geometry")]
+mod geometry;
+
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod compressed;
+
+#[cfg(feature = "encryption")]
+mod encrypted;
+
 pub use array::PgHasArrayType;
 pub use citext::PgCiText;
 pub use interval::PgInterval;
@@ -241,8 +274,11 @@ pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
+pub use numeric_ext::PgNumericExt;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use raw_text::PgRawText;
+pub use timestamp_ext::PgTimestampExt;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
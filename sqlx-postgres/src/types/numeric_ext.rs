@@ -0,0 +1,154 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+// https://github.com/postgres/postgres/blob/REL_16_0/src/backend/utils/adt/numeric.c#L200-L204
+const SIGN_NAN: u16 = 0xC000;
+const SIGN_PINF: u16 = 0xD000;
+const SIGN_NINF: u16 = 0xF000;
+
+/// A `NUMERIC` that may also be `'NaN'`, `'Infinity'` or `'-Infinity'`.
+///
+/// Neither `bigdecimal::BigDecimal` nor `rust_decimal::Decimal` can represent these special
+/// values, so decoding them directly fails. Wrap the Rust type in this enum to handle them
+/// instead:
+///
+/// ```rust,ignore
+/// use sqlx::postgres::types::PgNumericExt;
+/// use sqlx::types::Decimal;
+///
+/// let ratio: PgNumericExt<Decimal> = sqlx::query_scalar("SELECT 'NaN'::numeric")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// assert_eq!(ratio, PgNumericExt::NaN);
+/// ```
+///
+/// Infinities require Postgres 14 or newer.
+///
+/// Variants are ordered the same way Postgres orders these values, i.e. `NaN` is greater than
+/// all other values.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PgNumericExt<T> {
+    /// `'-Infinity'`, smaller than all other values.
+    NegInfinity,
+
+    /// A regular, finite value.
+    Value(T),
+
+    /// `'Infinity'`, greater than all finite values.
+    Infinity,
+
+    /// `'NaN'`, e.g. the result of `'Infinity' - 'Infinity'`.
+    NaN,
+}
+
+impl<T> PgNumericExt<T> {
+    /// Returns the finite value, if any.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            PgNumericExt::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the finite value, if any.
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            PgNumericExt::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for PgNumericExt<T> {
+    fn from(value: T) -> Self {
+        PgNumericExt::Value(value)
+    }
+}
+
+impl<T> Type<Postgres> for PgNumericExt<T>
+where
+    T: Type<Postgres>,
+{
+    fn type_info() -> PgTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<T> PgHasArrayType for PgNumericExt<T>
+where
+    T: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<'q, T> Encode<'q, Postgres> for PgNumericExt<T>
+where
+    T: Encode<'q, Postgres>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        let sign = match self {
+            PgNumericExt::Value(value) => return value.encode_by_ref(buf),
+            PgNumericExt::NegInfinity => SIGN_NINF,
+            PgNumericExt::Infinity => SIGN_PINF,
+            PgNumericExt::NaN => SIGN_NAN,
+        };
+
+        // number of digits, weight, sign and scale
+        buf.extend(&0_i16.to_be_bytes());
+        buf.extend(&0_i16.to_be_bytes());
+        buf.extend(&sign.to_be_bytes());
+        buf.extend(&0_i16.to_be_bytes());
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            PgNumericExt::Value(value) => value.size_hint(),
+            _ => 8,
+        }
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for PgNumericExt<T>
+where
+    T: Decode<'r, Postgres>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                if let Some(&[hi, lo]) = value.as_bytes()?.get(4..6) {
+                    match u16::from_be_bytes([hi, lo]) {
+                        SIGN_NINF => return Ok(PgNumericExt::NegInfinity),
+                        SIGN_PINF => return Ok(PgNumericExt::Infinity),
+                        SIGN_NAN => return Ok(PgNumericExt::NaN),
+                        _ => {}
+                    }
+                }
+            }
+
+            PgValueFormat::Text => match value.as_str()? {
+                "-Infinity" => return Ok(PgNumericExt::NegInfinity),
+                "Infinity" => return Ok(PgNumericExt::Infinity),
+                "NaN" => return Ok(PgNumericExt::NaN),
+                _ => {}
+            },
+        }
+
+        T::decode(value).map(PgNumericExt::Value)
+    }
+}
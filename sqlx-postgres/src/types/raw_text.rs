@@ -0,0 +1,94 @@
+use crate::types::array_compatible;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use std::ops::Deref;
+
+/// The bytes of a text value, without checking that they are valid UTF-8.
+///
+/// This is meant for legacy databases created with encoding `SQL_ASCII`, where Postgres stores
+/// text as given without validating or converting it, so it may be in any (or several)
+/// encodings. Connect with [`PgClientEncoding::SqlAscii`] so Postgres does not attempt to
+/// convert those bytes either, then decode text columns as `PgRawText` and interpret the bytes
+/// as appropriate:
+///
+/// ```rust,ignore
+/// use sqlx::postgres::{PgClientEncoding, PgConnectOptions};
+/// use sqlx::postgres::types::PgRawText;
+///
+/// let options: PgConnectOptions = "postgres:// ...".parse()?;
+/// let mut conn = options
+///     .client_encoding(PgClientEncoding::SqlAscii)
+///     .connect()
+///     .await?;
+///
+/// let name: PgRawText = sqlx::query_scalar("SELECT name FROM legacy_users")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// println!("{}", String::from_utf8_lossy(&name));
+/// ```
+///
+/// Encoded values are sent as `TEXT`, so databases that do validate their encoding reject
+/// bytes that are invalid in it.
+///
+/// [`PgClientEncoding::SqlAscii`]: crate::PgClientEncoding::SqlAscii
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgRawText(pub Vec<u8>);
+
+impl Type<Postgres> for PgRawText {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TEXT
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Deref for PgRawText {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for PgRawText {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PgRawText> for Vec<u8> {
+    fn from(value: PgRawText) -> Self {
+        value.0
+    }
+}
+
+impl PgHasArrayType for PgRawText {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TEXT_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<&str>(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for PgRawText {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend_from_slice(&self.0);
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgRawText {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        // text is sent as-is in both the text and the binary format
+        Ok(PgRawText(value.as_bytes()?.to_owned()))
+    }
+}
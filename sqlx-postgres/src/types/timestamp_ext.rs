@@ -0,0 +1,151 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// A `TIMESTAMP`, `TIMESTAMPTZ` or `DATE` that may also be `'infinity'` or `'-infinity'`.
+///
+/// Postgres supports these special values for all of its date and time types, but the Rust
+/// types from `chrono` and `time` cannot represent them, so decoding them directly fails.
+/// Wrap the Rust type in this enum to handle them instead:
+///
+/// ```rust,ignore
+/// use sqlx::postgres::types::PgTimestampExt;
+/// use sqlx::types::chrono::NaiveDateTime;
+///
+/// let valid_until: PgTimestampExt<NaiveDateTime> =
+///     sqlx::query_scalar("SELECT valid_until FROM subscriptions")
+///         .fetch_one(&mut conn)
+///         .await?;
+///
+/// if valid_until == PgTimestampExt::Infinity {
+///     // never expires
+/// }
+/// ```
+///
+/// Variants are ordered the same way Postgres orders these values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PgTimestampExt<T> {
+    /// `'-infinity'`, earlier than all other values.
+    NegInfinity,
+
+    /// A regular, finite value.
+    Value(T),
+
+    /// `'infinity'`, later than all other values.
+    Infinity,
+}
+
+impl<T> PgTimestampExt<T> {
+    /// Returns the finite value, if any.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            PgTimestampExt::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the finite value, if any.
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            PgTimestampExt::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for PgTimestampExt<T> {
+    fn from(value: T) -> Self {
+        PgTimestampExt::Value(value)
+    }
+}
+
+impl<T> Type<Postgres> for PgTimestampExt<T>
+where
+    T: Type<Postgres>,
+{
+    fn type_info() -> PgTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<T> PgHasArrayType for PgTimestampExt<T>
+where
+    T: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<'q, T> Encode<'q, Postgres> for PgTimestampExt<T>
+where
+    T: Encode<'q, Postgres> + Type<Postgres>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // `DATE` is sent as the number of days in an `int4`, timestamps as the number of
+        // microseconds in an `int8`; the respective minimum and maximum denote infinity
+        let is_date = T::type_info() == PgTypeInfo::DATE;
+
+        match (self, is_date) {
+            (PgTimestampExt::Value(value), _) => return value.encode_by_ref(buf),
+            (PgTimestampExt::NegInfinity, true) => buf.extend(&i32::MIN.to_be_bytes()),
+            (PgTimestampExt::Infinity, true) => buf.extend(&i32::MAX.to_be_bytes()),
+            (PgTimestampExt::NegInfinity, false) => buf.extend(&i64::MIN.to_be_bytes()),
+            (PgTimestampExt::Infinity, false) => buf.extend(&i64::MAX.to_be_bytes()),
+        }
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            PgTimestampExt::Value(value) => value.size_hint(),
+            _ => 8,
+        }
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for PgTimestampExt<T>
+where
+    T: Decode<'r, Postgres>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = value.as_bytes()?;
+
+                if let Ok(days) = <[u8; 4]>::try_from(buf) {
+                    match i32::from_be_bytes(days) {
+                        i32::MIN => return Ok(PgTimestampExt::NegInfinity),
+                        i32::MAX => return Ok(PgTimestampExt::Infinity),
+                        _ => {}
+                    }
+                } else if let Ok(micros) = <[u8; 8]>::try_from(buf) {
+                    match i64::from_be_bytes(micros) {
+                        i64::MIN => return Ok(PgTimestampExt::NegInfinity),
+                        i64::MAX => return Ok(PgTimestampExt::Infinity),
+                        _ => {}
+                    }
+                }
+            }
+
+            PgValueFormat::Text => match value.as_str()? {
+                "-infinity" => return Ok(PgTimestampExt::NegInfinity),
+                "infinity" => return Ok(PgTimestampExt::Infinity),
+                _ => {}
+            },
+        }
+
+        T::decode(value).map(PgTimestampExt::Value)
+    }
+}
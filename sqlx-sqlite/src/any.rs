@@ -13,7 +13,7 @@ use sqlx_core::any::{
 
 use crate::type_info::DataType;
 use sqlx_core::connection::{ConnectOptions, Connection};
-use sqlx_core::database::Database;
+use sqlx_core::database::{Database, DatabaseCapabilities};
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::transaction::TransactionManager;
@@ -25,6 +25,10 @@ impl AnyConnectionBackend for SqliteConnection {
         <Sqlite as Database>::NAME
     }
 
+    fn capabilities(&self) -> DatabaseCapabilities {
+        <Sqlite as Database>::capabilities()
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -53,6 +57,25 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::start_rollback(self)
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::prepare_cached(self, statements)
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::clear_table(self, table, restart_identity)
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, discard_session)
+    }
+
     fn shrink_buffers(&mut self) {
         // NO-OP.
     }
@@ -82,7 +105,7 @@ impl AnyConnectionBackend for SqliteConnection {
 
         Box::pin(
             self.worker
-                .execute(query, args, self.row_channel_size, persistent)
+                .execute(query, args, self.row_channel_size, persistent, None)
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream()
                 .map(
@@ -105,7 +128,7 @@ impl AnyConnectionBackend for SqliteConnection {
         Box::pin(async move {
             let stream = self
                 .worker
-                .execute(query, args, self.row_channel_size, persistent)
+                .execute(query, args, self.row_channel_size, persistent, None)
                 .map_ok(flume::Receiver::into_stream)
                 .await?;
             futures_util::pin_mut!(stream);
@@ -144,7 +167,7 @@ impl<'a> TryFrom<&'a SqliteTypeInfo> for AnyTypeInfo {
                 DataType::Int => AnyTypeInfoKind::Integer,
                 DataType::Int64 => AnyTypeInfoKind::BigInt,
                 DataType::Float => AnyTypeInfoKind::Double,
-                DataType::Blob => AnyTypeInfoKind::Blob,
+                DataType::Blob | DataType::Uuid => AnyTypeInfoKind::Blob,
                 DataType::Text => AnyTypeInfoKind::Text,
                 _ => {
                     return Err(sqlx_core::Error::AnyDriverError(
@@ -214,6 +237,8 @@ fn map_arguments(args: AnyArguments<'_>) -> SqliteArguments<'_> {
                 _ => unreachable!("BUG: missing mapping for {val:?}"),
             })
             .collect(),
+        sensitive: args.sensitive,
+        type_names: Vec::new(),
     }
 }
 
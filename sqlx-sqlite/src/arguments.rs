@@ -1,10 +1,13 @@
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::statement::StatementHandle;
-use crate::Sqlite;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::{Sqlite, SqliteError, SqliteTypeInfo};
 use atoi::atoi;
 use libsqlite3_sys::SQLITE_OK;
 use std::borrow::Cow;
+use std::fmt::{self, Write};
 
 pub(crate) use sqlx_core::arguments::*;
 
@@ -18,12 +21,46 @@ pub enum SqliteArgumentValue<'q> {
     Int64(i64),
 }
 
+// binds values encoded by a `SqliteTypeRegistry`
+impl Type<Sqlite> for SqliteArgumentValue<'_> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Null)
+    }
+
+    fn compatible(_ty: &SqliteTypeInfo) -> bool {
+        true
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for SqliteArgumentValue<'q> {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        if let SqliteArgumentValue::Null = self {
+            return IsNull::Yes;
+        }
+
+        args.push(self.clone());
+
+        IsNull::No
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct SqliteArguments<'q> {
     pub(crate) values: Vec<SqliteArgumentValue<'q>>,
+
+    // indexes of the values to redact when logging
+    pub(crate) sensitive: Vec<usize>,
+
+    // Rust type of each value, to name it when binding fails
+    pub(crate) type_names: Vec<&'static str>,
 }
 
 impl<'q> SqliteArguments<'q> {
+    /// The bound values, in the order of their placeholders.
+    pub fn values(&self) -> &[SqliteArgumentValue<'q>] {
+        &self.values
+    }
+
     pub(crate) fn add<T>(&mut self, value: T)
     where
         T: Encode<'q, Sqlite>,
@@ -31,6 +68,9 @@ impl<'q> SqliteArguments<'q> {
         if let IsNull::Yes = value.encode(&mut self.values) {
             self.values.push(SqliteArgumentValue::Null);
         }
+
+        self.type_names
+            .resize(self.values.len(), std::any::type_name::<T>());
     }
 
     pub(crate) fn into_static(self) -> SqliteArguments<'static> {
@@ -40,6 +80,8 @@ impl<'q> SqliteArguments<'q> {
                 .into_iter()
                 .map(SqliteArgumentValue::into_static)
                 .collect(),
+            sensitive: self.sensitive,
+            type_names: self.type_names,
         }
     }
 }
@@ -57,6 +99,30 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
     {
         self.add(value)
     }
+
+    fn add_sensitive<T>(&mut self, value: T)
+    where
+        T: Encode<'q, Self::Database>,
+    {
+        self.add(value);
+        self.sensitive.push(self.values.len() - 1);
+    }
+
+    fn format_redacted<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write_redacted(
+            writer,
+            self.values.len(),
+            &self.sensitive,
+            |writer, i| match &self.values[i] {
+                SqliteArgumentValue::Null => writer.write_str("NULL"),
+                SqliteArgumentValue::Text(text) => write_text(writer, text),
+                SqliteArgumentValue::Blob(blob) => write!(writer, "<{} bytes>", blob.len()),
+                SqliteArgumentValue::Double(value) => write!(writer, "{value}"),
+                SqliteArgumentValue::Int(value) => write!(writer, "{value}"),
+                SqliteArgumentValue::Int64(value) => write!(writer, "{value}"),
+            },
+        )
+    }
 }
 
 impl SqliteArguments<'_> {
@@ -98,7 +164,13 @@ impl SqliteArguments<'_> {
                 break;
             }
 
-            self.values[n - 1].bind(handle, param_i)?;
+            self.values[n - 1]
+                .bind(handle, param_i)
+                .map_err(|source| Error::ArgumentBind {
+                    index: n,
+                    type_name: self.type_names.get(n - 1).copied().unwrap_or("<unknown>"),
+                    source: source.into(),
+                })?;
         }
 
         Ok(arg_i - offset)
@@ -119,7 +191,7 @@ impl SqliteArgumentValue<'_> {
         }
     }
 
-    fn bind(&self, handle: &mut StatementHandle, i: usize) -> Result<(), Error> {
+    fn bind(&self, handle: &mut StatementHandle, i: usize) -> Result<(), SqliteError> {
         use SqliteArgumentValue::*;
 
         let status = match self {
@@ -132,7 +204,7 @@ impl SqliteArgumentValue<'_> {
         };
 
         if status != SQLITE_OK {
-            return Err(handle.last_error().into());
+            return Err(handle.last_error());
         }
 
         Ok(())
@@ -9,6 +9,19 @@ pub struct SqliteColumn {
     pub(crate) name: UStr,
     pub(crate) ordinal: usize,
     pub(crate) type_info: SqliteTypeInfo,
+    // verbatim, e.g. `POINT`, for the decoders of a `SqliteTypeRegistry`
+    #[cfg_attr(
+        feature = "offline",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) declared_type: Option<UStr>,
+}
+
+impl SqliteColumn {
+    /// The type the column was declared with, verbatim, if it is a table column.
+    pub fn declared_type(&self) -> Option<&str> {
+        self.declared_type.as_deref()
+    }
 }
 
 impl Column for SqliteColumn {
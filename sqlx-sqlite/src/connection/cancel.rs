@@ -0,0 +1,68 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Weak};
+
+use crate::connection::worker::WorkerSharedState;
+use crate::connection::ConnectionHandleRaw;
+use crate::SqliteConnection;
+
+/// Cancels the statements running on a [`SqliteConnection`], from any thread.
+///
+/// ```rust,ignore
+/// let token = conn.cancellation_token();
+///
+/// // e.g. when the user clicks "Cancel"
+/// std::thread::spawn(move || token.cancel());
+///
+/// let err = sqlx::query("SELECT * FROM huge_table").fetch_all(&mut conn).await.unwrap_err();
+/// ```
+///
+/// Cancelled statements fail with an error with the code `SQLITE_INTERRUPT` (9). An open
+/// transaction may be rolled back automatically, see
+/// [`sqlite3_interrupt()`](https://www.sqlite.org/c3ref/interrupt.html).
+///
+/// Dropping the future of a query instead does not stop the statement, which runs to completion
+/// on the worker thread of the connection.
+#[derive(Clone)]
+pub struct SqliteCancellationToken {
+    // keeps the database handle open while it is interrupted, without keeping the connection alive
+    shared: Weak<WorkerSharedState>,
+    handle: ConnectionHandleRaw,
+}
+
+// SAFETY: the handle is only used for `sqlite3_interrupt()`, which may be called from any thread
+unsafe impl Sync for SqliteCancellationToken {}
+
+impl SqliteConnection {
+    /// Get a token to cancel the statements running on this connection from another thread.
+    pub fn cancellation_token(&self) -> SqliteCancellationToken {
+        SqliteCancellationToken {
+            shared: Arc::downgrade(&self.worker.shared),
+            handle: self.worker.handle_raw.clone(),
+        }
+    }
+}
+
+impl SqliteCancellationToken {
+    /// Cancel the statements currently running on the connection, if any.
+    ///
+    /// Statements started afterwards are not affected. Returns `false` if the connection was
+    /// already closed.
+    pub fn cancel(&self) -> bool {
+        let Some(_shared) = self.shared.upgrade() else {
+            return false;
+        };
+
+        // SAFETY: the database handle is not closed while `_shared` is alive
+        unsafe { self.handle.interrupt() };
+
+        true
+    }
+}
+
+impl Debug for SqliteCancellationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteCancellationToken")
+            .field("closed", &(self.shared.strong_count() == 0))
+            .finish()
+    }
+}
@@ -1,10 +1,12 @@
 use crate::connection::explain::explain;
+use crate::connection::pragma::{pragma_column, pragma_name};
 use crate::connection::ConnectionState;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::statement::VirtualStatement;
 use crate::type_info::DataType;
-use crate::{Sqlite, SqliteColumn};
+use crate::{Sqlite, SqliteColumn, SqliteTypeInfo};
+use sqlx_core::ext::ustr::UStr;
 use sqlx_core::Either;
 use std::convert::identity;
 
@@ -47,9 +49,46 @@ pub(crate) fn describe(conn: &mut ConnectionState, query: &str) -> Result<Descri
             }
         };
 
+        let pragma = pragma_name(stmt.handle.sql()).map(str::to_owned);
+
         for col in 0..num {
             let name = stmt.handle.column_name(col).to_owned();
 
+            // the columns of `PRAGMA table_info(t)` and of `pragma_table_info('t')` have no
+            // declared types, and most PRAGMAs return no rows to step for an empty schema
+            let pragma_column = match stmt.handle.column_origin(col) {
+                Some((table, column)) => table
+                    .strip_prefix("pragma_")
+                    .and_then(|pragma| pragma_column(pragma, column)),
+                None => pragma
+                    .as_deref()
+                    .and_then(|pragma| pragma_column(pragma, &name)),
+            };
+
+            if let Some((ty, col_nullable)) = pragma_column {
+                nullable.push(Some(col_nullable));
+
+                columns.push(SqliteColumn {
+                    name: name.into(),
+                    type_info: SqliteTypeInfo(ty),
+                    ordinal: col,
+                    declared_type: stmt.handle.column_decltype_name(col).map(UStr::new),
+                });
+
+                continue;
+            }
+
+            // in strict mode, don't guess the type of columns declared with an unknown type
+            if conn.decode_settings.strict {
+                if let Some(decl) = stmt.handle.column_decltype_name(col) {
+                    if decl.parse::<DataType>().is_err() {
+                        return Err(Error::TypeNotFound {
+                            type_name: decl.to_owned(),
+                        });
+                    }
+                }
+            }
+
             let type_info = if let Some(ty) = stmt.handle.column_decltype(col) {
                 ty
             } else {
@@ -82,6 +121,7 @@ pub(crate) fn describe(conn: &mut ConnectionState, query: &str) -> Result<Descri
                 name: name.into(),
                 type_info,
                 ordinal: col,
+                declared_type: stmt.handle.column_decltype_name(col).map(UStr::new),
             });
         }
     }
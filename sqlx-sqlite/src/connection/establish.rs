@@ -2,7 +2,9 @@ use crate::connection::handle::ConnectionHandle;
 use crate::connection::LogSettings;
 use crate::connection::{ConnectionState, Statements};
 use crate::error::Error;
-use crate::{SqliteConnectOptions, SqliteError};
+use crate::options::TemporaryFile;
+use crate::value::DecodeSettings;
+use crate::{SqliteConnectOptions, SqliteError, SqliteTypeRegistry};
 use libsqlite3_sys::{
     sqlite3, sqlite3_busy_timeout, sqlite3_db_config, sqlite3_extended_result_codes, sqlite3_free,
     sqlite3_load_extension, sqlite3_open_v2, SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION, SQLITE_OK,
@@ -15,6 +17,7 @@ use std::io;
 use std::os::raw::c_int;
 use std::ptr::{addr_of_mut, null, null_mut};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
 
 // This was originally `AtomicU64` but that's not supported on MIPS (or PowerPC):
@@ -41,9 +44,15 @@ impl SqliteLoadExtensionMode {
 pub struct EstablishParams {
     filename: CString,
     open_flags: i32,
+    memory_anchor: Option<Arc<Mutex<Option<ConnectionHandle>>>>,
+    temporary: Option<Arc<TemporaryFile>>,
     busy_timeout: Duration,
     statement_cache_capacity: usize,
+    statement_cache_ttl: Option<Duration>,
     log_settings: LogSettings,
+    decode_settings: DecodeSettings,
+    type_registry: Option<Arc<SqliteTypeRegistry>>,
+    defer_foreign_keys: bool,
     extensions: IndexMap<CString, Option<CString>>,
     pub(crate) thread_name: String,
     pub(crate) command_channel_size: usize,
@@ -98,6 +107,10 @@ impl EstablishParams {
             query_params.push("immutable=true".into())
         }
 
+        if options.nolock {
+            query_params.push("nolock=1".into())
+        }
+
         if let Some(vfs) = &options.vfs {
             query_params.push(format!("vfs={vfs}"))
         }
@@ -150,9 +163,19 @@ impl EstablishParams {
         Ok(Self {
             filename,
             open_flags: flags,
+            memory_anchor: options.memory_anchor.clone(),
+            temporary: options.temporary.clone(),
             busy_timeout: options.busy_timeout,
             statement_cache_capacity: options.statement_cache_capacity,
+            statement_cache_ttl: options.statement_cache_ttl,
             log_settings: options.log_settings.clone(),
+            decode_settings: DecodeSettings {
+                numeric_overflow: options.numeric_overflow,
+                coercions: options.coercions,
+                strict: options.strict,
+            },
+            type_registry: options.type_registry.clone(),
+            defer_foreign_keys: options.defer_foreign_keys,
             extensions,
             thread_name: (options.thread_name)(thread_id as u64),
             command_channel_size: options.command_channel_size,
@@ -183,11 +206,11 @@ impl EstablishParams {
         Ok(())
     }
 
-    pub(crate) fn establish(&self) -> Result<ConnectionState, Error> {
+    fn open(&self) -> Result<ConnectionHandle, Error> {
         let mut handle = null_mut();
 
         // <https://www.sqlite.org/c3ref/open.html>
-        let mut status = unsafe {
+        let status = unsafe {
             sqlite3_open_v2(self.filename.as_ptr(), &mut handle, self.open_flags, null())
         };
 
@@ -207,6 +230,23 @@ impl EstablishParams {
             return Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))));
         }
 
+        Ok(handle)
+    }
+
+    pub(crate) fn establish(&self) -> Result<ConnectionState, Error> {
+        let handle = self.open()?;
+
+        if let Some(anchor) = &self.memory_anchor {
+            let mut anchor = anchor.lock().unwrap_or_else(PoisonError::into_inner);
+
+            // an additional connection that keeps the in-memory database alive
+            if anchor.is_none() {
+                *anchor = Some(self.open()?);
+            }
+        }
+
+        let mut status;
+
         // Enable extended result codes
         // https://www.sqlite.org/c3ref/extended_result_codes.html
         unsafe {
@@ -288,10 +328,16 @@ impl EstablishParams {
 
         Ok(ConnectionState {
             handle,
-            statements: Statements::new(self.statement_cache_capacity),
+            _temporary: self.temporary.clone(),
+            statements: Statements::new(self.statement_cache_capacity, self.statement_cache_ttl),
             transaction_depth: 0,
             log_settings: self.log_settings.clone(),
+            decode_settings: self.decode_settings,
+            type_registry: self.type_registry.clone(),
+            defer_foreign_keys: self.defer_foreign_keys,
             progress_handler_callback: None,
+            #[cfg(feature = "session")]
+            sessions: Default::default(),
         })
     }
 }
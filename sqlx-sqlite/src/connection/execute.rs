@@ -2,20 +2,51 @@ use crate::connection::{ConnectionHandle, ConnectionState};
 use crate::error::Error;
 use crate::logger::QueryLogger;
 use crate::statement::{StatementHandle, VirtualStatement};
-use crate::{SqliteArguments, SqliteQueryResult, SqliteRow};
+use crate::value::DecodeSettings;
+use crate::{SqliteArguments, SqliteQueryResult, SqliteQueryStats, SqliteRow, SqliteTypeRegistry};
+use libsqlite3_sys::{
+    SQLITE_STMTSTATUS_AUTOINDEX, SQLITE_STMTSTATUS_FULLSCAN_STEP, SQLITE_STMTSTATUS_MEMUSED,
+    SQLITE_STMTSTATUS_SORT, SQLITE_STMTSTATUS_VM_STEP,
+};
+use sqlx_core::types::NumericOverflow;
 use sqlx_core::Either;
+use std::os::raw::c_int;
+use std::sync::Arc;
+
+const STATS: [c_int; 4] = [
+    SQLITE_STMTSTATUS_FULLSCAN_STEP,
+    SQLITE_STMTSTATUS_SORT,
+    SQLITE_STMTSTATUS_AUTOINDEX,
+    SQLITE_STMTSTATUS_VM_STEP,
+];
 
 pub struct ExecuteIter<'a> {
     handle: &'a mut ConnectionHandle,
     statement: &'a mut VirtualStatement,
     logger: QueryLogger<'a>,
+    decode_settings: DecodeSettings,
+    type_registry: Option<&'a Arc<SqliteTypeRegistry>>,
     args: Option<SqliteArguments<'a>>,
 
     /// since a `VirtualStatement` can encompass multiple actual statements,
     /// this keeps track of the number of arguments so far
     args_used: usize,
 
+    /// the offset of the arguments of the current statement, to bind them again if it is
+    /// prepared again
+    args_offset: usize,
+
+    /// whether the current statement has not been stepped yet
+    first_step: bool,
+
+    /// the total changes of the connection when the current statement started, to count the
+    /// changes of triggers as well
+    total_changes: u64,
+
     goto_next: bool,
+
+    /// whether a statement failed, after which stepping it again would run it from the start
+    failed: bool,
 }
 
 pub(crate) fn iter<'a>(
@@ -27,15 +58,25 @@ pub(crate) fn iter<'a>(
     // fetch the cached statement or allocate a new one
     let statement = conn.statements.get(query, persistent)?;
 
-    let logger = QueryLogger::new(query, conn.log_settings.clone());
+    let mut logger = QueryLogger::new(query, conn.log_settings.clone());
+
+    if let Some(args) = &args {
+        logger.arguments(|| args.clone());
+    }
 
     Ok(ExecuteIter {
         handle: &mut conn.handle,
         statement,
         logger,
+        decode_settings: conn.decode_settings,
+        type_registry: conn.type_registry.as_ref(),
         args,
         args_used: 0,
+        args_offset: 0,
+        first_step: false,
+        total_changes: 0,
         goto_next: true,
+        failed: false,
     })
 }
 
@@ -54,6 +95,11 @@ fn bind(
 }
 
 impl ExecuteIter<'_> {
+    // overrides the connection's policy for the rows of this query
+    pub(crate) fn set_numeric_overflow(&mut self, policy: NumericOverflow) {
+        self.decode_settings.numeric_overflow = policy;
+    }
+
     pub fn finish(&mut self) -> Result<(), Error> {
         for res in self {
             let _ = res?;
@@ -67,6 +113,10 @@ impl Iterator for ExecuteIter<'_> {
     type Item = Result<Either<SqliteQueryResult, SqliteRow>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
         let statement = if self.goto_next {
             let mut statement = match self.statement.prepare_next(self.handle) {
                 Ok(Some(statement)) => statement,
@@ -83,6 +133,15 @@ impl Iterator for ExecuteIter<'_> {
 
             statement.handle.clear_bindings();
 
+            // the counters accumulate over executions of cached statements
+            for op in STATS {
+                statement.handle.status(op, true);
+            }
+
+            self.total_changes = self.handle.total_changes();
+            self.args_offset = self.args_used;
+            self.first_step = true;
+
             match bind(&mut statement.handle, &self.args, self.args_used) {
                 Ok(args_used) => self.args_used += args_used,
                 Err(e) => return Some(Err(e)),
@@ -93,7 +152,33 @@ impl Iterator for ExecuteIter<'_> {
             self.statement.current()?
         };
 
-        match statement.handle.step() {
+        let mut step = statement.handle.step();
+
+        if std::mem::replace(&mut self.first_step, false) {
+            if matches!(&step, Err(e) if e.is_schema_changed()) {
+                // SQLite recompiles statements after schema changes by itself, but gives up
+                // after a number of retries; prepare the statement again and retry once
+                if let Err(e) = self.statement.reprepare_current(self.handle) {
+                    return Some(Err(e));
+                }
+
+                let statement = self.statement.current()?;
+
+                if let Err(e) = bind(statement.handle, &self.args, self.args_offset) {
+                    return Some(Err(e));
+                }
+
+                step = statement.handle.step();
+            }
+
+            if let Ok(true) = step {
+                self.statement.refresh_current_columns();
+            }
+        }
+
+        let statement = self.statement.current()?;
+
+        match step {
             Ok(true) => {
                 self.logger.increment_rows_returned();
 
@@ -101,6 +186,8 @@ impl Iterator for ExecuteIter<'_> {
                     &statement.handle,
                     &statement.columns,
                     &statement.column_names,
+                    self.decode_settings,
+                    self.type_registry,
                 ))))
             }
             Ok(false) => {
@@ -109,16 +196,30 @@ impl Iterator for ExecuteIter<'_> {
                 let changes = statement.handle.changes();
                 self.logger.increase_rows_affected(changes);
 
+                let status = |op| statement.handle.status(op, false);
+
                 let done = SqliteQueryResult {
                     changes,
+                    total_changes: self.handle.total_changes() - self.total_changes,
                     last_insert_rowid,
+                    stats: SqliteQueryStats {
+                        fullscan_steps: status(SQLITE_STMTSTATUS_FULLSCAN_STEP),
+                        sorts: status(SQLITE_STMTSTATUS_SORT),
+                        autoindexes: status(SQLITE_STMTSTATUS_AUTOINDEX),
+                        vm_steps: status(SQLITE_STMTSTATUS_VM_STEP),
+                        memory_used: status(SQLITE_STMTSTATUS_MEMUSED),
+                    },
                 };
 
                 self.goto_next = true;
 
                 Some(Ok(Either::Left(done)))
             }
-            Err(e) => Some(Err(e.into())),
+            Err(e) => {
+                self.failed = true;
+
+                Some(Err(e.into()))
+            }
         }
     }
 }
@@ -7,6 +7,7 @@ use futures_util::{TryFutureExt, TryStreamExt};
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
 use sqlx_core::executor::{Execute, Executor};
+use sqlx_core::interceptor;
 use sqlx_core::Either;
 
 impl<'c> Executor<'c> for &'c mut SqliteConnection {
@@ -20,16 +21,39 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
+        // statements were intercepted when they were prepared
+        let interceptor = match query.statement() {
+            Some(_) => None,
+            None => self.query_interceptor.as_ref(),
+        };
         let arguments = query.take_arguments();
+        let sql = interceptor::apply(interceptor, query.sql(), arguments.as_ref());
         let persistent = query.persistent() && arguments.is_some();
+        let numeric_overflow = query.numeric_overflow();
 
-        Box::pin(
-            self.worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream(),
-        )
+        let stream = Box::pin(
+            async move {
+                self.worker
+                    .execute(
+                        &sql,
+                        arguments,
+                        self.row_channel_size,
+                        persistent,
+                        numeric_overflow,
+                    )
+                    .await
+            }
+            .map_ok(flume::Receiver::into_stream)
+            .try_flatten_stream(),
+        );
+
+        #[cfg(feature = "metrics")]
+        let stream = sqlx_core::metrics::instrument_stream(
+            <Sqlite as sqlx_core::database::Database>::NAME,
+            stream,
+        );
+
+        stream
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -40,14 +64,26 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
+        // statements were intercepted when they were prepared
+        let interceptor = match query.statement() {
+            Some(_) => None,
+            None => self.query_interceptor.as_ref(),
+        };
         let arguments = query.take_arguments();
+        let sql = interceptor::apply(interceptor, query.sql(), arguments.as_ref());
         let persistent = query.persistent() && arguments.is_some();
+        let numeric_overflow = query.numeric_overflow();
 
-        Box::pin(async move {
+        let future = Box::pin(async move {
             let stream = self
                 .worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
+                .execute(
+                    &sql,
+                    arguments,
+                    self.row_channel_size,
+                    persistent,
+                    numeric_overflow,
+                )
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream();
 
@@ -60,7 +96,15 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
             }
 
             Ok(None)
-        })
+        });
+
+        #[cfg(feature = "metrics")]
+        let future = sqlx_core::metrics::instrument_future(
+            <Sqlite as sqlx_core::database::Database>::NAME,
+            future,
+        );
+
+        future
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -72,12 +116,10 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            let statement = self.worker.prepare(sql).await?;
+            let sql = interceptor::rewrite(self.query_interceptor.as_ref(), sql);
+            let statement = self.worker.prepare(&sql).await?;
 
-            Ok(SqliteStatement {
-                sql: sql.into(),
-                ..statement
-            })
+            Ok(SqliteStatement { sql, ..statement })
         })
     }
 
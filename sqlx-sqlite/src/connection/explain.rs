@@ -99,6 +99,7 @@ const OP_MOVE: &str = "Move";
 const OP_COPY: &str = "Copy";
 const OP_SCOPY: &str = "SCopy";
 const OP_NULL: &str = "Null";
+const OP_BEGIN_SUBRTN: &str = "BeginSubrtn";
 const OP_NULL_ROW: &str = "NullRow";
 const OP_INT_COPY: &str = "IntCopy";
 const OP_CAST: &str = "Cast";
@@ -332,6 +333,55 @@ fn opcode_to_type(op: &str) -> DataType {
     }
 }
 
+/// The result type of a call to the builtin scalar function `func`, as shown by `EXPLAIN`
+/// (e.g. `abs(1)`, or `max(-1)` for a variable number of arguments), with the arguments in
+/// `r[p2..]`.
+fn scalar_function_type(func: &str, p2: i64, r: &IntMap<RegDataType>) -> Option<ColumnType> {
+    let (name, num_args) = func.strip_suffix(')')?.split_once('(')?;
+    let num_args: i64 = num_args.parse().ok()?;
+
+    let first = r.get(&p2);
+    let first_datatype = first.map_or(DataType::Null, |arg| arg.map_to_datatype());
+
+    // most functions return NULL if any argument is NULL
+    let args_nullable = if num_args >= 0 {
+        (p2..p2 + num_args).try_fold(false, |nullable, i| {
+            Some(nullable | r.get(&i)?.map_to_nullable()?)
+        })
+    } else {
+        None
+    };
+
+    let (datatype, nullable) = match name {
+        "random" | "changes" | "total_changes" => (DataType::Int64, Some(false)),
+        "typeof" | "quote" | "sqlite_version" | "sqlite_source_id" | "concat" => {
+            (DataType::Text, Some(false))
+        }
+        "length" | "octet_length" | "instr" => (DataType::Int64, args_nullable),
+        // NULL for an empty string
+        "unicode" => (DataType::Int64, Some(true)),
+        "like" | "glob" => (DataType::Bool, args_nullable),
+        "lower" | "upper" | "trim" | "ltrim" | "rtrim" | "substr" | "substring" | "replace"
+        | "hex" | "char" | "printf" | "format" | "soundex" | "concat_ws" => {
+            (DataType::Text, args_nullable)
+        }
+        "round" => (DataType::Float, args_nullable),
+        "randomblob" | "zeroblob" => (DataType::Blob, args_nullable),
+        // NULL for invalid hex digits
+        "unhex" => (DataType::Blob, Some(true)),
+        // NULL for arguments that are not numbers
+        "sign" => match first_datatype {
+            DataType::Int | DataType::Int64 | DataType::Float => (DataType::Int64, args_nullable),
+            _ => (DataType::Int64, Some(true)),
+        },
+        "abs" | "max" | "min" => (first_datatype, args_nullable),
+        "nullif" => (first_datatype, Some(true)),
+        _ => return None,
+    };
+
+    Some(ColumnType::Single { datatype, nullable })
+}
+
 fn root_block_columns(
     conn: &mut ConnectionState,
 ) -> Result<HashMap<(i64, i64), IntMap<ColumnType>>, Error> {
@@ -765,6 +815,9 @@ pub(super) fn explain(
                         state.mem.program_i = (*return_i + 1) as usize;
                         state.mem.r.remove(&p1);
                         continue;
+                    } else if p3 == 1 {
+                        // subroutine was entered inline after `BeginSubrtn`, continue with the
+                        // next instruction
                     } else {
                         if logger.log_enabled() {
                             let program_history: Vec<&(i64, String, i64, i64, i64, Vec<u8>)> =
@@ -1035,7 +1088,12 @@ pub(super) fn explain(
                             );
                         }
 
-                        _ => logger.add_unknown_operation(&program[state.mem.program_i]),
+                        func => match scalar_function_type(func, p2, &state.mem.r) {
+                            Some(ty) => {
+                                state.mem.r.insert(p3, RegDataType::Single(ty));
+                            }
+                            None => logger.add_unknown_operation(&program[state.mem.program_i]),
+                        },
                     }
                 }
 
@@ -1100,6 +1158,32 @@ pub(super) fn explain(
                                 RegDataType::Single(ColumnType::Single { datatype, nullable }),
                             );
                         }
+                    } else if p4.starts_with("total(") {
+                        // total(_) -> REAL, 0.0 for no rows
+                        state.mem.r.insert(
+                            p3,
+                            RegDataType::Single(ColumnType::Single {
+                                datatype: DataType::Float,
+                                nullable: Some(false),
+                            }),
+                        );
+                    } else if p4.starts_with("avg(")
+                        || p4.starts_with("group_concat(")
+                        || p4.starts_with("string_agg(")
+                    {
+                        // avg(_) -> REAL, group_concat(_) -> TEXT, NULL if all values are NULL
+                        if let Some(r_p2) = state.mem.r.get(&p2) {
+                            let datatype = if p4.starts_with("avg(") {
+                                DataType::Float
+                            } else {
+                                DataType::Text
+                            };
+                            let nullable = r_p2.map_to_nullable();
+                            state.mem.r.insert(
+                                p3,
+                                RegDataType::Single(ColumnType::Single { datatype, nullable }),
+                            );
+                        }
                     } else if p4.starts_with("lead(") || p4.starts_with("lag(") {
                         if let Some(r_p2) = state.mem.r.get(&p2) {
                             let datatype = r_p2.map_to_datatype();
@@ -1134,6 +1218,15 @@ pub(super) fn explain(
                                 nullable: Some(false),
                             }),
                         );
+                    } else if p4.starts_with("total(") {
+                        // total(_) -> REAL
+                        state.mem.r.insert(
+                            p1,
+                            RegDataType::Single(ColumnType::Single {
+                                datatype: DataType::Float,
+                                nullable: Some(false),
+                            }),
+                        );
                     } else if p4.starts_with("percent_rank(") || p4.starts_with("cume_dist") {
                         // percent_rank(_) -> REAL
                         state.mem.r.insert(
@@ -1227,6 +1320,14 @@ pub(super) fn explain(
                     }
                 }
 
+                OP_BEGIN_SUBRTN => {
+                    // r[p2] = null, marks a subroutine that is also entered inline
+                    state
+                        .mem
+                        .r
+                        .insert(p2, RegDataType::Single(ColumnType::null()));
+                }
+
                 OP_NULL => {
                     // r[p2..p3] = null
                     let idx_range = if p2 < p3 { p2..=p3 } else { p2..=p2 };
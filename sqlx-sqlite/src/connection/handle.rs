@@ -1,11 +1,13 @@
 use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_void};
 use std::ptr;
 use std::ptr::NonNull;
 
 use crate::error::Error;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_exec, sqlite3_last_insert_rowid, SQLITE_LOCKED_SHAREDCACHE,
-    SQLITE_OK,
+    sqlite3, sqlite3_changes64, sqlite3_close, sqlite3_exec, sqlite3_file_control,
+    sqlite3_get_autocommit, sqlite3_interrupt, sqlite3_last_insert_rowid, sqlite3_total_changes64,
+    SQLITE_FCNTL_HAS_MOVED, SQLITE_LOCKED_SHAREDCACHE, SQLITE_OK,
 };
 
 use crate::{statement::unlock_notify, SqliteError};
@@ -33,6 +35,17 @@ unsafe impl Send for ConnectionHandle {}
 // SAFETY: this type does nothing but provide access to the DB handle pointer.
 unsafe impl Send for ConnectionHandleRaw {}
 
+impl ConnectionHandleRaw {
+    /// Interrupt the statements running on the database handle, from any thread.
+    ///
+    /// # Safety
+    /// The database handle must not be closed.
+    pub(crate) unsafe fn interrupt(&self) {
+        // https://sqlite.org/c3ref/interrupt.html
+        sqlite3_interrupt(self.0.as_ptr())
+    }
+}
+
 impl ConnectionHandle {
     #[inline]
     pub(super) unsafe fn new(ptr: *mut sqlite3) -> Self {
@@ -58,6 +71,45 @@ impl ConnectionHandle {
         unsafe { sqlite3_last_insert_rowid(self.as_ptr()) }
     }
 
+    pub(crate) fn changes(&mut self) -> u64 {
+        // https://sqlite.org/c3ref/changes.html
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_changes64(self.as_ptr()) as u64 }
+    }
+
+    pub(crate) fn total_changes(&mut self) -> u64 {
+        // https://sqlite.org/c3ref/total_changes.html
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_total_changes64(self.as_ptr()) as u64 }
+    }
+
+    pub(crate) fn in_transaction(&mut self) -> bool {
+        // https://sqlite.org/c3ref/get_autocommit.html
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_get_autocommit(self.as_ptr()) == 0 }
+    }
+
+    pub(crate) fn has_moved(&mut self) -> bool {
+        // the name of the database to check, as a C string
+        static MAIN: &[u8] = b"main\0";
+
+        let mut moved: c_int = 0;
+
+        // https://sqlite.org/c3ref/c_fcntl_begin_atomic_write.html#sqlitefcntlhasmoved
+        // SAFETY: we have exclusive access to the database handle, and `moved` outlives the call
+        let status = unsafe {
+            sqlite3_file_control(
+                self.as_ptr(),
+                MAIN.as_ptr() as *const c_char,
+                SQLITE_FCNTL_HAS_MOVED,
+                &mut moved as *mut c_int as *mut c_void,
+            )
+        };
+
+        // e.g. in-memory databases do not implement the file control
+        status == SQLITE_OK && moved != 0
+    }
+
     pub(crate) fn exec(&mut self, query: impl Into<String>) -> Result<(), Error> {
         let query = query.into();
         let query = CString::new(query).map_err(|_| err_protocol!("query contains nul bytes"))?;
@@ -0,0 +1,88 @@
+use sqlx_core::connection::Connection;
+use sqlx_core::error::Error;
+use sqlx_core::query_as::query_as;
+use sqlx_core::query_scalar::query_scalar;
+
+use crate::SqliteConnection;
+
+/// A row violating a foreign key constraint, see [`SqliteConnection::foreign_key_check()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteForeignKeyViolation {
+    /// The table of the row holding the foreign key.
+    pub table: String,
+
+    /// The rowid of the row, or `None` if the table is a `WITHOUT ROWID` table.
+    pub rowid: Option<i64>,
+
+    /// The table referenced by the foreign key.
+    pub parent: String,
+
+    /// The index of the foreign key among those of `table`, as listed by
+    /// [`PRAGMA foreign_key_list`](https://www.sqlite.org/pragma.html#pragma_foreign_key_list).
+    pub foreign_key_index: i64,
+}
+
+impl SqliteConnection {
+    /// Check that the connection is alive, the database file was not moved and its header is
+    /// readable.
+    ///
+    /// In addition to [`ping()`](Connection::ping), this reads the database header with
+    /// `PRAGMA schema_version`, which waits for the
+    /// [`busy_timeout`](crate::SqliteConnectOptions::busy_timeout) while another connection
+    /// holds an exclusive lock. A full disk is only reported by writes.
+    pub async fn check_health(&mut self) -> Result<(), Error> {
+        self.ping().await?;
+
+        // bypasses the interceptor and the statement log
+        self.lock_handle()
+            .await?
+            .guard
+            .handle
+            .exec("PRAGMA schema_version")
+    }
+
+    /// Check the database for corruption and return the problems found, or an empty `Vec`.
+    ///
+    /// Runs [`PRAGMA integrity_check`](https://www.sqlite.org/pragma.html#pragma_integrity_check),
+    /// or `PRAGMA quick_check` if `quick` is set, which skips verifying that indexes match their
+    /// tables and runs in linear time. Both read the whole database.
+    pub async fn integrity_check(&mut self, quick: bool) -> Result<Vec<String>, Error> {
+        let pragma = if quick {
+            "PRAGMA quick_check"
+        } else {
+            "PRAGMA integrity_check"
+        };
+
+        let mut problems: Vec<String> = query_scalar(pragma).fetch_all(&mut *self).await?;
+
+        // a single `ok` row if no problems were found
+        if problems.len() == 1 && problems[0] == "ok" {
+            problems.clear();
+        }
+
+        Ok(problems)
+    }
+
+    /// Find the rows that violate a foreign key constraint, e.g. after inserting rows with
+    /// [foreign keys](crate::SqliteConnectOptions::foreign_keys) disabled.
+    ///
+    /// See [`PRAGMA foreign_key_check`](https://www.sqlite.org/pragma.html#pragma_foreign_key_check).
+    pub async fn foreign_key_check(&mut self) -> Result<Vec<SqliteForeignKeyViolation>, Error> {
+        let rows: Vec<(String, Option<i64>, String, i64)> = query_as("PRAGMA foreign_key_check")
+            .fetch_all(&mut *self)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(table, rowid, parent, foreign_key_index)| SqliteForeignKeyViolation {
+                    table,
+                    rowid,
+                    parent,
+                    foreign_key_index,
+                },
+            )
+            .collect())
+    }
+}
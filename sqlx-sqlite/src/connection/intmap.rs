@@ -69,6 +69,16 @@ impl<V: std::fmt::Debug + Clone + Eq + PartialEq + std::hash::Hash> IntMap<V> {
     }
 }
 
+impl<V: std::fmt::Debug + Clone + Eq + PartialEq + std::hash::Hash> std::ops::Index<&i64>
+    for IntMap<V>
+{
+    type Output = V;
+
+    fn index(&self, idx: &i64) -> &V {
+        self.get(idx).expect("no value at index")
+    }
+}
+
 impl<V: std::fmt::Debug + Clone + Eq + PartialEq + std::hash::Hash> std::hash::Hash for IntMap<V> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         for value in self.values() {
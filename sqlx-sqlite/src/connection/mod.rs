@@ -1,28 +1,38 @@
 use futures_core::future::BoxFuture;
 use futures_intrusive::sync::MutexGuard;
 use futures_util::future;
-use libsqlite3_sys::{sqlite3, sqlite3_progress_handler};
+use libsqlite3_sys::{sqlite3, sqlite3_progress_handler, SQLITE_BUSY};
 use sqlx_core::common::StatementCache;
 use sqlx_core::error::Error;
+use sqlx_core::interceptor::QueryInterceptor;
 use sqlx_core::transaction::Transaction;
+use sqlx_core::types::NumericOverflow;
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
+use std::io;
 use std::os::raw::{c_int, c_void};
 use std::panic::catch_unwind;
 use std::ptr::NonNull;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::worker::ConnectionWorker;
-use crate::options::OptimizeOnClose;
+use crate::options::{OptimizeOnClose, TemporaryFile};
 use crate::statement::VirtualStatement;
-use crate::{Sqlite, SqliteConnectOptions};
+use crate::value::DecodeSettings;
+use crate::{Sqlite, SqliteCoercions, SqliteConnectOptions, SqliteError, SqliteTypeRegistry};
 use sqlx_core::executor::Executor;
+use sqlx_core::query::query;
+use sqlx_core::query_as::query_as;
+use sqlx_core::query_scalar::query_scalar;
 use std::fmt::Write;
 
 pub(crate) use sqlx_core::connection::*;
 
 pub(crate) use handle::{ConnectionHandle, ConnectionHandleRaw};
 
+mod cancel;
 pub(crate) mod collation;
 pub(crate) mod describe;
 pub(crate) mod establish;
@@ -30,10 +40,18 @@ pub(crate) mod execute;
 mod executor;
 mod explain;
 mod handle;
+mod health;
 mod intmap;
-
+mod pragma;
+#[cfg(feature = "session")]
+pub(crate) mod session;
+mod storage;
 mod worker;
 
+pub use cancel::SqliteCancellationToken;
+pub use health::SqliteForeignKeyViolation;
+pub use storage::SqliteStorageSettings;
+
 /// A connection to an open [Sqlite] database.
 ///
 /// Because SQLite is an in-process database accessed by blocking API calls, SQLx uses a background
@@ -48,6 +66,10 @@ pub struct SqliteConnection {
     optimize_on_close: OptimizeOnClose,
     pub(crate) worker: ConnectionWorker,
     pub(crate) row_channel_size: usize,
+    // only read when creating the migrations table
+    #[cfg(feature = "migrate")]
+    pub(crate) strict: bool,
+    pub(crate) query_interceptor: Option<QueryInterceptor<Sqlite>>,
 }
 
 pub struct LockedSqliteHandle<'a> {
@@ -61,6 +83,9 @@ unsafe impl Send for Handler {}
 pub(crate) struct ConnectionState {
     pub(crate) handle: ConnectionHandle,
 
+    // the file of a temporary database, deleted after the last handle to it is closed
+    _temporary: Option<Arc<TemporaryFile>>,
+
     // transaction status
     pub(crate) transaction_depth: usize,
 
@@ -68,9 +93,19 @@ pub(crate) struct ConnectionState {
 
     log_settings: LogSettings,
 
+    pub(crate) decode_settings: DecodeSettings,
+
+    pub(crate) type_registry: Option<Arc<SqliteTypeRegistry>>,
+
+    // set `PRAGMA defer_foreign_keys` at the start of each transaction
+    pub(crate) defer_foreign_keys: bool,
+
     /// Stores the progress handler set on the current connection. If the handler returns `false`,
     /// the query is interrupted.
     progress_handler_callback: Option<Handler>,
+
+    #[cfg(feature = "session")]
+    pub(crate) sessions: session::Sessions,
 }
 
 impl ConnectionState {
@@ -86,12 +121,35 @@ impl ConnectionState {
 }
 
 pub(crate) struct Statements {
-    // cache of semi-persistent statements
-    cached: StatementCache<VirtualStatement>,
+    // cache of semi-persistent statements, with the time they were prepared
+    cached: StatementCache<(VirtualStatement, Instant)>,
+    // re-prepare cached statements older than this
+    ttl: Option<Duration>,
+    stats: SqliteStatementCacheStats,
     // most recent non-persistent statement
     temp: Option<VirtualStatement>,
 }
 
+/// Counters of the statement cache of a connection, see
+/// [`SqliteConnection::statement_cache_stats()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteStatementCacheStats {
+    /// The number of persistent queries whose statement was found in the cache.
+    pub hits: u64,
+
+    /// The number of persistent queries whose statement had to be prepared, including those
+    /// whose cached statement expired.
+    pub misses: u64,
+
+    /// The number of statements removed to make room for others.
+    pub evictions: u64,
+
+    /// The number of statements prepared again because they were older than the
+    /// [`statement_cache_ttl`](crate::SqliteConnectOptions::statement_cache_ttl).
+    pub expirations: u64,
+}
+
 impl SqliteConnection {
     pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<Self, Error> {
         let params = EstablishParams::from_options(options)?;
@@ -100,9 +158,18 @@ impl SqliteConnection {
             optimize_on_close: options.optimize_on_close.clone(),
             worker,
             row_channel_size: options.row_channel_size,
+            #[cfg(feature = "migrate")]
+            strict: options.strict,
+            query_interceptor: options.query_interceptor.clone(),
         })
     }
 
+    /// Replace the query interceptor set by
+    /// [`SqliteConnectOptions::query_interceptor()`], or remove it with `None`.
+    pub fn set_query_interceptor(&mut self, interceptor: Option<QueryInterceptor<Sqlite>>) {
+        self.query_interceptor = interceptor;
+    }
+
     /// Lock the SQLite database handle out from the worker thread so direct SQLite API calls can
     /// be made safely.
     ///
@@ -112,6 +179,56 @@ impl SqliteConnection {
 
         Ok(LockedSqliteHandle { guard })
     }
+
+    /// The rowid of the row inserted last on this connection, e.g. by a script run with
+    /// [`Executor::execute_many()`], or `0` if none was.
+    ///
+    /// See [`LockedSqliteHandle::last_insert_rowid()`].
+    pub async fn last_insert_rowid(&mut self) -> Result<i64, Error> {
+        Ok(self.lock_handle().await?.last_insert_rowid())
+    }
+
+    /// The counters of the statement cache used by persistent queries.
+    ///
+    /// See [`LockedSqliteHandle::statement_cache_stats()`].
+    pub async fn statement_cache_stats(&mut self) -> Result<SqliteStatementCacheStats, Error> {
+        Ok(self.lock_handle().await?.statement_cache_stats())
+    }
+
+    /// Run the commands queued before, checkpoint the write-ahead log into the database and
+    /// close the connection, waiting at most `timeout`.
+    ///
+    /// After this returns `Ok`, the worker thread has closed the database, so the process may
+    /// exit without leaving a write-ahead log behind that still needs to be replayed. If the
+    /// database is not in WAL mode, checkpointing does nothing. If `timeout` passes first, this
+    /// fails with an I/O error of kind [`TimedOut`](std::io::ErrorKind::TimedOut) and the worker
+    /// thread closes the database in the background once it finishes its current command.
+    ///
+    /// If another connection keeps the checkpoint from completing, this fails with a
+    /// [`SqliteError`](crate::SqliteError) with the code `SQLITE_BUSY` and the connection is
+    /// closed in the background.
+    pub async fn shutdown(mut self, timeout: Duration) -> Result<(), Error> {
+        let shutdown = async move {
+            // https://www.sqlite.org/pragma.html#pragma_wal_checkpoint
+            let (busy, _, _): (i32, i64, i64) = query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+                .fetch_one(&mut self)
+                .await?;
+
+            if busy != 0 {
+                return Err(Error::Database(Box::new(SqliteError::from_code(
+                    SQLITE_BUSY,
+                    "the write-ahead log could not be checkpointed, it is in use by another \
+                     connection",
+                ))));
+            }
+
+            self.close().await
+        };
+
+        crate::rt::timeout(timeout, shutdown)
+            .await
+            .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))?
+    }
 }
 
 impl Debug for SqliteConnection {
@@ -154,9 +271,25 @@ impl Connection for SqliteConnection {
         })
     }
 
-    /// Ensure the background worker thread is alive and accepting commands.
+    /// Ensure the background worker thread is alive and accepting commands, and that the database
+    /// file was not moved.
+    ///
+    /// Fails with an I/O error of kind [`NotFound`](io::ErrorKind::NotFound) if the database file
+    /// was deleted or renamed, e.g. by log rotation, since the connection opened it. Does not
+    /// access the database; see [`check_health()`](SqliteConnection::check_health) to also
+    /// check that it is readable.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
-        Box::pin(self.worker.ping())
+        Box::pin(async move {
+            if self.worker.ping().await? {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "the database file was deleted or renamed",
+                )
+                .into());
+            }
+
+            Ok(())
+        })
     }
 
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
@@ -173,6 +306,92 @@ impl Connection for SqliteConnection {
             .load(std::sync::atomic::Ordering::Acquire)
     }
 
+    fn prepare_cached<'a>(
+        &'a mut self,
+        statements: &'a [&'a str],
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            // preparing is local, no round trips to save
+            for sql in statements {
+                self.worker.prepare(sql).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn clear_table<'a>(
+        &'a mut self,
+        table: &'a str,
+        restart_identity: bool,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            // without a WHERE clause, SQLite drops all pages at once instead of deleting row by row
+            self.execute(&*format!("DELETE FROM {table}")).await?;
+
+            if restart_identity {
+                // `sqlite_sequence` holds the unquoted name, and each schema has its own
+                let (schema, name) = split_table_name(table);
+                let schema = schema.map_or_else(
+                    || "main".to_owned(),
+                    |schema| format!("\"{}\"", schema.replace('"', "\"\"")),
+                );
+
+                // only exists once a table with an AUTOINCREMENT column was created
+                let has_sequences: Option<i32> = query_scalar(&format!(
+                    "SELECT 1 FROM {schema}.sqlite_master \
+                     WHERE type = 'table' AND name = 'sqlite_sequence'"
+                ))
+                .fetch_optional(&mut *self)
+                .await?;
+
+                if has_sequences.is_some() {
+                    // names are compared case-insensitively, like SQLite does for identifiers
+                    query(&format!(
+                        "DELETE FROM {schema}.sqlite_sequence WHERE name = ?1 COLLATE NOCASE"
+                    ))
+                    .bind(name)
+                    .execute(&mut *self)
+                    .await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn reset(&mut self, discard_session: bool) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            {
+                let mut locked = self.lock_handle().await?;
+                let conn = &mut *locked.guard;
+
+                // also catches transactions begun with a raw `BEGIN`, which are not counted
+                if conn.transaction_depth > 0 || conn.handle.in_transaction() {
+                    conn.handle.exec("ROLLBACK")?;
+                }
+
+                conn.transaction_depth = 0;
+            }
+
+            if discard_session {
+                let temporary: Vec<(String, String)> = query_as(
+                    "SELECT type, name FROM temp.sqlite_master WHERE type IN ('table', 'view')",
+                )
+                .fetch_all(&mut *self)
+                .await?;
+
+                for (kind, name) in temporary {
+                    let name = name.replace('"', "\"\"");
+                    self.execute(&*format!("DROP {kind} temp.\"{name}\""))
+                        .await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.worker.clear_cache().await?;
@@ -234,6 +453,43 @@ impl LockedSqliteHandle<'_> {
         self.guard.handle.as_non_null_ptr()
     }
 
+    /// The rowid of the row inserted last on this connection, or `0` if none was.
+    ///
+    /// Rows inserted by triggers do not count. See
+    /// [`sqlite3_last_insert_rowid()`](https://www.sqlite.org/c3ref/last_insert_rowid.html).
+    pub fn last_insert_rowid(&mut self) -> i64 {
+        self.guard.handle.last_insert_rowid()
+    }
+
+    /// The counters of the statement cache, see [`SqliteStatementCacheStats`].
+    pub fn statement_cache_stats(&mut self) -> SqliteStatementCacheStats {
+        self.guard.statements.stats
+    }
+
+    /// Whether the file of the main database was deleted or renamed since it was opened.
+    ///
+    /// The connection keeps working on the file it opened, so its writes would be lost. Always
+    /// `false` for in-memory databases.
+    pub fn has_moved(&mut self) -> bool {
+        self.guard.handle.has_moved()
+    }
+
+    /// The number of rows inserted, updated or deleted by the last statement that completed,
+    /// without those changed by triggers.
+    ///
+    /// See [`sqlite3_changes64()`](https://www.sqlite.org/c3ref/changes.html).
+    pub fn changes(&mut self) -> u64 {
+        self.guard.handle.changes()
+    }
+
+    /// The number of rows inserted, updated or deleted since the connection was opened,
+    /// including those changed by triggers.
+    ///
+    /// See [`sqlite3_total_changes64()`](https://www.sqlite.org/c3ref/total_changes.html).
+    pub fn total_changes(&mut self) -> u64 {
+        self.guard.handle.total_changes()
+    }
+
     /// Apply a collation to the open database.
     ///
     /// See [`SqliteConnectOptions::collation()`] for details.
@@ -245,6 +501,20 @@ impl LockedSqliteHandle<'_> {
         collation::create_collation(&mut self.guard.handle, name, compare)
     }
 
+    /// Change how out-of-range numerics are decoded for rows fetched from now on.
+    ///
+    /// See [`SqliteConnectOptions::numeric_overflow()`] for details.
+    pub fn set_numeric_overflow(&mut self, policy: NumericOverflow) {
+        self.guard.decode_settings.numeric_overflow = policy;
+    }
+
+    /// Change which implicit conversions are allowed for rows fetched from now on.
+    ///
+    /// See [`SqliteConnectOptions::coercions()`] for details.
+    pub fn set_coercions(&mut self, coercions: SqliteCoercions) {
+        self.guard.decode_settings.coercions = coercions;
+    }
+
     /// Sets a progress handler that is invoked periodically during long running calls. If the progress callback
     /// returns `false`, then the operation is interrupted.
     ///
@@ -283,20 +553,73 @@ impl LockedSqliteHandle<'_> {
     pub fn remove_progress_handler(&mut self) {
         self.guard.remove_progress_handler();
     }
+
+    /// Create a [session](https://www.sqlite.org/sessionintro.html) recording changes made to
+    /// all tables of the given database (e.g. `"main"`) on this connection.
+    ///
+    /// The session stays attached to the connection, so further statements executed through it
+    /// are recorded; use [`.session_changeset()`][Self::session_changeset] to export them.
+    ///
+    /// Only changes to tables with a declared `PRIMARY KEY` are recorded.
+    #[cfg(feature = "session")]
+    pub fn create_session(&mut self, database: &str) -> Result<session::SqliteSession, Error> {
+        let state = &mut *self.guard;
+        state.sessions.create(&mut state.handle, database)
+    }
+
+    /// Export the changes recorded by `session` so far as a changeset blob.
+    ///
+    /// The changeset can be applied to another database with
+    /// [`.apply_changeset()`][Self::apply_changeset].
+    #[cfg(feature = "session")]
+    pub fn session_changeset(
+        &mut self,
+        session: &session::SqliteSession,
+    ) -> Result<Vec<u8>, Error> {
+        let state = &mut *self.guard;
+        state.sessions.changeset(&mut state.handle, session)
+    }
+
+    /// Stop recording changes and free the resources of `session`.
+    ///
+    /// Sessions still alive are deleted when the connection is closed.
+    #[cfg(feature = "session")]
+    pub fn delete_session(&mut self, session: session::SqliteSession) -> Result<(), Error> {
+        self.guard.sessions.delete(session)
+    }
+
+    /// Apply a changeset produced by [`.session_changeset()`][Self::session_changeset]
+    /// (possibly on another connection) to this database.
+    ///
+    /// `on_conflict` is called for every change that cannot be applied cleanly and decides how to
+    /// resolve it. If it returns [`SqliteConflictAction::Abort`][session::SqliteConflictAction::Abort]
+    /// (or panics), all changes made so far are rolled back and an error is returned.
+    #[cfg(feature = "session")]
+    pub fn apply_changeset(
+        &mut self,
+        changeset: &[u8],
+        on_conflict: impl FnMut(&session::SqliteChangesetConflict) -> session::SqliteConflictAction,
+    ) -> Result<(), Error> {
+        session::apply_changeset(&mut self.guard.handle, changeset, on_conflict)
+    }
 }
 
 impl Drop for ConnectionState {
     fn drop(&mut self) {
         // explicitly drop statements before the connection handle is dropped
         self.statements.clear();
+        #[cfg(feature = "session")]
+        self.sessions.clear();
         self.remove_progress_handler();
     }
 }
 
 impl Statements {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, ttl: Option<Duration>) -> Self {
         Statements {
             cached: StatementCache::new(capacity),
+            ttl,
+            stats: SqliteStatementCacheStats::default(),
             temp: None,
         }
     }
@@ -306,14 +629,42 @@ impl Statements {
             return Ok(self.temp.insert(VirtualStatement::new(query, false)?));
         }
 
-        let exists = self.cached.contains_key(query);
+        let mut exists = self.cached.contains_key(query);
+
+        if exists {
+            let (_, prepared_at) = self.cached.get_mut(query).unwrap();
+
+            if matches!(self.ttl, Some(ttl) if prepared_at.elapsed() >= ttl) {
+                self.stats.expirations += 1;
+                exists = false;
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        sqlx_core::metrics::statement_cache_lookup(
+            <crate::Sqlite as sqlx_core::database::Database>::NAME,
+            exists,
+        );
+
+        if exists {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
 
-        if !exists {
             let statement = VirtualStatement::new(query, true)?;
-            self.cached.insert(query, statement);
+            let was_cached = self.cached.contains_key(query);
+
+            if self
+                .cached
+                .insert(query, (statement, Instant::now()))
+                .is_some()
+                && !was_cached
+            {
+                self.stats.evictions += 1;
+            }
         }
 
-        let statement = self.cached.get_mut(query).unwrap();
+        let (statement, _) = self.cached.get_mut(query).unwrap();
 
         if exists {
             // as this statement has been executed before, we reset before continuing
@@ -332,3 +683,63 @@ impl Statements {
         self.temp = None;
     }
 }
+
+/// Split a possibly schema-qualified and quoted table name, e.g. `main."my table"`, into the
+/// unquoted schema and table names.
+fn split_table_name(table: &str) -> (Option<String>, String) {
+    let mut parts = vec![String::new()];
+    let mut chars = table.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let close = match c {
+            '"' => '"',
+            '`' => '`',
+            '[' => ']',
+            '\'' => '\'',
+            '.' => {
+                parts.push(String::new());
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            c => {
+                parts.last_mut().expect("at least one part").push(c);
+                continue;
+            }
+        };
+
+        while let Some(c) = chars.next() {
+            // the closing quote is escaped by doubling it, except for brackets
+            if c == close {
+                if close != ']' && chars.peek() == Some(&close) {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            parts.last_mut().expect("at least one part").push(c);
+        }
+    }
+
+    let name = parts.pop().expect("at least one part");
+
+    (parts.pop(), name)
+}
+
+#[test]
+fn test_split_table_name() {
+    let split = |table| split_table_name(table);
+
+    assert_eq!(split("users"), (None, "users".to_owned()));
+    assert_eq!(
+        split("main.users"),
+        (Some("main".to_owned()), "users".to_owned())
+    );
+    assert_eq!(split("\"my table\""), (None, "my table".to_owned()));
+    assert_eq!(split("\"a\"\"b\""), (None, "a\"b".to_owned()));
+    assert_eq!(split("[a.b]"), (None, "a.b".to_owned()));
+    assert_eq!(
+        split(" \"aux\" . `t` "),
+        (Some("aux".to_owned()), "t".to_owned())
+    );
+}
@@ -0,0 +1,103 @@
+use crate::type_info::DataType;
+
+use DataType::{Int64, Text};
+
+// name, type, nullable
+type PragmaColumn = (&'static str, DataType, bool);
+
+// the result columns of the PRAGMAs that describe the schema, from
+// https://www.sqlite.org/pragma.html
+#[rustfmt::skip]
+const PRAGMAS: &[(&str, &[PragmaColumn])] = &[
+    ("collation_list", &[("seq", Int64, false), ("name", Text, false)]),
+    ("compile_options", &[("compile_options", Text, false)]),
+    ("database_list", &[("seq", Int64, false), ("name", Text, false), ("file", Text, false)]),
+    ("foreign_key_check", &[
+        ("table", Text, false), ("rowid", Int64, true), ("parent", Text, false),
+        ("fkid", Int64, false),
+    ]),
+    ("foreign_key_list", &[
+        ("id", Int64, false), ("seq", Int64, false), ("table", Text, false),
+        ("from", Text, false), ("to", Text, true), ("on_update", Text, false),
+        ("on_delete", Text, false), ("match", Text, false),
+    ]),
+    ("function_list", &[
+        ("name", Text, false), ("builtin", Int64, false), ("type", Text, false),
+        ("enc", Text, false), ("narg", Int64, false), ("flags", Int64, false),
+    ]),
+    ("index_info", &[("seqno", Int64, false), ("cid", Int64, false), ("name", Text, true)]),
+    ("index_list", &[
+        ("seq", Int64, false), ("name", Text, false), ("unique", Int64, false),
+        ("origin", Text, false), ("partial", Int64, false),
+    ]),
+    ("index_xinfo", &[
+        ("seqno", Int64, false), ("cid", Int64, false), ("name", Text, true),
+        ("desc", Int64, false), ("coll", Text, true), ("key", Int64, false),
+    ]),
+    ("module_list", &[("name", Text, false)]),
+    ("pragma_list", &[("name", Text, false)]),
+    ("table_info", &[
+        ("cid", Int64, false), ("name", Text, false), ("type", Text, false),
+        ("notnull", Int64, false), ("dflt_value", Text, true), ("pk", Int64, false),
+    ]),
+    ("table_list", &[
+        ("schema", Text, false), ("name", Text, false), ("type", Text, false),
+        ("ncol", Int64, false), ("wr", Int64, false), ("strict", Int64, false),
+    ]),
+    ("table_xinfo", &[
+        ("cid", Int64, false), ("name", Text, false), ("type", Text, false),
+        ("notnull", Int64, false), ("dflt_value", Text, true), ("pk", Int64, false),
+        ("hidden", Int64, false),
+    ]),
+];
+
+/// The type and nullability of the result column `column` of the PRAGMA `pragma`, if it is one
+/// of the PRAGMAs that describe the schema.
+pub(crate) fn pragma_column(pragma: &str, column: &str) -> Option<(DataType, bool)> {
+    let (_, columns) = PRAGMAS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(pragma))?;
+
+    columns
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(column))
+        .map(|(_, ty, nullable)| (*ty, *nullable))
+}
+
+/// The name of the PRAGMA that `sql` runs, e.g. `table_info` for `PRAGMA main.table_info(t)`.
+pub(crate) fn pragma_name(sql: &str) -> Option<&str> {
+    let sql = sql.trim_start();
+
+    if !sql.get(..6)?.eq_ignore_ascii_case("PRAGMA") {
+        return None;
+    }
+
+    let rest = &sql[6..];
+
+    if !rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(rest.len());
+
+    // without the schema, e.g. `main.`
+    rest[..end]
+        .rsplit('.')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+#[test]
+fn test_pragma_name() {
+    assert_eq!(pragma_name("PRAGMA table_info(t)"), Some("table_info"));
+    assert_eq!(
+        pragma_name("  pragma main.index_list('t');"),
+        Some("index_list")
+    );
+    assert_eq!(pragma_name("PRAGMA user_version = 3"), Some("user_version"));
+    assert_eq!(pragma_name("PRAGMAtable_info(t)"), None);
+    assert_eq!(pragma_name("SELECT * FROM pragma_table_info('t')"), None);
+}
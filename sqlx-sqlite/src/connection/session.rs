@@ -0,0 +1,293 @@
+//! Support for the [Session Extension](https://www.sqlite.org/sessionintro.html).
+//!
+//! A session records changes made to the tables of a database so they can be exported as a
+//! changeset and applied to another database, e.g. for offline-first synchronization.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::{self, NonNull};
+use std::slice;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use libsqlite3_sys as ffi;
+use sqlx_core::HashMap;
+
+use crate::connection::ConnectionHandle;
+use crate::error::Error;
+use crate::SqliteError;
+
+static SESSION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a session created with
+/// [`LockedSqliteHandle::create_session()`][crate::LockedSqliteHandle::create_session].
+///
+/// The session itself is owned by the connection that created it and is deleted when that
+/// connection is closed, or explicitly with
+/// [`LockedSqliteHandle::delete_session()`][crate::LockedSqliteHandle::delete_session].
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct SqliteSession {
+    id: u64,
+}
+
+/// The operation of a change in a changeset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteChangesetOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// The reason the conflict handler passed to
+/// [`LockedSqliteHandle::apply_changeset()`][crate::LockedSqliteHandle::apply_changeset]
+/// was invoked.
+///
+/// See [the SQLite manual](https://www.sqlite.org/session/c_changeset_conflict.html) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteConflictKind {
+    /// The row to be updated or deleted exists but does not contain the expected values.
+    Data,
+    /// The row to be updated or deleted does not exist.
+    NotFound,
+    /// The row to be inserted has a primary key which already exists.
+    Conflict,
+    /// Applying the change would violate a `UNIQUE`, `CHECK` or `NOT NULL` constraint.
+    Constraint,
+    /// Applying the changeset would leave foreign key violations behind.
+    ForeignKey,
+}
+
+/// How to resolve a conflict while applying a changeset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteConflictAction {
+    /// Skip the conflicting change.
+    Omit,
+    /// Replace the conflicting row with the change.
+    ///
+    /// Only valid for [`SqliteConflictKind::Data`] and [`SqliteConflictKind::Conflict`];
+    /// for any other conflict SQLite stops applying the changeset, rolls back the changes made
+    /// so far and fails with `SQLITE_MISUSE`.
+    Replace,
+    /// Abort applying the changeset and roll back all changes made so far.
+    Abort,
+}
+
+/// A conflict encountered while applying a changeset.
+#[derive(Debug, Clone)]
+pub struct SqliteChangesetConflict {
+    pub(crate) kind: SqliteConflictKind,
+    pub(crate) table: String,
+    pub(crate) op: SqliteChangesetOp,
+}
+
+impl SqliteChangesetConflict {
+    pub fn kind(&self) -> SqliteConflictKind {
+        self.kind
+    }
+
+    /// The name of the table the conflicting change applies to.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn op(&self) -> SqliteChangesetOp {
+        self.op
+    }
+}
+
+/// Owned `sqlite3_session` pointer.
+///
+/// These must be deleted before the database handle they were created on is closed.
+pub(crate) struct SessionHandle(NonNull<ffi::sqlite3_session>);
+
+// SAFETY: sessions are only accessed through the connection state, like the database handle.
+unsafe impl Send for SessionHandle {}
+
+impl Drop for SessionHandle {
+    fn drop(&mut self) {
+        // https://www.sqlite.org/session/sqlite3session_delete.html
+        unsafe { ffi::sqlite3session_delete(self.0.as_ptr()) }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Sessions {
+    handles: HashMap<u64, SessionHandle>,
+}
+
+impl Sessions {
+    pub(crate) fn create(
+        &mut self,
+        handle: &mut ConnectionHandle,
+        database: &str,
+    ) -> Result<SqliteSession, Error> {
+        let c_database = CString::new(database)
+            .map_err(|_| err_protocol!("invalid database name: {:?}", database))?;
+
+        let mut session = ptr::null_mut();
+
+        // https://www.sqlite.org/session/sqlite3session_create.html
+        let status = unsafe {
+            ffi::sqlite3session_create(handle.as_ptr(), c_database.as_ptr(), &mut session)
+        };
+
+        if status != ffi::SQLITE_OK {
+            return Err(SqliteError::new(handle.as_ptr()).into());
+        }
+
+        let session = SessionHandle(
+            NonNull::new(session).ok_or_else(|| err_protocol!("sqlite3session_create failed"))?,
+        );
+
+        // attach all tables of the database; passing NULL also picks up tables created later
+        // https://www.sqlite.org/session/sqlite3session_attach.html
+        let status = unsafe { ffi::sqlite3session_attach(session.0.as_ptr(), ptr::null()) };
+
+        if status != ffi::SQLITE_OK {
+            return Err(SqliteError::new(handle.as_ptr()).into());
+        }
+
+        let id = SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        self.handles.insert(id, session);
+
+        Ok(SqliteSession { id })
+    }
+
+    pub(crate) fn changeset(
+        &mut self,
+        handle: &mut ConnectionHandle,
+        session: &SqliteSession,
+    ) -> Result<Vec<u8>, Error> {
+        let session = self.get(session)?;
+
+        let mut len: c_int = 0;
+        let mut buf: *mut c_void = ptr::null_mut();
+
+        // https://www.sqlite.org/session/sqlite3session_changeset.html
+        let status =
+            unsafe { ffi::sqlite3session_changeset(session.0.as_ptr(), &mut len, &mut buf) };
+
+        if status != ffi::SQLITE_OK {
+            return Err(SqliteError::new(handle.as_ptr()).into());
+        }
+
+        if buf.is_null() {
+            return Ok(Vec::new());
+        }
+
+        // SAFETY: SQLite guarantees `buf` points to `len` bytes; we are responsible for freeing it
+        let changeset = unsafe {
+            let changeset = slice::from_raw_parts(buf as *const u8, len as usize).to_vec();
+            ffi::sqlite3_free(buf);
+            changeset
+        };
+
+        Ok(changeset)
+    }
+
+    pub(crate) fn delete(&mut self, session: SqliteSession) -> Result<(), Error> {
+        self.handles
+            .remove(&session.id)
+            .map(drop)
+            .ok_or_else(|| err_protocol!("session was not created on this connection"))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.handles.clear();
+    }
+
+    fn get(&self, session: &SqliteSession) -> Result<&SessionHandle, Error> {
+        self.handles
+            .get(&session.id)
+            .ok_or_else(|| err_protocol!("session was not created on this connection"))
+    }
+}
+
+pub(crate) fn apply_changeset<F>(
+    handle: &mut ConnectionHandle,
+    changeset: &[u8],
+    mut on_conflict: F,
+) -> Result<(), Error>
+where
+    F: FnMut(&SqliteChangesetConflict) -> SqliteConflictAction,
+{
+    let len = c_int::try_from(changeset.len())
+        .map_err(|_| err_protocol!("changeset must be smaller than {} bytes", c_int::MAX))?;
+
+    // https://www.sqlite.org/session/sqlite3changeset_apply.html
+    let status = unsafe {
+        ffi::sqlite3changeset_apply(
+            handle.as_ptr(),
+            len,
+            // SQLite does not modify the changeset, the pointer is only `*mut` for historical reasons
+            changeset.as_ptr() as *mut c_void,
+            None,
+            Some(conflict_callback::<F>),
+            &mut on_conflict as *mut F as *mut c_void,
+        )
+    };
+
+    if status != ffi::SQLITE_OK {
+        return Err(SqliteError::new(handle.as_ptr()).into());
+    }
+
+    Ok(())
+}
+
+unsafe extern "C" fn conflict_callback<F>(
+    ctx: *mut c_void,
+    conflict: c_int,
+    iter: *mut ffi::sqlite3_changeset_iter,
+) -> c_int
+where
+    F: FnMut(&SqliteChangesetConflict) -> SqliteConflictAction,
+{
+    let kind = match conflict {
+        ffi::SQLITE_CHANGESET_DATA => SqliteConflictKind::Data,
+        ffi::SQLITE_CHANGESET_NOTFOUND => SqliteConflictKind::NotFound,
+        ffi::SQLITE_CHANGESET_CONFLICT => SqliteConflictKind::Conflict,
+        ffi::SQLITE_CHANGESET_CONSTRAINT => SqliteConflictKind::Constraint,
+        ffi::SQLITE_CHANGESET_FOREIGN_KEY => SqliteConflictKind::ForeignKey,
+        _ => return ffi::SQLITE_CHANGESET_ABORT,
+    };
+
+    let mut table: *const c_char = ptr::null();
+    let mut num_columns: c_int = 0;
+    let mut op: c_int = 0;
+
+    // https://www.sqlite.org/session/sqlite3changeset_op.html
+    let status =
+        ffi::sqlite3changeset_op(iter, &mut table, &mut num_columns, &mut op, ptr::null_mut());
+
+    if status != ffi::SQLITE_OK {
+        return ffi::SQLITE_CHANGESET_ABORT;
+    }
+
+    let op = match op {
+        ffi::SQLITE_INSERT => SqliteChangesetOp::Insert,
+        ffi::SQLITE_UPDATE => SqliteChangesetOp::Update,
+        ffi::SQLITE_DELETE => SqliteChangesetOp::Delete,
+        _ => return ffi::SQLITE_CHANGESET_ABORT,
+    };
+
+    let table = if table.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(table).to_string_lossy().into_owned()
+    };
+
+    let conflict = SqliteChangesetConflict { kind, table, op };
+
+    // a panic must not unwind across the FFI boundary; abort applying the changeset instead
+    let action = catch_unwind(AssertUnwindSafe(|| {
+        let on_conflict = &mut *(ctx as *mut F);
+        on_conflict(&conflict)
+    }))
+    .unwrap_or(SqliteConflictAction::Abort);
+
+    match action {
+        SqliteConflictAction::Omit => ffi::SQLITE_CHANGESET_OMIT,
+        SqliteConflictAction::Replace => ffi::SQLITE_CHANGESET_REPLACE,
+        SqliteConflictAction::Abort => ffi::SQLITE_CHANGESET_ABORT,
+    }
+}
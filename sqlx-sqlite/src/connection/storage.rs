@@ -0,0 +1,55 @@
+use sqlx_core::error::Error;
+use sqlx_core::query_scalar::query_scalar;
+
+use crate::{SqliteAutoVacuum, SqliteCacheSize, SqliteConnection};
+
+/// The effective storage settings of an open connection, see
+/// [`SqliteConnection::storage_settings()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteStorageSettings {
+    /// The [page size](https://www.sqlite.org/pragma.html#pragma_page_size) in bytes.
+    pub page_size: u32,
+
+    /// The [page cache size](https://www.sqlite.org/pragma.html#pragma_cache_size).
+    pub cache_size: SqliteCacheSize,
+
+    /// The number of bytes accessed through
+    /// [memory-mapped I/O](https://www.sqlite.org/pragma.html#pragma_mmap_size).
+    pub mmap_size: u64,
+
+    /// The [auto-vacuum](https://www.sqlite.org/pragma.html#pragma_auto_vacuum) mode.
+    pub auto_vacuum: SqliteAutoVacuum,
+}
+
+impl SqliteConnection {
+    /// Query the storage settings currently in effect for the main database.
+    ///
+    /// These may differ from the ones set on [`SqliteConnectOptions`][crate::SqliteConnectOptions],
+    /// e.g. because `page_size` and `auto_vacuum` only take effect for new databases or because
+    /// SQLite caps `mmap_size` at its compile-time maximum.
+    pub async fn storage_settings(&mut self) -> Result<SqliteStorageSettings, Error> {
+        let page_size: i64 = query_scalar("PRAGMA page_size")
+            .fetch_one(&mut *self)
+            .await?;
+        let cache_size: i64 = query_scalar("PRAGMA cache_size")
+            .fetch_one(&mut *self)
+            .await?;
+        // yields no row if memory-mapped I/O is disabled at compile time
+        let mmap_size: Option<i64> = query_scalar("PRAGMA mmap_size")
+            .fetch_optional(&mut *self)
+            .await?;
+        let auto_vacuum: i64 = query_scalar("PRAGMA auto_vacuum")
+            .fetch_one(&mut *self)
+            .await?;
+
+        Ok(SqliteStorageSettings {
+            page_size: u32::try_from(page_size)
+                .map_err(|_| Error::Protocol(format!("invalid page_size {page_size}")))?,
+            cache_size: SqliteCacheSize::from_pragma_value(cache_size),
+            mmap_size: mmap_size.map_or(0, |size| size.unsigned_abs()),
+            auto_vacuum: SqliteAutoVacuum::from_pragma_value(auto_vacuum)
+                .ok_or_else(|| Error::Protocol(format!("invalid auto_vacuum {auto_vacuum}")))?,
+        })
+    }
+}
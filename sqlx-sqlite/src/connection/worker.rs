@@ -12,6 +12,7 @@ use sqlx_core::error::Error;
 use sqlx_core::transaction::{
     begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
 };
+use sqlx_core::types::NumericOverflow;
 use sqlx_core::Either;
 
 use crate::connection::describe::describe;
@@ -29,7 +30,7 @@ use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStateme
 pub(crate) struct ConnectionWorker {
     command_tx: flume::Sender<Command>,
     /// The `sqlite3` pointer. NOTE: access is unsynchronized!
-    pub(crate) _handle_raw: ConnectionHandleRaw,
+    pub(crate) handle_raw: ConnectionHandleRaw,
     /// Mutex for locking access to the database.
     pub(crate) shared: Arc<WorkerSharedState>,
 }
@@ -52,6 +53,7 @@ enum Command {
         query: Box<str>,
         arguments: Option<SqliteArguments<'static>>,
         persistent: bool,
+        numeric_overflow: Option<NumericOverflow>,
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
     },
     Begin {
@@ -68,7 +70,7 @@ enum Command {
         tx: oneshot::Sender<()>,
     },
     Ping {
-        tx: oneshot::Sender<()>,
+        tx: oneshot::Sender<bool>,
     },
     Shutdown {
         tx: oneshot::Sender<()>,
@@ -92,6 +94,9 @@ impl ConnectionWorker {
                     }
                 };
 
+                // not needed anymore, and holds e.g. the anchor of a shared in-memory database
+                drop(params);
+
                 let shared = Arc::new(WorkerSharedState {
                     cached_statements_size: AtomicUsize::new(0),
                     // note: must be fair because in `Command::UnlockDb` we unlock the mutex
@@ -104,7 +109,7 @@ impl ConnectionWorker {
                 if establish_tx
                     .send(Ok(Self {
                         command_tx,
-                        _handle_raw: conn.handle.to_raw(),
+                        handle_raw: conn.handle.to_raw(),
                         shared: Arc::clone(&shared),
                     }))
                     .is_err()
@@ -136,9 +141,10 @@ impl ConnectionWorker {
                             query,
                             arguments,
                             persistent,
+                            numeric_overflow,
                             tx,
                         } => {
-                            let iter = match execute::iter(&mut conn, &query, arguments, persistent)
+                            let mut iter = match execute::iter(&mut conn, &query, arguments, persistent)
                             {
                                 Ok(iter) => iter,
                                 Err(e) => {
@@ -147,6 +153,10 @@ impl ConnectionWorker {
                                 }
                             };
 
+                            if let Some(policy) = numeric_overflow {
+                                iter.set_numeric_overflow(policy);
+                            }
+
                             for res in iter {
                                 if tx.send(res).is_err() {
                                     break;
@@ -157,9 +167,14 @@ impl ConnectionWorker {
                         }
                         Command::Begin { tx } => {
                             let depth = conn.transaction_depth;
+                            let mut sql = begin_ansi_transaction_sql(depth);
+                            if depth == 0 && conn.defer_foreign_keys {
+                                // reset by SQLite at the end of each transaction
+                                sql.to_mut().push_str("; PRAGMA defer_foreign_keys = ON");
+                            }
                             let res =
                                 conn.handle
-                                    .exec(begin_ansi_transaction_sql(depth))
+                                    .exec(sql)
                                     .map(|_| {
                                         conn.transaction_depth += 1;
                                     });
@@ -246,7 +261,7 @@ impl ConnectionWorker {
                             conn = futures_executor::block_on(shared.conn.lock());
                         }
                         Command::Ping { tx } => {
-                            tx.send(()).ok();
+                            tx.send(conn.handle.has_moved()).ok();
                         }
                         Command::Shutdown { tx } => {
                             // drop the connection references before sending confirmation
@@ -285,6 +300,7 @@ impl ConnectionWorker {
         args: Option<SqliteArguments<'_>>,
         chan_size: usize,
         persistent: bool,
+        numeric_overflow: Option<NumericOverflow>,
     ) -> Result<flume::Receiver<Result<Either<SqliteQueryResult, SqliteRow>, Error>>, Error> {
         let (tx, rx) = flume::bounded(chan_size);
 
@@ -293,6 +309,7 @@ impl ConnectionWorker {
                 query: query.into(),
                 arguments: args.map(SqliteArguments::into_static),
                 persistent,
+                numeric_overflow,
                 tx,
             })
             .await
@@ -322,7 +339,8 @@ impl ConnectionWorker {
             .map_err(|_| Error::WorkerCrashed)
     }
 
-    pub(crate) async fn ping(&mut self) -> Result<(), Error> {
+    /// Returns whether the database file was deleted or renamed since it was opened.
+    pub(crate) async fn ping(&mut self) -> Result<bool, Error> {
         self.oneshot_cmd(|tx| Command::Ping { tx }).await
     }
 
@@ -398,9 +416,13 @@ fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'s
     let mut parameters = 0;
     let mut columns = None;
     let mut column_names = None;
+    let mut read_only = true;
+    let mut controls_transaction = false;
 
     while let Some(statement) = statement.prepare_next(&mut conn.handle)? {
         parameters += statement.handle.bind_parameter_count();
+        read_only &= statement.handle.read_only();
+        controls_transaction |= statement.handle.controls_transaction();
 
         // the first non-empty statement is chosen as the statement we pull columns from
         if !statement.columns.is_empty() && columns.is_none() {
@@ -414,6 +436,8 @@ fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'s
         columns: columns.unwrap_or_default(),
         column_names: column_names.unwrap_or_default(),
         parameters,
+        read_only,
+        controls_transaction,
     })
 }
 
@@ -1,5 +1,5 @@
 pub(crate) use sqlx_core::database::{
-    Database, HasArguments, HasStatement, HasStatementCache, HasValueRef,
+    Database, DatabaseCapabilities, HasArguments, HasStatement, HasStatementCache, HasValueRef,
 };
 
 use crate::{
@@ -30,6 +30,20 @@ impl Database for Sqlite {
     const NAME: &'static str = "SQLite";
 
     const URL_SCHEMES: &'static [&'static str] = &["sqlite"];
+
+    // `SQLITE_MAX_VARIABLE_NUMBER` of the bundled SQLite; 999 before 3.32.0
+    const MAX_BIND_PARAMETERS: usize = 32766;
+
+    fn capabilities() -> DatabaseCapabilities {
+        // `RETURNING` was added in 3.35.0, which matters when linking against the system SQLite
+        let returning = unsafe { libsqlite3_sys::sqlite3_libversion_number() } >= 3_035_000;
+
+        DatabaseCapabilities::new()
+            .returning(returning)
+            .multiple_statements(true)
+            .multiple_statements_with_arguments(true)
+            .max_bind_parameters(Self::MAX_BIND_PARAMETERS)
+    }
 }
 
 impl<'r> HasValueRef<'r> for Sqlite {
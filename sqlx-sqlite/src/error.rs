@@ -7,7 +7,7 @@ use std::{borrow::Cow, str::from_utf8_unchecked};
 use libsqlite3_sys::{
     sqlite3, sqlite3_errmsg, sqlite3_extended_errcode, SQLITE_CONSTRAINT_CHECK,
     SQLITE_CONSTRAINT_FOREIGNKEY, SQLITE_CONSTRAINT_NOTNULL, SQLITE_CONSTRAINT_PRIMARYKEY,
-    SQLITE_CONSTRAINT_UNIQUE,
+    SQLITE_CONSTRAINT_UNIQUE, SQLITE_SCHEMA,
 };
 
 pub(crate) use sqlx_core::error::*;
@@ -40,6 +40,19 @@ impl SqliteError {
         }
     }
 
+    /// An error that SQLite reported in a result row rather than with a result code.
+    pub(crate) fn from_code(code: c_int, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Whether a statement failed because the schema changed and SQLite could not recompile it.
+    pub(crate) fn is_schema_changed(&self) -> bool {
+        self.code & 0xff == SQLITE_SCHEMA
+    }
+
     /// For errors during extension load, the error message is supplied via a separate pointer
     pub(crate) fn extension(handle: *mut sqlite3, error_msg: &CStr) -> Self {
         let mut err = Self::new(handle);
@@ -87,6 +100,15 @@ impl DatabaseError for SqliteError {
         self
     }
 
+    fn constraint_violation(&self) -> Option<ConstraintViolation> {
+        match self.kind() {
+            ErrorKind::UniqueViolation => Some(parse_unique_violation(&self.message)),
+            // SQLite does not report which foreign key is violated
+            ErrorKind::ForeignKeyViolation => Some(ConstraintViolation::default()),
+            _ => None,
+        }
+    }
+
     fn kind(&self) -> ErrorKind {
         match self.code {
             SQLITE_CONSTRAINT_UNIQUE | SQLITE_CONSTRAINT_PRIMARYKEY => ErrorKind::UniqueViolation,
@@ -97,3 +119,35 @@ impl DatabaseError for SqliteError {
         }
     }
 }
+
+// Parses the message of unique violations, which is one of:
+//
+// UNIQUE constraint failed: table.a, table.b
+// UNIQUE constraint failed: index 'name'
+fn parse_unique_violation(message: &str) -> ConstraintViolation {
+    let mut violation = ConstraintViolation::default();
+
+    let Some(key) = message.strip_prefix("UNIQUE constraint failed: ") else {
+        return violation;
+    };
+
+    // unique indexes on expressions are reported by name
+    if let Some(index) = key
+        .strip_prefix("index '")
+        .and_then(|key| key.strip_suffix('\''))
+    {
+        violation.constraint = Some(index.to_owned());
+        return violation;
+    }
+
+    for column in key.split(", ") {
+        let Some((table, column)) = column.split_once('.') else {
+            continue;
+        };
+
+        violation.table = Some(table.to_owned());
+        violation.columns.push(column.to_owned());
+    }
+
+    violation
+}
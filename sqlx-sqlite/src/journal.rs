@@ -0,0 +1,405 @@
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sqlx_core::error::Error;
+use sqlx_core::executor::{Execute, Executor};
+
+use crate::{Sqlite, SqliteArgumentValue, SqliteArguments, SqliteConnection, SqliteQueryResult};
+
+const MAGIC: &[u8; 8] = b"SQLXJRN1";
+
+type Redact = dyn Fn(&str, usize, &SqliteArgumentValue<'_>) -> Option<SqliteArgumentValue<'static>>
+    + Send
+    + Sync;
+
+/// A connection that records every successful write, with its arguments, to an append-only
+/// journal file.
+///
+/// The journal can be read back with [`SqliteJournalEntry::read_all()`], e.g. for an audit, or
+/// replayed onto another database with [`SqliteConnection::replay_journal()`] to rebuild it:
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::{SqliteConnection, SqliteJournaledConnection};
+///
+/// let conn = SqliteConnection::connect("sqlite://data.db").await?;
+/// let mut conn = SqliteJournaledConnection::open(conn, "data.journal")?;
+///
+/// conn.execute(sqlx::query("INSERT INTO users (name) VALUES (?)").bind("alice")).await?;
+///
+/// // later, e.g. on a replica
+/// let mut replica = SqliteConnection::connect("sqlite://replica.db").await?;
+/// replica.replay_journal("data.journal").await?;
+/// ```
+///
+/// Statements that SQLite reports as read-only are not recorded, except for transaction control
+/// statements, e.g. `BEGIN` and `ROLLBACK`, and `ATTACH`, so that a replay has the same effect.
+/// Statements run directly on [`connection()`](Self::connection) are not recorded.
+///
+/// Each entry is synced to disk before [`execute()`](Self::execute) returns. A final entry torn
+/// by a crash is ignored when the journal is read, and overwritten when it is opened again.
+pub struct SqliteJournaledConnection {
+    conn: SqliteConnection,
+    journal: BufWriter<File>,
+    redact: Option<Arc<Redact>>,
+    // the number of entries in the journal
+    entries: u64,
+}
+
+impl SqliteJournaledConnection {
+    /// Record the writes on `conn` to the journal at `path`, which is created if it is missing
+    /// and appended to otherwise.
+    pub fn open(conn: SqliteConnection, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut entries = 0;
+        let len = file.metadata()?.len();
+
+        if len == 0 {
+            file.write_all(MAGIC)?;
+        } else {
+            let mut reader = JournalReader::new(BufReader::new(&mut file), len)?;
+            let mut end = reader.position();
+
+            while reader.read_entry()?.is_some() {
+                entries += 1;
+                end = reader.position();
+            }
+
+            // drop a torn final entry, so that the next one is appended after the last complete
+            // entry
+            if end < len {
+                file.set_len(end)?;
+            }
+        }
+
+        Ok(Self {
+            conn,
+            journal: BufWriter::new(file),
+            redact: None,
+            entries,
+        })
+    }
+
+    /// Replace argument values before they are recorded.
+    ///
+    /// `redact` is called with the SQL, the index and the value of each argument, and returns
+    /// the value to record instead, or `None` to record it as is. Arguments bound with
+    /// [`bind_sensitive()`](sqlx_core::query::Query::bind_sensitive) are always recorded as
+    /// `NULL`. A replay uses the recorded values, so it does not restore redacted data.
+    pub fn redact<F>(mut self, redact: F) -> Self
+    where
+        F: Fn(&str, usize, &SqliteArgumentValue<'_>) -> Option<SqliteArgumentValue<'static>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.redact = Some(Arc::new(redact));
+        self
+    }
+
+    /// Execute `query` and record it in the journal if it succeeds.
+    pub async fn execute<'q, E>(&mut self, mut query: E) -> Result<SqliteQueryResult, Error>
+    where
+        E: 'q + Execute<'q, Sqlite>,
+    {
+        let sql = query.sql();
+        let arguments = query.take_arguments();
+
+        // statements that depend on earlier ones of the same query may fail to prepare, these
+        // are recorded
+        let read_only = match self.conn.prepare(sql).await {
+            Ok(statement) => statement.read_only && !statement.controls_transaction,
+            Err(_) => false,
+        };
+
+        let result = match &arguments {
+            Some(arguments) => {
+                self.conn
+                    .execute(sqlx_core::query::query_with(sql, arguments.clone()))
+                    .await?
+            }
+            None => self.conn.execute(sql).await?,
+        };
+
+        if !read_only {
+            let values = arguments
+                .as_ref()
+                .map_or(&[][..], |arguments| arguments.values());
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let mut entry = Vec::new();
+
+            entry.extend_from_slice(&(timestamp.as_micros() as u64).to_le_bytes());
+            write_bytes(&mut entry, sql.as_bytes());
+            entry.extend_from_slice(&(values.len() as u32).to_le_bytes());
+
+            for (i, value) in values.iter().enumerate() {
+                let sensitive = arguments
+                    .as_ref()
+                    .is_some_and(|arguments| arguments.sensitive.contains(&i));
+
+                let redacted = match &self.redact {
+                    _ if sensitive => Some(SqliteArgumentValue::Null),
+                    Some(redact) => redact(sql, i, value),
+                    None => None,
+                };
+
+                write_value(&mut entry, redacted.as_ref().unwrap_or(value));
+            }
+
+            self.journal.write_all(&entry)?;
+            self.journal.flush()?;
+            self.journal.get_ref().sync_data()?;
+            self.entries += 1;
+        }
+
+        Ok(result)
+    }
+
+    /// The connection, to run statements that are not recorded, e.g. queries.
+    pub fn connection(&mut self) -> &mut SqliteConnection {
+        &mut self.conn
+    }
+
+    pub(crate) fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// Stop recording and return the connection.
+    pub fn into_inner(self) -> SqliteConnection {
+        self.conn
+    }
+}
+
+impl Debug for SqliteJournaledConnection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteJournaledConnection")
+            .field("conn", &self.conn)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A statement recorded by a [`SqliteJournaledConnection`].
+#[derive(Debug, Clone)]
+pub struct SqliteJournalEntry {
+    timestamp: SystemTime,
+    sql: String,
+    arguments: Vec<SqliteArgumentValue<'static>>,
+}
+
+impl SqliteJournalEntry {
+    /// Read the entries of the journal at `path`, in the order they were recorded.
+    pub fn read_all(path: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        let mut reader = JournalReader::new(BufReader::new(file), len)?;
+        let mut entries = Vec::new();
+
+        while let Some(entry) = reader.read_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// When the statement was run.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The SQL of the statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The recorded arguments of the statement, in the order of their placeholders.
+    pub fn arguments(&self) -> &[SqliteArgumentValue<'static>] {
+        &self.arguments
+    }
+}
+
+impl SqliteConnection {
+    /// Run the statements recorded in the journal at `path`, see [`SqliteJournaledConnection`].
+    ///
+    /// Returns the number of statements run. Stops at the first failing statement.
+    pub async fn replay_journal(&mut self, path: impl AsRef<Path>) -> Result<u64, Error> {
+        let entries = SqliteJournalEntry::read_all(path)?;
+
+        for entry in &entries {
+            entry.replay(self).await?;
+        }
+
+        Ok(entries.len() as u64)
+    }
+}
+
+impl SqliteJournalEntry {
+    pub(crate) async fn replay(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
+        if self.arguments.is_empty() {
+            conn.execute(&*self.sql).await?;
+        } else {
+            let arguments = SqliteArguments {
+                values: self.arguments.clone(),
+                ..Default::default()
+            };
+
+            conn.execute(sqlx_core::query::query_with(&self.sql, arguments))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn write_value(buf: &mut Vec<u8>, value: &SqliteArgumentValue<'_>) {
+    match value {
+        SqliteArgumentValue::Null => buf.push(0),
+        SqliteArgumentValue::Text(text) => {
+            buf.push(1);
+            write_bytes(buf, text.as_bytes());
+        }
+        SqliteArgumentValue::Blob(blob) => {
+            buf.push(2);
+            write_bytes(buf, blob);
+        }
+        SqliteArgumentValue::Double(value) => {
+            buf.push(3);
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+        SqliteArgumentValue::Int(value) => {
+            buf.push(4);
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+        SqliteArgumentValue::Int64(value) => {
+            buf.push(5);
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+// reads a journal, checking the lengths it contains against the bytes left in the file before
+// allocating for them, so that a corrupt journal cannot make it allocate more memory than the
+// file takes
+struct JournalReader<R> {
+    reader: R,
+    len: u64,
+    remaining: u64,
+}
+
+impl<R: Read> JournalReader<R> {
+    // `len` is the length of the journal
+    fn new(reader: R, len: u64) -> Result<Self, Error> {
+        let mut reader = Self {
+            reader,
+            len,
+            remaining: len,
+        };
+
+        if reader.read_array().ok().as_ref() != Some(MAGIC) {
+            return Err(Error::Protocol("not a SQLx journal file".into()));
+        }
+
+        Ok(reader)
+    }
+
+    // the offset of the next entry
+    fn position(&self) -> u64 {
+        self.len - self.remaining
+    }
+
+    fn read_entry(&mut self) -> Result<Option<SqliteJournalEntry>, Error> {
+        match self.read_complete_entry() {
+            // the end of the journal, or a final entry torn by a crash
+            Err(Error::Io(e)) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    fn read_complete_entry(&mut self) -> Result<SqliteJournalEntry, Error> {
+        let timestamp = UNIX_EPOCH + Duration::from_micros(u64::from_le_bytes(self.read_array()?));
+
+        let sql = String::from_utf8(self.read_bytes()?)
+            .map_err(|_| Error::Protocol("journal entry contains invalid UTF-8".into()))?;
+
+        // every argument takes at least the byte of its tag
+        let count = self.read_len()?;
+        let mut arguments = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let [tag] = self.read_array()?;
+
+            arguments.push(match tag {
+                0 => SqliteArgumentValue::Null,
+                1 => SqliteArgumentValue::Text(
+                    String::from_utf8(self.read_bytes()?)
+                        .map_err(|_| {
+                            Error::Protocol("journal entry contains invalid UTF-8".into())
+                        })?
+                        .into(),
+                ),
+                2 => SqliteArgumentValue::Blob(self.read_bytes()?.into()),
+                3 => SqliteArgumentValue::Double(f64::from_le_bytes(self.read_array()?)),
+                4 => SqliteArgumentValue::Int(i32::from_le_bytes(self.read_array()?)),
+                5 => SqliteArgumentValue::Int64(i64::from_le_bytes(self.read_array()?)),
+                tag => {
+                    return Err(Error::Protocol(format!(
+                        "unknown argument type {tag} in journal entry"
+                    )))
+                }
+            });
+        }
+
+        Ok(SqliteJournalEntry {
+            timestamp,
+            sql,
+            arguments,
+        })
+    }
+
+    fn read_bytes(&mut self) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; self.read_len()?];
+        self.read_exact(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    // a length, which is torn if it runs past the end of the journal
+    fn read_len(&mut self) -> io::Result<usize> {
+        let len = u32::from_le_bytes(self.read_array()?);
+
+        if u64::from(len) > self.remaining {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(len as usize)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut array = [0; N];
+        self.read_exact(&mut array)?;
+
+        Ok(array)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.reader.read_exact(buf)?;
+        self.remaining = self.remaining.saturating_sub(buf.len() as u64);
+
+        Ok(())
+    }
+}
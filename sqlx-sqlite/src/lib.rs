@@ -33,18 +33,36 @@ use std::sync::atomic::AtomicBool;
 
 pub use arguments::{SqliteArgumentValue, SqliteArguments};
 pub use column::SqliteColumn;
-pub use connection::{LockedSqliteHandle, SqliteConnection};
+#[cfg(feature = "session")]
+pub use connection::session::{
+    SqliteChangesetConflict, SqliteChangesetOp, SqliteConflictAction, SqliteConflictKind,
+    SqliteSession,
+};
+pub use connection::{
+    LockedSqliteHandle, SqliteCancellationToken, SqliteConnection, SqliteForeignKeyViolation,
+    SqliteStatementCacheStats, SqliteStorageSettings,
+};
 pub use database::Sqlite;
 pub use error::SqliteError;
+pub use journal::{SqliteJournalEntry, SqliteJournaledConnection};
+pub use maintenance::{SqliteMaintenanceHandle, SqliteMaintenancePlan};
 pub use options::{
-    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqliteLockingMode, SqliteSynchronous,
+    SqliteAutoVacuum, SqliteCacheSize, SqliteCoercions, SqliteConnectOptions, SqliteForeignKeys,
+    SqliteJournalMode, SqliteLockingMode, SqliteNetworkFilesystem, SqliteOpenMode, SqliteProfile,
+    SqliteSynchronous,
 };
-pub use query_result::SqliteQueryResult;
+pub use query_plan::{SqliteQueryPlan, SqliteQueryPlanNode};
+pub use query_result::{SqliteQueryResult, SqliteQueryStats};
+pub use read_pool::SqliteReadPool;
 pub use row::SqliteRow;
+pub use serialized_writer::{SqliteSerializedWriter, SqliteWriterGuard};
 pub use statement::SqliteStatement;
+pub use tenant_manager::SqliteTenantManager;
 pub use transaction::SqliteTransactionManager;
 pub use type_info::SqliteTypeInfo;
+pub use type_registry::SqliteTypeRegistry;
 pub use value::{SqliteValue, SqliteValueRef};
+pub use vfs::{register_vfs, SqliteVfs, SqliteVfsFile, SqliteVfsFileKind, SqliteVfsOpen};
 
 use crate::connection::establish::EstablishParams;
 
@@ -59,15 +77,24 @@ mod column;
 mod connection;
 mod database;
 mod error;
+mod journal;
 mod logger;
+mod maintenance;
 mod options;
+mod query_plan;
 mod query_result;
+mod read_pool;
+pub mod recovery;
 mod row;
+mod serialized_writer;
 mod statement;
+mod tenant_manager;
 mod transaction;
 mod type_info;
+mod type_registry;
 pub mod types;
 mod value;
+mod vfs;
 
 #[cfg(feature = "any")]
 pub mod any;
@@ -118,3 +145,16 @@ pub fn describe_blocking(query: &str, database_url: &str) -> Result<Describe<Sql
 
     // SQLite database is closed immediately when `conn` is dropped
 }
+
+/// UNSTABLE: for use by `sqlite-macros-core` only.
+#[doc(hidden)]
+pub fn describe_blocking_with_schema(query: &str, schema: &str) -> Result<Describe<Sqlite>, Error> {
+    let opts: SqliteConnectOptions = "sqlite::memory:".parse()?;
+    let params = EstablishParams::from_options(&opts)?;
+    let mut conn = params.establish()?;
+
+    connection::execute::iter(&mut conn, &opts.pragma_string(), None, false)?.finish()?;
+    connection::execute::iter(&mut conn, schema, None, false)?.finish()?;
+
+    connection::describe::describe(&mut conn, query)
+}
@@ -0,0 +1,180 @@
+use std::fmt::Write;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use futures_executor::block_on;
+
+use sqlx_core::connection::Connection;
+use sqlx_core::error::Error;
+use sqlx_core::executor::Executor;
+
+use crate::SqliteConnection;
+
+impl SqliteConnection {
+    /// Run [`PRAGMA optimize`](https://www.sqlite.org/pragma.html#pragma_optimize), which
+    /// updates the statistics of the query planner where they are likely out of date.
+    ///
+    /// SQLite recommends running it every few hours on long-lived connections, e.g. with a
+    /// [`SqliteMaintenancePlan`], and when closing short-lived ones, see
+    /// [`SqliteConnectOptions::optimize_on_close()`](crate::SqliteConnectOptions::optimize_on_close).
+    pub async fn optimize(&mut self) -> Result<(), Error> {
+        self.execute("PRAGMA optimize").await?;
+        Ok(())
+    }
+}
+
+/// Database maintenance to run on a connection at an interval.
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use sqlx::sqlite::{SqliteConnectOptions, SqliteMaintenancePlan};
+///
+/// let conn = SqliteConnectOptions::new().filename("data.db").connect().await?;
+///
+/// let maintenance = SqliteMaintenancePlan::new(Duration::from_secs(3600))
+///     .incremental_vacuum(true, 1000)
+///     .wal_checkpoint(true)
+///     .spawn(conn);
+///
+/// // ...
+///
+/// maintenance.stop();
+/// ```
+///
+/// By default, only [`optimize()`](SqliteConnection::optimize) is run.
+#[derive(Debug, Clone)]
+pub struct SqliteMaintenancePlan {
+    interval: Duration,
+    optimize: bool,
+    incremental_vacuum: Option<Option<u32>>,
+    wal_checkpoint: bool,
+}
+
+impl SqliteMaintenancePlan {
+    /// Run the maintenance every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            optimize: true,
+            incremental_vacuum: None,
+            wal_checkpoint: false,
+        }
+    }
+
+    /// Sets whether to run [`PRAGMA optimize`](SqliteConnection::optimize).
+    ///
+    /// The default is `true`.
+    pub fn optimize(mut self, enabled: bool) -> Self {
+        self.optimize = enabled;
+        self
+    }
+
+    /// Sets whether to run
+    /// [`PRAGMA incremental_vacuum`](https://www.sqlite.org/pragma.html#pragma_incremental_vacuum),
+    /// which returns up to `max_pages` free pages, or all of them, to the file system.
+    ///
+    /// Does nothing unless [`auto_vacuum`](crate::SqliteConnectOptions::auto_vacuum) is
+    /// [`Incremental`](crate::SqliteAutoVacuum::Incremental). The default is `false`.
+    pub fn incremental_vacuum(mut self, enabled: bool, max_pages: impl Into<Option<u32>>) -> Self {
+        self.incremental_vacuum = enabled.then(|| max_pages.into());
+        self
+    }
+
+    /// Sets whether to run
+    /// [`PRAGMA wal_checkpoint(PASSIVE)`](https://www.sqlite.org/pragma.html#pragma_wal_checkpoint),
+    /// which copies as much of the write-ahead log into the database as possible without waiting
+    /// for readers or writers.
+    ///
+    /// Does nothing unless the database is in WAL mode. The default is `false`.
+    pub fn wal_checkpoint(mut self, enabled: bool) -> Self {
+        self.wal_checkpoint = enabled;
+        self
+    }
+
+    /// Run the maintenance once on `conn`.
+    pub async fn run(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
+        let mut pragma_string = String::new();
+
+        if self.optimize {
+            pragma_string.push_str("PRAGMA optimize; ");
+        }
+
+        match self.incremental_vacuum {
+            Some(Some(max_pages)) => {
+                write!(pragma_string, "PRAGMA incremental_vacuum({max_pages}); ").ok();
+            }
+            Some(None) => pragma_string.push_str("PRAGMA incremental_vacuum; "),
+            None => {}
+        }
+
+        if self.wal_checkpoint {
+            pragma_string.push_str("PRAGMA wal_checkpoint(PASSIVE); ");
+        }
+
+        if !pragma_string.is_empty() {
+            conn.execute(&*pragma_string).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Move `conn` to a background thread that runs the maintenance on it every
+    /// [`interval`](Self::new), starting one interval from now.
+    ///
+    /// The thread needs no async runtime. Failed runs are logged and retried at the next
+    /// interval. The connection is closed when the returned handle is stopped or dropped.
+    pub fn spawn(self, mut conn: SqliteConnection) -> SqliteMaintenanceHandle {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+        let thread = thread::Builder::new()
+            .name("sqlx-sqlite-maintenance".into())
+            .spawn(move || {
+                // returns `Ok` or `Disconnected` once stopped
+                while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(self.interval) {
+                    if let Err(error) = block_on(self.run(&mut conn)) {
+                        tracing::warn!(%error, "SQLite maintenance failed");
+                    }
+                }
+
+                if let Err(error) = block_on(conn.close()) {
+                    tracing::warn!(%error, "failed to close the SQLite maintenance connection");
+                }
+            })
+            .expect("failed to spawn the SQLite maintenance thread");
+
+        SqliteMaintenanceHandle {
+            stop_tx,
+            thread: Some(thread),
+        }
+    }
+}
+
+/// The background thread of a [`SqliteMaintenancePlan`], see
+/// [`spawn()`](SqliteMaintenancePlan::spawn).
+///
+/// Dropping the handle stops the thread without waiting for it.
+#[derive(Debug)]
+pub struct SqliteMaintenanceHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SqliteMaintenanceHandle {
+    /// Stop the thread and wait until it closed the connection.
+    ///
+    /// This blocks the current thread while a maintenance run finishes.
+    pub fn stop(mut self) {
+        self.stop_tx.send(()).ok();
+
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+impl Drop for SqliteMaintenanceHandle {
+    fn drop(&mut self) {
+        self.stop_tx.send(()).ok();
+    }
+}
@@ -67,8 +67,21 @@ impl MigrateDatabase for Sqlite {
 impl Migrate for SqliteConnection {
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
         Box::pin(async move {
-            // language=SQLite
-            self.execute(
+            let sql = if self.strict {
+                // `STRICT` tables only allow the fundamental datatypes
+                // language=SQLite
+                r#"
+CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+    version INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success INTEGER NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time INTEGER NOT NULL
+) STRICT;
+                "#
+            } else {
+                // language=SQLite
                 r#"
 CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     version BIGINT PRIMARY KEY,
@@ -78,9 +91,10 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     checksum BLOB NOT NULL,
     execution_time BIGINT NOT NULL
 );
-                "#,
-            )
-            .await?;
+                "#
+            };
+
+            self.execute(sql).await?;
 
             Ok(())
         })
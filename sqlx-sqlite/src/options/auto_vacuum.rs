@@ -16,6 +16,15 @@ impl SqliteAutoVacuum {
             SqliteAutoVacuum::Incremental => "INCREMENTAL",
         }
     }
+
+    pub(crate) fn from_pragma_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(SqliteAutoVacuum::None),
+            1 => Some(SqliteAutoVacuum::Full),
+            2 => Some(SqliteAutoVacuum::Incremental),
+            _ => None,
+        }
+    }
 }
 
 impl Default for SqliteAutoVacuum {
@@ -0,0 +1,38 @@
+/// Refer to [SQLite documentation] for the meaning of the page cache size.
+///
+/// [SQLite documentation]: https://www.sqlite.org/pragma.html#pragma_cache_size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteCacheSize {
+    /// A number of database pages, independent of the page size.
+    Pages(u32),
+
+    /// An amount of memory in KiB, i.e. units of 1024 bytes.
+    Kibibytes(u32),
+}
+
+impl SqliteCacheSize {
+    pub(crate) fn to_pragma_value(self) -> String {
+        // negative values denote KiB
+        match self {
+            SqliteCacheSize::Pages(pages) => pages.to_string(),
+            SqliteCacheSize::Kibibytes(kib) => format!("-{kib}"),
+        }
+    }
+
+    pub(crate) fn from_pragma_value(value: i64) -> Self {
+        let size = |value: i64| u32::try_from(value.unsigned_abs()).unwrap_or(u32::MAX);
+
+        if value < 0 {
+            SqliteCacheSize::Kibibytes(size(value))
+        } else {
+            SqliteCacheSize::Pages(size(value))
+        }
+    }
+}
+
+impl Default for SqliteCacheSize {
+    fn default() -> Self {
+        // https://www.sqlite.org/compile.html#default_cache_size
+        SqliteCacheSize::Kibibytes(2000)
+    }
+}
@@ -0,0 +1,78 @@
+/// Which implicit conversions are allowed when decoding a value whose storage class does not
+/// match the requested Rust type.
+///
+/// SQLite is dynamically typed: a column declared as `BOOLEAN` or `INTEGER` may well contain
+/// `'true'` or `'42'`, especially in databases written by other, loosely-typed applications.
+/// By default such values fail to decode with a mismatched types error. Each flag below opts into
+/// one family of conversions instead:
+///
+/// | Flag | Storage class | Rust types |
+/// | -- | -- | -- |
+/// | [`text_to_bool`][Self::text_to_bool] | `TEXT` | `bool` |
+/// | [`text_to_number`][Self::text_to_number] | `TEXT` | `i8`-`i64`, `u8`-`u32`, `f32`, `f64` |
+/// | [`number_to_text`][Self::number_to_text] | `INTEGER`, `REAL` | `String`, `&str`, `Box<str>`, `Cow<str>` |
+///
+/// Conversions are independent of the process locale: numbers are always parsed and formatted
+/// with a `.` as the decimal separator and without grouping.
+///
+/// ```rust,no_run
+/// # use sqlx_core::error::Error;
+/// use sqlx_sqlite::{SqliteCoercions, SqliteConnectOptions};
+/// use std::str::FromStr;
+///
+/// # fn options() -> Result<SqliteConnectOptions, Error> {
+/// let options = SqliteConnectOptions::from_str("sqlite://legacy.db")?
+///     .coercions(SqliteCoercions::new().text_to_bool(true));
+/// # Ok(options)
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteCoercions {
+    pub(crate) text_to_bool: bool,
+    pub(crate) text_to_number: bool,
+    pub(crate) number_to_text: bool,
+}
+
+impl SqliteCoercions {
+    /// No implicit conversions. This is the default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All implicit conversions.
+    pub fn lenient() -> Self {
+        Self {
+            text_to_bool: true,
+            text_to_number: true,
+            number_to_text: true,
+        }
+    }
+
+    /// Decode `TEXT` into `bool`.
+    ///
+    /// `'1'`, `'true'`, `'t'`, `'yes'`, `'y'` and `'on'` are `true`; `'0'`, `'false'`, `'f'`,
+    /// `'no'`, `'n'` and `'off'` are `false`. Comparison ignores ASCII case and surrounding
+    /// whitespace, any other text fails to decode.
+    pub fn text_to_bool(mut self, enabled: bool) -> Self {
+        self.text_to_bool = enabled;
+        self
+    }
+
+    /// Decode `TEXT` into integers and floats.
+    ///
+    /// The text is parsed after trimming surrounding whitespace. Text that does not parse fails
+    /// to decode, and parsed integers are subject to
+    /// [`numeric_overflow`][crate::SqliteConnectOptions::numeric_overflow] like any other.
+    pub fn text_to_number(mut self, enabled: bool) -> Self {
+        self.text_to_number = enabled;
+        self
+    }
+
+    /// Decode `INTEGER` and `REAL` into strings.
+    ///
+    /// The number is formatted by SQLite, e.g. `42` or `0.5`.
+    pub fn number_to_text(mut self, enabled: bool) -> Self {
+        self.number_to_text = enabled;
+        self
+    }
+}
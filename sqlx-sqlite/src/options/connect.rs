@@ -1,9 +1,11 @@
-use crate::{SqliteConnectOptions, SqliteConnection};
+use crate::options::network_filesystem;
+use crate::{SqliteAutoVacuum, SqliteConnectOptions, SqliteConnection, SqliteNetworkFilesystem};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use sqlx_core::connection::ConnectOptions;
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
+use sqlx_core::query_scalar::query_scalar;
 use std::fmt::Write;
 use std::str::FromStr;
 use std::time::Duration;
@@ -29,11 +31,43 @@ impl ConnectOptions for SqliteConnectOptions {
         Self::Connection: Sized,
     {
         Box::pin(async move {
+            let page_size = self.requested_page_size()?;
+
             let mut conn = SqliteConnection::establish(self).await?;
 
             // Execute PRAGMAs
             conn.execute(&*self.pragma_string()).await?;
 
+            // `page_size` and `auto_vacuum` are silently ignored for existing databases
+            // unless they are vacuumed afterwards
+            let auto_vacuum = self.requested_auto_vacuum();
+
+            if page_size.is_some() || auto_vacuum.is_some() {
+                let settings = conn.storage_settings().await?;
+
+                if matches!(page_size, Some(size) if size != settings.page_size) {
+                    tracing::warn!(
+                        requested = page_size,
+                        effective = settings.page_size,
+                        "`page_size` did not take effect; it can only be changed for new \
+                         databases, or by running `VACUUM` outside of WAL mode"
+                    );
+                }
+
+                if matches!(auto_vacuum, Some(mode) if mode != settings.auto_vacuum) {
+                    tracing::warn!(
+                        requested = ?auto_vacuum,
+                        effective = ?settings.auto_vacuum,
+                        "`auto_vacuum` did not take effect; it can only be changed for new \
+                         databases, or by running `VACUUM`"
+                    );
+                }
+            }
+
+            if !self.in_memory && self.network_filesystem != SqliteNetworkFilesystem::Ignore {
+                self.check_network_filesystem(&mut conn).await?;
+            }
+
             if !self.collations.is_empty() {
                 let mut locked = conn.lock_handle().await?;
 
@@ -55,9 +89,37 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_arguments(mut self, enabled: bool) -> Self {
+        self.log_settings.log_arguments(enabled);
+        self
+    }
 }
 
 impl SqliteConnectOptions {
+    fn requested_page_size(&self) -> Result<Option<u32>, Error> {
+        let Some(Some(value)) = self.pragmas.get("page_size") else {
+            return Ok(None);
+        };
+
+        match value.parse::<u32>() {
+            Ok(size) if size.is_power_of_two() && (512..=65536).contains(&size) => Ok(Some(size)),
+            _ => Err(Error::Configuration(
+                format!(
+                    "invalid `page_size` {value:?}: must be a power of two between 512 and 65536"
+                )
+                .into(),
+            )),
+        }
+    }
+
+    fn requested_auto_vacuum(&self) -> Option<SqliteAutoVacuum> {
+        match self.pragmas.get("auto_vacuum") {
+            Some(Some(value)) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
     /// Collect all `PRAMGA` commands into a single string
     pub(crate) fn pragma_string(&self) -> String {
         let mut string = String::new();
@@ -71,3 +133,49 @@ impl SqliteConnectOptions {
         string
     }
 }
+
+impl SqliteConnectOptions {
+    async fn check_network_filesystem(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
+        let Some(fs_type) = network_filesystem::detect(&self.filename) else {
+            return Ok(());
+        };
+
+        let journal_mode: String = query_scalar("PRAGMA journal_mode")
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            return Ok(());
+        }
+
+        match self.network_filesystem {
+            SqliteNetworkFilesystem::Ignore => {}
+
+            SqliteNetworkFilesystem::Warn => {
+                tracing::warn!(
+                    filename = %self.filename.display(),
+                    fs_type,
+                    "database is in WAL mode on a network filesystem, which may corrupt it; \
+                     use `journal_mode = DELETE` instead"
+                );
+            }
+
+            SqliteNetworkFilesystem::Error => {
+                return Err(Error::Configuration(
+                    format!(
+                        "database {:?} is in WAL mode on network filesystem {fs_type}, which \
+                         may corrupt it",
+                        self.filename
+                    )
+                    .into(),
+                ));
+            }
+
+            SqliteNetworkFilesystem::UseDeleteJournal => {
+                conn.execute("PRAGMA journal_mode = DELETE").await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// The enforcement of [foreign key constraints](https://www.sqlite.org/foreignkeys.html), see
+/// [`SqliteConnectOptions::foreign_keys()`][crate::SqliteConnectOptions::foreign_keys].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqliteForeignKeys {
+    /// Foreign key constraints are not checked.
+    Off,
+    /// Foreign key constraints are checked at the end of each statement, unless they are
+    /// declared `DEFERRABLE INITIALLY DEFERRED`.
+    #[default]
+    On,
+    /// Foreign key constraints are checked when each transaction commits, as if they were all
+    /// declared `DEFERRABLE INITIALLY DEFERRED`.
+    ///
+    /// Sets [`PRAGMA defer_foreign_keys`](https://www.sqlite.org/pragma.html#pragma_defer_foreign_keys)
+    /// at the start of every transaction begun with [`Connection::begin()`], as SQLite resets it
+    /// at each `COMMIT` or `ROLLBACK`.
+    ///
+    /// [`Connection::begin()`]: sqlx_core::connection::Connection::begin
+    Deferred,
+}
+
+impl SqliteForeignKeys {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SqliteForeignKeys::Off => "OFF",
+            SqliteForeignKeys::On | SqliteForeignKeys::Deferred => "ON",
+        }
+    }
+}
+
+impl From<bool> for SqliteForeignKeys {
+    fn from(on: bool) -> Self {
+        if on {
+            SqliteForeignKeys::On
+        } else {
+            SqliteForeignKeys::Off
+        }
+    }
+}
+
+impl FromStr for SqliteForeignKeys {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "off" | "false" => SqliteForeignKeys::Off,
+            "on" | "true" => SqliteForeignKeys::On,
+            "deferred" => SqliteForeignKeys::Deferred,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `foreign_keys`").into(),
+                ));
+            }
+        })
+    }
+}
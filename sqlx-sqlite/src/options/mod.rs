@@ -1,23 +1,41 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod auto_vacuum;
+mod cache_size;
+mod coercions;
 mod connect;
+mod foreign_keys;
 mod journal_mode;
 mod locking_mode;
+mod network_filesystem;
+mod open_mode;
 mod parse;
+mod profile;
 mod synchronous;
+mod temporary;
 
-use crate::connection::LogSettings;
+use crate::connection::{ConnectionHandle, LogSettings};
 pub use auto_vacuum::SqliteAutoVacuum;
+pub use cache_size::SqliteCacheSize;
+pub use coercions::SqliteCoercions;
+pub use foreign_keys::SqliteForeignKeys;
 pub use journal_mode::SqliteJournalMode;
 pub use locking_mode::SqliteLockingMode;
+pub use network_filesystem::SqliteNetworkFilesystem;
+pub use open_mode::SqliteOpenMode;
+pub use profile::SqliteProfile;
 use std::cmp::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{borrow::Cow, time::Duration};
 pub use synchronous::SqliteSynchronous;
+pub(crate) use temporary::TemporaryFile;
 
 use crate::common::DebugFn;
 use crate::connection::collation::Collation;
+use crate::{Sqlite, SqliteTypeRegistry};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use sqlx_core::interceptor::{self, QueryInterceptor, QueryRewriter};
+use sqlx_core::types::NumericOverflow;
 use sqlx_core::IndexMap;
 
 /// Options and flags which can be used to configure a SQLite connection.
@@ -35,6 +53,13 @@ use sqlx_core::IndexMap;
 /// `sqlite://data.db` | Open the file `data.db` in the current directory. |
 /// `sqlite:///data.db` | Open the file `data.db` from the root (`/`) directory. |
 /// `sqlite://data.db?mode=ro` | Open the file `data.db` for read-only access. |
+/// `sqlite:file:data.db?immutable=1` | Open the file `data.db`, which cannot change, as a [URI filename](https://www.sqlite.org/uri.html). |
+///
+/// The URI parameters `mode`, `cache`, `immutable`, `nolock` and `vfs` are supported, the same
+/// as their builder methods [`open_mode()`][Self::open_mode], [`shared_cache()`][Self::shared_cache],
+/// [`immutable()`][Self::immutable], [`nolock()`][Self::nolock] and [`vfs()`][Self::vfs], as is
+/// `foreign_keys` (`on`, `off` or `deferred`) for [`foreign_keys()`][Self::foreign_keys].
+/// Other parameters are rejected.
 ///
 /// # Example
 ///
@@ -56,13 +81,19 @@ use sqlx_core::IndexMap;
 pub struct SqliteConnectOptions {
     pub(crate) filename: Cow<'static, Path>,
     pub(crate) in_memory: bool,
+    // keeps an in-memory database alive between connections, see `memory_shared()`
+    pub(crate) memory_anchor: Option<Arc<Mutex<Option<ConnectionHandle>>>>,
+    // deletes the database file of `temporary()`
+    pub(crate) temporary: Option<Arc<TemporaryFile>>,
     pub(crate) read_only: bool,
     pub(crate) create_if_missing: bool,
     pub(crate) shared_cache: bool,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) statement_cache_ttl: Option<Duration>,
     pub(crate) busy_timeout: Duration,
     pub(crate) log_settings: LogSettings,
     pub(crate) immutable: bool,
+    pub(crate) nolock: bool,
     pub(crate) vfs: Option<Cow<'static, str>>,
 
     pub(crate) pragmas: IndexMap<Cow<'static, str>, Option<Cow<'static, str>>>,
@@ -82,6 +113,20 @@ pub struct SqliteConnectOptions {
 
     pub(crate) optimize_on_close: OptimizeOnClose,
 
+    pub(crate) numeric_overflow: NumericOverflow,
+
+    pub(crate) network_filesystem: SqliteNetworkFilesystem,
+
+    pub(crate) coercions: SqliteCoercions,
+
+    pub(crate) strict: bool,
+
+    pub(crate) defer_foreign_keys: bool,
+
+    pub(crate) query_interceptor: Option<QueryInterceptor<Sqlite>>,
+
+    pub(crate) type_registry: Option<Arc<SqliteTypeRegistry>>,
+
     #[cfg(feature = "regexp")]
     pub(crate) register_regexp_function: bool,
 }
@@ -184,13 +229,17 @@ impl SqliteConnectOptions {
         Self {
             filename: Cow::Borrowed(Path::new(":memory:")),
             in_memory: false,
+            memory_anchor: None,
+            temporary: None,
             read_only: false,
             create_if_missing: false,
             shared_cache: false,
             statement_cache_capacity: 100,
+            statement_cache_ttl: None,
             busy_timeout: Duration::from_secs(5),
             log_settings: Default::default(),
             immutable: false,
+            nolock: false,
             vfs: None,
             pragmas,
             extensions: Default::default(),
@@ -200,23 +249,95 @@ impl SqliteConnectOptions {
             command_channel_size: 50,
             row_channel_size: 50,
             optimize_on_close: OptimizeOnClose::Disabled,
+            numeric_overflow: NumericOverflow::DriverDefault,
+            network_filesystem: SqliteNetworkFilesystem::Warn,
+            coercions: SqliteCoercions::new(),
+            strict: false,
+            defer_foreign_keys: false,
+            query_interceptor: None,
+            type_registry: None,
             #[cfg(feature = "regexp")]
             register_regexp_function: false,
         }
     }
 
+    /// Construct `Self` for the in-memory database `name`, which is shared by all connections
+    /// to it in the process, e.g. for tests.
+    ///
+    /// This corresponds to the URI filename `file:name?mode=memory&cache=shared`. An in-memory
+    /// database is deleted when its last connection closes, so the database is additionally
+    /// kept open from the first connection until these options and all their clones are
+    /// dropped, such as those of a pool created with them:
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    ///
+    /// let pool = SqlitePoolOptions::new()
+    ///     .min_connections(0)
+    ///     .connect_with(SqliteConnectOptions::memory_shared("test-db"))
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memory_shared(name: &str) -> Self {
+        let mut options = Self::new();
+
+        // the name is the path of a URI, so `?` and `#` would end it
+        const PATH: &AsciiSet = &CONTROLS.add(b'%').add(b'?').add(b'#').add(b'&');
+
+        let name = utf8_percent_encode(name, PATH);
+
+        options.filename = Cow::Owned(PathBuf::from(format!("file:{name}")));
+        options.in_memory = true;
+        options.shared_cache = true;
+        options.memory_anchor = Some(Default::default());
+
+        options
+    }
+
+    /// Construct `Self` for a new database file in the [temporary directory](std::env::temp_dir),
+    /// e.g. for tests.
+    ///
+    /// The file is created right away, under a random name that is not in use yet. The file and
+    /// its journals are deleted once these options and all their clones, such as
+    /// those of a pool created with them, are dropped and the last connection opened with them
+    /// is closed. Deleting a file that is still open fails on Windows, and is retried a few
+    /// times in case another process briefly opened it.
+    ///
+    /// A crashed process leaves the file behind.
+    pub fn temporary() -> Self {
+        let mut options = Self::new();
+        let temporary = TemporaryFile::new();
+
+        options.filename = Cow::Owned(temporary.path().to_owned());
+        options.create_if_missing = true;
+        options.temporary = Some(Arc::new(temporary));
+
+        options
+    }
+
     /// Sets the name of the database file.
     pub fn filename(mut self, filename: impl AsRef<Path>) -> Self {
         self.filename = Cow::Owned(filename.as_ref().to_owned());
         self
     }
 
+    /// Get the name of the database file, e.g. of a [`temporary()`](Self::temporary) database.
+    pub fn get_filename(&self) -> &Path {
+        &self.filename
+    }
+
     /// Set the enforcement of [foreign key constraints](https://www.sqlite.org/pragma.html#pragma_foreign_keys).
     ///
     /// SQLx chooses to enable this by default so that foreign keys function as expected,
-    /// compared to other database flavors.
-    pub fn foreign_keys(self, on: bool) -> Self {
-        self.pragma("foreign_keys", if on { "ON" } else { "OFF" })
+    /// compared to other database flavors. Takes a `bool` or a [`SqliteForeignKeys`], e.g.
+    /// [`SqliteForeignKeys::Deferred`] to check them only when transactions commit.
+    pub fn foreign_keys(mut self, mode: impl Into<SqliteForeignKeys>) -> Self {
+        let mode = mode.into();
+        self.defer_foreign_keys = mode == SqliteForeignKeys::Deferred;
+        self.pragma("foreign_keys", mode.as_str())
     }
 
     /// Set the [`SQLITE_OPEN_SHAREDCACHE` flag](https://sqlite.org/sharedcache.html).
@@ -275,6 +396,25 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets the [access mode](https://www.sqlite.org/uri.html#urimode) to open the database with,
+    /// as the `mode` URI parameter does.
+    ///
+    /// This sets [`read_only()`][Self::read_only] and [`create_if_missing()`][Self::create_if_missing]
+    /// accordingly. [`SqliteOpenMode::Memory`] also opens an in-memory database and enables
+    /// [`shared_cache()`][Self::shared_cache], so that all connections of a pool use the same
+    /// in-memory database; the other modes keep a database that is already in-memory.
+    pub fn open_mode(mut self, mode: SqliteOpenMode) -> Self {
+        self.read_only = mode == SqliteOpenMode::ReadOnly;
+        self.create_if_missing = mode == SqliteOpenMode::ReadWriteCreate;
+
+        if mode == SqliteOpenMode::Memory {
+            self.in_memory = true;
+            self.shared_cache = true;
+        }
+
+        self
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
@@ -286,6 +426,20 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets the time after which a cached statement is prepared again the next time it is used.
+    ///
+    /// SQLx records the columns of a statement when preparing it, and updates them when SQLite
+    /// recompiles the statement after a schema change, e.g. when the table of a `SELECT *` gains
+    /// a column. An expiry additionally bounds how long a cached statement, and the memory SQLite
+    /// holds for it, outlives the queries that use it. The counters of the cache are reported by
+    /// [`SqliteConnection::statement_cache_stats()`](crate::SqliteConnection::statement_cache_stats).
+    ///
+    /// By default, cached statements do not expire.
+    pub fn statement_cache_ttl(mut self, ttl: impl Into<Option<Duration>>) -> Self {
+        self.statement_cache_ttl = ttl.into();
+        self
+    }
+
     /// Sets a timeout value to wait when the database is locked, before
     /// returning a busy timeout error.
     ///
@@ -320,10 +474,31 @@ impl SqliteConnectOptions {
     /// For existing databases, a change to this value does not take effect unless a
     /// [`VACUUM` command](https://www.sqlite.org/lang_vacuum.html) is executed.
     /// However, it cannot be changed in WAL mode.
+    ///
+    /// Must be a power of two between 512 and 65536, otherwise connecting fails. If the change
+    /// does not take effect for one of the reasons above, a warning is logged on connect; use
+    /// [`SqliteConnection::storage_settings()`][crate::SqliteConnection::storage_settings]
+    /// to check the effective value.
     pub fn page_size(self, page_size: u32) -> Self {
         self.pragma("page_size", page_size.to_string())
     }
 
+    /// Sets the [cache_size](https://www.sqlite.org/pragma.html#pragma_cache_size) setting for the database connection.
+    ///
+    /// The default cache_size setting is 2000 KiB.
+    pub fn cache_size(self, cache_size: SqliteCacheSize) -> Self {
+        self.pragma("cache_size", cache_size.to_pragma_value())
+    }
+
+    /// Sets the [mmap_size](https://www.sqlite.org/pragma.html#pragma_mmap_size) setting for the database connection,
+    /// i.e. the number of bytes of the database file to access through memory-mapped I/O.
+    ///
+    /// The default mmap_size setting is 0, which disables memory-mapped I/O. SQLite silently
+    /// caps the value at its compile-time maximum.
+    pub fn mmap_size(self, mmap_size: u64) -> Self {
+        self.pragma("mmap_size", mmap_size.to_string())
+    }
+
     /// Sets custom initial pragma for the database connection.
     pub fn pragma<K, V>(mut self, key: K, value: V) -> Self
     where
@@ -334,6 +509,31 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Set the pragmas of a [`SqliteProfile`] curated for a common workload.
+    ///
+    /// This is shorthand for setting each pragma listed in the [`SqliteProfile`] docs, so
+    /// setters called afterwards override individual values:
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::error::Error;
+    /// # use std::str::FromStr;
+    /// # use sqlx_sqlite::{SqliteConnectOptions, SqliteProfile, SqliteSynchronous};
+    /// # fn options() -> Result<SqliteConnectOptions, Error> {
+    /// let options = SqliteConnectOptions::from_str("sqlite://app.db")?
+    ///     .optimize_for(SqliteProfile::EmbeddedWrite)
+    ///     // we'd rather not lose the latest transactions on power loss
+    ///     .synchronous(SqliteSynchronous::Full);
+    /// # Ok(options)
+    /// # }
+    /// ```
+    pub fn optimize_for(mut self, profile: SqliteProfile) -> Self {
+        for (key, value) in profile.pragmas() {
+            self = self.pragma(key, value);
+        }
+
+        self
+    }
+
     /// Add a custom collation for comparing strings in SQL.
     ///
     /// If a collation with the same name already exists, it will be replaced.
@@ -377,6 +577,18 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets the `nolock` flag on the database connection, so that SQLite does not use file locks.
+    ///
+    /// This can be needed for file systems that do not support locking, but two connections
+    /// writing to, or one writing and one reading from, the database at the same time will then
+    /// corrupt it or read inconsistent data.
+    ///
+    /// See [URI filenames](https://www.sqlite.org/uri.html#urinolock) for details.
+    pub fn nolock(mut self, nolock: bool) -> Self {
+        self.nolock = nolock;
+        self
+    }
+
     /// Sets the [threading mode](https://www.sqlite.org/threadsafe.html) for the database connection.
     ///
     /// The default setting is `false` corresponding to using `OPEN_NOMUTEX`.
@@ -429,6 +641,26 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Rewrite the SQL of every query executed on the connections, e.g. to hide soft-deleted
+    /// rows. See the [`interceptor`](sqlx_core::interceptor) module for details.
+    pub fn query_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.query_interceptor = Some(interceptor::query_interceptor(interceptor));
+        self
+    }
+
+    /// Rewrite the SQL of every query executed on the connections with `rewriter`, which also
+    /// sees the bound arguments, e.g. to route the queries of a tenant to the tables of its
+    /// shard.
+    ///
+    /// Replaces the [`query_interceptor()`](Self::query_interceptor), if any.
+    pub fn query_rewriter<R: QueryRewriter<Sqlite>>(mut self, rewriter: R) -> Self {
+        self.query_interceptor = Some(interceptor::query_interceptor(rewriter));
+        self
+    }
+
     /// Sets the [`vfs`](https://www.sqlite.org/vfs.html) parameter of the database connection.
     ///
     /// The default value is empty, and sqlite will use the default VFS object depending on the
@@ -438,6 +670,71 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Set how integers and floats that do not fit into the requested Rust type are decoded,
+    /// e.g. a value of `2^40` into an `i32` or `1e300` into an `f32`.
+    ///
+    /// SQLite stores all integers as 64-bit and all floats as double precision, so this applies
+    /// to every narrower type. Defaults to [`NumericOverflow::DriverDefault`], which converts
+    /// `i32` and `f32` like an `as` cast and fails for the other types.
+    ///
+    /// Can be changed for an open connection with
+    /// [`LockedSqliteHandle::set_numeric_overflow()`][crate::LockedSqliteHandle::set_numeric_overflow].
+    pub fn numeric_overflow(mut self, policy: NumericOverflow) -> Self {
+        self.numeric_overflow = policy;
+        self
+    }
+
+    /// Set what to do when the database is in WAL mode and on a network filesystem, such as NFS
+    /// or SMB, where WAL mode can corrupt the database.
+    ///
+    /// Checked on every connect, after the other PRAGMAs have been set. Defaults to
+    /// [`SqliteNetworkFilesystem::Warn`]; see [`SqliteNetworkFilesystem`] for how network
+    /// filesystems are detected.
+    pub fn network_filesystem(mut self, policy: SqliteNetworkFilesystem) -> Self {
+        self.network_filesystem = policy;
+        self
+    }
+
+    /// Set which implicit conversions are allowed when a value's storage class does not match
+    /// the requested Rust type, e.g. decoding `'true'` into a `bool`.
+    ///
+    /// Defaults to [`SqliteCoercions::new()`], which allows none. See [`SqliteCoercions`] for
+    /// the available conversions.
+    ///
+    /// Can be changed for an open connection with
+    /// [`LockedSqliteHandle::set_coercions()`][crate::LockedSqliteHandle::set_coercions].
+    pub fn coercions(mut self, coercions: SqliteCoercions) -> Self {
+        self.coercions = coercions;
+        self
+    }
+
+    /// Set the encode and decode functions for user types, see [`SqliteTypeRegistry`].
+    pub fn type_registry(mut self, registry: SqliteTypeRegistry) -> Self {
+        self.type_registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// Opt into Postgres-like type discipline on top of SQLite's dynamic typing.
+    ///
+    /// When enabled:
+    ///
+    /// * tables created by SQLx itself, like the `_sqlx_migrations` table, are
+    ///   [`STRICT`](https://www.sqlite.org/stricttables.html) where they don't exist yet;
+    /// * [`describe()`][sqlx_core::executor::Executor::describe], which backs the query macros,
+    ///   fails on columns whose declared type SQLx does not recognize instead of guessing;
+    /// * [`Row::try_get()`][sqlx_core::row::Row::try_get] fails on values whose storage class
+    ///   contradicts the declared type of their column, e.g. `TEXT` in an `INTEGER` column,
+    ///   even if the requested Rust type could decode either.
+    ///
+    /// Columns declared as `DATE`, `TIME`, `DATETIME` or `NUMERIC`, and columns without a
+    /// declared type, legitimately hold several storage classes and are not checked.
+    ///
+    /// Disabled by default.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Load an [extension](https://www.sqlite.org/loadext.html) at run-time when the database connection
     /// is established, using the default entry point.
     ///
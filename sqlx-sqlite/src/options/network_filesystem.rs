@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use sqlx_core::HashMap;
+
+/// What to do when a database in WAL mode is on a network filesystem, such as NFS or SMB.
+///
+/// WAL mode needs shared memory between all connections to the database, which network
+/// filesystems cannot provide; connections from different hosts, or even from the same host,
+/// may then silently corrupt the database. See [the SQLite documentation][wal] for details.
+///
+/// Network filesystems are detected from the mount table on Linux and from UNC paths on
+/// Windows; on other platforms and for mapped network drives, nothing is detected. The result
+/// is remembered for each database file for the lifetime of the process.
+///
+/// It is used by the [`network_filesystem`](super::SqliteConnectOptions::network_filesystem)
+/// method.
+///
+/// [wal]: https://www.sqlite.org/wal.html
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqliteNetworkFilesystem {
+    /// Do not check the filesystem.
+    Ignore,
+
+    /// Log a warning. This is the default.
+    #[default]
+    Warn,
+
+    /// Fail to connect with [`Error::Configuration`][sqlx_core::error::Error::Configuration].
+    Error,
+
+    /// Switch the database to `journal_mode = DELETE`, which works with the file locking of
+    /// most network filesystems.
+    ///
+    /// The journal mode of a database is persistent, so this affects all other connections
+    /// opened later on.
+    UseDeleteJournal,
+}
+
+// filesystem types of the Linux mount table that are backed by remote storage
+#[cfg(target_os = "linux")]
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "ncpfs",
+    "afs",
+    // not `9p`, which WSL2 uses for the drives of Windows
+    "fuse.sshfs",
+];
+
+/// The name of the network filesystem `path` is on, if any.
+pub(crate) fn detect(path: &Path) -> Option<String> {
+    // reading the mount table on every connect is too slow, and mounts rarely change
+    static DETECTED: OnceLock<Mutex<HashMap<PathBuf, Option<String>>>> = OnceLock::new();
+
+    let detected = DETECTED.get_or_init(Default::default);
+    let lock = || detected.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(fs_type) = lock().get(path) {
+        return fs_type.clone();
+    }
+
+    let fs_type = detect_uncached(path);
+    lock().insert(path.to_owned(), fs_type.clone());
+
+    fs_type
+}
+
+fn detect_uncached(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+
+    #[cfg(target_os = "linux")]
+    {
+        let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+
+        network_filesystem_from_mounts(&mounts, &path)
+    }
+
+    #[cfg(windows)]
+    {
+        use std::path::{Component, Prefix};
+
+        match path.components().next() {
+            Some(Component::Prefix(prefix))
+                if matches!(prefix.kind(), Prefix::UNC(..) | Prefix::VerbatimUNC(..)) =>
+            {
+                Some("smb".to_owned())
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+// the filesystem of the mount point closest to `path`, if it is a network filesystem
+#[cfg(target_os = "linux")]
+fn network_filesystem_from_mounts(mounts: &str, path: &Path) -> Option<String> {
+    let mut closest: Option<(usize, &str)> = None;
+
+    for line in mounts.lines() {
+        let mut fields = line.split(' ');
+
+        let (Some(_device), Some(mount_point), Some(fs_type)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        // spaces and other special characters are escaped as octal, e.g. `\040`
+        let mount_point = unescape_mount_point(mount_point);
+
+        if !path.starts_with(&mount_point) {
+            continue;
+        }
+
+        let depth = Path::new(&mount_point).components().count();
+
+        if !matches!(closest, Some((closest, _)) if depth < closest) {
+            closest = Some((depth, fs_type));
+        }
+    }
+
+    let (_, fs_type) = closest?;
+
+    NETWORK_FILESYSTEMS
+        .contains(&fs_type)
+        .then(|| fs_type.to_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn unescape_mount_point(mount_point: &str) -> String {
+    let mut unescaped = String::with_capacity(mount_point.len());
+    let mut rest = mount_point;
+
+    while let Some(i) = rest.find('\\') {
+        unescaped.push_str(&rest[..i]);
+
+        let escape = rest.get(i + 1..i + 4);
+
+        match escape.and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(char::from(byte));
+                rest = &rest[i + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_network_filesystem_from_mounts() {
+    let mounts = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+server:/export /mnt/data nfs4 rw,relatime,vers=4.2 0 0
+/dev/sdb1 /mnt/data/local ext4 rw,relatime 0 0
+//server/share /mnt/my\\040share cifs rw 0 0
+drvfs /mnt/c 9p rw,noatime,dirsync,aname=drvfs 0 0
+";
+
+    let detect = |path: &str| network_filesystem_from_mounts(mounts, Path::new(path));
+
+    assert_eq!(detect("/home/app/db.sqlite"), None);
+    assert_eq!(detect("/mnt/data/db.sqlite").as_deref(), Some("nfs4"));
+    assert_eq!(detect("/mnt/data/local/db.sqlite"), None);
+    assert_eq!(detect("/mnt/my share/db.sqlite").as_deref(), Some("cifs"));
+    assert_eq!(detect("/mnt/database.sqlite"), None);
+    assert_eq!(detect("/mnt/c/Users/app/db.sqlite"), None);
+}
@@ -0,0 +1,38 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Refer to [SQLite documentation] for the meaning of the open modes, which correspond to the
+/// `mode` parameter of URI filenames.
+///
+/// [SQLite documentation]: https://www.sqlite.org/uri.html#urimode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqliteOpenMode {
+    /// Open the database for reading only (`ro`).
+    ReadOnly,
+    /// Open the database for reading and writing, failing if it does not exist (`rw`).
+    #[default]
+    ReadWrite,
+    /// Open the database for reading and writing, creating it if it does not exist (`rwc`).
+    ReadWriteCreate,
+    /// Open a pure in-memory database that never interacts with disk (`memory`).
+    Memory,
+}
+
+impl FromStr for SqliteOpenMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "ro" => SqliteOpenMode::ReadOnly,
+            "rw" => SqliteOpenMode::ReadWrite,
+            "rwc" => SqliteOpenMode::ReadWriteCreate,
+            "memory" => SqliteOpenMode::Memory,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `mode`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::SqliteConnectOptions;
+use crate::{SqliteConnectOptions, SqliteForeignKeys};
 use percent_encoding::percent_decode_str;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
@@ -39,29 +39,7 @@ impl SqliteConnectOptions {
                     // database is a pure in-memory database that never interacts with disk,
                     // respectively.
                     "mode" => {
-                        match &*value {
-                            "ro" => {
-                                options.read_only = true;
-                            }
-
-                            // default
-                            "rw" => {}
-
-                            "rwc" => {
-                                options.create_if_missing = true;
-                            }
-
-                            "memory" => {
-                                options.in_memory = true;
-                                options.shared_cache = true;
-                            }
-
-                            _ => {
-                                return Err(Error::Configuration(
-                                    format!("unknown value {value:?} for `mode`").into(),
-                                ));
-                            }
-                        }
+                        options = options.open_mode(value.parse()?);
                     }
 
                     // The cache query parameter specifies the cache behaviour across multiple
@@ -97,8 +75,26 @@ impl SqliteConnectOptions {
                         }
                     },
 
+                    "nolock" => match &*value {
+                        "true" | "1" => {
+                            options.nolock = true;
+                        }
+                        "false" | "0" => {
+                            options.nolock = false;
+                        }
+                        _ => {
+                            return Err(Error::Configuration(
+                                format!("unknown value {value:?} for `nolock`").into(),
+                            ));
+                        }
+                    },
+
                     "vfs" => options.vfs = Some(Cow::Owned(value.into_owned())),
 
+                    "foreign_keys" => {
+                        options = options.foreign_keys(value.parse::<SqliteForeignKeys>()?);
+                    }
+
                     _ => {
                         return Err(Error::Configuration(
                             format!("unknown query parameter `{key}` while parsing connection URL")
@@ -122,6 +118,20 @@ impl FromStr for SqliteConnectOptions {
             .trim_start_matches("sqlite://")
             .trim_start_matches("sqlite:");
 
+        // a URI filename, e.g. `file:data.db` or `file:///var/data.db`
+        if let Some(path) = url.strip_prefix("file:") {
+            url = match path.strip_prefix("//") {
+                Some(path) if path.starts_with('/') => path,
+                Some(path) if path.starts_with("localhost/") => &path["localhost".len()..],
+                Some(_) => {
+                    return Err(Error::Configuration(
+                        "the authority of a `file:` URI must be empty or `localhost`".into(),
+                    ));
+                }
+                None => path,
+            };
+        }
+
         let mut database_and_params = url.splitn(2, '?');
 
         let database = database_and_params.next().unwrap_or_default();
@@ -149,6 +159,14 @@ fn test_parse_in_memory() -> Result<(), Error> {
     assert!(options.in_memory);
     assert!(!options.shared_cache);
 
+    let options: SqliteConnectOptions = "sqlite::memory:?mode=rwc".parse()?;
+    assert!(options.in_memory);
+    assert!(options.create_if_missing);
+
+    let options: SqliteConnectOptions = "sqlite::memory:?mode=ro".parse()?;
+    assert!(options.in_memory);
+    assert!(options.read_only);
+
     Ok(())
 }
 
@@ -169,3 +187,56 @@ fn test_parse_shared_in_memory() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_uri_filename() -> Result<(), Error> {
+    let options: SqliteConnectOptions =
+        "sqlite:file:a.db?mode=rwc&immutable=1&nolock=1&vfs=unix-none".parse()?;
+    assert_eq!(&*options.filename.to_string_lossy(), "a.db");
+    assert!(options.create_if_missing);
+    assert!(options.immutable);
+    assert!(options.nolock);
+    assert_eq!(options.vfs.as_deref(), Some("unix-none"));
+
+    let options: SqliteConnectOptions = "file:///var/a.db?mode=ro".parse()?;
+    assert_eq!(&*options.filename.to_string_lossy(), "/var/a.db");
+    assert!(options.read_only);
+
+    let options: SqliteConnectOptions = "sqlite:file://localhost/var/a.db".parse()?;
+    assert_eq!(&*options.filename.to_string_lossy(), "/var/a.db");
+
+    assert!("sqlite:file://host/a.db"
+        .parse::<SqliteConnectOptions>()
+        .is_err());
+    assert!("sqlite:a.db?mode=rwx"
+        .parse::<SqliteConnectOptions>()
+        .is_err());
+    assert!("sqlite:a.db?psow=1"
+        .parse::<SqliteConnectOptions>()
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_foreign_keys() -> Result<(), Error> {
+    let options: SqliteConnectOptions = "sqlite://a.db?foreign_keys=deferred".parse()?;
+    assert!(options.defer_foreign_keys);
+    assert_eq!(
+        options.pragmas.get("foreign_keys"),
+        Some(&Some("ON".into()))
+    );
+
+    let options: SqliteConnectOptions = "sqlite://a.db?foreign_keys=off".parse()?;
+    assert!(!options.defer_foreign_keys);
+    assert_eq!(
+        options.pragmas.get("foreign_keys"),
+        Some(&Some("OFF".into()))
+    );
+
+    assert!("sqlite://a.db?foreign_keys=later"
+        .parse::<SqliteConnectOptions>()
+        .is_err());
+
+    Ok(())
+}
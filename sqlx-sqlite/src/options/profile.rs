@@ -0,0 +1,60 @@
+/// A curated bundle of pragmas for a common workload, see
+/// [`SqliteConnectOptions::optimize_for()`][crate::SqliteConnectOptions::optimize_for].
+///
+/// | Pragma | `EmbeddedWrite` | `ReadHeavy` | `InMemoryTest` |
+/// | -- | -- | -- | -- |
+/// | [`journal_mode`](https://www.sqlite.org/pragma.html#pragma_journal_mode) | `WAL` | `WAL` | `MEMORY` |
+/// | [`synchronous`](https://www.sqlite.org/pragma.html#pragma_synchronous) | `NORMAL` | `NORMAL` | `OFF` |
+/// | [`cache_size`](https://www.sqlite.org/pragma.html#pragma_cache_size) | 16 MiB | 64 MiB | 16 MiB |
+/// | [`mmap_size`](https://www.sqlite.org/pragma.html#pragma_mmap_size) | 0 | 256 MiB | 0 |
+/// | [`temp_store`](https://www.sqlite.org/pragma.html#pragma_temp_store) | `MEMORY` | `MEMORY` | `MEMORY` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqliteProfile {
+    /// An application-local database with frequent small writes, e.g. on a desktop or mobile
+    /// device.
+    ///
+    /// Transactions stay atomic and consistent, but a power loss may roll back the most recent
+    /// ones.
+    EmbeddedWrite,
+
+    /// A database that is mostly read, possibly by many connections at once.
+    ///
+    /// Reads are served from a memory map of the first 256 MiB of the database file where
+    /// possible, which avoids copying pages but means I/O errors surface as signals rather than
+    /// error codes; see [the SQLite documentation](https://www.sqlite.org/mmap.html).
+    ReadHeavy,
+
+    /// A throwaway database for tests, whether in memory or in a temporary file.
+    ///
+    /// **Not durable**: a crash may corrupt a file database.
+    InMemoryTest,
+}
+
+impl SqliteProfile {
+    /// The pragmas set by this profile, in the order they are applied.
+    pub fn pragmas(&self) -> [(&'static str, &'static str); 5] {
+        match self {
+            SqliteProfile::EmbeddedWrite => [
+                ("journal_mode", "WAL"),
+                ("synchronous", "NORMAL"),
+                ("cache_size", "-16384"),
+                ("mmap_size", "0"),
+                ("temp_store", "MEMORY"),
+            ],
+            SqliteProfile::ReadHeavy => [
+                ("journal_mode", "WAL"),
+                ("synchronous", "NORMAL"),
+                ("cache_size", "-65536"),
+                ("mmap_size", "268435456"),
+                ("temp_store", "MEMORY"),
+            ],
+            SqliteProfile::InMemoryTest => [
+                ("journal_mode", "MEMORY"),
+                ("synchronous", "OFF"),
+                ("cache_size", "-16384"),
+                ("mmap_size", "0"),
+                ("temp_store", "MEMORY"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::hash_map::RandomState;
+use std::fs::OpenOptions;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, thread};
+
+/// A database file in the temporary directory, deleted with its journals when dropped.
+#[derive(Debug)]
+pub(crate) struct TemporaryFile {
+    path: PathBuf,
+}
+
+impl TemporaryFile {
+    /// Create a new, empty file with a random name, so that no other process can have
+    /// created it before.
+    pub(crate) fn new() -> Self {
+        loop {
+            let path = env::temp_dir().join(format!("sqlx-temporary-{:016x}.db", random()));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+
+                // if the file could not be created, opening the database reports why
+                _ => return Self { path },
+            }
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TemporaryFile {
+    fn drop(&mut self) {
+        for suffix in ["", "-journal", "-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+
+            remove_file(Path::new(&path));
+        }
+    }
+}
+
+// the keys of `RandomState` are seeded from the operating system's random number generator
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+fn remove_file(path: &Path) {
+    // on Windows, files cannot be deleted while open, which other processes such as virus
+    // scanners may briefly do after the last connection closed the database
+    for attempt in 1.. {
+        match fs::remove_file(path) {
+            Err(e) if cfg!(windows) && e.kind() == io::ErrorKind::PermissionDenied => {
+                if attempt == 10 {
+                    return;
+                }
+
+                thread::sleep(Duration::from_millis(10 * attempt));
+            }
+
+            // the file is gone, was never created or cannot be deleted
+            _ => return,
+        }
+    }
+}
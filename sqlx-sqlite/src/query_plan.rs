@@ -0,0 +1,146 @@
+use sqlx_core::executor::Executor;
+use sqlx_core::row::Row;
+
+use crate::error::Error;
+use crate::SqliteConnection;
+
+/// The parsed output of `EXPLAIN QUERY PLAN`, as returned by [`SqliteConnection::explain()`].
+///
+/// See [the SQLite manual](https://www.sqlite.org/eqp.html) for how to interpret the plan.
+/// Note that the exact wording of the nodes is not guaranteed to be stable across SQLite versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteQueryPlan {
+    nodes: Vec<SqliteQueryPlanNode>,
+}
+
+/// A single step of a [`SqliteQueryPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteQueryPlanNode {
+    id: i64,
+    detail: String,
+    children: Vec<SqliteQueryPlanNode>,
+}
+
+impl SqliteConnection {
+    /// Run `EXPLAIN QUERY PLAN` for `query` and parse the output into a tree.
+    ///
+    /// The query is not executed. Any bind parameters in it are treated as `NULL`.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+    /// let plan = conn.explain("SELECT * FROM users WHERE email = ?").await?;
+    /// assert!(plan.uses_index("users_email_idx"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain(&mut self, query: &str) -> Result<SqliteQueryPlan, Error> {
+        let rows = self
+            .fetch_all(&*format!("EXPLAIN QUERY PLAN {query}"))
+            .await?;
+
+        let mut steps = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let id: i64 = row.try_get(0)?;
+            let parent: i64 = row.try_get(1)?;
+            let detail: String = row.try_get(3)?;
+
+            steps.push((id, parent, detail));
+        }
+
+        Ok(SqliteQueryPlan::from_steps(steps))
+    }
+}
+
+impl SqliteQueryPlan {
+    /// Build the tree from `(id, parent, detail)` rows, in the order SQLite outputs them.
+    fn from_steps(steps: Vec<(i64, i64, String)>) -> Self {
+        fn children_of(steps: &[(i64, i64, String)], parent: i64) -> Vec<SqliteQueryPlanNode> {
+            steps
+                .iter()
+                // ids are always greater than their parent, which also rules out cycles
+                .filter(|(id, p, _)| *p == parent && *id > parent)
+                .map(|(id, _, detail)| SqliteQueryPlanNode {
+                    id: *id,
+                    detail: detail.clone(),
+                    children: children_of(steps, *id),
+                })
+                .collect()
+        }
+
+        Self {
+            nodes: children_of(&steps, 0),
+        }
+    }
+
+    /// The top-level steps of the plan.
+    pub fn nodes(&self) -> &[SqliteQueryPlanNode] {
+        &self.nodes
+    }
+
+    /// Iterate over all steps of the plan, depth-first.
+    pub fn iter(&self) -> impl Iterator<Item = &SqliteQueryPlanNode> {
+        self.nodes.iter().flat_map(SqliteQueryPlanNode::iter)
+    }
+
+    /// Returns `true` if any step of the plan uses the index with the given name.
+    pub fn uses_index(&self, index: &str) -> bool {
+        self.iter().any(|node| node.index() == Some(index))
+    }
+
+    /// Returns `true` if any step of the plan scans the given table without using an index.
+    pub fn scans_table(&self, table: &str) -> bool {
+        self.iter().any(|node| {
+            node.detail.starts_with("SCAN ")
+                && node.table() == Some(table)
+                && node.index().is_none()
+        })
+    }
+}
+
+impl SqliteQueryPlanNode {
+    /// The id of this step, unique within the plan.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The description of this step, e.g. `SEARCH users USING INDEX users_email_idx (email=?)`.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    /// The steps nested under this one.
+    pub fn children(&self) -> &[SqliteQueryPlanNode] {
+        &self.children
+    }
+
+    /// The table this step scans or searches, if any.
+    pub fn table(&self) -> Option<&str> {
+        let rest = self
+            .detail
+            .strip_prefix("SCAN ")
+            .or_else(|| self.detail.strip_prefix("SEARCH "))?;
+
+        // SQLite versions before 3.36 include the word `TABLE`
+        let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+
+        // `SCAN CONSTANT ROW` and scans of subqueries, e.g. `SCAN (subquery-1)`, are not tables
+        if rest == "CONSTANT ROW" || rest.starts_with('(') {
+            return None;
+        }
+
+        rest.split(' ').next()
+    }
+
+    /// The index used by this step, if any.
+    pub fn index(&self) -> Option<&str> {
+        let (_, rest) = self.detail.split_once(" USING ")?;
+        let rest = rest.strip_prefix("COVERING ").unwrap_or(rest);
+
+        rest.strip_prefix("INDEX ")?.split(' ').next()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &SqliteQueryPlanNode> + '_> {
+        Box::new(std::iter::once(self).chain(self.children.iter().flat_map(Self::iter)))
+    }
+}
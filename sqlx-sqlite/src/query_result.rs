@@ -3,7 +3,33 @@ use std::iter::{Extend, IntoIterator};
 #[derive(Debug, Default)]
 pub struct SqliteQueryResult {
     pub(super) changes: u64,
+    pub(super) total_changes: u64,
     pub(super) last_insert_rowid: i64,
+    pub(super) stats: SqliteQueryStats,
+}
+
+/// Counters of the work SQLite did to execute a statement, see
+/// [`sqlite3_stmt_status()`](https://www.sqlite.org/c3ref/stmt_status.html).
+///
+/// They can be used to assert in tests that a query uses an index:
+///
+/// ```rust,ignore
+/// let result = sqlx::query("UPDATE users SET active = false WHERE email = ?")
+///     .bind(email)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// assert_eq!(result.stats().fullscan_steps(), 0);
+/// ```
+///
+/// The counters of a query of several statements are summed up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteQueryStats {
+    pub(crate) fullscan_steps: u64,
+    pub(crate) sorts: u64,
+    pub(crate) autoindexes: u64,
+    pub(crate) vm_steps: u64,
+    pub(crate) memory_used: u64,
 }
 
 impl SqliteQueryResult {
@@ -11,16 +37,63 @@ impl SqliteQueryResult {
         self.changes
     }
 
+    /// The number of rows inserted, updated or deleted by the query, including those changed
+    /// by triggers and foreign key actions, which [`rows_affected()`](Self::rows_affected) omits.
+    ///
+    /// See [`sqlite3_total_changes64()`](https://www.sqlite.org/c3ref/total_changes.html).
+    pub fn total_changes(&self) -> u64 {
+        self.total_changes
+    }
+
     pub fn last_insert_rowid(&self) -> i64 {
         self.last_insert_rowid
     }
+
+    /// The counters of the work SQLite did to execute the query.
+    pub fn stats(&self) -> SqliteQueryStats {
+        self.stats
+    }
+}
+
+impl SqliteQueryStats {
+    /// The number of times SQLite stepped forward in a table as part of a full table scan.
+    pub fn fullscan_steps(&self) -> u64 {
+        self.fullscan_steps
+    }
+
+    /// The number of sort operations.
+    pub fn sorts(&self) -> u64 {
+        self.sorts
+    }
+
+    /// The number of rows inserted into automatic indexes, created because there was no index
+    /// to use.
+    pub fn autoindexes(&self) -> u64 {
+        self.autoindexes
+    }
+
+    /// The number of virtual machine operations executed.
+    pub fn vm_steps(&self) -> u64 {
+        self.vm_steps
+    }
+
+    /// The approximate number of bytes of heap memory used to store the prepared statement.
+    pub fn memory_used(&self) -> u64 {
+        self.memory_used
+    }
 }
 
 impl Extend<SqliteQueryResult> for SqliteQueryResult {
     fn extend<T: IntoIterator<Item = SqliteQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.changes += elem.changes;
+            self.total_changes += elem.total_changes;
             self.last_insert_rowid = elem.last_insert_rowid;
+            self.stats.fullscan_steps += elem.stats.fullscan_steps;
+            self.stats.sorts += elem.stats.sorts;
+            self.stats.autoindexes += elem.stats.autoindexes;
+            self.stats.vm_steps += elem.stats.vm_steps;
+            self.stats.memory_used += elem.stats.memory_used;
         }
     }
 }
@@ -0,0 +1,87 @@
+use std::cmp;
+
+use futures_util::future::try_join_all;
+
+use sqlx_core::error::Error;
+use sqlx_core::executor::{Execute, Executor};
+
+use crate::{Sqlite, SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+
+/// A pool of read-only connections to a SQLite database in WAL mode, to run queries in parallel.
+///
+/// Each SQLite connection runs its statements on its own worker thread, and in WAL mode readers
+/// do not block each other, so [`par_fetch_all()`](Self::par_fetch_all) can run up to `readers`
+/// queries at once on separate threads, e.g. to scan disjoint ranges of a table:
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::{SqliteConnectOptions, SqliteReadPool};
+///
+/// let pool = SqliteReadPool::connect_with(SqliteConnectOptions::new().filename("data.db"), 4).await?;
+///
+/// let results = pool
+///     .par_fetch_all((0..4).map(|part| {
+///         sqlx::query("SELECT sum(amount) FROM sales WHERE id % 4 = ?").bind(part)
+///     }))
+///     .await?;
+/// ```
+///
+/// Writes go through a separate, writable connection or pool.
+#[derive(Debug, Clone)]
+pub struct SqliteReadPool {
+    pool: SqlitePool,
+}
+
+impl SqliteReadPool {
+    /// Open `readers` read-only connections to the database of `options`.
+    ///
+    /// Fails with [`Error::Configuration`] if the database is not in WAL mode, as readers would
+    /// block writers and the other way around otherwise.
+    pub async fn connect_with(options: SqliteConnectOptions, readers: u32) -> Result<Self, Error> {
+        let readers = cmp::max(readers, 1);
+
+        let pool = SqlitePoolOptions::new()
+            .min_connections(readers)
+            .max_connections(readers)
+            .connect_with(options.read_only(true))
+            .await?;
+
+        let journal_mode: String = crate::query_scalar::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&pool)
+            .await?;
+
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            pool.close().await;
+
+            return Err(Error::Configuration(
+                format!("SqliteReadPool requires a database in WAL mode, not {journal_mode}")
+                    .into(),
+            ));
+        }
+
+        Ok(Self { pool })
+    }
+
+    /// Run `queries` concurrently, each on a connection of its own, and return their rows in
+    /// the order of `queries`.
+    ///
+    /// If a query fails, the first error is returned and the other queries are dropped.
+    pub async fn par_fetch_all<'q, E>(
+        &self,
+        queries: impl IntoIterator<Item = E>,
+    ) -> Result<Vec<Vec<SqliteRow>>, Error>
+    where
+        E: 'q + Execute<'q, Sqlite>,
+    {
+        try_join_all(queries.into_iter().map(|query| self.pool.fetch_all(query))).await
+    }
+
+    /// The pool of the read-only connections.
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Close all connections.
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+}
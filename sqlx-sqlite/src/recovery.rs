@@ -0,0 +1,242 @@
+//! Point-in-time restore from backups and a write journal.
+//!
+//! A [`SqliteRecovery`] takes backups of a database whose writes are recorded by a
+//! [`SqliteJournaledConnection`], and restores the database as it was at a given time by copying
+//! the latest backup taken before it and replaying the journal entries recorded after the backup:
+//!
+//! ```rust,ignore
+//! use std::time::{Duration, SystemTime};
+//! use sqlx::sqlite::recovery::SqliteRecovery;
+//! use sqlx::sqlite::SqliteJournaledConnection;
+//!
+//! let recovery = SqliteRecovery::new("backups", "data.journal");
+//! let mut conn = SqliteJournaledConnection::open(conn, "data.journal")?;
+//!
+//! // e.g. once a day
+//! recovery.backup(&mut conn).await?;
+//!
+//! // restore the state of an hour ago
+//! let time = SystemTime::now() - Duration::from_secs(3600);
+//! recovery.restore_to("restored.db", time).await?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sqlx_core::connection::{ConnectOptions, Connection};
+use sqlx_core::error::Error;
+use sqlx_core::executor::Executor;
+
+use crate::{SqliteConnectOptions, SqliteJournalEntry, SqliteJournaledConnection};
+
+/// Backups and the write journal of a database, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct SqliteRecovery {
+    backup_dir: PathBuf,
+    journal: PathBuf,
+}
+
+/// A backup taken by [`SqliteRecovery::backup()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteBackup {
+    path: PathBuf,
+    timestamp: SystemTime,
+    // the number of journal entries contained in the backup
+    journal_entries: u64,
+}
+
+impl SqliteRecovery {
+    /// Keep backups in `backup_dir`, which is created if it is missing, for the database whose
+    /// writes are recorded in the journal at `journal`.
+    pub fn new(backup_dir: impl AsRef<Path>, journal: impl AsRef<Path>) -> Self {
+        Self {
+            backup_dir: backup_dir.as_ref().to_owned(),
+            journal: journal.as_ref().to_owned(),
+        }
+    }
+
+    /// Back up the database of `conn`, whose journal must be the one of this recovery.
+    ///
+    /// The backup is a consistent snapshot taken with
+    /// [`VACUUM INTO`](https://www.sqlite.org/lang_vacuum.html#vacuuminto), which includes the
+    /// changes not yet checkpointed from the WAL.
+    pub async fn backup(
+        &self,
+        conn: &mut SqliteJournaledConnection,
+    ) -> Result<SqliteBackup, Error> {
+        crate::fs::create_dir_all(&self.backup_dir).await?;
+
+        let timestamp = SystemTime::now();
+        let journal_entries = conn.entries();
+
+        let path = self
+            .backup_dir
+            .join(backup_file_name(timestamp, journal_entries));
+        let temp_path = path.with_extension("tmp");
+
+        let temp_path_str = temp_path
+            .to_str()
+            .ok_or_else(|| Error::Configuration("the backup path must be UTF-8".into()))?;
+
+        // a partial backup must not be picked up by a restore
+        conn.connection()
+            .execute(sqlx_core::query::query("VACUUM INTO ?").bind(temp_path_str))
+            .await?;
+
+        crate::fs::rename(&temp_path, &path).await?;
+
+        Ok(SqliteBackup {
+            path,
+            timestamp: round_to_micros(timestamp),
+            journal_entries,
+        })
+    }
+
+    /// The backups, oldest first.
+    pub fn backups(&self) -> Result<Vec<SqliteBackup>, Error> {
+        let mut backups = Vec::new();
+
+        let dir = match std::fs::read_dir(&self.backup_dir) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(backups),
+            Err(e) => return Err(e.into()),
+        };
+
+        for entry in dir {
+            let path = entry?.path();
+
+            if let Some(backup) = parse_backup_path(path) {
+                backups.push(backup);
+            }
+        }
+
+        backups.sort_by_key(|backup| backup.timestamp);
+
+        Ok(backups)
+    }
+
+    /// Restore the database as it was at `timestamp` to a new database file at `target`.
+    ///
+    /// Copies the latest backup taken at or before `timestamp` and replays the journal entries
+    /// recorded after the backup, up to and including `timestamp`. Returns the number of
+    /// entries replayed.
+    ///
+    /// The database is restored to a temporary file next to `target`, which is renamed to
+    /// `target` once the restore succeeded and removed otherwise.
+    pub async fn restore_to(
+        &self,
+        target: impl AsRef<Path>,
+        timestamp: SystemTime,
+    ) -> Result<u64, Error> {
+        let target = target.as_ref();
+
+        if target.exists() {
+            return Err(Error::Configuration(
+                format!("the restore target {} already exists", target.display()).into(),
+            ));
+        }
+
+        let backup = self
+            .backups()?
+            .into_iter()
+            .rev()
+            .find(|backup| backup.timestamp <= timestamp)
+            .ok_or_else(|| {
+                Error::Configuration(
+                    format!(
+                        "no backup in {} before the restore time",
+                        self.backup_dir.display()
+                    )
+                    .into(),
+                )
+            })?;
+
+        let mut temp_path = target.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        // a partial restore must not be mistaken for a complete one
+        match self
+            .replay_onto_backup(&backup, &temp_path, timestamp)
+            .await
+        {
+            Ok(replayed) => {
+                crate::fs::rename(&temp_path, target).await?;
+                Ok(replayed)
+            }
+            Err(e) => {
+                crate::fs::remove_file(&temp_path).await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    async fn replay_onto_backup(
+        &self,
+        backup: &SqliteBackup,
+        path: &Path,
+        timestamp: SystemTime,
+    ) -> Result<u64, Error> {
+        std::fs::copy(&backup.path, path)?;
+
+        let mut conn = SqliteConnectOptions::new().filename(path).connect().await?;
+        let mut replayed = 0;
+
+        let entries = SqliteJournalEntry::read_all(&self.journal)?;
+
+        for entry in entries.iter().skip(backup.journal_entries as usize) {
+            if entry.timestamp() > timestamp {
+                break;
+            }
+
+            entry.replay(&mut conn).await?;
+            replayed += 1;
+        }
+
+        conn.close().await?;
+
+        Ok(replayed)
+    }
+}
+
+impl SqliteBackup {
+    /// The path of the backup file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// When the backup was taken.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+// `<microseconds since the epoch>-<journal entries>.db`
+fn backup_file_name(timestamp: SystemTime, journal_entries: u64) -> String {
+    let micros = timestamp
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+
+    format!("{micros:020}-{journal_entries}.db")
+}
+
+fn parse_backup_path(path: PathBuf) -> Option<SqliteBackup> {
+    if path.extension()? != "db" {
+        return None;
+    }
+
+    let (micros, journal_entries) = path.file_stem()?.to_str()?.split_once('-')?;
+
+    Some(SqliteBackup {
+        timestamp: UNIX_EPOCH + Duration::from_micros(micros.parse().ok()?),
+        journal_entries: journal_entries.parse().ok()?,
+        path,
+    })
+}
+
+fn round_to_micros(timestamp: SystemTime) -> SystemTime {
+    let since_epoch = timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    UNIX_EPOCH + Duration::from_micros(since_epoch.as_micros() as u64)
+}
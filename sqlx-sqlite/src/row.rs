@@ -3,19 +3,25 @@
 use std::sync::Arc;
 
 use sqlx_core::column::ColumnIndex;
-use sqlx_core::error::Error;
+use sqlx_core::decode::Decode;
+use sqlx_core::error::{mismatched_types, BoxDynError, Error, UnexpectedNullError};
 use sqlx_core::ext::ustr::UStr;
 use sqlx_core::row::Row;
+use sqlx_core::type_info::TypeInfo;
+use sqlx_core::types::Type;
+use sqlx_core::value::ValueRef;
 use sqlx_core::HashMap;
 
 use crate::statement::StatementHandle;
-use crate::{Sqlite, SqliteColumn, SqliteValue, SqliteValueRef};
+use crate::value::{DecodeSettings, ValueBuffer};
+use crate::{Sqlite, SqliteColumn, SqliteTypeRegistry, SqliteValue, SqliteValueRef};
 
 /// Implementation of [`Row`] for SQLite.
 pub struct SqliteRow {
     pub(crate) values: Box<[SqliteValue]>,
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) type_registry: Option<Arc<SqliteTypeRegistry>>,
 }
 
 // Accessing values from the statement object is
@@ -32,24 +38,133 @@ impl SqliteRow {
         statement: &StatementHandle,
         columns: &Arc<Vec<SqliteColumn>>,
         column_names: &Arc<HashMap<UStr, usize>>,
+        decode_settings: DecodeSettings,
+        type_registry: Option<&Arc<SqliteTypeRegistry>>,
     ) -> Self {
-        let size = statement.column_count();
-        let mut values = Vec::with_capacity(size);
-
-        for i in 0..size {
-            values.push(unsafe {
-                let raw = statement.column_value(i);
-
-                SqliteValue::new(raw, columns[i].type_info.clone())
-            });
-        }
+        let buffer = Arc::new(ValueBuffer::read(statement));
+
+        let values: Vec<SqliteValue> = (0..statement.column_count())
+            .map(|i| {
+                SqliteValue::new(
+                    Arc::clone(&buffer),
+                    i,
+                    columns[i].type_info.clone(),
+                    decode_settings,
+                )
+            })
+            .collect();
 
         Self {
             values: values.into_boxed_slice(),
             columns: Arc::clone(columns),
             column_names: Arc::clone(column_names),
+            type_registry: type_registry.cloned(),
+        }
+    }
+
+    /// Build a row from column names and values, e.g. to return canned rows from a mock in
+    /// unit tests.
+    ///
+    /// The type of each column is the type the value was built from. If several columns have
+    /// the same name, accessing them by name returns the last one.
+    ///
+    /// ```rust,no_run
+    /// use sqlx_core::row::Row;
+    /// use sqlx_sqlite::{SqliteRow, SqliteValue};
+    ///
+    /// let row = SqliteRow::from_values([
+    ///     ("id", SqliteValue::from_encodable(1_i64)),
+    ///     ("name", SqliteValue::from_encodable("Alice")),
+    ///     ("email", SqliteValue::from_encodable(None::<String>)),
+    /// ]);
+    ///
+    /// assert_eq!(row.get::<&str, _>("name"), "Alice");
+    /// ```
+    pub fn from_values<N>(values: impl IntoIterator<Item = (N, SqliteValue)>) -> Self
+    where
+        N: Into<String>,
+    {
+        let mut columns = Vec::new();
+        let mut column_names = HashMap::default();
+
+        let values: Vec<SqliteValue> = values
+            .into_iter()
+            .enumerate()
+            .map(|(ordinal, (name, value))| {
+                let name = UStr::from(name.into());
+
+                column_names.insert(name.clone(), ordinal);
+                columns.push(SqliteColumn {
+                    name,
+                    ordinal,
+                    type_info: value.type_info.clone(),
+                    declared_type: None,
+                });
+
+                value
+            })
+            .collect();
+
+        Self {
+            values: values.into_boxed_slice(),
+            columns: Arc::new(columns),
+            column_names: Arc::new(column_names),
+            type_registry: None,
         }
     }
+
+    /// Decode the value at `index` with the decoder the
+    /// [`type_registry`](crate::SqliteConnectOptions::type_registry) has for `T` and the declared
+    /// type of the column, see [`SqliteTypeRegistry`].
+    ///
+    /// This is the fallback for types that do not implement [`Decode`], which
+    /// [`try_get()`](Row::try_get) requires. Fails with [`Error::ColumnDecode`] if no such
+    /// decoder is registered, e.g. because the column is an expression without a declared type,
+    /// or if the value is `NULL`; see [`try_get_registered_opt()`](Self::try_get_registered_opt)
+    /// for nullable columns.
+    pub fn try_get_registered<T, I>(&self, index: I) -> Result<T, Error>
+    where
+        T: 'static,
+        I: ColumnIndex<Self>,
+    {
+        self.try_get_registered_opt(&index)?
+            .ok_or_else(|| Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source: UnexpectedNullError.into(),
+            })
+    }
+
+    /// Like [`try_get_registered()`](Self::try_get_registered), but returns `None` for `NULL`.
+    pub fn try_get_registered_opt<T, I>(&self, index: I) -> Result<Option<T>, Error>
+    where
+        T: 'static,
+        I: ColumnIndex<Self>,
+    {
+        let ordinal = index.index(self)?;
+        let value = SqliteValueRef::value(&self.values[ordinal]);
+        let declared_type = self.columns[ordinal].declared_type.as_deref();
+
+        let decoded = self
+            .type_registry
+            .as_ref()
+            .zip(declared_type)
+            .and_then(|(registry, declared_type)| registry.decode::<T>(declared_type, value))
+            .ok_or_else(|| {
+                format!(
+                    "no decoder for {} is registered for the declared type {}",
+                    std::any::type_name::<T>(),
+                    declared_type.unwrap_or("(none)"),
+                )
+            });
+
+        decoded
+            .map_err(BoxDynError::from)
+            .and_then(|decoded| decoded)
+            .map_err(|source| Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source,
+            })
+    }
 }
 
 impl Row for SqliteRow {
@@ -66,6 +181,38 @@ impl Row for SqliteRow {
         let index = index.index(self)?;
         Ok(SqliteValueRef::value(&self.values[index]))
     }
+
+    // overridden to also admit the conversions enabled by `SqliteCoercions`
+    fn try_get<'r, T, I>(&'r self, index: I) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Sqlite> + Type<Sqlite>,
+    {
+        let value = self.try_get_raw(&index)?;
+
+        value
+            .check_strict_affinity()
+            .map_err(|source| Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source,
+            })?;
+
+        if !value.is_null() {
+            let ty = value.type_info();
+
+            if !ty.is_null() && !T::compatible(&ty) && !value.is_coercible_to::<T>() {
+                return Err(Error::ColumnDecode {
+                    index: format!("{index:?}"),
+                    source: mismatched_types::<Sqlite, T>(&ty),
+                });
+            }
+        }
+
+        T::decode(value).map_err(|source| Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source,
+        })
+    }
 }
 
 impl ColumnIndex<SqliteRow> for &'_ str {
@@ -0,0 +1,95 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use futures_intrusive::sync::{Mutex, MutexGuard};
+
+use sqlx_core::connection::ConnectOptions;
+use sqlx_core::error::Error;
+use sqlx_core::executor::{Execute, Executor};
+
+use crate::{Sqlite, SqliteConnectOptions, SqliteConnection, SqliteQueryResult};
+
+/// A cloneable handle to a single connection that runs the writes of all its clones one at a
+/// time, in the order they were queued.
+///
+/// SQLite allows only one writer per database at a time; concurrent writers on different
+/// connections wait for each other's locks and may fail with `SQLITE_BUSY`. Sending all writes
+/// through one `SqliteSerializedWriter` avoids this without a mutex of your own:
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::{SqliteConnectOptions, SqliteSerializedWriter};
+///
+/// let writer = SqliteSerializedWriter::connect_with(&SqliteConnectOptions::new().filename("data.db")).await?;
+///
+/// for event in events {
+///     let writer = writer.clone();
+///
+///     tokio::spawn(async move {
+///         writer.execute(sqlx::query("INSERT INTO events (name) VALUES (?)").bind(event)).await
+///     });
+/// }
+/// ```
+///
+/// The connection runs its statements on its own worker thread, like every SQLite connection.
+/// Use [`lock()`](Self::lock) to run several statements, e.g. a transaction, without writes of
+/// other clones in between. The connection is closed when the last clone is dropped.
+#[derive(Clone)]
+pub struct SqliteSerializedWriter {
+    conn: Arc<Mutex<SqliteConnection>>,
+}
+
+impl SqliteSerializedWriter {
+    /// Open the write connection to the database of `options`.
+    pub async fn connect_with(options: &SqliteConnectOptions) -> Result<Self, Error> {
+        Ok(Self::new(options.connect().await?))
+    }
+
+    /// Queue the writes of all clones onto `conn`.
+    pub fn new(conn: SqliteConnection) -> Self {
+        Self {
+            // fair, so writes run in the order they were queued
+            conn: Arc::new(Mutex::new(conn, true)),
+        }
+    }
+
+    /// Wait for the writes queued before, then execute `query`.
+    pub async fn execute<'q, E>(&self, query: E) -> Result<SqliteQueryResult, Error>
+    where
+        E: 'q + Execute<'q, Sqlite>,
+    {
+        let mut conn = self.conn.lock().await;
+
+        conn.execute(query).await
+    }
+
+    /// Wait for the writes queued before, then take the connection until the guard is dropped.
+    pub async fn lock(&self) -> SqliteWriterGuard<'_> {
+        SqliteWriterGuard(self.conn.lock().await)
+    }
+}
+
+/// The connection of a [`SqliteSerializedWriter`], taken with
+/// [`lock()`](SqliteSerializedWriter::lock).
+pub struct SqliteWriterGuard<'a>(MutexGuard<'a, SqliteConnection>);
+
+impl Deref for SqliteWriterGuard<'_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SqliteWriterGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Debug for SqliteSerializedWriter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteSerializedWriter")
+            .finish_non_exhaustive()
+    }
+}
@@ -10,14 +10,14 @@ use std::str::{from_utf8, from_utf8_unchecked};
 use libsqlite3_sys::{
     sqlite3, sqlite3_bind_blob64, sqlite3_bind_double, sqlite3_bind_int, sqlite3_bind_int64,
     sqlite3_bind_null, sqlite3_bind_parameter_count, sqlite3_bind_parameter_name,
-    sqlite3_bind_text64, sqlite3_changes, sqlite3_clear_bindings, sqlite3_column_blob,
+    sqlite3_bind_text64, sqlite3_changes64, sqlite3_clear_bindings, sqlite3_column_blob,
     sqlite3_column_bytes, sqlite3_column_count, sqlite3_column_database_name,
     sqlite3_column_decltype, sqlite3_column_double, sqlite3_column_int, sqlite3_column_int64,
     sqlite3_column_name, sqlite3_column_origin_name, sqlite3_column_table_name,
     sqlite3_column_type, sqlite3_column_value, sqlite3_db_handle, sqlite3_finalize, sqlite3_reset,
-    sqlite3_sql, sqlite3_step, sqlite3_stmt, sqlite3_stmt_readonly, sqlite3_table_column_metadata,
-    sqlite3_value, SQLITE_DONE, SQLITE_LOCKED_SHAREDCACHE, SQLITE_MISUSE, SQLITE_OK, SQLITE_ROW,
-    SQLITE_TRANSIENT, SQLITE_UTF8,
+    sqlite3_sql, sqlite3_step, sqlite3_stmt, sqlite3_stmt_readonly, sqlite3_stmt_status,
+    sqlite3_table_column_metadata, sqlite3_value, SQLITE_DONE, SQLITE_LOCKED_SHAREDCACHE,
+    SQLITE_MISUSE, SQLITE_OK, SQLITE_ROW, SQLITE_TRANSIENT, SQLITE_UTF8,
 };
 
 use crate::error::{BoxDynError, Error};
@@ -53,6 +53,52 @@ impl StatementHandle {
         unsafe { sqlite3_stmt_readonly(self.0.as_ptr()) != 0 }
     }
 
+    /// Whether this is a `BEGIN`, `COMMIT`, `END`, `ROLLBACK`, `SAVEPOINT`, `RELEASE`, `ATTACH`
+    /// or `DETACH` statement.
+    ///
+    /// SQLite has no API for the kind of a statement, so this checks the first keyword.
+    pub(crate) fn controls_transaction(&self) -> bool {
+        const KEYWORDS: [&str; 8] = [
+            "BEGIN",
+            "COMMIT",
+            "END",
+            "ROLLBACK",
+            "SAVEPOINT",
+            "RELEASE",
+            "ATTACH",
+            "DETACH",
+        ];
+
+        let mut sql = self.sql();
+
+        // skip whitespace and comments
+        loop {
+            sql = sql.trim_start();
+
+            if let Some(comment) = sql.strip_prefix("--") {
+                sql = comment.split_once('\n').map_or("", |(_, rest)| rest);
+            } else if let Some(comment) = sql.strip_prefix("/*") {
+                sql = comment.split_once("*/").map_or("", |(_, rest)| rest);
+            } else {
+                break;
+            }
+        }
+
+        let keyword = sql
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+
+        KEYWORDS
+            .iter()
+            .any(|candidate| keyword.eq_ignore_ascii_case(candidate))
+    }
+
+    pub(crate) fn status(&self, op: c_int, reset: bool) -> u64 {
+        // https://sqlite.org/c3ref/stmt_status.html
+        unsafe { sqlite3_stmt_status(self.0.as_ptr(), op, reset.into()) as u64 }
+    }
+
     pub(crate) fn sql(&self) -> &str {
         // https://sqlite.org/c3ref/expanded_sql.html
         unsafe {
@@ -79,7 +125,7 @@ impl StatementHandle {
         // returns the number of changes of the *last* statement; not
         // necessarily this statement.
         // https://sqlite.org/c3ref/changes.html
-        unsafe { sqlite3_changes(self.db_handle()) as u64 }
+        unsafe { sqlite3_changes64(self.db_handle()) as u64 }
     }
 
     #[inline]
@@ -106,6 +152,13 @@ impl StatementHandle {
 
     #[inline]
     pub(crate) fn column_decltype(&self, index: usize) -> Option<SqliteTypeInfo> {
+        let ty: DataType = self.column_decltype_name(index)?.parse().ok()?;
+
+        Some(SqliteTypeInfo(ty))
+    }
+
+    /// The type the column was declared with, verbatim.
+    pub(crate) fn column_decltype_name(&self, index: usize) -> Option<&str> {
         unsafe {
             let decl = sqlite3_column_decltype(self.0.as_ptr(), index as c_int);
             if decl.is_null() {
@@ -114,10 +167,26 @@ impl StatementHandle {
                 return None;
             }
 
-            let decl = from_utf8_unchecked(CStr::from_ptr(decl).to_bytes());
-            let ty: DataType = decl.parse().ok()?;
+            Some(from_utf8_unchecked(CStr::from_ptr(decl).to_bytes()))
+        }
+    }
+
+    /// The table and column that the column of the result set comes from, if it is not an
+    /// expression.
+    pub(crate) fn column_origin(&self, index: usize) -> Option<(&str, &str)> {
+        unsafe {
+            // https://sqlite.org/c3ref/column_database_name.html
+            let table_name = sqlite3_column_table_name(self.0.as_ptr(), index as c_int);
+            let origin_name = sqlite3_column_origin_name(self.0.as_ptr(), index as c_int);
 
-            Some(SqliteTypeInfo(ty))
+            if table_name.is_null() || origin_name.is_null() {
+                return None;
+            }
+
+            Some((
+                from_utf8(CStr::from_ptr(table_name).to_bytes()).ok()?,
+                from_utf8(CStr::from_ptr(origin_name).to_bytes()).ok()?,
+            ))
         }
     }
 
@@ -155,6 +224,15 @@ impl StatementHandle {
             );
 
             if status != SQLITE_OK {
+                // table-valued PRAGMA functions, e.g. `pragma_table_info('t')`, are eponymous
+                // virtual tables without column metadata
+                if CStr::from_ptr(table_name)
+                    .to_bytes()
+                    .starts_with(b"pragma_")
+                {
+                    return Ok(None);
+                }
+
                 // implementation note: the docs for sqlite3_table_column_metadata() specify
                 // that an error can be returned if the column came from a view; however,
                 // experimentally we found that the above functions give us the true origin
@@ -270,6 +348,11 @@ impl StatementHandle {
         unsafe { sqlite3_column_value(self.0.as_ptr(), index as c_int) }
     }
 
+    #[inline]
+    pub(crate) fn column_bytes(&self, index: usize) -> usize {
+        unsafe { sqlite3_column_bytes(self.0.as_ptr(), index as c_int) as usize }
+    }
+
     pub(crate) fn column_blob(&self, index: usize) -> &[u8] {
         let index = index as c_int;
         let len = unsafe { sqlite3_column_bytes(self.0.as_ptr(), index) } as usize;
@@ -22,6 +22,10 @@ pub struct SqliteStatement<'q> {
     pub(crate) parameters: usize,
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) read_only: bool,
+    // whether any of the statements controls transactions or attaches databases, which SQLite
+    // reports as read-only
+    pub(crate) controls_transaction: bool,
 }
 
 impl<'q> Statement<'q> for SqliteStatement<'q> {
@@ -33,6 +37,8 @@ impl<'q> Statement<'q> for SqliteStatement<'q> {
             parameters: self.parameters,
             columns: Arc::clone(&self.columns),
             column_names: Arc::clone(&self.column_names),
+            read_only: self.read_only,
+            controls_transaction: self.controls_transaction,
         }
     }
 
@@ -48,6 +54,14 @@ impl<'q> Statement<'q> for SqliteStatement<'q> {
         &self.columns
     }
 
+    fn is_read_only(&self) -> Option<bool> {
+        Some(self.read_only)
+    }
+
+    fn controls_transaction(&self) -> Option<bool> {
+        Some(self.controls_transaction)
+    }
+
     impl_statement_query!(SqliteArguments<'_>);
 }
 
@@ -89,25 +89,7 @@ impl VirtualStatement {
             }
 
             if let Some(statement) = prepare(conn.as_ptr(), &mut self.tail, self.persistent)? {
-                let num = statement.column_count();
-
-                let mut columns = Vec::with_capacity(num);
-                let mut column_names = HashMap::with_capacity(num);
-
-                for i in 0..num {
-                    let name: UStr = statement.column_name(i).to_owned().into();
-                    let type_info = statement
-                        .column_decltype(i)
-                        .unwrap_or_else(|| statement.column_type_info(i));
-
-                    columns.push(SqliteColumn {
-                        ordinal: i,
-                        name: name.clone(),
-                        type_info,
-                    });
-
-                    column_names.insert(name, i);
-                }
+                let (columns, column_names) = columns_of(&statement);
 
                 self.handles.push(statement);
                 self.columns.push(Arc::new(columns));
@@ -128,6 +110,51 @@ impl VirtualStatement {
             })
     }
 
+    /// Prepare the current statement again, e.g. after the schema changed.
+    pub(crate) fn reprepare_current(&mut self, conn: &mut ConnectionHandle) -> Result<(), Error> {
+        let Some(idx) = self.index.filter(|&idx| idx < self.handles.len()) else {
+            return Ok(());
+        };
+
+        let mut sql = Bytes::from(self.handles[idx].sql().to_owned());
+
+        if let Some(statement) = prepare(conn.as_ptr(), &mut sql, self.persistent)? {
+            let (columns, column_names) = columns_of(&statement);
+
+            self.handles[idx] = statement;
+            self.columns[idx] = Arc::new(columns);
+            self.column_names[idx] = Arc::new(column_names);
+        }
+
+        Ok(())
+    }
+
+    /// Update the columns of the current statement if SQLite recompiled it after a schema change,
+    /// e.g. because a table queried with `SELECT *` gained a column or a column was declared
+    /// with another type.
+    pub(crate) fn refresh_current_columns(&mut self) {
+        let Some(idx) = self.index.filter(|&idx| idx < self.handles.len()) else {
+            return;
+        };
+
+        let handle = &self.handles[idx];
+        let columns = &self.columns[idx];
+
+        let changed = handle.column_count() != columns.len()
+            || columns.iter().any(|column| {
+                handle.column_name(column.ordinal) != &*column.name
+                    || handle.column_decltype_name(column.ordinal)
+                        != column.declared_type.as_deref()
+            });
+
+        if changed {
+            let (columns, column_names) = columns_of(handle);
+
+            self.columns[idx] = Arc::new(columns);
+            self.column_names[idx] = Arc::new(column_names);
+        }
+    }
+
     pub fn reset(&mut self) -> Result<(), Error> {
         self.index = None;
 
@@ -140,6 +167,31 @@ impl VirtualStatement {
     }
 }
 
+fn columns_of(statement: &StatementHandle) -> (Vec<SqliteColumn>, HashMap<UStr, usize>) {
+    let num = statement.column_count();
+
+    let mut columns = Vec::with_capacity(num);
+    let mut column_names = HashMap::with_capacity(num);
+
+    for i in 0..num {
+        let name: UStr = statement.column_name(i).to_owned().into();
+        let type_info = statement
+            .column_decltype(i)
+            .unwrap_or_else(|| statement.column_type_info(i));
+
+        columns.push(SqliteColumn {
+            ordinal: i,
+            name: name.clone(),
+            type_info,
+            declared_type: statement.column_decltype_name(i).map(UStr::new),
+        });
+
+        column_names.insert(name, i);
+    }
+
+    (columns, column_names)
+}
+
 fn prepare(
     conn: *mut sqlite3,
     query: &mut Bytes,
@@ -0,0 +1,251 @@
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures_intrusive::sync::Mutex;
+
+#[cfg(feature = "migrate")]
+use sqlx_core::migrate::Migrator;
+
+use sqlx_core::error::Error;
+use sqlx_core::IndexMap;
+
+use crate::{SqliteConnectOptions, SqliteSerializedWriter};
+
+/// Opens the database files of tenants on demand and keeps the most recently used ones open.
+///
+/// Each tenant has a database file of its own, `<directory>/<tenant>.db`, with a single
+/// connection shared by all users of the tenant through a [`SqliteSerializedWriter`]:
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::{SqliteConnectOptions, SqliteTenantManager};
+///
+/// let tenants = SqliteTenantManager::new("data/tenants", SqliteConnectOptions::new())
+///     .max_open(64)
+///     .migrator(sqlx::migrate!());
+///
+/// let conn = tenants.get("acme").await?;
+/// conn.execute(sqlx::query("INSERT INTO orders (item) VALUES (?)").bind("anvil")).await?;
+/// ```
+///
+/// Once more than [`max_open`](Self::max_open) tenants are open, the least recently used tenant
+/// is closed. Its connection stays usable through the handles returned by
+/// [`get()`](Self::get) before and is closed when the last of them is dropped, so the limit of
+/// open files may briefly be exceeded. Tenants that are being opened are not closed, so that
+/// their database is never opened twice at once.
+pub struct SqliteTenantManager {
+    directory: PathBuf,
+    options: SqliteConnectOptions,
+    max_open: usize,
+    #[cfg(feature = "migrate")]
+    migrator: Option<Arc<Migrator>>,
+    // least recently used first; only locked briefly, opening a tenant locks its slot instead
+    tenants: Mutex<IndexMap<String, TenantSlot>>,
+}
+
+// `None` until the tenant is opened; locked while it is opened, so that its migrations run once
+type TenantSlot = Arc<Mutex<Option<SqliteSerializedWriter>>>;
+
+impl SqliteTenantManager {
+    /// Manage the tenant databases in `directory`, connecting with `options`.
+    ///
+    /// The filename of `options` is replaced by the file of each tenant, which is created if it
+    /// is missing.
+    pub fn new(directory: impl AsRef<Path>, options: SqliteConnectOptions) -> Self {
+        Self {
+            directory: directory.as_ref().to_owned(),
+            options: options.create_if_missing(true),
+            max_open: 100,
+            #[cfg(feature = "migrate")]
+            migrator: None,
+            tenants: Mutex::new(IndexMap::new(), false),
+        }
+    }
+
+    /// Set the maximum number of tenants kept open at once.
+    ///
+    /// The default is 100.
+    pub fn max_open(mut self, max_open: usize) -> Self {
+        self.max_open = cmp::max(max_open, 1);
+        self
+    }
+
+    /// Run the migrations of `migrator` on the database of each tenant when it is opened.
+    #[cfg(feature = "migrate")]
+    pub fn migrator(mut self, migrator: Migrator) -> Self {
+        self.migrator = Some(Arc::new(migrator));
+        self
+    }
+
+    /// Get the connection of `tenant`, opening its database if it is not open.
+    ///
+    /// Tenant IDs may only contain ASCII letters, digits, `-` and `_`, so that they cannot name
+    /// files outside of the directory. They are case-insensitive, as the filesystem may be.
+    ///
+    /// Opening a tenant only waits for other calls opening the same tenant, not for others.
+    pub async fn get(&self, tenant: &str) -> Result<SqliteSerializedWriter, Error> {
+        let tenant = &*normalize(tenant)?;
+        let path = self.path(tenant)?;
+
+        loop {
+            let slot = self.slot(tenant).await;
+            let mut opened = slot.lock().await;
+
+            if let Some(writer) = &*opened {
+                return Ok(writer.clone());
+            }
+
+            // the slot was removed while waiting for it, e.g. because opening the tenant failed
+            // in another call; start over instead of opening it into a slot nobody else sees
+            if !self.is_managed(tenant, &slot).await {
+                continue;
+            }
+
+            match self.open(&path).await {
+                Ok(writer) => {
+                    *opened = Some(writer.clone());
+                    return Ok(writer);
+                }
+
+                Err(error) => {
+                    let mut tenants = self.tenants.lock().await;
+
+                    if tenants.get(tenant).is_some_and(|s| Arc::ptr_eq(s, &slot)) {
+                        tenants.shift_remove(tenant);
+                    }
+
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    // the slot of `tenant`, marked as the most recently used; a new one if it is not open
+    async fn slot(&self, tenant: &str) -> TenantSlot {
+        let mut tenants = self.tenants.lock().await;
+
+        if let Some(index) = tenants.get_index_of(tenant) {
+            let last = tenants.len() - 1;
+            tenants.move_index(index, last);
+
+            return Arc::clone(&tenants[last]);
+        }
+
+        while tenants.len() >= self.max_open {
+            // a slot that is locked may be opening its tenant, which must finish first
+            let Some(index) = tenants.values().position(|slot| slot.try_lock().is_some()) else {
+                break;
+            };
+
+            tenants.shift_remove_index(index);
+        }
+
+        let slot = Arc::new(Mutex::new(None, false));
+        tenants.insert(tenant.to_owned(), Arc::clone(&slot));
+
+        slot
+    }
+
+    async fn is_managed(&self, tenant: &str, slot: &TenantSlot) -> bool {
+        self.tenants
+            .lock()
+            .await
+            .get(tenant)
+            .is_some_and(|s| Arc::ptr_eq(s, slot))
+    }
+
+    async fn open(&self, path: &Path) -> Result<SqliteSerializedWriter, Error> {
+        let writer =
+            SqliteSerializedWriter::connect_with(&self.options.clone().filename(path)).await?;
+
+        #[cfg(feature = "migrate")]
+        if let Some(migrator) = &self.migrator {
+            migrator.run(&mut *writer.lock().await).await?;
+        }
+
+        Ok(writer)
+    }
+
+    /// Get the path of the database file of `tenant`.
+    pub fn path(&self, tenant: &str) -> Result<PathBuf, Error> {
+        Ok(self.directory.join(format!("{}.db", normalize(tenant)?)))
+    }
+
+    /// The IDs of the open tenants in lowercase, least recently used first, including those being
+    /// opened.
+    pub async fn open_tenants(&self) -> Vec<String> {
+        self.tenants.lock().await.keys().cloned().collect()
+    }
+
+    /// Close the database of `tenant`, if it is open.
+    ///
+    /// If the tenant is being opened, this waits until it is. Returns `false` if it was not
+    /// open.
+    pub async fn close(&self, tenant: &str) -> bool {
+        let Ok(tenant) = normalize(tenant) else {
+            return false;
+        };
+
+        let Some(slot) = self.tenants.lock().await.get(&tenant).cloned() else {
+            return false;
+        };
+
+        self.close_slot(&tenant, &slot).await
+    }
+
+    /// Close the databases of all tenants, waiting for those being opened.
+    pub async fn close_all(&self) {
+        let slots: Vec<(String, TenantSlot)> = self
+            .tenants
+            .lock()
+            .await
+            .iter()
+            .map(|(tenant, slot)| (tenant.clone(), Arc::clone(slot)))
+            .collect();
+
+        for (tenant, slot) in slots {
+            self.close_slot(&tenant, &slot).await;
+        }
+    }
+
+    async fn close_slot(&self, tenant: &str, slot: &TenantSlot) -> bool {
+        // removing the slot while the tenant is opened would let it be opened a second time
+        let _opened = slot.lock().await;
+
+        let mut tenants = self.tenants.lock().await;
+
+        if !tenants.get(tenant).is_some_and(|s| Arc::ptr_eq(s, slot)) {
+            return false;
+        }
+
+        tenants.shift_remove(tenant);
+        true
+    }
+}
+
+// validates `tenant` and converts it to lowercase
+fn normalize(tenant: &str) -> Result<String, Error> {
+    let valid = !tenant.is_empty()
+        && tenant
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+
+    if !valid {
+        return Err(Error::Configuration(
+            format!("invalid tenant ID {tenant:?}").into(),
+        ));
+    }
+
+    Ok(tenant.to_ascii_lowercase())
+}
+
+impl Debug for SqliteTenantManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteTenantManager")
+            .field("directory", &self.directory)
+            .field("options", &self.options)
+            .field("max_open", &self.max_open)
+            .finish_non_exhaustive()
+    }
+}
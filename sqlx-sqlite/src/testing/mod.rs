@@ -1,14 +1,28 @@
+use crate::connection::ConnectOptions;
 use crate::error::Error;
+use crate::migrate::Migrator;
 use crate::pool::PoolOptions;
 use crate::testing::{FixtureSnapshot, TestArgs, TestContext, TestSupport};
-use crate::{Sqlite, SqliteConnectOptions};
+use crate::{Sqlite, SqliteConnectOptions, SqliteError};
 use futures_core::future::BoxFuture;
+use libsqlite3_sys::{
+    sqlite3, sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_step, SQLITE_OK,
+};
+use sqlx_core::connection::Connection;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::os::raw::c_char;
 use std::path::{Path, PathBuf};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub(crate) use sqlx_core::testing::*;
 
 const BASE_PATH: &str = "target/sqlx/test-dbs";
 
+// not a valid Rust identifier, so it cannot collide with the path of a test
+const TEMPLATES_PATH: &str = "target/sqlx/test-dbs/.templates";
+
 impl TestSupport for Sqlite {
     fn test_context(args: &TestArgs) -> BoxFuture<'_, Result<TestContext<Self>, Error>> {
         Box::pin(async move {
@@ -50,15 +64,121 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<Sqlite>, Error> {
             .expect("failed to remove database from previous test run");
     }
 
-    Ok(TestContext {
-        connect_opts: SqliteConnectOptions::new()
-            .filename(&db_path)
-            .create_if_missing(true),
+    // instead of running the migrations for every test, apply them once to a template database
+    // and clone that with the backup API, which is considerably faster
+    let migrations_applied = match args.migrator {
+        Some(migrator) => {
+            let template = migrated_template(migrator).await?;
+            clone_database(&template, &db_path).await?;
+            true
+        }
+        None => false,
+    };
+
+    let connect_opts = SqliteConnectOptions::new()
+        .filename(&db_path)
+        .create_if_missing(true);
+
+    Ok(TestContext::new(
         // This doesn't really matter for SQLite as the databases are independent of each other.
         // The main limitation is going to be the number of concurrent running tests.
-        pool_opts: PoolOptions::new().max_connections(1000),
-        db_name: db_path,
-    })
+        PoolOptions::new().max_connections(1000),
+        connect_opts,
+        db_path,
+    )
+    .with_migrations_applied(migrations_applied))
+}
+
+/// Get the path of a database with all migrations of `migrator` applied, creating it if necessary.
+async fn migrated_template(migrator: &Migrator) -> Result<PathBuf, Error> {
+    static TEMP_ID: AtomicUsize = AtomicUsize::new(0);
+
+    // templates are keyed by their migrations, so changed migrations get a fresh template
+    let mut hasher = DefaultHasher::new();
+
+    for migration in migrator.iter() {
+        migration.version.hash(&mut hasher);
+        migration.checksum.hash(&mut hasher);
+    }
+
+    let path = Path::new(TEMPLATES_PATH).join(format!("{:016x}.sqlite", hasher.finish()));
+
+    if path.exists() {
+        return Ok(path);
+    }
+
+    crate::fs::create_dir_all(TEMPLATES_PATH).await?;
+
+    // tests run concurrently, so migrate a private copy and move it into place atomically;
+    // if several tests race to do this, they all produce the same template
+    let temp_path = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        TEMP_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&temp_path)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+
+    migrator.run_direct(&mut conn).await?;
+    conn.close().await?;
+
+    crate::fs::rename(&temp_path, &path).await?;
+
+    Ok(path)
+}
+
+/// Copy the database at `template` to `db_path` using the
+/// [Online Backup API](https://www.sqlite.org/backup.html).
+async fn clone_database(template: &Path, db_path: &str) -> Result<(), Error> {
+    let mut source = SqliteConnectOptions::new()
+        .filename(template)
+        .read_only(true)
+        .connect()
+        .await?;
+
+    let mut target = SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+
+    {
+        let mut source_handle = source.lock_handle().await?;
+        let mut target_handle = target.lock_handle().await?;
+
+        // SAFETY: both handles are locked, so their worker threads are not using them
+        unsafe { backup(source_handle.as_raw_handle(), target_handle.as_raw_handle())? };
+    }
+
+    source.close().await?;
+    target.close().await
+}
+
+unsafe fn backup(source: NonNull<sqlite3>, target: NonNull<sqlite3>) -> Result<(), Error> {
+    // the name of the database to copy, as a C string
+    static MAIN: &[u8] = b"main\0";
+
+    let main = MAIN.as_ptr() as *const c_char;
+
+    // https://www.sqlite.org/c3ref/backup_finish.html
+    let backup = sqlite3_backup_init(target.as_ptr(), main, source.as_ptr(), main);
+
+    if backup.is_null() {
+        return Err(SqliteError::new(target.as_ptr()).into());
+    }
+
+    // copy all pages in one step; errors are reported by `sqlite3_backup_finish()`
+    sqlite3_backup_step(backup, -1);
+
+    if sqlite3_backup_finish(backup) != SQLITE_OK {
+        return Err(SqliteError::new(target.as_ptr()).into());
+    }
+
+    Ok(())
 }
 
 fn convert_path(test_path: &str) -> String {
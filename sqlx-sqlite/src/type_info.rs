@@ -27,6 +27,8 @@ pub(crate) enum DataType {
     Date,
     Time,
     Datetime,
+    Uuid,
+    Interval,
 }
 
 /// Type information for a SQLite type.
@@ -59,6 +61,8 @@ impl TypeInfo for SqliteTypeInfo {
             DataType::Date => "DATE",
             DataType::Time => "TIME",
             DataType::Datetime => "DATETIME",
+            DataType::Uuid => "UUID",
+            DataType::Interval => "INTERVAL",
         }
     }
 }
@@ -95,6 +99,11 @@ impl FromStr for DataType {
             "time" => DataType::Time,
             "datetime" | "timestamp" => DataType::Datetime,
 
+            "uuid" => DataType::Uuid,
+
+            // before the `int` affinity rule below, which would match it
+            "interval" => DataType::Interval,
+
             _ if s.contains("int") => DataType::Int64,
 
             _ if s.contains("char") || s.contains("clob") || s.contains("text") => DataType::Text,
@@ -148,6 +157,8 @@ fn test_data_type_from_str() -> Result<(), BoxDynError> {
     assert_eq!(DataType::Datetime, "DATETIME".parse()?);
     assert_eq!(DataType::Time, "TIME".parse()?);
     assert_eq!(DataType::Date, "DATE".parse()?);
+    assert_eq!(DataType::Uuid, "UUID".parse()?);
+    assert_eq!(DataType::Interval, "INTERVAL".parse()?);
 
     Ok(())
 }
@@ -0,0 +1,155 @@
+use std::any::{Any, TypeId};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use sqlx_core::error::{BoxDynError, Error};
+use sqlx_core::value::ValueRef;
+use sqlx_core::HashMap;
+
+use crate::{SqliteArgumentValue, SqliteValueRef};
+
+type DecodeFn =
+    dyn Fn(SqliteValueRef<'_>) -> Result<Box<dyn Any + Send>, BoxDynError> + Send + Sync;
+
+type EncodeFn = dyn Fn(&dyn Any) -> SqliteArgumentValue<'static> + Send + Sync;
+
+/// Encode and decode functions for Rust types that do not implement
+/// [`Encode`](sqlx_core::encode::Encode) and [`Decode`](sqlx_core::decode::Decode) for
+/// [`Sqlite`](crate::Sqlite), keyed by the declared type of their columns.
+///
+/// Applications whose schema is only known at run-time, or types from other crates that the
+/// orphan rule keeps from implementing the traits, would otherwise need a wrapper type for each
+/// of them. With a registry set on the connect options, values of columns declared with a
+/// registered type are decoded with [`SqliteRow::try_get_registered()`](crate::SqliteRow::try_get_registered),
+/// and values to bind are encoded with [`encode()`](Self::encode):
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::{SqliteArgumentValue, SqliteConnectOptions, SqliteTypeRegistry};
+///
+/// struct Point { x: f64, y: f64 }
+///
+/// let registry = SqliteTypeRegistry::new().register::<Point, _, _>(
+///     "POINT",
+///     |value| {
+///         let text = <&str as sqlx::Decode<sqlx::Sqlite>>::decode(value)?;
+///         let (x, y) = text.split_once(',').ok_or("not a point")?;
+///         Ok(Point { x: x.parse()?, y: y.parse()? })
+///     },
+///     |point| SqliteArgumentValue::Text(format!("{},{}", point.x, point.y).into()),
+/// );
+///
+/// let mut conn = SqliteConnectOptions::new()
+///     .filename("shapes.db")
+///     .type_registry(registry.clone())
+///     .connect()
+///     .await?;
+///
+/// sqlx::query("INSERT INTO shapes (origin) VALUES (?)")
+///     .bind(registry.encode(&Point { x: 1.0, y: 2.0 })?)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let row = sqlx::query("SELECT origin FROM shapes").fetch_one(&mut conn).await?;
+/// let origin: Point = row.try_get_registered("origin")?;
+/// ```
+///
+/// Declared types are matched case-insensitively and in full, e.g. a decoder for `POINT` does
+/// not apply to columns declared as `POINT(2)`.
+#[derive(Clone, Default)]
+pub struct SqliteTypeRegistry {
+    // keyed by the uppercased declared type
+    decoders: HashMap<(String, TypeId), Arc<DecodeFn>>,
+    encoders: HashMap<TypeId, Arc<EncodeFn>>,
+}
+
+impl SqliteTypeRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode `T` from the values of columns declared as `declared_type` with `decode`, and
+    /// encode it with `encode`.
+    ///
+    /// `T` may be registered for several declared types; the last encoder registered for it is
+    /// used. `decode` is not called for `NULL` values.
+    pub fn register<T, D, E>(mut self, declared_type: &str, decode: D, encode: E) -> Self
+    where
+        T: Send + 'static,
+        D: Fn(SqliteValueRef<'_>) -> Result<T, BoxDynError> + Send + Sync + 'static,
+        E: Fn(&T) -> SqliteArgumentValue<'static> + Send + Sync + 'static,
+    {
+        self.decoders.insert(
+            (declared_type.to_ascii_uppercase(), TypeId::of::<T>()),
+            Arc::new(move |value| Ok(Box::new(decode(value)?))),
+        );
+
+        self.encoders.insert(
+            TypeId::of::<T>(),
+            Arc::new(move |value| {
+                encode(
+                    value
+                        .downcast_ref::<T>()
+                        .expect("encoders are keyed by the type they encode"),
+                )
+            }),
+        );
+
+        self
+    }
+
+    /// Encode `value` with the encoder registered for `T`, e.g. to
+    /// [`bind()`](sqlx_core::query::Query::bind) it.
+    ///
+    /// Fails with [`Error::TypeNotFound`] if `T` is not registered.
+    pub fn encode<T: 'static>(&self, value: &T) -> Result<SqliteArgumentValue<'static>, Error> {
+        let encode = self
+            .encoders
+            .get(&TypeId::of::<T>())
+            .ok_or_else(|| Error::TypeNotFound {
+                type_name: std::any::type_name::<T>().into(),
+            })?;
+
+        Ok(encode(value))
+    }
+
+    // `None` if no decoder is registered, `Some(Ok(None))` for `NULL`
+    pub(crate) fn decode<T: 'static>(
+        &self,
+        declared_type: &str,
+        value: SqliteValueRef<'_>,
+    ) -> Option<Result<Option<T>, BoxDynError>> {
+        let decode = self
+            .decoders
+            .get(&(declared_type.to_ascii_uppercase(), TypeId::of::<T>()))?;
+
+        if value.is_null() {
+            return Some(Ok(None));
+        }
+
+        Some(decode(value).map(|value| {
+            Some(
+                *value
+                    .downcast::<T>()
+                    .expect("decoders are keyed by the type they decode"),
+            )
+        }))
+    }
+}
+
+impl Debug for SqliteTypeRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut declared_types: Vec<&str> = self
+            .decoders
+            .keys()
+            .map(|(declared_type, _)| declared_type.as_str())
+            .collect();
+
+        declared_types.sort_unstable();
+        declared_types.dedup();
+
+        f.debug_struct("SqliteTypeRegistry")
+            .field("declared_types", &declared_types)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Codec, Compressed, CompressedData, Type};
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl<T, C> Type<Sqlite> for Compressed<T, C> {
+    fn type_info() -> SqliteTypeInfo {
+        <Vec<u8> as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <Vec<u8> as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q, T, C> Encode<'q, Sqlite> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <Vec<u8> as Encode<Sqlite>>::encode(self.compress(), buf)
+    }
+}
+
+impl<'r, T, C> Decode<'r, Sqlite> for Compressed<T, C>
+where
+    T: CompressedData,
+    C: Codec,
+{
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Self::decompress(<&[u8] as Decode<Sqlite>>::decode(value)?)
+    }
+}
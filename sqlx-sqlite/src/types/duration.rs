@@ -0,0 +1,310 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+/// Store a duration as [ISO 8601] text, e.g. `PT1H30M` or `-PT0.5S`, instead of an integer
+/// count of microseconds.
+///
+/// Durations decode from either form, so this is only needed to choose the text form for
+/// writing:
+///
+/// ```rust,ignore
+/// use sqlx::sqlite::types::Iso8601;
+///
+/// sqlx::query("INSERT INTO jobs (name, every) VALUES (?, ?)")
+///     .bind("backup")
+///     .bind(Iso8601(Duration::from_secs(3600)))
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// Years and months are rejected on decoding as their length is not fixed; a day is 24 hours.
+///
+/// [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Iso8601<T>(pub T);
+
+impl Type<Sqlite> for Duration {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Int64)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(
+            ty.0,
+            DataType::Interval | DataType::Int | DataType::Int64 | DataType::Text
+        )
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Duration {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        let micros = i64::try_from(self.as_micros()).expect("failed to encode `Duration`");
+
+        args.push(SqliteArgumentValue::Int64(micros));
+
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Duration {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let (negative, duration) = decode_duration(value)?;
+
+        if negative && !duration.is_zero() {
+            return Err("negative duration cannot be decoded as `std::time::Duration`".into());
+        }
+
+        Ok(duration)
+    }
+}
+
+impl Type<Sqlite> for Iso8601<Duration> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Text)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <Duration as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Iso8601<Duration> {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(format_iso8601(
+            false, self.0,
+        ))));
+
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Iso8601<Duration> {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Duration::decode(value).map(Self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_duration {
+    use super::*;
+
+    impl Type<Sqlite> for chrono::Duration {
+        fn type_info() -> SqliteTypeInfo {
+            <Duration as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <Duration as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for chrono::Duration {
+        fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+            let micros = self
+                .num_microseconds()
+                .expect("failed to encode `chrono::Duration`");
+
+            args.push(SqliteArgumentValue::Int64(micros));
+
+            IsNull::No
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for chrono::Duration {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            let (negative, duration) = decode_duration(value)?;
+            let duration = chrono::Duration::from_std(duration)?;
+
+            Ok(if negative { -duration } else { duration })
+        }
+    }
+
+    impl Type<Sqlite> for Iso8601<chrono::Duration> {
+        fn type_info() -> SqliteTypeInfo {
+            SqliteTypeInfo(DataType::Text)
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <Duration as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for Iso8601<chrono::Duration> {
+        fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+            let negative = self.0 < chrono::Duration::zero();
+            let duration = self
+                .0
+                .abs()
+                .to_std()
+                .expect("absolute duration is positive");
+
+            args.push(SqliteArgumentValue::Text(Cow::Owned(format_iso8601(
+                negative, duration,
+            ))));
+
+            IsNull::No
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for Iso8601<chrono::Duration> {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            chrono::Duration::decode(value).map(Self)
+        }
+    }
+}
+
+// the sign and the magnitude of an integer count of microseconds or an ISO 8601 duration
+fn decode_duration(value: SqliteValueRef<'_>) -> Result<(bool, Duration), BoxDynError> {
+    match value.type_info().0 {
+        DataType::Text => parse_iso8601(value.text()?),
+        DataType::Int | DataType::Int64 => {
+            let micros = value.int64();
+
+            Ok((micros < 0, Duration::from_micros(micros.unsigned_abs())))
+        }
+
+        ty => Err(format!("cannot decode a duration from {ty:?}").into()),
+    }
+}
+
+fn format_iso8601(negative: bool, duration: Duration) -> String {
+    let sign = if negative { "-" } else { "" };
+    let secs = duration.as_secs();
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    let mut s = format!("{sign}PT");
+
+    if hours > 0 {
+        s += &format!("{hours}H");
+    }
+
+    if minutes > 0 {
+        s += &format!("{minutes}M");
+    }
+
+    let micros = duration.subsec_micros();
+
+    if micros > 0 {
+        let fraction = format!("{micros:06}");
+        s += &format!("{secs}.{}S", fraction.trim_end_matches('0'));
+    } else if secs > 0 || (hours == 0 && minutes == 0) {
+        s += &format!("{secs}S");
+    }
+
+    s
+}
+
+fn parse_iso8601(s: &str) -> Result<(bool, Duration), BoxDynError> {
+    let invalid = || format!("invalid ISO 8601 duration: {s:?}");
+
+    let (negative, rest) = match s.trim().strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.trim().strip_prefix('+').unwrap_or(s.trim())),
+    };
+
+    let rest = rest
+        .strip_prefix(|c| c == 'P' || c == 'p')
+        .ok_or_else(invalid)?;
+
+    let mut nanos: u128 = 0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut any = false;
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' => number.push(c),
+            '.' | ',' if !number.is_empty() && !number.contains('.') => number.push('.'),
+            unit => {
+                let secs_per_unit: u128 = match (in_time, unit) {
+                    (false, 'W') => 604_800,
+                    (false, 'D') => 86_400,
+                    (true, 'H') => 3_600,
+                    (true, 'M') => 60,
+                    (true, 'S') => 1,
+                    (false, 'Y' | 'M') => {
+                        return Err(
+                            format!("cannot decode a duration with years or months: {s:?}").into(),
+                        )
+                    }
+                    _ => return Err(invalid().into()),
+                };
+
+                let (whole, fraction) = number.split_once('.').unwrap_or((&number, ""));
+
+                if whole.is_empty() {
+                    return Err(invalid().into());
+                }
+
+                // nanoseconds are the finest precision of `Duration`
+                let fraction = format!("{:0<9.9}", fraction);
+
+                let value = whole
+                    .parse::<u128>()
+                    .ok()
+                    .and_then(|whole| whole.checked_mul(1_000_000_000))
+                    .and_then(|whole| whole.checked_add(fraction.parse::<u128>().ok()?))
+                    .and_then(|value| value.checked_mul(secs_per_unit))
+                    .ok_or_else(invalid)?;
+
+                nanos = nanos.checked_add(value).ok_or_else(invalid)?;
+                number.clear();
+                any = true;
+            }
+        }
+    }
+
+    if !any || !number.is_empty() {
+        return Err(invalid().into());
+    }
+
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| invalid())?;
+    let duration = Duration::new(secs, (nanos % 1_000_000_000) as u32);
+
+    Ok((negative, duration))
+}
+
+#[test]
+fn test_iso8601_duration() {
+    let cases = [
+        ("PT0S", Duration::ZERO),
+        ("PT1.5S", Duration::from_millis(1_500)),
+        ("PT1H30M", Duration::from_secs(5_400)),
+        ("PT25H0.000001S", Duration::new(90_000, 1_000)),
+    ];
+
+    for (text, duration) in cases {
+        assert_eq!(format_iso8601(false, duration), text);
+        assert_eq!(parse_iso8601(text).unwrap(), (false, duration));
+    }
+
+    assert_eq!(format_iso8601(true, Duration::from_secs(90)), "-PT1M30S");
+    assert_eq!(
+        parse_iso8601("-P1DT2H").unwrap(),
+        (true, Duration::from_secs(93_600))
+    );
+    assert_eq!(
+        parse_iso8601("P1W").unwrap(),
+        (false, Duration::from_secs(604_800))
+    );
+    assert_eq!(
+        parse_iso8601("PT0,25S").unwrap(),
+        (false, Duration::from_millis(250))
+    );
+
+    assert!(parse_iso8601("P1M").is_err());
+    assert!(parse_iso8601("P1Y").is_err());
+    assert!(parse_iso8601("PT").is_err());
+    assert!(parse_iso8601("1H").is_err());
+    assert!(parse_iso8601("PT1").is_err());
+    assert!(parse_iso8601("P1H").is_err());
+}
@@ -0,0 +1,29 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Encrypted, Type};
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl<T, K> Type<Sqlite> for Encrypted<T, K> {
+    fn type_info() -> SqliteTypeInfo {
+        <Vec<u8> as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <Vec<u8> as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q, T, K> Encode<'q, Sqlite> for Encrypted<T, K> {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <Vec<u8> as Encode<Sqlite>>::encode(self.as_bytes().to_vec(), buf)
+    }
+}
+
+impl<'r, T, K> Decode<'r, Sqlite> for Encrypted<T, K> {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::from_bytes(
+            <&[u8] as Decode<Sqlite>>::decode(value)?.to_vec(),
+        ))
+    }
+}
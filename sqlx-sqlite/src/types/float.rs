@@ -2,7 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::type_info::DataType;
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 
 impl Type<Sqlite> for f32 {
@@ -21,7 +21,10 @@ impl<'q> Encode<'q, Sqlite> for f32 {
 
 impl<'r> Decode<'r, Sqlite> for f32 {
     fn decode(value: SqliteValueRef<'r>) -> Result<f32, BoxDynError> {
-        Ok(value.double() as f32)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Lossy)
+            .narrow(value.double_coerced()?)
     }
 }
 
@@ -41,6 +44,6 @@ impl<'q> Encode<'q, Sqlite> for f64 {
 
 impl<'r> Decode<'r, Sqlite> for f64 {
     fn decode(value: SqliteValueRef<'r>) -> Result<f64, BoxDynError> {
-        Ok(value.double())
+        value.double_coerced()
     }
 }
@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::{Geometry, Type};
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for Geometry {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Blob)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Blob)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Geometry {
+    fn encode(self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(self.into_wkb())));
+
+        IsNull::No
+    }
+
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(
+            self.as_wkb().to_vec(),
+        )));
+
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Geometry {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Geometry::from_wkb(value.blob())
+    }
+}
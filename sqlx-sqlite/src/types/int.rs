@@ -2,7 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::type_info::DataType;
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 
 impl Type<Sqlite> for i8 {
@@ -25,7 +25,10 @@ impl<'q> Encode<'q, Sqlite> for i8 {
 
 impl<'r> Decode<'r, Sqlite> for i8 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(value.int64_coerced()?)
     }
 }
 
@@ -49,7 +52,10 @@ impl<'q> Encode<'q, Sqlite> for i16 {
 
 impl<'r> Decode<'r, Sqlite> for i16 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(value.int64_coerced()?)
     }
 }
 
@@ -73,7 +79,11 @@ impl<'q> Encode<'q, Sqlite> for i32 {
 
 impl<'r> Decode<'r, Sqlite> for i32 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int())
+        // `i32` used to be decoded with `sqlite3_value_int()`, which keeps the low 32 bits
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Lossy)
+            .narrow(value.int64_coerced()?)
     }
 }
 
@@ -97,6 +107,6 @@ impl<'q> Encode<'q, Sqlite> for i64 {
 
 impl<'r> Decode<'r, Sqlite> for i64 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int64())
+        value.int64_coerced()
     }
 }
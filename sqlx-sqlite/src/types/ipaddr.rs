@@ -0,0 +1,42 @@
+use std::borrow::Cow;
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for IpAddr {
+    fn type_info() -> SqliteTypeInfo {
+        <IpNetwork as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <IpNetwork as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for IpAddr {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Sqlite> for IpAddr {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let ipnetwork = IpNetwork::decode(value)?;
+
+        if ipnetwork.is_ipv4() && ipnetwork.prefix() != 32
+            || ipnetwork.is_ipv6() && ipnetwork.prefix() != 128
+        {
+            Err("lossy decode from network with prefix")?
+        }
+
+        Ok(ipnetwork.ip())
+    }
+}
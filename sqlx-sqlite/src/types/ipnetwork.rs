@@ -0,0 +1,35 @@
+use std::borrow::Cow;
+
+use ipnetwork::IpNetwork;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for IpNetwork {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Text)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Text)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for IpNetwork {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Sqlite> for IpNetwork {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // an address without a prefix is a single host
+        value.text()?.parse().map_err(Into::into)
+    }
+}
@@ -0,0 +1,34 @@
+use std::borrow::Cow;
+
+use mac_address::MacAddress;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for MacAddress {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Text)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Text)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for MacAddress {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Text(Cow::Owned(self.to_string())));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Sqlite> for MacAddress {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        value.text()?.parse().map_err(Into::into)
+    }
+}
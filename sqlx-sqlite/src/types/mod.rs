@@ -16,6 +16,12 @@
 //! | `f64`                                 | REAL                                                 |
 //! | `&str`, [`String`]                    | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `std::time::Duration`                 | INTEGER, TEXT, INTERVAL                              |
+//!
+//! #### Note: Durations
+//! Durations are stored as an integer count of microseconds. Wrap them in [`Iso8601`] to store
+//! them as ISO 8601 text, e.g. `PT1H30M`, instead; either form decodes. Declare the column as
+//! `INTERVAL` for the query macros to map it to `std::time::Duration`.
 //!
 //! #### Note: Unsigned Integers
 //! The unsigned integer types `u8`, `u16` and `u32` are implemented by zero-extending to the
@@ -42,6 +48,7 @@
 //! | `chrono::DateTime<Local>`             | DATETIME                                             |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
+//! | `chrono::Duration`                    | INTEGER, TEXT, INTERVAL                              |
 //!
 //! ### [`time`](https://crates.io/crates/time)
 //!
@@ -60,9 +67,42 @@
 //!
 //! | Rust type                             | Sqlite type(s)                                       |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `uuid::Uuid`                          | BLOB, TEXT                                           |
-//! | `uuid::fmt::Hyphenated`               | TEXT                                                 |
-//! | `uuid::fmt::Simple`                   | TEXT                                                 |
+//! | `uuid::Uuid`                          | BLOB, TEXT, UUID                                     |
+//! | `uuid::fmt::Hyphenated`               | TEXT, UUID                                           |
+//! | `uuid::fmt::Simple`                   | TEXT, UUID                                           |
+//!
+//! `Uuid` is stored as a 16-byte `BLOB`, which takes less than half the space of the `TEXT`
+//! forms. Declare the column as `UUID` for the query macros to map it to `Uuid`, as `BLOB`
+//! columns map to `Vec<u8>`.
+//!
+//! ### [`ipnetwork`](https://crates.io/crates/ipnetwork)
+//!
+//! Requires the `ipnetwork` Cargo feature flag.
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `ipnetwork::IpNetwork`                | TEXT                                                 |
+//! | [`std::net::IpAddr`]                  | TEXT                                                 |
+//!
+//! Networks are stored in CIDR notation, e.g. `192.168.0.0/24`; an address without a prefix
+//! decodes as a single host. Decoding an `IpAddr` fails for a network that is not a single host.
+//!
+//! ### [`mac_address`](https://crates.io/crates/mac_address)
+//!
+//! Requires the `mac_address` Cargo feature flag.
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `mac_address::MacAddress`             | TEXT                                                 |
+//!
+//! ### Geometry
+//!
+//! Requires the `geometry` Cargo feature flag. See its documentation for use with
+//! SpatiaLite.
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `sqlx::types::Geometry`               | BLOB                                                 |
 //!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
@@ -135,14 +175,29 @@
 
 pub(crate) use sqlx_core::types::*;
 
+pub use duration::Iso8601;
+
 mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod compressed;
+mod duration;
+#[cfg(feature = "encryption")]
+mod encrypted;
 mod float;
+#[cfg(feature = "geometry")]
+mod geometry;
 mod int;
+#[cfg(feature = "ipnetwork")]
+mod ipaddr;
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
 #[cfg(feature = "json")]
 mod json;
+#[cfg(feature = "mac_address")]
+mod mac_address;
 mod str;
 mod text;
 #[cfg(feature = "time")]
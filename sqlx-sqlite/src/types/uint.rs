@@ -2,7 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::type_info::DataType;
-use crate::types::Type;
+use crate::types::{NumericOverflow, Type};
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 
 impl Type<Sqlite> for u8 {
@@ -25,7 +25,10 @@ impl<'q> Encode<'q, Sqlite> for u8 {
 
 impl<'r> Decode<'r, Sqlite> for u8 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(value.int64_coerced()?)
     }
 }
 
@@ -49,7 +52,10 @@ impl<'q> Encode<'q, Sqlite> for u16 {
 
 impl<'r> Decode<'r, Sqlite> for u16 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(value.int64_coerced()?)
     }
 }
 
@@ -73,6 +79,9 @@ impl<'q> Encode<'q, Sqlite> for u32 {
 
 impl<'r> Decode<'r, Sqlite> for u32 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int64().try_into()?)
+        value
+            .numeric_overflow()
+            .or(NumericOverflow::Error)
+            .narrow(value.int64_coerced()?)
     }
 }
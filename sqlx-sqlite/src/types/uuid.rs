@@ -16,7 +16,7 @@ impl Type<Sqlite> for Uuid {
     }
 
     fn compatible(ty: &SqliteTypeInfo) -> bool {
-        matches!(ty.0, DataType::Blob | DataType::Text)
+        matches!(ty.0, DataType::Uuid | DataType::Blob | DataType::Text)
     }
 }
 
@@ -41,6 +41,10 @@ impl Type<Sqlite> for Hyphenated {
     fn type_info() -> SqliteTypeInfo {
         SqliteTypeInfo(DataType::Text)
     }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Text | DataType::Uuid)
+    }
 }
 
 impl<'q> Encode<'q, Sqlite> for Hyphenated {
@@ -64,6 +68,10 @@ impl Type<Sqlite> for Simple {
     fn type_info() -> SqliteTypeInfo {
         SqliteTypeInfo(DataType::Text)
     }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Text | DataType::Uuid)
+    }
 }
 
 impl<'q> Encode<'q, Sqlite> for Simple {
@@ -1,20 +1,26 @@
 use std::borrow::Cow;
-use std::ptr::NonNull;
-use std::slice::from_raw_parts;
 use std::str::from_utf8;
-use std::sync::Arc;
-
-use libsqlite3_sys::{
-    sqlite3_value, sqlite3_value_blob, sqlite3_value_bytes, sqlite3_value_double,
-    sqlite3_value_dup, sqlite3_value_free, sqlite3_value_int, sqlite3_value_int64,
-    sqlite3_value_type, SQLITE_NULL,
-};
+use std::sync::{Arc, OnceLock};
 
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::type_info::TypeInfo;
+use sqlx_core::types::NumericOverflow;
 pub(crate) use sqlx_core::value::{Value, ValueRef};
 
+use crate::arguments::SqliteArgumentValue;
 use crate::error::BoxDynError;
+use crate::statement::StatementHandle;
 use crate::type_info::DataType;
-use crate::{Sqlite, SqliteTypeInfo};
+use crate::types::Type;
+use crate::{Sqlite, SqliteCoercions, SqliteTypeInfo};
+
+/// Connection settings that affect how values are decoded, captured with each value.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct DecodeSettings {
+    pub(crate) numeric_overflow: NumericOverflow,
+    pub(crate) coercions: SqliteCoercions,
+    pub(crate) strict: bool,
+}
 
 enum SqliteValueData<'r> {
     Value(&'r SqliteValue),
@@ -45,6 +51,123 @@ impl<'r> SqliteValueRef<'r> {
         }
     }
 
+    pub(super) fn numeric_overflow(&self) -> NumericOverflow {
+        match self.0 {
+            SqliteValueData::Value(v) => v.decode_settings.numeric_overflow,
+        }
+    }
+
+    pub(super) fn coercions(&self) -> SqliteCoercions {
+        match self.0 {
+            SqliteValueData::Value(v) => v.decode_settings.coercions,
+        }
+    }
+
+    /// The storage class of this value, as opposed to the declared type of its column.
+    pub(super) fn storage_class(&self) -> DataType {
+        match self.0 {
+            SqliteValueData::Value(v) => v.storage_class(),
+        }
+    }
+
+    /// Whether the configured coercions allow decoding this value into `T`, even though
+    /// `T` is not [compatible][Type::compatible] with its storage class.
+    pub(crate) fn is_coercible_to<T: Type<Sqlite>>(&self) -> bool {
+        let coercions = self.coercions();
+
+        match (self.storage_class(), T::type_info().0) {
+            (DataType::Text, DataType::Bool) => coercions.text_to_bool,
+            (DataType::Text, DataType::Int | DataType::Int64 | DataType::Float) => {
+                coercions.text_to_number
+            }
+            (DataType::Int | DataType::Float, DataType::Text) => coercions.number_to_text,
+
+            _ => false,
+        }
+    }
+
+    /// In strict mode, check that the storage class of this value matches the declared type
+    /// of its column.
+    pub(crate) fn check_strict_affinity(&self) -> Result<(), BoxDynError> {
+        let SqliteValueData::Value(v) = self.0;
+
+        if !v.decode_settings.strict {
+            return Ok(());
+        }
+
+        let expected = match v.type_info.0 {
+            DataType::Int | DataType::Int64 | DataType::Bool => DataType::Int,
+            DataType::Float => DataType::Float,
+            DataType::Text => DataType::Text,
+            DataType::Blob => DataType::Blob,
+
+            DataType::Null
+            | DataType::Numeric
+            | DataType::Date
+            | DataType::Time
+            | DataType::Datetime
+            | DataType::Uuid
+            | DataType::Interval => return Ok(()),
+        };
+
+        match self.storage_class() {
+            DataType::Null => Ok(()),
+            actual if actual == expected => Ok(()),
+            actual => Err(format!(
+                "strict mode: column declared as `{}` holds a value of type `{}`",
+                v.type_info.name(),
+                SqliteTypeInfo(actual).name()
+            )
+            .into()),
+        }
+    }
+
+    /// Read an integer, parsing `TEXT` if [`SqliteCoercions::text_to_number()`] allows it.
+    pub(super) fn int64_coerced(&self) -> Result<i64, BoxDynError> {
+        if self.storage_class() == DataType::Text && self.coercions().text_to_number {
+            return Ok(self.text()?.trim().parse()?);
+        }
+
+        Ok(self.int64())
+    }
+
+    /// Read a float, parsing `TEXT` if [`SqliteCoercions::text_to_number()`] allows it.
+    pub(super) fn double_coerced(&self) -> Result<f64, BoxDynError> {
+        if self.storage_class() == DataType::Text && self.coercions().text_to_number {
+            return Ok(self.text()?.trim().parse()?);
+        }
+
+        Ok(self.double())
+    }
+
+    /// Read a boolean, parsing `TEXT` if [`SqliteCoercions::text_to_bool()`] allows it.
+    pub(super) fn bool_coerced(&self) -> Result<bool, BoxDynError> {
+        if self.storage_class() != DataType::Text || !self.coercions().text_to_bool {
+            return Ok(self.int() != 0);
+        }
+
+        let text = self.text()?.trim();
+
+        for (truthy, falsy) in [
+            ("1", "0"),
+            ("true", "false"),
+            ("t", "f"),
+            ("yes", "no"),
+            ("y", "n"),
+            ("on", "off"),
+        ] {
+            if text.eq_ignore_ascii_case(truthy) {
+                return Ok(true);
+            }
+
+            if text.eq_ignore_ascii_case(falsy) {
+                return Ok(false);
+            }
+        }
+
+        Err(format!("invalid boolean value {text:?}").into())
+    }
+
     pub(super) fn blob(&self) -> &'r [u8] {
         match self.0 {
             SqliteValueData::Value(v) => v.blob(),
@@ -82,30 +205,159 @@ impl<'r> ValueRef<'r> for SqliteValueRef<'r> {
 
 #[derive(Clone)]
 pub struct SqliteValue {
-    pub(crate) handle: Arc<ValueHandle>,
+    pub(crate) values: Arc<ValueBuffer>,
+    pub(crate) index: usize,
     pub(crate) type_info: SqliteTypeInfo,
+    pub(crate) decode_settings: DecodeSettings,
+}
+
+/// The values of a row, or a single value built with [`SqliteValue::from_encodable()`].
+///
+/// The bytes of all text and blob values are stored in one buffer, so reading a row allocates
+/// the same few times regardless of its number of columns, and not at all for bytes if it
+/// only holds numbers and `NULL`s.
+pub(crate) struct ValueBuffer {
+    cells: Vec<Cell>,
+    bytes: Vec<u8>,
+}
+
+/// The representations SQLite would convert a value to.
+struct Cell {
+    storage_class: DataType,
+    int64: i64,
+    double: f64,
+    // the text or blob is `bytes[start..end]`
+    start: usize,
+    end: usize,
+    // the text of an integer or float, formatted when it is first decoded as text
+    number_text: OnceLock<Box<str>>,
 }
 
-pub(crate) struct ValueHandle(NonNull<sqlite3_value>);
+impl Cell {
+    fn new(storage_class: DataType, int64: i64, double: f64, start: usize, end: usize) -> Self {
+        Self {
+            storage_class,
+            int64,
+            double,
+            start,
+            end,
+            number_text: OnceLock::new(),
+        }
+    }
+}
+
+impl ValueBuffer {
+    /// Read the values of the current row of `statement`.
+    pub(crate) fn read(statement: &StatementHandle) -> Self {
+        let size = statement.column_count();
+        let mut cells = Vec::with_capacity(size);
+        let mut len = 0;
+
+        for i in 0..size {
+            let storage_class = DataType::from_code(statement.column_type(i));
+
+            // the numeric conversions of text and blobs keep their bytes, so they can be
+            // copied below
+            // https://www.sqlite.org/c3ref/column_blob.html
+            let int64 = statement.column_int64(i);
+            let double = statement.column_double(i);
+
+            let bytes = match storage_class {
+                DataType::Text | DataType::Blob => statement.column_bytes(i),
+                _ => 0,
+            };
+
+            cells.push(Cell::new(storage_class, int64, double, len, len + bytes));
+
+            len += bytes;
+        }
+
+        let mut bytes = Vec::with_capacity(len);
+
+        for (i, cell) in cells.iter().enumerate() {
+            if cell.start != cell.end {
+                bytes.extend_from_slice(statement.column_blob(i));
+            }
+        }
+
+        Self { cells, bytes }
+    }
+
+    fn owned(argument: SqliteArgumentValue<'_>) -> Self {
+        // mirrors the conversions of `sqlite3_value_int64()` and friends
+        let (storage_class, int64, double, bytes) = match argument {
+            SqliteArgumentValue::Null => (DataType::Null, 0, 0.0, Vec::new()),
+            SqliteArgumentValue::Int(v) => (DataType::Int, v.into(), v.into(), Vec::new()),
+            SqliteArgumentValue::Int64(v) => (DataType::Int, v, v as f64, Vec::new()),
+            SqliteArgumentValue::Double(v) => (DataType::Float, v as i64, v, Vec::new()),
+            SqliteArgumentValue::Text(v) => {
+                let (int64, double) = (int_prefix(&v), real_prefix(&v));
+
+                (DataType::Text, int64, double, v.into_owned().into_bytes())
+            }
+            SqliteArgumentValue::Blob(v) => (DataType::Blob, 0, 0.0, v.into_owned()),
+        };
 
-// SAFE: only protected value objects are stored in SqliteValue
-unsafe impl Send for ValueHandle {}
-unsafe impl Sync for ValueHandle {}
+        Self {
+            cells: vec![Cell::new(storage_class, int64, double, 0, bytes.len())],
+            bytes,
+        }
+    }
+}
 
 impl SqliteValue {
-    pub(crate) unsafe fn new(value: *mut sqlite3_value, type_info: SqliteTypeInfo) -> Self {
-        debug_assert!(!value.is_null());
+    pub(crate) fn new(
+        values: Arc<ValueBuffer>,
+        index: usize,
+        type_info: SqliteTypeInfo,
+        decode_settings: DecodeSettings,
+    ) -> Self {
+        debug_assert!(index < values.cells.len());
 
         Self {
+            values,
+            index,
             type_info,
-            handle: Arc::new(ValueHandle(NonNull::new_unchecked(sqlite3_value_dup(
-                value,
-            )))),
+            decode_settings,
         }
     }
 
+    /// Build a value from anything that can be bound to a query, without a database.
+    ///
+    /// Together with [`SqliteRow::from_values()`][crate::SqliteRow::from_values] this allows
+    /// building fake rows, e.g. for mocks in unit tests. The value is decoded like one read from
+    /// a column declared as `T`, with the default [`SqliteConnectOptions`] decode settings.
+    ///
+    /// [`SqliteConnectOptions`]: crate::SqliteConnectOptions
+    pub fn from_encodable<'q, T>(value: T) -> Self
+    where
+        T: Encode<'q, Sqlite> + Type<Sqlite>,
+    {
+        let mut buf = Vec::with_capacity(1);
+
+        let argument = match value.encode(&mut buf) {
+            IsNull::Yes => SqliteArgumentValue::Null,
+            IsNull::No => buf.pop().unwrap_or(SqliteArgumentValue::Null),
+        };
+
+        Self::new(
+            Arc::new(ValueBuffer::owned(argument)),
+            0,
+            T::type_info(),
+            DecodeSettings::default(),
+        )
+    }
+
+    fn cell(&self) -> &Cell {
+        &self.values.cells[self.index]
+    }
+
+    fn storage_class(&self) -> DataType {
+        self.cell().storage_class
+    }
+
     fn type_info_opt(&self) -> Option<SqliteTypeInfo> {
-        let dt = DataType::from_code(unsafe { sqlite3_value_type(self.handle.0.as_ptr()) });
+        let dt = self.storage_class();
 
         if let DataType::Null = dt {
             None
@@ -115,33 +367,149 @@ impl SqliteValue {
     }
 
     fn int(&self) -> i32 {
-        unsafe { sqlite3_value_int(self.handle.0.as_ptr()) }
+        // truncates like `sqlite3_value_int()`
+        self.cell().int64 as i32
     }
 
     fn int64(&self) -> i64 {
-        unsafe { sqlite3_value_int64(self.handle.0.as_ptr()) }
+        self.cell().int64
     }
 
     fn double(&self) -> f64 {
-        unsafe { sqlite3_value_double(self.handle.0.as_ptr()) }
+        self.cell().double
     }
 
     fn blob(&self) -> &[u8] {
-        let len = unsafe { sqlite3_value_bytes(self.handle.0.as_ptr()) } as usize;
+        let cell = self.cell();
+
+        // like `sqlite3_value_blob()`, numbers are read as their text
+        let number_text = match cell.storage_class {
+            DataType::Int => cell
+                .number_text
+                .get_or_init(|| cell.int64.to_string().into()),
+            DataType::Float => cell
+                .number_text
+                .get_or_init(|| real_text(cell.double).into()),
+            _ => return &self.values.bytes[cell.start..cell.end],
+        };
+
+        number_text.as_bytes()
+    }
+
+    fn text(&self) -> Result<&str, BoxDynError> {
+        Ok(from_utf8(self.blob())?)
+    }
+}
+
+// the integer at the start of `text`, ignoring anything after it, like `sqlite3_value_int64()`;
+// out of range values saturate
+fn int_prefix(text: &str) -> i64 {
+    let text = text.trim_start();
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+
+    let mut value: i64 = 0;
+
+    for digit in digits.bytes().take_while(u8::is_ascii_digit) {
+        let digit = i64::from(digit - b'0');
+
+        value = match value.checked_mul(10).and_then(|v| {
+            if negative {
+                v.checked_sub(digit)
+            } else {
+                v.checked_add(digit)
+            }
+        }) {
+            Some(value) => value,
+            None if negative => return i64::MIN,
+            None => return i64::MAX,
+        };
+    }
+
+    value
+}
 
-        if len == 0 {
-            // empty blobs are NULL so just return an empty slice
-            return &[];
+// the real number at the start of `text`, ignoring anything after it, like
+// `sqlite3_value_double()`
+fn real_prefix(text: &str) -> f64 {
+    let text = text.trim_start();
+    let bytes = text.as_bytes();
+
+    let digits = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+    };
+
+    let sign = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let mut end = digits(sign);
+    let mut has_digits = end > sign;
+
+    if bytes.get(end) == Some(&b'.') {
+        let fraction = digits(end + 1);
+        has_digits |= fraction > end + 1;
+        end = fraction;
+    }
+
+    if !has_digits {
+        return 0.0;
+    }
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let exponent_sign = usize::from(matches!(bytes.get(end + 1), Some(b'-' | b'+')));
+        let exponent = digits(end + 1 + exponent_sign);
+
+        if exponent > end + 1 + exponent_sign {
+            end = exponent;
         }
+    }
 
-        let ptr = unsafe { sqlite3_value_blob(self.handle.0.as_ptr()) } as *const u8;
-        debug_assert!(!ptr.is_null());
+    text[..end].parse().unwrap_or(0.0)
+}
 
-        unsafe { from_raw_parts(ptr, len) }
+// formats `v` like SQLite does when converting a `REAL` to `TEXT`, i.e. with `printf("%!.15g")`
+fn real_text(v: f64) -> String {
+    if v.is_infinite() {
+        return if v < 0.0 { "-Inf" } else { "Inf" }.into();
     }
 
-    fn text(&self) -> Result<&str, BoxDynError> {
-        Ok(from_utf8(self.blob())?)
+    // `NaN` is stored as `NULL`, and `-0.0` is formatted as `0.0`
+    let sign = if v < 0.0 { "-" } else { "" };
+    let scientific = format!("{:.14e}", v.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation has an exponent");
+    let exponent: i32 = exponent.parse().expect("the exponent is an integer");
+    let digits = mantissa.replace('.', "");
+
+    // trailing zeros are removed, but at least one digit follows the decimal point
+    let trim = |digits: &str| match digits.trim_end_matches('0') {
+        "" => "0".to_owned(),
+        digits => digits.to_owned(),
+    };
+
+    if !(-4..=14).contains(&exponent) {
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+
+        format!(
+            "{sign}{}.{}e{exponent_sign}{:02}",
+            &digits[..1],
+            trim(&digits[1..]),
+            exponent.unsigned_abs()
+        )
+    } else if exponent >= 0 {
+        let (int, frac) = digits.split_at(exponent as usize + 1);
+
+        format!("{sign}{int}.{}", trim(frac))
+    } else {
+        let zeros = "0".repeat((-exponent - 1) as usize);
+
+        format!("{sign}0.{zeros}{}", trim(&digits))
     }
 }
 
@@ -159,15 +527,7 @@ impl Value for SqliteValue {
     }
 
     fn is_null(&self) -> bool {
-        unsafe { sqlite3_value_type(self.handle.0.as_ptr()) == SQLITE_NULL }
-    }
-}
-
-impl Drop for ValueHandle {
-    fn drop(&mut self) {
-        unsafe {
-            sqlite3_value_free(self.0.as_ptr());
-        }
+        self.storage_class() == DataType::Null
     }
 }
 
@@ -192,3 +552,136 @@ impl Drop for ValueHandle {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+fn read_row(sql: &str) -> crate::SqliteRow {
+    use crate::connection::establish::EstablishParams;
+    use crate::connection::execute;
+    use crate::SqliteConnectOptions;
+
+    let options: SqliteConnectOptions = "sqlite::memory:".parse().unwrap();
+    let mut conn = EstablishParams::from_options(&options)
+        .unwrap()
+        .establish()
+        .unwrap();
+
+    let mut rows = execute::iter(&mut conn, sql, None, false).unwrap();
+
+    rows.find_map(|res| res.unwrap().right()).unwrap()
+}
+
+#[test]
+fn test_read_copies_only_text_and_blobs() {
+    let row = read_row("SELECT 1, 2.5, NULL, 'abc', x'0102'");
+    let values = &row.values[0].values;
+
+    assert_eq!(values.bytes.capacity(), 5);
+    assert!(values
+        .cells
+        .iter()
+        .all(|cell| cell.number_text.get().is_none()));
+
+    assert_eq!(row.values[3].text().unwrap(), "abc");
+    assert_eq!(row.values[4].blob(), [1, 2]);
+    assert_eq!(row.values[0].text().unwrap(), "1");
+    assert_eq!(row.values[1].blob(), b"2.5");
+
+    // a row of numbers does not allocate a buffer for bytes at all
+    let row = read_row("SELECT 1, 2, 3.5, NULL");
+    assert_eq!(row.values[0].values.bytes.capacity(), 0);
+}
+
+#[test]
+fn test_real_text_matches_sqlite() {
+    let reals = [
+        "0.0",
+        "-0.0",
+        "1.0",
+        "-2.5",
+        "0.1",
+        "1.0 / 3",
+        "100.0",
+        "123456.789",
+        "1e14",
+        "1e15",
+        "-1e15",
+        "999999999999999.0",
+        "9999999999999999.0",
+        "0.0001",
+        "0.00001",
+        "1.5e-10",
+        "1e100",
+        "1e300",
+        "-1e-300",
+        "2.2250738585072014e-308",
+        "1.7976931348623157e308",
+        "9e999",
+        "-9e999",
+    ];
+
+    let sql = reals
+        .iter()
+        .map(|real| format!("{real}, CAST({real} AS TEXT)"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let row = read_row(&format!("SELECT {sql}"));
+
+    for (i, real) in reals.iter().enumerate() {
+        let value = &row.values[i * 2];
+        assert_eq!(value.storage_class(), DataType::Float, "{real}");
+
+        assert_eq!(
+            value.text().unwrap(),
+            row.values[i * 2 + 1].text().unwrap(),
+            "{real}"
+        );
+    }
+}
+
+#[test]
+fn test_owned_text_conversions_match_sqlite() {
+    let texts = [
+        "12",
+        " 12",
+        "12abc",
+        "-12abc",
+        "+7",
+        "abc",
+        "",
+        " ",
+        "-",
+        "1.5",
+        "1.5e3",
+        "1e3x",
+        ".5",
+        "5.",
+        ".",
+        "1e",
+        "1e+",
+        "-.5e-2",
+        "9223372036854775807",
+        "9223372036854775808",
+        "-9223372036854775808",
+        "-9223372036854775809",
+        "99999999999999999999",
+        "0x10",
+        "  -3.25 apples",
+    ];
+
+    let sql = texts
+        .iter()
+        .map(|text| format!("'{text}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let row = read_row(&format!("SELECT {sql}"));
+
+    for (i, text) in texts.iter().enumerate() {
+        let read = row.values[i].cell();
+        let owned = ValueBuffer::owned(SqliteArgumentValue::Text((*text).into()));
+
+        assert_eq!(owned.cells[0].int64, read.int64, "{text:?}");
+        assert_eq!(owned.cells[0].double, read.double, "{text:?}");
+    }
+}
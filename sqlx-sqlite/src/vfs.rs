@@ -0,0 +1,623 @@
+//! Virtual file systems implemented in Rust.
+//!
+//! See [`register_vfs()`] and <https://www.sqlite.org/vfs.html>.
+
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::io;
+use std::mem;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::{Mutex, PoisonError};
+
+use libsqlite3_sys::{
+    self as ffi, SQLITE_CANTOPEN, SQLITE_IOERR, SQLITE_IOERR_ACCESS, SQLITE_IOERR_DELETE,
+    SQLITE_IOERR_DELETE_NOENT, SQLITE_IOERR_FSTAT, SQLITE_IOERR_FSYNC, SQLITE_IOERR_READ,
+    SQLITE_IOERR_SHORT_READ, SQLITE_IOERR_TRUNCATE, SQLITE_IOERR_WRITE, SQLITE_NOTFOUND, SQLITE_OK,
+    SQLITE_OPEN_CREATE, SQLITE_OPEN_DELETEONCLOSE, SQLITE_OPEN_EXCLUSIVE, SQLITE_OPEN_MAIN_DB,
+    SQLITE_OPEN_MAIN_JOURNAL, SQLITE_OPEN_READONLY, SQLITE_OPEN_SUBJOURNAL,
+    SQLITE_OPEN_SUPER_JOURNAL, SQLITE_OPEN_TEMP_DB, SQLITE_OPEN_TEMP_JOURNAL,
+    SQLITE_OPEN_TRANSIENT_DB, SQLITE_OPEN_WAL,
+};
+
+use crate::error::Error;
+
+/// A virtual file system implemented in Rust, e.g. for encrypted storage, files in an object
+/// store or injecting faults in tests.
+///
+/// Register it with [`register_vfs()`] and select it for a connection with
+/// [`SqliteConnectOptions::vfs()`][crate::SqliteConnectOptions::vfs].
+///
+/// SQLite opens the database as well as its journals and temporary files through the VFS.
+/// Shared memory is not supported, so the [WAL journal mode](https://www.sqlite.org/wal.html)
+/// works only with an exclusive [locking mode][crate::SqliteLockingMode::Exclusive].
+///
+/// The VFS does not lock files. Use it with a single connection, e.g. a pool with
+/// `max_connections(1)`, unless all connections only read or use the exclusive locking mode.
+pub trait SqliteVfs: Send + Sync + 'static {
+    /// The type of the files opened by the VFS.
+    type File: SqliteVfsFile;
+
+    /// Open the file at `path`, or an anonymous temporary file if it is `None`.
+    ///
+    /// Files opened with [`SqliteVfsOpen::delete_on_close()`] are deleted by calling
+    /// [`delete()`](Self::delete) after they are closed (dropped). Anonymous files are always
+    /// opened that way and have no path to delete, so they must free their storage when dropped.
+    fn open(&self, path: Option<&str>, options: SqliteVfsOpen) -> io::Result<Self::File>;
+
+    /// Delete the file at `path`; fail with [`io::ErrorKind::NotFound`] if it does not exist.
+    fn delete(&self, path: &str) -> io::Result<()>;
+
+    /// Whether there is a file at `path`.
+    fn exists(&self, path: &str) -> io::Result<bool>;
+
+    /// Return the canonical form of `path`, the name under which the file is opened.
+    ///
+    /// The path is used as is by default.
+    fn full_path(&self, path: &str) -> io::Result<String> {
+        Ok(path.to_owned())
+    }
+}
+
+/// A file opened by a [`SqliteVfs`], closed when dropped.
+pub trait SqliteVfsFile: Send + 'static {
+    /// Read into `buf` from `offset`, returning the number of bytes read; fewer than requested
+    /// only at the end of the file.
+    fn read_at(&mut self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+
+    /// Write all of `buf` at `offset`, extending the file if needed.
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()>;
+
+    /// Truncate or extend the file to `size` bytes.
+    fn truncate(&mut self, size: u64) -> io::Result<()>;
+
+    /// Make prior writes durable.
+    fn sync(&mut self) -> io::Result<()>;
+
+    /// The size of the file in bytes.
+    fn size(&mut self) -> io::Result<u64>;
+}
+
+/// How SQLite opens a file of a [`SqliteVfs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteVfsOpen {
+    flags: c_int,
+}
+
+/// What a file opened by a [`SqliteVfs`] is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SqliteVfsFileKind {
+    MainDb,
+    MainJournal,
+    TempDb,
+    TempJournal,
+    TransientDb,
+    Subjournal,
+    SuperJournal,
+    Wal,
+    Other,
+}
+
+impl SqliteVfsOpen {
+    /// What the file is used for.
+    pub fn kind(&self) -> SqliteVfsFileKind {
+        match self.flags {
+            f if f & SQLITE_OPEN_MAIN_DB != 0 => SqliteVfsFileKind::MainDb,
+            f if f & SQLITE_OPEN_MAIN_JOURNAL != 0 => SqliteVfsFileKind::MainJournal,
+            f if f & SQLITE_OPEN_TEMP_DB != 0 => SqliteVfsFileKind::TempDb,
+            f if f & SQLITE_OPEN_TEMP_JOURNAL != 0 => SqliteVfsFileKind::TempJournal,
+            f if f & SQLITE_OPEN_TRANSIENT_DB != 0 => SqliteVfsFileKind::TransientDb,
+            f if f & SQLITE_OPEN_SUBJOURNAL != 0 => SqliteVfsFileKind::Subjournal,
+            f if f & SQLITE_OPEN_SUPER_JOURNAL != 0 => SqliteVfsFileKind::SuperJournal,
+            f if f & SQLITE_OPEN_WAL != 0 => SqliteVfsFileKind::Wal,
+            _ => SqliteVfsFileKind::Other,
+        }
+    }
+
+    /// Whether the file is only read from.
+    pub fn read_only(&self) -> bool {
+        self.flags & SQLITE_OPEN_READONLY != 0
+    }
+
+    /// Whether the file is created if it does not exist.
+    pub fn create(&self) -> bool {
+        self.flags & SQLITE_OPEN_CREATE != 0
+    }
+
+    /// Whether opening must fail if the file exists, along with [`create()`](Self::create).
+    pub fn exclusive(&self) -> bool {
+        self.flags & SQLITE_OPEN_EXCLUSIVE != 0
+    }
+
+    /// Whether the file is deleted after it is closed.
+    pub fn delete_on_close(&self) -> bool {
+        self.flags & SQLITE_OPEN_DELETEONCLOSE != 0
+    }
+}
+
+// serializes checking for and registering VFS names
+static REGISTER: Mutex<()> = Mutex::new(());
+
+/// Register `vfs` under `name`, to be used for connections with
+/// [`SqliteConnectOptions::vfs(name)`][crate::SqliteConnectOptions::vfs].
+///
+/// ```rust,ignore
+/// sqlx::sqlite::register_vfs("encrypted", EncryptedVfs::new(key))?;
+///
+/// let pool = SqlitePoolOptions::new()
+///     .max_connections(1)
+///     .connect_with(SqliteConnectOptions::new().filename("data.db").vfs("encrypted"))
+///     .await?;
+/// ```
+///
+/// A registered VFS cannot be unregistered, and registering a second VFS under the same name
+/// fails. Functions SQLite needs besides the file system, such as for randomness, the current
+/// time and loading extensions, are those of the default VFS of the operating system.
+pub fn register_vfs<V: SqliteVfs>(name: &str, vfs: V) -> Result<(), Error> {
+    let name = CString::new(name)
+        .map_err(|_| Error::Configuration("VFS names must not contain nul bytes".into()))?;
+
+    // SQLite allocates files with the alignment of `malloc()`, which is at least 8 bytes
+    if mem::align_of::<VfsFile<V>>() > 8 {
+        return Err(Error::Configuration(
+            "the files of a VFS must not require an alignment of more than 8 bytes".into(),
+        ));
+    }
+
+    let _guard = REGISTER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    // SAFETY: `sqlite3_vfs_find()` and `sqlite3_vfs_register()` initialize SQLite and are
+    // thread-safe. The registered VFS and its data are leaked, as SQLite uses them until the
+    // end of the process.
+    // https://www.sqlite.org/c3ref/vfs_find.html
+    unsafe {
+        if !ffi::sqlite3_vfs_find(name.as_ptr()).is_null() {
+            return Err(Error::Configuration(
+                format!("a VFS named {name:?} is already registered").into(),
+            ));
+        }
+
+        let default = ffi::sqlite3_vfs_find(ptr::null());
+
+        if default.is_null() {
+            return Err(Error::Configuration("SQLite has no default VFS".into()));
+        }
+
+        let data: &'static mut VfsData<V> = Box::leak(Box::new(VfsData {
+            vfs,
+            default,
+            io_methods: ffi::sqlite3_io_methods {
+                iVersion: 1,
+                xClose: Some(x_close::<V>),
+                xRead: Some(x_read::<V>),
+                xWrite: Some(x_write::<V>),
+                xTruncate: Some(x_truncate::<V>),
+                xSync: Some(x_sync::<V>),
+                xFileSize: Some(x_file_size::<V>),
+                xLock: Some(x_lock),
+                xUnlock: Some(x_lock),
+                xCheckReservedLock: Some(x_check_reserved_lock),
+                xFileControl: Some(x_file_control),
+                xSectorSize: Some(x_sector_size),
+                xDeviceCharacteristics: Some(x_device_characteristics),
+                xShmMap: None,
+                xShmLock: None,
+                xShmBarrier: None,
+                xShmUnmap: None,
+                xFetch: None,
+                xUnfetch: None,
+            },
+        }));
+
+        let sqlite_vfs = Box::leak(Box::new(ffi::sqlite3_vfs {
+            iVersion: 2,
+            szOsFile: mem::size_of::<VfsFile<V>>() as c_int,
+            mxPathname: (*default).mxPathname,
+            pNext: ptr::null_mut(),
+            zName: name.into_raw(),
+            pAppData: (data as *mut VfsData<V>).cast(),
+            xOpen: Some(x_open::<V>),
+            xDelete: Some(x_delete::<V>),
+            xAccess: Some(x_access::<V>),
+            xFullPathname: Some(x_full_pathname::<V>),
+            xDlOpen: Some(x_dl_open::<V>),
+            xDlError: Some(x_dl_error::<V>),
+            xDlSym: Some(x_dl_sym::<V>),
+            xDlClose: Some(x_dl_close::<V>),
+            xRandomness: Some(x_randomness::<V>),
+            xSleep: Some(x_sleep::<V>),
+            xCurrentTime: Some(x_current_time::<V>),
+            xGetLastError: Some(x_get_last_error::<V>),
+            xCurrentTimeInt64: Some(x_current_time_int64::<V>),
+            xSetSystemCall: None,
+            xGetSystemCall: None,
+            xNextSystemCall: None,
+        }));
+
+        match ffi::sqlite3_vfs_register(sqlite_vfs, 0) {
+            SQLITE_OK => Ok(()),
+            rc => Err(Error::Configuration(
+                format!("failed to register VFS: error code {rc}").into(),
+            )),
+        }
+    }
+}
+
+struct VfsData<V: SqliteVfs> {
+    vfs: V,
+    default: *mut ffi::sqlite3_vfs,
+    io_methods: ffi::sqlite3_io_methods,
+}
+
+// the `sqlite3_file` SQLite allocates `szOsFile` bytes for, which must start with its methods
+#[repr(C)]
+struct VfsFile<V: SqliteVfs> {
+    base: ffi::sqlite3_file,
+    vfs: &'static V,
+    delete_path: Option<String>,
+    file: V::File,
+}
+
+// returns `error` if `f` panics, as panics must not unwind into SQLite
+fn guard(error: c_int, f: impl FnOnce() -> c_int) -> c_int {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(error)
+}
+
+unsafe fn vfs_data<'a, V: SqliteVfs>(vfs: *mut ffi::sqlite3_vfs) -> &'a VfsData<V> {
+    &*(*vfs).pAppData.cast::<VfsData<V>>()
+}
+
+unsafe fn vfs_file<'a, V: SqliteVfs>(file: *mut ffi::sqlite3_file) -> &'a mut VfsFile<V> {
+    &mut *file.cast::<VfsFile<V>>()
+}
+
+unsafe fn path<'a>(path: *const c_char) -> Option<&'a str> {
+    CStr::from_ptr(path).to_str().ok()
+}
+
+// https://www.sqlite.org/c3ref/vfs.html
+
+unsafe extern "C" fn x_open<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    file: *mut ffi::sqlite3_file,
+    flags: c_int,
+    out_flags: *mut c_int,
+) -> c_int {
+    // SQLite does not close the file if opening fails and its methods are null
+    (*file).pMethods = ptr::null();
+
+    guard(SQLITE_CANTOPEN, || {
+        let data = vfs_data::<V>(vfs);
+
+        let name = if name.is_null() {
+            None
+        } else {
+            match path(name) {
+                Some(name) => Some(name),
+                None => return SQLITE_CANTOPEN,
+            }
+        };
+
+        let options = SqliteVfsOpen { flags };
+
+        let Ok(opened) = data.vfs.open(name, options) else {
+            return SQLITE_CANTOPEN;
+        };
+
+        ptr::write(
+            file.cast::<VfsFile<V>>(),
+            VfsFile {
+                base: ffi::sqlite3_file {
+                    pMethods: &data.io_methods,
+                },
+                vfs: &data.vfs,
+                delete_path: name
+                    .filter(|_| options.delete_on_close())
+                    .map(str::to_owned),
+                file: opened,
+            },
+        );
+
+        if !out_flags.is_null() {
+            *out_flags = flags;
+        }
+
+        SQLITE_OK
+    })
+}
+
+unsafe extern "C" fn x_delete<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    _sync_dir: c_int,
+) -> c_int {
+    guard(SQLITE_IOERR_DELETE, || {
+        let Some(name) = path(name) else {
+            return SQLITE_IOERR_DELETE;
+        };
+
+        match vfs_data::<V>(vfs).vfs.delete(name) {
+            Ok(()) => SQLITE_OK,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => SQLITE_IOERR_DELETE_NOENT,
+            Err(_) => SQLITE_IOERR_DELETE,
+        }
+    })
+}
+
+unsafe extern "C" fn x_access<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    _flags: c_int,
+    out: *mut c_int,
+) -> c_int {
+    // files that exist are taken to be readable and writable
+    guard(SQLITE_IOERR_ACCESS, || {
+        let Some(name) = path(name) else {
+            return SQLITE_IOERR_ACCESS;
+        };
+
+        match vfs_data::<V>(vfs).vfs.exists(name) {
+            Ok(exists) => {
+                *out = exists.into();
+                SQLITE_OK
+            }
+            Err(_) => SQLITE_IOERR_ACCESS,
+        }
+    })
+}
+
+unsafe extern "C" fn x_full_pathname<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    out_len: c_int,
+    out: *mut c_char,
+) -> c_int {
+    guard(SQLITE_CANTOPEN, || {
+        let Some(name) = path(name) else {
+            return SQLITE_CANTOPEN;
+        };
+
+        let Ok(full) = vfs_data::<V>(vfs).vfs.full_path(name) else {
+            return SQLITE_CANTOPEN;
+        };
+
+        if full.len() >= out_len as usize || full.contains('\0') {
+            return SQLITE_CANTOPEN;
+        }
+
+        ptr::copy_nonoverlapping(full.as_ptr().cast(), out, full.len());
+        *out.add(full.len()) = 0;
+
+        SQLITE_OK
+    })
+}
+
+// the functions besides the file system are those of the default VFS
+
+unsafe extern "C" fn x_dl_open<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+) -> *mut c_void {
+    let default = vfs_data::<V>(vfs).default;
+    (*default)
+        .xDlOpen
+        .map_or(ptr::null_mut(), |f| f(default, name))
+}
+
+unsafe extern "C" fn x_dl_error<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    len: c_int,
+    out: *mut c_char,
+) {
+    let default = vfs_data::<V>(vfs).default;
+
+    if let Some(f) = (*default).xDlError {
+        f(default, len, out);
+    }
+}
+
+unsafe extern "C" fn x_dl_sym<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    handle: *mut c_void,
+    symbol: *const c_char,
+) -> Option<unsafe extern "C" fn(*mut ffi::sqlite3_vfs, *mut c_void, *const c_char)> {
+    let default = vfs_data::<V>(vfs).default;
+    (*default).xDlSym.and_then(|f| f(default, handle, symbol))
+}
+
+unsafe extern "C" fn x_dl_close<V: SqliteVfs>(vfs: *mut ffi::sqlite3_vfs, handle: *mut c_void) {
+    let default = vfs_data::<V>(vfs).default;
+
+    if let Some(f) = (*default).xDlClose {
+        f(default, handle);
+    }
+}
+
+unsafe extern "C" fn x_randomness<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    len: c_int,
+    out: *mut c_char,
+) -> c_int {
+    let default = vfs_data::<V>(vfs).default;
+    (*default).xRandomness.map_or(0, |f| f(default, len, out))
+}
+
+unsafe extern "C" fn x_sleep<V: SqliteVfs>(vfs: *mut ffi::sqlite3_vfs, micros: c_int) -> c_int {
+    let default = vfs_data::<V>(vfs).default;
+    (*default).xSleep.map_or(0, |f| f(default, micros))
+}
+
+unsafe extern "C" fn x_current_time<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    out: *mut f64,
+) -> c_int {
+    let default = vfs_data::<V>(vfs).default;
+    (*default)
+        .xCurrentTime
+        .map_or(SQLITE_IOERR, |f| f(default, out))
+}
+
+unsafe extern "C" fn x_get_last_error<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    len: c_int,
+    out: *mut c_char,
+) -> c_int {
+    let default = vfs_data::<V>(vfs).default;
+    (*default).xGetLastError.map_or(0, |f| f(default, len, out))
+}
+
+unsafe extern "C" fn x_current_time_int64<V: SqliteVfs>(
+    vfs: *mut ffi::sqlite3_vfs,
+    out: *mut ffi::sqlite3_int64,
+) -> c_int {
+    let default = vfs_data::<V>(vfs).default;
+
+    match (*default).xCurrentTimeInt64 {
+        Some(f) if (*default).iVersion >= 2 => f(default, out),
+        _ => {
+            let mut days = 0.0;
+            let rc = x_current_time::<V>(vfs, &mut days);
+            *out = (days * 86_400_000.0) as ffi::sqlite3_int64;
+            rc
+        }
+    }
+}
+
+// https://www.sqlite.org/c3ref/io_methods.html
+
+unsafe extern "C" fn x_close<V: SqliteVfs>(file: *mut ffi::sqlite3_file) -> c_int {
+    guard(SQLITE_IOERR, || {
+        let VfsFile {
+            vfs,
+            delete_path,
+            file: opened,
+            ..
+        } = ptr::read(file.cast::<VfsFile<V>>());
+
+        drop(opened);
+
+        match delete_path.map(|path| vfs.delete(&path)) {
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => SQLITE_IOERR_DELETE,
+            _ => SQLITE_OK,
+        }
+    })
+}
+
+unsafe extern "C" fn x_read<V: SqliteVfs>(
+    file: *mut ffi::sqlite3_file,
+    buf: *mut c_void,
+    len: c_int,
+    offset: ffi::sqlite3_int64,
+) -> c_int {
+    guard(SQLITE_IOERR_READ, || {
+        let file = vfs_file::<V>(file);
+        let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), len as usize);
+
+        let mut read = 0;
+
+        while read < buf.len() {
+            match file
+                .file
+                .read_at(&mut buf[read..], offset as u64 + read as u64)
+            {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => return SQLITE_IOERR_READ,
+            }
+        }
+
+        if read < buf.len() {
+            // SQLite requires the rest of the buffer to be zeroed
+            buf[read..].fill(0);
+            return SQLITE_IOERR_SHORT_READ;
+        }
+
+        SQLITE_OK
+    })
+}
+
+unsafe extern "C" fn x_write<V: SqliteVfs>(
+    file: *mut ffi::sqlite3_file,
+    buf: *const c_void,
+    len: c_int,
+    offset: ffi::sqlite3_int64,
+) -> c_int {
+    guard(SQLITE_IOERR_WRITE, || {
+        let file = vfs_file::<V>(file);
+        let buf = slice::from_raw_parts(buf.cast::<u8>(), len as usize);
+
+        match file.file.write_all_at(buf, offset as u64) {
+            Ok(()) => SQLITE_OK,
+            Err(_) => SQLITE_IOERR_WRITE,
+        }
+    })
+}
+
+unsafe extern "C" fn x_truncate<V: SqliteVfs>(
+    file: *mut ffi::sqlite3_file,
+    size: ffi::sqlite3_int64,
+) -> c_int {
+    guard(SQLITE_IOERR_TRUNCATE, || {
+        match vfs_file::<V>(file).file.truncate(size as u64) {
+            Ok(()) => SQLITE_OK,
+            Err(_) => SQLITE_IOERR_TRUNCATE,
+        }
+    })
+}
+
+unsafe extern "C" fn x_sync<V: SqliteVfs>(file: *mut ffi::sqlite3_file, _flags: c_int) -> c_int {
+    guard(SQLITE_IOERR_FSYNC, || {
+        match vfs_file::<V>(file).file.sync() {
+            Ok(()) => SQLITE_OK,
+            Err(_) => SQLITE_IOERR_FSYNC,
+        }
+    })
+}
+
+unsafe extern "C" fn x_file_size<V: SqliteVfs>(
+    file: *mut ffi::sqlite3_file,
+    out: *mut ffi::sqlite3_int64,
+) -> c_int {
+    guard(SQLITE_IOERR_FSTAT, || {
+        match vfs_file::<V>(file).file.size() {
+            Ok(size) => {
+                *out = size as ffi::sqlite3_int64;
+                SQLITE_OK
+            }
+            Err(_) => SQLITE_IOERR_FSTAT,
+        }
+    })
+}
+
+// files are not locked, see the documentation of `SqliteVfs`
+unsafe extern "C" fn x_lock(_file: *mut ffi::sqlite3_file, _level: c_int) -> c_int {
+    SQLITE_OK
+}
+
+unsafe extern "C" fn x_check_reserved_lock(
+    _file: *mut ffi::sqlite3_file,
+    out: *mut c_int,
+) -> c_int {
+    *out = 0;
+    SQLITE_OK
+}
+
+unsafe extern "C" fn x_file_control(
+    _file: *mut ffi::sqlite3_file,
+    _op: c_int,
+    _arg: *mut c_void,
+) -> c_int {
+    SQLITE_NOTFOUND
+}
+
+unsafe extern "C" fn x_sector_size(_file: *mut ffi::sqlite3_file) -> c_int {
+    // the default sector size
+    0
+}
+
+unsafe extern "C" fn x_device_characteristics(_file: *mut ffi::sqlite3_file) -> c_int {
+    0
+}
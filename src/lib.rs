@@ -5,17 +5,23 @@ pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{
+    wait_for_database, wait_for_database_blocking, ConnectBackoff, ConnectOptions, Connection,
+};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
-pub use sqlx_core::executor::{Execute, Executor};
+pub use sqlx_core::executor::{DynExecutor, DynQuery, Execute, Executor};
+pub use sqlx_core::export;
 pub use sqlx_core::from_row::FromRow;
+pub use sqlx_core::interceptor;
+pub use sqlx_core::ops;
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{query, query_with};
 pub use sqlx_core::query_as::{query_as, query_as_with};
 pub use sqlx_core::query_builder::{self, QueryBuilder};
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
 pub use sqlx_core::row::Row;
+pub use sqlx_core::sqlcommenter;
 pub use sqlx_core::statement::Statement;
 pub use sqlx_core::transaction::{Transaction, TransactionManager};
 pub use sqlx_core::type_info::TypeInfo;
@@ -29,6 +35,10 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use sqlx_core::metrics;
+
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 #[doc(inline)]
@@ -129,6 +139,24 @@ pub mod decode {
 
 pub use self::decode::Decode;
 
+/// Named query parameters that work the same with every driver.
+pub mod named {
+    pub use sqlx_core::named::*;
+
+    #[cfg(feature = "macros")]
+    #[doc(hidden)]
+    pub use sqlx_macros::BindNamed;
+}
+
+/// Verify at startup that the database schema matches [`FromRow`] structs.
+pub mod schema {
+    pub use sqlx_core::schema::*;
+
+    #[cfg(feature = "macros")]
+    #[doc(hidden)]
+    pub use sqlx_macros::SchemaExpectations;
+}
+
 /// Types and traits for the `query` family of functions and macros.
 pub mod query {
     pub use sqlx_core::query::{Map, Query};
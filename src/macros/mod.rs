@@ -309,6 +309,13 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## SQLite Schema File
+/// For SQLite, the macros can instead check queries against a schema checked into version control:
+/// with `SQLX_SQLITE_SCHEMA` set to the path of a SQL file, relative to the project root, and
+/// `DATABASE_URL` not set, each query is described in a new in-memory database created by running
+/// the statements of the file. Unlike the files of `query_file!`, changes to the schema file
+/// do not cause a rebuild on stable Rust; touch a source file to recheck the queries.
+///
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
 /// * [query_file!] if you want to define the SQL query out-of-line,
@@ -346,6 +353,33 @@ macro_rules! query_unchecked (
     })
 );
 
+/// A variant of [query!] which binds an arguments object built at runtime, like
+/// [`query_with()`](crate::query_with).
+///
+/// The query and its output columns are checked at compile time as with [query!], and the
+/// arguments must be the arguments type of the database, e.g.
+/// [`SqliteArguments`](crate::sqlite::SqliteArguments). Their number and types are only known at
+/// runtime and are not checked against the parameters of the query.
+///
+/// ```rust,ignore
+/// use sqlx::Arguments;
+/// use sqlx::sqlite::SqliteArguments;
+///
+/// let mut args = SqliteArguments::default();
+/// args.add(42_i64);
+///
+/// let account = sqlx::query_checked_with!("SELECT id, name FROM accounts WHERE id = ?", args)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_checked_with (
+    ($query:expr, $args:expr $(,)?) => ({
+        $crate::sqlx_macros::expand_query!(source = $query, args_object = $args)
+    })
+);
+
 /// A variant of [query!] where the SQL query is stored in a separate file.
 ///
 /// Useful for large queries and potentially cleaner than multiline strings.
@@ -357,6 +391,10 @@ macro_rules! query_unchecked (
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
+/// A line of the form `-- include: <path>` is replaced by the contents of the file at `<path>`,
+/// relative to the project root as well, so that queries can share fragments such as CTEs.
+/// Included files may include further files and are tracked for recompilation like the query file.
+///
 /// -----
 ///
 /// `examples/queries/account-by-id.sql`:
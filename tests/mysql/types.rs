@@ -70,6 +70,31 @@ test_type!(uuid_simple<sqlx::types::uuid::fmt::Simple>(MySql,
         == sqlx::types::Uuid::parse_str("00000000000000000000000000000000").unwrap().simple()
 ));
 
+#[cfg(feature = "ipnetwork")]
+test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(MySql,
+    "'8.8.8.8/24'"
+        == "8.8.8.8/24"
+            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+            .unwrap(),
+    "'2001:4f8:3:ba::/64'"
+        == "2001:4f8:3:ba::/64"
+            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+            .unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipaddr<std::net::IpAddr>(MySql,
+    "'127.0.0.1'" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "mac_address")]
+test_type!(mac_address<sqlx::types::mac_address::MacAddress>(MySql,
+    "'00:01:02:03:04:05'"
+        == "00:01:02:03:04:05"
+            .parse::<sqlx::types::mac_address::MacAddress>()
+            .unwrap()
+));
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -40,6 +40,83 @@ async fn it_fails_with_foreign_key_violation() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_constraint_violations() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO tweet(id, text, owner_id) VALUES (1, 'Foo', 1);")
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("SAVEPOINT violation").execute(&mut *tx).await?;
+
+    let err = sqlx::query("INSERT INTO tweet VALUES (1, NOW(), 'Foo', 1);")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    let violation = err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .unwrap();
+
+    assert_eq!(violation.constraint.as_deref(), Some("tweet_pkey"));
+    assert_eq!(violation.table.as_deref(), Some("tweet"));
+    assert_eq!(violation.columns, ["id"]);
+    assert_eq!(violation.value.as_deref(), Some("1"));
+    assert_eq!(violation.referenced_table, None);
+
+    sqlx::query("ROLLBACK TO SAVEPOINT violation")
+        .execute(&mut *tx)
+        .await?;
+
+    let err = sqlx::query("INSERT INTO tweet_reply (tweet_id, text) VALUES (2, 'Reply!');")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    let violation = err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .unwrap();
+
+    assert_eq!(
+        violation.constraint.as_deref(),
+        Some("tweet_reply_tweet_id_fkey")
+    );
+    assert_eq!(violation.table.as_deref(), Some("tweet_reply"));
+    assert_eq!(violation.columns, ["tweet_id"]);
+    assert_eq!(violation.value.as_deref(), Some("2"));
+    assert_eq!(violation.referenced_table.as_deref(), Some("tweet"));
+
+    sqlx::query("ROLLBACK TO SAVEPOINT violation")
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("INSERT INTO tweet_reply (tweet_id, text) VALUES (1, 'Reply!');")
+        .execute(&mut *tx)
+        .await?;
+
+    let err = sqlx::query("DELETE FROM tweet WHERE id = 1;")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    let violation = err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .unwrap();
+
+    assert_eq!(violation.table.as_deref(), Some("tweet_reply"));
+    assert!(violation.columns.is_empty());
+    assert_eq!(violation.value.as_deref(), Some("1"));
+    assert_eq!(violation.referenced_table.as_deref(), Some("tweet"));
+    assert_eq!(violation.referenced_columns, ["id"]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fails_with_not_null_violation() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1,8 +1,8 @@
 use futures::{StreamExt, TryStreamExt};
-use sqlx::postgres::types::Oid;
+use sqlx::postgres::types::{Oid, PgRawText};
 use sqlx::postgres::{
-    PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgAdvisoryLock, PgClientEncoding, PgConnectOptions, PgConnection, PgDatabaseError,
+    PgErrorPosition, PgListener, PgPoolOptions, PgRow, PgSeverity, Postgres,
 };
 use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_test::{new, pool, setup_if_needed};
@@ -767,6 +767,228 @@ async fn it_sets_application_name() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_sets_and_verifies_client_encoding() -> anyhow::Result<()> {
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    let mut conn =
+        PgConnection::connect_with(&options.clone().client_encoding(PgClientEncoding::SqlAscii))
+            .await?;
+
+    let encoding: String = sqlx::query_scalar("SHOW client_encoding")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(encoding, "SQL_ASCII");
+
+    // text is passed through as stored, in both the text and binary format
+    let text: PgRawText = conn.fetch_one("SELECT 'café'::text").await?.get(0);
+    assert_eq!(text.0, "café".as_bytes());
+
+    let text: PgRawText = sqlx::query_scalar("SELECT $1::text")
+        .bind(PgRawText(b"caf\xc3\xa9".to_vec()))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(text.0, "café".as_bytes());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_on_connect() -> anyhow::Result<()> {
+    let statements = [
+        "SELECT $1::int4 + 1",
+        "SELECT $1::text || 'b'",
+        // duplicates are only prepared once
+        "SELECT $1::int4 + 1",
+    ];
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .prepare_statements(statements)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    let value: i32 = sqlx::query_scalar(statements[0])
+        .bind(1_i32)
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(value, 2);
+
+    let value: String = sqlx::query_scalar(statements[1])
+        .bind("a")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(value, "ab");
+
+    // executing them did not prepare them again
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    // the connection still works after a statement fails to prepare
+    let mut conn = PgConnection::connect(&dotenvy::var("DATABASE_URL")?).await?;
+
+    assert!(conn
+        .prepare_cached(&["SELECT 1", "SELEC 2", "SELECT 3"])
+        .await
+        .is_err());
+
+    let value: i32 = sqlx::query_scalar("SELECT 4").fetch_one(&mut conn).await?;
+    assert_eq!(value, 4);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_formats_redacted_arguments() -> anyhow::Result<()> {
+    use sqlx::{Arguments, Execute};
+
+    let mut query =
+        sqlx::query::<sqlx::Postgres>("SELECT * FROM users WHERE id = $1 AND password = $2")
+            .bind(42_i64)
+            .bind_sensitive("hunter2")
+            .bind("alice")
+            .bind(None::<i32>)
+            .bind(1.5_f64)
+            .bind(&b"raw"[..])
+            .bind("x".repeat(100));
+
+    let mut rendered = String::new();
+    query
+        .take_arguments()
+        .unwrap()
+        .format_redacted(&mut rendered)?;
+
+    assert_eq!(
+        rendered,
+        format!(
+            r#"[42, <redacted>, "alice", NULL, 1.5, <3 bytes>, "{}"…]"#,
+            "x".repeat(64)
+        )
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_named_parameters() -> anyhow::Result<()> {
+    use sqlx::named::NamedQuery;
+    use std::collections::BTreeMap;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let query = NamedQuery::parse("SELECT :a::int8 + :b, :a, $$:b$$");
+
+    let params = BTreeMap::from([("a".to_owned(), 1_i64), ("b".to_owned(), 2_i64)]);
+
+    let (sql, arguments) = query.bind::<Postgres, _>(&params)?;
+
+    assert_eq!(sql, "SELECT $1::int8 + $2, $3, $$:b$$");
+
+    let row: (i64, i64, String) = sqlx::query_as_with(&sql, arguments)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row, (3, 1, ":b".to_owned()));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_exports_csv() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut csv = Vec::new();
+    let rows = sqlx::export::write_csv(
+        &mut conn,
+        sqlx::query(
+            "SELECT * FROM (VALUES (1::int4, 'a,b'::text, true, '\\x01ab'::bytea, 2::int8), \
+             (2, NULL, false, NULL, NULL)) AS t (id, name, flag, data, total)",
+        ),
+        &mut csv,
+    )
+    .await?;
+
+    assert_eq!(rows, 2);
+    assert_eq!(
+        String::from_utf8(csv)?,
+        "id,name,flag,data,total\n1,\"a,b\",true,\\x01ab,2\n2,,false,,\n"
+    );
+
+    let err = sqlx::export::write_csv(&mut conn, sqlx::query("SELECT NOW()"), Vec::new())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_guards_pool_queries() -> anyhow::Result<()> {
+    use sqlx::pool::{QueryGuard, QueryLimit};
+
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .query_guard(QueryGuard::new().max_execution_time(Duration::from_millis(100)))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let err = sqlx::query("SELECT pg_sleep(5)")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        sqlx::Error::QueryLimitExceeded(QueryLimit::ExecutionTime(_))
+    ));
+
+    let err = sqlx::query("SELECT pg_sleep(5)")
+        .fetch_optional(&pool)
+        .await
+        .map(|_| ())
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::QueryLimitExceeded(_)));
+
+    // the connections of the exceeded queries were closed, so the pool is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_clears_tables() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE cleared (id SERIAL PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let insert = "INSERT INTO cleared (name) VALUES ('alice'), ('bob') RETURNING id";
+
+    let ids: Vec<i32> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [1, 2]);
+
+    sqlx::ops::clear_table(&mut conn, "cleared", false).await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [3, 4]);
+
+    sqlx::ops::clear_table(&mut conn, "cleared", true).await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [1, 2]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
     new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
@@ -995,6 +1217,48 @@ async fn test_listener_cleanup() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_routes_notifications_to_typed_subscriptions() -> anyhow::Result<()> {
+    use sqlx::postgres::{PgChannelRouter, PgListener, PgOverflowPolicy};
+
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let router = PgChannelRouter::new(PgListener::connect(&env::var("DATABASE_URL")?).await?);
+
+    let mut all = router
+        .subscribe("router_test", 16, PgOverflowPolicy::DropNewest, |payload| {
+            Ok(payload.parse::<i32>()?)
+        })
+        .await?;
+    let mut latest = router
+        .subscribe("router_test", 1, PgOverflowPolicy::DropOldest, |payload| {
+            Ok(payload.to_owned())
+        })
+        .await?;
+    let mut json = router
+        .subscribe_json::<serde_json::Value>("router_test_json", 4, PgOverflowPolicy::Close)
+        .await?;
+
+    notify_conn
+        .execute(
+            "SELECT pg_notify('router_test', '1'), pg_notify('router_test', 'two'), \
+             pg_notify('router_test', '3'), pg_notify('router_test_json', '{\"a\": 1}')",
+        )
+        .await?;
+
+    assert_eq!(all.recv().await.unwrap()?, 1);
+    assert!(all.recv().await.unwrap().is_err());
+    assert_eq!(all.recv().await.unwrap()?, 3);
+
+    // `all` received every notification, so `latest` has seen them too
+    assert_eq!(latest.recv().await.unwrap()?, "3");
+    assert_eq!(latest.dropped(), 2);
+
+    assert_eq!(json.recv().await.unwrap()?, serde_json::json!({ "a": 1 }));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     let pool = pool::<Postgres>().await?;
@@ -1813,3 +2077,47 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_reports_copy_in_progress() -> anyhow::Result<()> {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);")
+        .await?;
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+
+    let mut copy = conn
+        .copy_in_raw("COPY users (id) FROM STDIN WITH (FORMAT CSV);")
+        .await?;
+
+    let started = Instant::now();
+
+    copy.max_bytes_per_second(40).on_progress({
+        let progress = progress.clone();
+        move |p| {
+            progress
+                .lock()
+                .unwrap()
+                .push((p.bytes_written, p.rows_written))
+        }
+    });
+
+    copy.send("1\n2\n".as_bytes()).await?;
+    copy.read_from("3\n4\n5\n".as_bytes()).await?;
+
+    assert_eq!(copy.bytes_written(), 10);
+    assert_eq!(copy.rows_written(), 5);
+
+    let rows = copy.finish().await?;
+    assert_eq!(rows, 5);
+
+    // 10 bytes at 40 bytes per second
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    assert_eq!(*progress.lock().unwrap(), [(4, 2), (10, 5)]);
+
+    Ok(())
+}
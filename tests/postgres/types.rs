@@ -257,6 +257,8 @@ mod chrono {
         DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
 
+    use sqlx::postgres::types::PgTimestampExt;
+
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<NaiveTime, FixedOffset>;
 
     test_type!(chrono_date<NaiveDate>(Postgres,
@@ -300,6 +302,31 @@ mod chrono {
             ]
     ));
 
+    test_type!(chrono_date_infinite<PgTimestampExt<NaiveDate>>(Postgres,
+        "'-infinity'::date" == PgTimestampExt::<NaiveDate>::NegInfinity,
+        "DATE '2001-01-05'" == PgTimestampExt::Value(NaiveDate::from_ymd(2001, 1, 5)),
+        "'infinity'::date" == PgTimestampExt::<NaiveDate>::Infinity
+    ));
+
+    test_type!(chrono_date_time_infinite<PgTimestampExt<NaiveDateTime>>(Postgres,
+        "'-infinity'::timestamp" == PgTimestampExt::<NaiveDateTime>::NegInfinity,
+        "'2019-01-02 05:10:20'::timestamp"
+            == PgTimestampExt::Value(NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20)),
+        "'infinity'::timestamp" == PgTimestampExt::<NaiveDateTime>::Infinity
+    ));
+
+    test_type!(chrono_date_time_tz_infinite_vec<Vec<PgTimestampExt<DateTime::<Utc>>>>(Postgres,
+        "array['-infinity', '2019-01-02 05:10:20', 'infinity']::timestamptz[]"
+            == vec![
+                PgTimestampExt::NegInfinity,
+                PgTimestampExt::Value(DateTime::<Utc>::from_utc(
+                    NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20),
+                    Utc,
+                )),
+                PgTimestampExt::Infinity,
+            ]
+    ));
+
     test_type!(chrono_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: NaiveTime::from_hms_micro(5, 10, 20, 115100), offset: FixedOffset::east(0) },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: NaiveTime::from_hms_micro(5, 10, 20, 115100), offset: FixedOffset::east(60 * 60 * 6 + 1800) },
@@ -470,6 +497,17 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(Postgres,
     "12345.6789::numeric" == "12345.6789".parse::<sqlx::types::BigDecimal>().unwrap(),
 ));
 
+#[cfg(feature = "bigdecimal")]
+type BigDecimalExt = sqlx::postgres::types::PgNumericExt<sqlx::types::BigDecimal>;
+
+#[cfg(feature = "bigdecimal")]
+test_type!(bigdecimal_ext<BigDecimalExt>(Postgres,
+    "'NaN'::numeric" == BigDecimalExt::NaN,
+    "'Infinity'::numeric" == BigDecimalExt::Infinity,
+    "'-Infinity'::numeric" == BigDecimalExt::NegInfinity,
+    "12.34::numeric" == BigDecimalExt::Value("12.34".parse::<sqlx::types::BigDecimal>().unwrap()),
+));
+
 #[cfg(feature = "bigdecimal")]
 test_type!(numrange_bigdecimal<PgRange<sqlx::types::BigDecimal>>(Postgres,
     "'(1.3,2.4)'::numrange" == PgRange::from(
@@ -550,6 +588,20 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
+test_prepared_type!(interval_std<std::time::Duration>(
+    Postgres,
+    "INTERVAL '1.5 seconds'" == std::time::Duration::from_millis(1_500),
+    "INTERVAL '1 day 2 hours'" == std::time::Duration::from_secs(26 * 3_600),
+));
+
+#[cfg(feature = "chrono")]
+test_prepared_type!(interval_chrono<sqlx::types::chrono::Duration>(
+    Postgres,
+    "INTERVAL '-1 hours'" == sqlx::types::chrono::Duration::hours(-1),
+    "INTERVAL '2 days 30 minutes'"
+        == sqlx::types::chrono::Duration::days(2) + sqlx::types::chrono::Duration::minutes(30),
+));
+
 test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
 
 test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
@@ -625,3 +677,89 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+#[cfg(all(feature = "zstd", feature = "lz4"))]
+#[sqlx_macros::test]
+async fn test_compressed_adapter() -> anyhow::Result<()> {
+    use sqlx::types::{Compressed, Lz4, Zstd};
+
+    #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+    struct Event {
+        payload: Compressed<String, Zstd>,
+        trace: Compressed<Vec<u8>, Lz4>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE events (payload BYTEA NOT NULL, trace BYTEA NOT NULL)")
+        .await?;
+
+    let payload = r#"{"level":"info","message":"user logged in"}"#.repeat(100);
+    let trace = b"frame ".repeat(100);
+
+    sqlx::query("INSERT INTO events (payload, trace) VALUES ($1, $2)")
+        .bind(Compressed::<_, Zstd>::new(payload.clone()))
+        .bind(Compressed::<_, Lz4>::new(trace.clone()))
+        .execute(&mut conn)
+        .await?;
+
+    let event: Event = sqlx::query_as("SELECT payload, trace FROM events")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(*event.payload, payload);
+    assert_eq!(*event.trace, trace);
+
+    let stored: i64 = sqlx::query_scalar("SELECT length(payload)::int8 FROM events")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(stored < payload.len() as i64 / 10);
+
+    Ok(())
+}
+
+#[cfg(feature = "encryption")]
+#[sqlx_macros::test]
+async fn test_encrypted_adapter() -> anyhow::Result<()> {
+    use sqlx::types::{Encrypted, KeyProvider};
+
+    struct TestKey;
+
+    impl KeyProvider for TestKey {
+        fn key() -> [u8; 32] {
+            *b"an example very very secret key."
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id BIGINT PRIMARY KEY, email BYTEA NOT NULL)")
+        .await?;
+
+    let email = "alice@example.com".to_owned();
+
+    sqlx::query("INSERT INTO users (id, email) VALUES ($1, $2)")
+        .bind(1_i64)
+        .bind(Encrypted::<_, TestKey>::seal(&email, "users.email", 1_i64.to_be_bytes()).unwrap())
+        .execute(&mut conn)
+        .await?;
+
+    let stored: Vec<u8> = sqlx::query_scalar("SELECT email FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(!stored
+        .windows(email.len())
+        .any(|window| window == email.as_bytes()));
+
+    let (id, decrypted): (i64, Encrypted<String, TestKey>) =
+        sqlx::query_as("SELECT id, email FROM users")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(
+        decrypted.open("users.email", id.to_be_bytes()).unwrap(),
+        email
+    );
+    assert!(decrypted.open("users.email", 2_i64.to_be_bytes()).is_err());
+
+    Ok(())
+}
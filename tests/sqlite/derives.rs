@@ -12,3 +12,136 @@ test_type!(origin_enum<Origin>(Sqlite,
     "1" == Origin::Foo,
     "2" == Origin::Bar,
 ));
+
+#[derive(sqlx::FromRow, sqlx::schema::SchemaExpectations)]
+#[allow(dead_code)] // Only the expectations derived from the fields are checked.
+struct Account {
+    id: i64,
+    #[sqlx(rename = "display_name")]
+    name: String,
+    email: Option<String>,
+    #[sqlx(default)]
+    status: Option<String>,
+    #[sqlx(flatten)]
+    audit: Audit,
+    #[sqlx(skip)]
+    _cached: bool,
+}
+
+#[derive(sqlx::FromRow, sqlx::schema::SchemaExpectations, sqlx::named::BindNamed)]
+struct Audit {
+    created_at: i64,
+}
+
+#[derive(sqlx::FromRow, sqlx::schema::SchemaExpectations)]
+#[allow(dead_code)] // Only the expectations derived from the fields are checked.
+struct DriftedAccount {
+    id: String,
+    email: String,
+    deleted_at: Option<i64>,
+}
+
+#[sqlx_macros::test]
+async fn it_verifies_schema_expectations() -> anyhow::Result<()> {
+    use sqlx::schema::{self, SchemaError, SchemaMismatchKind, TableExpectation};
+    use sqlx::{Connection, Executor, SqliteConnection};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute(
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY NOT NULL, display_name TEXT NOT NULL, \
+         email TEXT, created_at INTEGER NOT NULL)",
+    )
+    .await?;
+
+    schema::verify(
+        &mut conn,
+        &[TableExpectation::for_row::<Account>("accounts")],
+    )
+    .await?;
+
+    let err = schema::verify(
+        &mut conn,
+        &[TableExpectation::for_row::<DriftedAccount>("accounts")],
+    )
+    .await
+    .unwrap_err();
+
+    let SchemaError::Mismatch(mismatches) = err else {
+        panic!("unexpected error: {err}");
+    };
+
+    let kinds: Vec<_> = mismatches
+        .iter()
+        .map(|m| (m.column.as_str(), &m.kind))
+        .collect();
+
+    assert!(matches!(
+        kinds[..],
+        [
+            ("id", SchemaMismatchKind::IncompatibleType { .. }),
+            ("email", SchemaMismatchKind::Nullable { .. }),
+            ("deleted_at", SchemaMismatchKind::MissingColumn),
+        ]
+    ));
+
+    assert!(matches!(
+        schema::verify(
+            &mut conn,
+            &[TableExpectation::for_row::<Account>("missing")]
+        )
+        .await,
+        Err(SchemaError::Execute { .. })
+    ));
+
+    Ok(())
+}
+
+#[derive(sqlx::named::BindNamed)]
+#[sqlx(rename_all = "camelCase")]
+struct AccountFilter<'a> {
+    display_name: &'a str,
+    #[sqlx(rename = "minId")]
+    id: i64,
+    #[sqlx(flatten)]
+    audit: Audit,
+    #[sqlx(skip)]
+    _cached: bool,
+}
+
+#[sqlx_macros::test]
+async fn it_binds_named_parameters() -> anyhow::Result<()> {
+    use sqlx::named::NamedQuery;
+    use std::collections::HashMap;
+
+    let mut conn = sqlx_test::new::<Sqlite>().await?;
+
+    let query =
+        NamedQuery::parse("SELECT :displayName, :minId, :created_at, ':displayName', :minId + 1");
+
+    let filter = AccountFilter {
+        display_name: "alice",
+        id: 10,
+        audit: Audit { created_at: 20 },
+        _cached: false,
+    };
+
+    let (sql, arguments) = query.bind::<Sqlite, _>(&filter)?;
+
+    assert_eq!(sql, "SELECT ?, ?, ?, ':displayName', ? + 1");
+
+    let row: (String, i64, i64, String, i64) = sqlx::query_as_with(&sql, arguments)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row, ("alice".into(), 10, 20, ":displayName".into(), 11));
+
+    let params = HashMap::from([("minId", 10_i64)]);
+
+    let err = query.bind::<Sqlite, _>(&params).unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::Configuration(_)));
+    assert!(err.to_string().contains(":displayName"));
+
+    Ok(())
+}
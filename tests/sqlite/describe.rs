@@ -138,6 +138,34 @@ async fn it_describes_temporary_table() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_uuid_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id UUID PRIMARY KEY NOT NULL, name TEXT)")
+        .await?;
+
+    let d = conn.describe("SELECT id, name FROM users").await?;
+    assert_eq!(d.column(0).type_info().name(), "UUID");
+    assert_eq!(d.column(1).type_info().name(), "TEXT");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_describes_interval_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE jobs (id INTEGER PRIMARY KEY, every INTERVAL NOT NULL)")
+        .await?;
+
+    let d = conn.describe("SELECT id, every FROM jobs").await?;
+    assert_eq!(d.column(0).type_info().name(), "INTEGER");
+    assert_eq!(d.column(1).type_info().name(), "INTERVAL");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_expression_from_empty_table() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -823,6 +851,115 @@ async fn it_describes_func_strftime() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_outer_joins_and_subqueries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let query = "SELECT tweet.id, tweet_reply.text FROM tweet \
+                 FULL OUTER JOIN tweet_reply ON tweet_reply.tweet_id = tweet.id";
+    let info = conn.describe(query).await?;
+    assert_eq!(info.nullable(0), Some(true), "{query}");
+    assert_eq!(info.nullable(1), Some(true), "{query}");
+
+    let query = "SELECT tweet.id, tweet_reply.text FROM tweet_reply \
+                 RIGHT JOIN tweet ON tweet_reply.tweet_id = tweet.id";
+    let info = conn.describe(query).await?;
+    assert_eq!(info.nullable(0), Some(false), "{query}");
+    assert_eq!(info.nullable(1), Some(true), "{query}");
+
+    // NULL if the subquery has no rows
+    let query = "SELECT (SELECT text FROM tweet_reply WHERE tweet_id = tweet.id), \
+                 (SELECT max(id) FROM tweet_reply WHERE tweet_id = tweet.id) FROM tweet";
+    let info = conn.describe(query).await?;
+    assert_eq!(info.column(0).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(0), Some(true), "{query}");
+    assert_eq!(info.column(1).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(1), Some(true), "{query}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_describes_func_builtins() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // `id < 0` so that the types cannot be taken from a result row
+    let query = "SELECT lower(text), length(text), abs(id), round(id, 2), abs(owner_id), \
+                 nullif(id, 1), typeof(owner_id), text LIKE '%sqlx%' \
+                 FROM tweet WHERE id < 0";
+    let info = conn.describe(query).await?;
+
+    assert_eq!(info.column(0).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(0), Some(false), "{query}");
+    assert_eq!(info.column(1).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(1), Some(false), "{query}");
+    assert_eq!(info.column(2).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(2), Some(false), "{query}");
+    assert_eq!(info.column(3).type_info().name(), "REAL", "{query}");
+    assert_eq!(info.nullable(3), Some(false), "{query}");
+    assert_eq!(info.column(4).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(4), Some(true), "{query}");
+    assert_eq!(info.column(5).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(5), Some(true), "{query}");
+    assert_eq!(info.column(6).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(6), Some(false), "{query}");
+    assert_eq!(info.column(7).type_info().name(), "BOOLEAN", "{query}");
+    assert_eq!(info.nullable(7), Some(false), "{query}");
+
+    let query = "SELECT COALESCE(owner_id, 0), \
+                 CASE WHEN is_sent THEN text ELSE 'draft' END, \
+                 CASE WHEN is_sent THEN id END \
+                 FROM tweet WHERE id < 0";
+    let info = conn.describe(query).await?;
+
+    assert_eq!(info.column(0).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(0), Some(false), "{query}");
+    assert_eq!(info.column(1).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(1), Some(false), "{query}");
+    assert_eq!(info.column(2).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(2), Some(true), "{query}");
+
+    // `unicode('')` is NULL
+    let query = "SELECT unicode(text) FROM tweet WHERE id < 0";
+    let info = conn.describe(query).await?;
+
+    assert_eq!(info.column(0).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(0), Some(true), "{query}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_describes_func_aggregates() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let query = "SELECT avg(id), total(id), group_concat(text), count(owner_id) \
+                 FROM tweet WHERE id < 0";
+    let info = conn.describe(query).await?;
+
+    assert_eq!(info.column(0).type_info().name(), "REAL", "{query}");
+    assert_eq!(info.nullable(0), Some(true), "{query}"); // NULL for no rows
+    assert_eq!(info.column(1).type_info().name(), "REAL", "{query}");
+    assert_eq!(info.nullable(1), Some(false), "{query}");
+    assert_eq!(info.column(2).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(2), Some(true), "{query}");
+    assert_eq!(info.column(3).type_info().name(), "INTEGER", "{query}");
+    assert_eq!(info.nullable(3), Some(false), "{query}");
+
+    let query = "SELECT avg(id), total(owner_id), group_concat(text, ', ') \
+                 FROM tweet WHERE id < 0 GROUP BY owner_id";
+    let info = conn.describe(query).await?;
+
+    assert_eq!(info.column(0).type_info().name(), "REAL", "{query}");
+    assert_eq!(info.nullable(0), Some(false), "{query}");
+    assert_eq!(info.column(1).type_info().name(), "REAL", "{query}");
+    assert_eq!(info.nullable(1), Some(false), "{query}");
+    assert_eq!(info.column(2).type_info().name(), "TEXT", "{query}");
+    assert_eq!(info.nullable(2), Some(false), "{query}");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_with_recursive() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -968,3 +1105,34 @@ async fn it_describes_analytical_function() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_pragmas() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let d = conn.describe("PRAGMA table_info(tweet)").await?;
+    assert_eq!(d.columns().len(), 6);
+    assert_eq!(d.column(0).name(), "cid");
+    assert_eq!(d.column(0).type_info().name(), "INTEGER");
+    assert_eq!(d.column(1).type_info().name(), "TEXT");
+    assert_eq!(d.nullable(1), Some(false));
+    assert_eq!(d.nullable(4), Some(true)); // dflt_value
+
+    let d = conn
+        .describe("SELECT name, \"notnull\", dflt_value FROM pragma_table_info('tweet')")
+        .await?;
+    assert_eq!(d.column(0).type_info().name(), "TEXT");
+    assert_eq!(d.column(1).type_info().name(), "INTEGER");
+    assert_eq!(d.nullable(0), Some(false));
+    assert_eq!(d.nullable(2), Some(true));
+
+    // no rows to infer the types from
+    let d = conn
+        .describe("PRAGMA main.foreign_key_list(accounts)")
+        .await?;
+    assert_eq!(d.column(2).name(), "table");
+    assert_eq!(d.column(2).type_info().name(), "TEXT");
+    assert_eq!(d.nullable(4), Some(true)); // to
+
+    Ok(())
+}
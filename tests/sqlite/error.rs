@@ -36,6 +36,58 @@ async fn it_fails_with_foreign_key_violation() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_constraint_violations() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        "CREATE TEMPORARY TABLE pairs (a INTEGER, b INTEGER, c TEXT, UNIQUE (a, b));
+         CREATE UNIQUE INDEX pairs_lower_c ON pairs (lower(c));
+         INSERT INTO pairs VALUES (1, 2, 'x');",
+    )
+    .await?;
+
+    let err = sqlx::query("INSERT INTO pairs VALUES (1, 2, 'y');")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    let violation = err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .unwrap();
+
+    assert_eq!(violation.constraint, None);
+    assert_eq!(violation.table.as_deref(), Some("pairs"));
+    assert_eq!(violation.columns, ["a", "b"]);
+
+    let err = sqlx::query("INSERT INTO pairs VALUES (3, 4, 'X');")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    let violation = err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .unwrap();
+
+    assert_eq!(violation.constraint.as_deref(), Some("pairs_lower_c"));
+    assert!(violation.columns.is_empty());
+
+    let err = sqlx::query("INSERT INTO tweet_reply (id, tweet_id, text) VALUES (2, 2, 'Reply!');")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    assert!(err
+        .as_database_error()
+        .and_then(|err| err.constraint_violation())
+        .is_some());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fails_with_not_null_violation() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -110,6 +110,40 @@ async fn test_query_as_raw() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_file_with_include() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let accounts = sqlx::query_file!("tests/sqlite/queries/active-accounts.sql")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(accounts[0].id, 1);
+    assert_eq!(accounts[0].name, "Herp Derpinson");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_checked_with() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteArguments;
+    use sqlx::Arguments;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut args = SqliteArguments::default();
+    args.add(1_i64);
+
+    let account = sqlx::query_checked_with!("select id, name from accounts where id = ?", args)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -191,6 +225,21 @@ async fn macro_select_from_view() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_from_pragma() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let columns = sqlx::query!("SELECT name, pk, dflt_value FROM pragma_table_info('accounts')")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let id = columns.iter().find(|column| column.name == "id").unwrap();
+    assert_eq!(id.pk, 1);
+    assert_eq!(id.dflt_value, None::<String>);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_column_override_not_null() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
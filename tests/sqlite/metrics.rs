@@ -0,0 +1,66 @@
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::MetricKind;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::Executor;
+
+#[sqlx_macros::test]
+async fn it_records_query_and_pool_metrics() -> anyhow::Result<()> {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install()?;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    for _ in 0..2 {
+        sqlx::query("SELECT ?").bind(1_i32).fetch_one(&pool).await?;
+    }
+
+    assert!(pool.execute("SELECT * FROM missing").await.is_err());
+
+    // histograms are drained by a snapshot, so take only one
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    let counter = |name: &str, labels: &[(&str, &str)]| {
+        snapshot.iter().find_map(|(key, _, _, value)| {
+            let key = key.key();
+
+            let matches = key.name() == name
+                && labels.iter().all(|(label, value)| {
+                    key.labels()
+                        .any(|l| l.key() == *label && l.value() == *value)
+                });
+
+            match value {
+                DebugValue::Counter(n) if matches => Some(*n),
+                _ => None,
+            }
+        })
+    };
+
+    // connecting runs queries as well
+    assert!(counter("sqlx_queries_total", &[("database", "SQLite")]) >= Some(3));
+    assert_eq!(
+        counter(
+            "sqlx_query_errors_total",
+            &[("database", "SQLite"), ("code", "1")]
+        ),
+        Some(1)
+    );
+    assert_eq!(counter("sqlx_statement_cache_hits_total", &[]), Some(1));
+    assert_eq!(counter("sqlx_statement_cache_misses_total", &[]), Some(1));
+
+    let acquire_waits = snapshot.iter().find(|(key, _, _, _)| {
+        key.kind() == MetricKind::Histogram
+            && key.key().name() == "sqlx_pool_acquire_duration_seconds"
+    });
+
+    assert!(matches!(
+        acquire_waits,
+        Some((_, _, _, DebugValue::Histogram(waits))) if waits.len() >= 3
+    ));
+
+    Ok(())
+}
@@ -0,0 +1 @@
+WITH active_accounts AS (SELECT id, name FROM accounts WHERE is_active)
@@ -0,0 +1,2 @@
+-- include: tests/sqlite/queries/active-accounts-cte.sql
+SELECT id, name FROM active_accounts ORDER BY id
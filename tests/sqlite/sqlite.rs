@@ -1,7 +1,11 @@
 use futures::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::database::{Database, PlaceholderStyle};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteReadPool,
+    SqliteSerializedWriter,
+};
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
     SqliteConnection, SqlitePool, Statement, TypeInfo,
@@ -14,6 +18,52 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(new::<Sqlite>().await?.ping().await?)
 }
 
+#[sqlx_macros::test]
+async fn it_waits_for_database() -> anyhow::Result<()> {
+    use sqlx::ConnectBackoff;
+    use std::time::Duration;
+
+    sqlx::wait_for_database::<Sqlite>(
+        "sqlite::memory:",
+        Duration::from_secs(5),
+        ConnectBackoff::default(),
+    )
+    .await?;
+
+    // a missing database file is not a transient error, so it is returned right away
+    let res = sqlx::wait_for_database::<Sqlite>(
+        "sqlite://tests/sqlite/does-not-exist.db",
+        Duration::from_secs(60),
+        ConnectBackoff::constant(Duration::from_secs(60)),
+    )
+    .await;
+    assert!(matches!(res, Err(sqlx::Error::Database(_))), "{res:?}");
+
+    // cannot block the runtime of this test
+    let res = sqlx::wait_for_database_blocking::<Sqlite>(
+        "sqlite::memory:",
+        Duration::from_secs(5),
+        ConnectBackoff::default(),
+    );
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))), "{res:?}");
+
+    Ok(())
+}
+
+#[test]
+fn it_waits_for_database_blocking() -> anyhow::Result<()> {
+    use sqlx::ConnectBackoff;
+    use std::time::Duration;
+
+    sqlx::wait_for_database_blocking::<Sqlite>(
+        "sqlite::memory:",
+        Duration::from_secs(5),
+        ConnectBackoff::default(),
+    )?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fetches_and_inflates_row() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -205,6 +255,108 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resets_connections_on_return() -> anyhow::Result<()> {
+    use sqlx::pool::ResetOnReturn;
+
+    for (reset, temp_tables) in [(ResetOnReturn::Rollback, 1), (ResetOnReturn::Full, 0)] {
+        let pool: SqlitePool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .reset_on_return(reset)
+            .connect("sqlite::memory:")
+            .await?;
+
+        let mut conn = pool.acquire().await?;
+        conn.execute(
+            "CREATE TEMP TABLE scratch (x INTEGER); BEGIN; INSERT INTO scratch VALUES (1)",
+        )
+        .await?;
+        drop(conn);
+
+        let mut conn = pool.acquire().await?;
+
+        // fails if the transaction is still open
+        conn.execute("BEGIN; ROLLBACK").await?;
+
+        let count: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM temp.sqlite_master WHERE type = 'table'")
+                .fetch_one(&mut *conn)
+                .await?;
+        assert_eq!(count, temp_tables, "{reset:?}");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_acquires_dedicated_connections() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .max_dedicated_connections(1)
+        .acquire_timeout(std::time::Duration::from_millis(100))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let size = pool.size();
+    let mut dedicated = pool.acquire_dedicated().await?;
+    let n: i32 = sqlx::query_scalar("SELECT 1")
+        .fetch_one(&mut *dedicated)
+        .await?;
+    assert_eq!(n, 1);
+
+    // the dedicated connection does not count against `max_connections`
+    assert_eq!(pool.size(), size);
+    assert_eq!(pool.num_dedicated(), 1);
+    let conn = pool.acquire().await?;
+
+    assert!(matches!(
+        pool.acquire_dedicated().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    dedicated.close().await?;
+    assert_eq!(pool.num_dedicated(), 0);
+
+    // nor waits for a saturated pool
+    let dedicated = pool.acquire_dedicated().await?;
+    assert_eq!(pool.size(), 1);
+    dedicated.close().await?;
+    drop(conn);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_pool_health() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let health = pool.health_check().await;
+
+    assert!(health.can_connect, "{health:?}");
+    assert!(health.latency.is_some());
+    assert_eq!(health.last_error, None);
+    assert_eq!(health.max_connections, 2);
+    assert_eq!(health.size, 1);
+    assert_eq!(health.num_idle, 1);
+    assert_eq!(health.utilization(), 0.0);
+
+    let conn = pool.acquire().await?;
+    assert_eq!(pool.health_check().await.utilization(), 0.5);
+    drop(conn);
+
+    pool.close().await;
+
+    let health = pool.health_check().await;
+    assert!(!health.can_connect);
+    assert_eq!(health.latency, None);
+    assert!(health.last_error.is_some());
+
+    Ok(())
+}
+
 #[cfg(sqlite_ipaddr)]
 #[sqlx_macros::test]
 async fn it_opens_with_extension() -> anyhow::Result<()> {
@@ -794,3 +946,2337 @@ async fn test_multiple_set_progress_handler_calls_drop_old_handler() -> anyhow::
     assert_eq!(1, Arc::strong_count(&ref_counted_object));
     Ok(())
 }
+
+#[cfg(feature = "sqlite-session")]
+#[sqlx_macros::test]
+async fn it_applies_session_changesets() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteConflictAction, SqliteConflictKind};
+
+    let mut source = SqliteConnection::connect("sqlite::memory:").await?;
+    let mut target = SqliteConnection::connect("sqlite::memory:").await?;
+
+    for conn in [&mut source, &mut target] {
+        conn.execute("CREATE TABLE kv (key TEXT PRIMARY KEY, value TEXT NOT NULL)")
+            .await?;
+    }
+
+    target.execute("INSERT INTO kv VALUES ('b', 'old')").await?;
+
+    let session = source.lock_handle().await?.create_session("main")?;
+
+    source
+        .execute("INSERT INTO kv VALUES ('a', '1'), ('b', '2')")
+        .await?;
+
+    let changeset = source.lock_handle().await?.session_changeset(&session)?;
+    source.lock_handle().await?.delete_session(session)?;
+
+    let mut conflicts = Vec::new();
+
+    target
+        .lock_handle()
+        .await?
+        .apply_changeset(&changeset, |conflict| {
+            conflicts.push((conflict.kind(), conflict.table().to_owned()));
+            SqliteConflictAction::Replace
+        })?;
+
+    assert_eq!(conflicts, [(SqliteConflictKind::Conflict, "kv".to_owned())]);
+
+    let rows: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM kv ORDER BY key")
+        .fetch_all(&mut target)
+        .await?;
+
+    assert_eq!(rows, [("a".into(), "1".into()), ("b".into(), "2".into())]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_explains_query_plans() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, name TEXT);
+         CREATE INDEX users_email_idx ON users (email);",
+    )
+    .await?;
+
+    let plan = conn
+        .explain("SELECT name FROM users WHERE email = ?")
+        .await?;
+    assert!(plan.uses_index("users_email_idx"), "{plan:?}");
+    assert!(!plan.scans_table("users"));
+
+    let plan = conn
+        .explain("SELECT email FROM users ORDER BY name")
+        .await?;
+    assert!(!plan.uses_index("users_email_idx"));
+    assert!(plan.scans_table("users"), "{plan:?}");
+    assert_eq!(plan.nodes().len(), 2);
+    assert_eq!(plan.nodes()[0].table(), Some("users"));
+    assert!(plan.nodes()[1].detail().contains("ORDER BY"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_isolates_unique_violations_in_bulk_inserts() -> anyhow::Result<()> {
+    use sqlx::query_builder::{insert_isolating_unique_violations, QueryBuilder};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE)")
+        .await?;
+
+    let users: Vec<(i64, String)> = (1..=10)
+        .map(|id| (id, format!("user{}@example.com", id % 8)))
+        .collect();
+
+    let mut duplicates = Vec::new();
+
+    let result = insert_isolating_unique_violations(
+        &mut conn,
+        &users,
+        4,
+        |batch| {
+            let mut qb = QueryBuilder::new("INSERT INTO users (id, email) ");
+            qb.push_values(batch, |mut b, (id, email)| {
+                b.push_bind(id).push_bind(email);
+            });
+            qb
+        },
+        |(id, _), error| {
+            assert!(error.is_unique_violation());
+            duplicates.push(*id);
+        },
+    )
+    .await?;
+
+    assert_eq!(duplicates, [9, 10]);
+    assert_eq!(result.rows_affected(), 8);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 8);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_chunks_bulk_inserts_at_the_bind_limit() -> anyhow::Result<()> {
+    use sqlx::query_builder::execute_values_chunked;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+        .await?;
+
+    // 40,000 parameters, more than fit into one statement
+    let users: Vec<(i64, String)> = (0..20_000)
+        .map(|id| (id, format!("user{id}@example.com")))
+        .collect();
+
+    let result = execute_values_chunked(
+        &mut conn,
+        "INSERT INTO users (id, email) ",
+        &users,
+        |mut b, (id, email)| {
+            b.push_bind(id).push_bind(email);
+        },
+    )
+    .await?;
+
+    assert_eq!(result.rows_affected(), 20_000);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 20_000);
+
+    // all or nothing: the duplicate in the second statement rolls back the first
+    let duplicates: Vec<(i64, String)> = (20_000..40_000)
+        .chain([0])
+        .map(|id| (id, format!("user{id}@example.com")))
+        .collect();
+
+    let err = execute_values_chunked(
+        &mut conn,
+        "INSERT INTO users (id, email) ",
+        &duplicates,
+        |mut b, (id, email)| {
+            b.push_bind(id).push_bind(email);
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.as_database_error().unwrap().is_unique_violation());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 20_000);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_formats_redacted_arguments() -> anyhow::Result<()> {
+    use sqlx::{Arguments, Execute};
+
+    let mut query =
+        sqlx::query::<sqlx::Sqlite>("SELECT * FROM users WHERE id = $1 AND password = $2")
+            .bind(42_i64)
+            .bind_sensitive("hunter2")
+            .bind("alice")
+            .bind(None::<i32>)
+            .bind(1.5_f64)
+            .bind(&b"raw"[..])
+            .bind("x".repeat(100));
+
+    let mut rendered = String::new();
+    query
+        .take_arguments()
+        .unwrap()
+        .format_redacted(&mut rendered)?;
+
+    assert_eq!(
+        rendered,
+        format!(
+            r#"[42, <redacted>, "alice", NULL, 1.5, <3 bytes>, "{}"…]"#,
+            "x".repeat(64)
+        )
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_exports_csv() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute(
+        "CREATE TABLE export (id INTEGER PRIMARY KEY, name TEXT, score REAL, data BLOB);
+         INSERT INTO export VALUES (1, 'alice', 1.5, x'00ff'), (2, 'say \"hi\", bob', NULL, NULL),
+             (3, '', 2.0, NULL);",
+    )
+    .await?;
+
+    let mut csv = Vec::new();
+    let rows = sqlx::export::write_csv(
+        &mut conn,
+        sqlx::query("SELECT * FROM export ORDER BY id"),
+        &mut csv,
+    )
+    .await?;
+
+    assert_eq!(rows, 3);
+    assert_eq!(
+        String::from_utf8(csv)?,
+        "id,name,score,data\n\
+         1,alice,1.5,\\x00ff\n\
+         2,\"say \"\"hi\"\", bob\",,\n\
+         3,\"\",2,\n"
+    );
+
+    let mut tsv = Vec::new();
+    let rows = sqlx::export::write_tsv(
+        &mut conn,
+        sqlx::query("SELECT id, name FROM export WHERE id > ?").bind(10),
+        &mut tsv,
+    )
+    .await?;
+
+    assert_eq!(rows, 0);
+    assert_eq!(String::from_utf8(tsv)?, "id\tname\n");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_guards_pool_queries() -> anyhow::Result<()> {
+    use sqlx::pool::{QueryGuard, QueryGuardAction, QueryLimit};
+
+    const COUNT: &str =
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 100) \
+         SELECT x FROM c";
+
+    let pool = SqlitePoolOptions::new()
+        .query_guard(QueryGuard::new().max_rows(10))
+        .connect("sqlite::memory:")
+        .await?;
+
+    let err = sqlx::query_scalar::<_, i64>(COUNT)
+        .fetch_all(&pool)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        sqlx::Error::QueryLimitExceeded(QueryLimit::Rows(10))
+    ));
+
+    let rows: Vec<i64> = sqlx::query_scalar(&format!("{COUNT} LIMIT 10"))
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(rows.len(), 10);
+
+    let pool = SqlitePoolOptions::new()
+        .query_guard(
+            QueryGuard::new()
+                .max_rows(10)
+                .on_exceeded(QueryGuardAction::Truncate),
+        )
+        .connect("sqlite::memory:")
+        .await?;
+
+    let rows: Vec<i64> = sqlx::query_scalar(COUNT).fetch_all(&pool).await?;
+
+    assert_eq!(rows, (1..=10).collect::<Vec<_>>());
+
+    // connections acquired from the pool are not guarded
+    let mut conn = pool.acquire().await?;
+    let rows: Vec<i64> = sqlx::query_scalar(COUNT).fetch_all(&mut *conn).await?;
+
+    assert_eq!(rows.len(), 100);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_routes_queries_to_replicas() -> anyhow::Result<()> {
+    use sqlx::pool::ReplicaPool;
+
+    let dir = tempdir::TempDir::new("replica_test")?;
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("database.sqlite3"))
+        .create_if_missing(true);
+
+    let primary = SqlitePoolOptions::new()
+        .connect_with(options.clone())
+        .await?;
+
+    sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+        .execute(&primary)
+        .await?;
+
+    // writes fail on the read-only replica, so they must have been routed to the primary
+    let replica = SqlitePoolOptions::new()
+        .connect_with(options.read_only(true))
+        .await?;
+
+    let pool = ReplicaPool::new(primary, [replica]);
+
+    sqlx::query("INSERT INTO items (id) VALUES (1), (2)")
+        .execute(&pool)
+        .await?;
+
+    let insert = (&pool).prepare("INSERT INTO items (id) VALUES (?)").await?;
+    let select = (&pool).prepare("SELECT count(*) FROM items").await?;
+
+    assert_eq!(insert.is_read_only(), Some(false));
+    assert_eq!(select.is_read_only(), Some(true));
+
+    // SQLite reports transaction control as read-only
+    let begin = (&pool).prepare("BEGIN").await?;
+
+    assert_eq!(begin.is_read_only(), Some(true));
+    assert_eq!(begin.controls_transaction(), Some(true));
+    assert_eq!(select.controls_transaction(), Some(false));
+
+    insert.query().bind(3).execute(&pool).await?;
+
+    pool.primary().close().await;
+
+    // reads still work once the primary is closed, so they were routed to the replica
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM items")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    let count: i64 = select.query_scalar().fetch_one(&pool).await?;
+
+    assert_eq!(count, 3);
+
+    let err = sqlx::query("DELETE FROM items").execute(&pool).await;
+
+    assert!(matches!(err, Err(sqlx::Error::PoolClosed)));
+
+    // transaction control goes to the primary even though it is read-only
+    let err = begin.query().execute(&pool).await;
+
+    assert!(matches!(err, Err(sqlx::Error::PoolClosed)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_with_uri_filename() -> anyhow::Result<()> {
+    let mut conn = "sqlite:file:tests/sqlite/sqlite.db?mode=ro&nolock=1"
+        .parse::<SqliteConnectOptions>()?
+        .connect()
+        .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tweet")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(count >= 0);
+
+    let res = sqlx::query("CREATE TABLE not_created (id INTEGER)")
+        .execute(&mut conn)
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_uses_a_custom_vfs() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteVfs, SqliteVfsFile, SqliteVfsFileKind, SqliteVfsOpen};
+    use std::cmp;
+    use std::collections::BTreeMap;
+    use std::io;
+    use std::sync::Mutex;
+
+    type Files = Arc<Mutex<BTreeMap<String, Arc<Mutex<Vec<u8>>>>>>;
+
+    struct MemoryVfs(Files);
+
+    struct MemoryFile {
+        data: Arc<Mutex<Vec<u8>>>,
+        // anonymous temporary files have no path for SQLite to delete
+        delete_on_drop: Option<(Files, String)>,
+    }
+
+    impl Drop for MemoryFile {
+        fn drop(&mut self) {
+            if let Some((files, path)) = self.delete_on_drop.take() {
+                files.lock().unwrap().remove(&path);
+            }
+        }
+    }
+
+    impl SqliteVfs for MemoryVfs {
+        type File = MemoryFile;
+
+        fn open(&self, path: Option<&str>, options: SqliteVfsOpen) -> io::Result<MemoryFile> {
+            let Some(path) = path else {
+                assert!(options.delete_on_close());
+
+                let data = Arc::<Mutex<Vec<u8>>>::default();
+                let path = format!("temp-{:p}", Arc::as_ptr(&data));
+
+                self.0.lock().unwrap().insert(path.clone(), data.clone());
+
+                return Ok(MemoryFile {
+                    data,
+                    delete_on_drop: Some((self.0.clone(), path)),
+                });
+            };
+
+            let mut files = self.0.lock().unwrap();
+
+            if !files.contains_key(path) && !options.create() {
+                assert_eq!(options.kind(), SqliteVfsFileKind::MainDb);
+                return Err(io::ErrorKind::NotFound.into());
+            }
+
+            Ok(MemoryFile {
+                data: files.entry(path.to_owned()).or_default().clone(),
+                delete_on_drop: None,
+            })
+        }
+
+        fn delete(&self, path: &str) -> io::Result<()> {
+            match self.0.lock().unwrap().remove(path) {
+                Some(_) => Ok(()),
+                None => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        fn exists(&self, path: &str) -> io::Result<bool> {
+            Ok(self.0.lock().unwrap().contains_key(path))
+        }
+    }
+
+    impl SqliteVfsFile for MemoryFile {
+        fn read_at(&mut self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+            let data = self.data.lock().unwrap();
+            let start = cmp::min(data.len(), offset as usize);
+            let len = cmp::min(buf.len(), data.len() - start);
+            buf[..len].copy_from_slice(&data[start..start + len]);
+            Ok(len)
+        }
+
+        fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()> {
+            let mut data = self.data.lock().unwrap();
+            let end = offset as usize + buf.len();
+            if data.len() < end {
+                data.resize(end, 0);
+            }
+            data[offset as usize..end].copy_from_slice(buf);
+            Ok(())
+        }
+
+        fn truncate(&mut self, size: u64) -> io::Result<()> {
+            self.data.lock().unwrap().resize(size as usize, 0);
+            Ok(())
+        }
+
+        fn sync(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn size(&mut self) -> io::Result<u64> {
+            Ok(self.data.lock().unwrap().len() as u64)
+        }
+    }
+
+    let files = Files::default();
+
+    sqlx::sqlite::register_vfs("sqlx-test-memory", MemoryVfs(files.clone()))?;
+    assert!(sqlx::sqlite::register_vfs("sqlx-test-memory", MemoryVfs(files.clone())).is_err());
+
+    let options = SqliteConnectOptions::new()
+        .filename("vfs.db")
+        .vfs("sqlx-test-memory");
+
+    assert!(options.clone().connect().await.is_err());
+
+    let mut conn = options.clone().create_if_missing(true).connect().await?;
+
+    conn.execute("CREATE TABLE note (text TEXT); INSERT INTO note VALUES ('kept in memory');")
+        .await?;
+    conn.close().await?;
+
+    // the journal was deleted after the transactions
+    assert_eq!(files.lock().unwrap().keys().collect::<Vec<_>>(), ["vfs.db"]);
+
+    let mut conn = options.connect().await?;
+
+    let text: String = sqlx::query_scalar("SELECT text FROM note")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(text, "kept in memory");
+
+    // spill the temporary database into an anonymous file
+    conn.execute(
+        "PRAGMA temp_store = FILE; PRAGMA temp.cache_size = 2; \
+         CREATE TEMP TABLE spilled AS \
+         WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100) \
+         SELECT i, randomblob(4096) AS data FROM n;",
+    )
+    .await?;
+
+    let is_temp = |path: &&String| path.starts_with("temp-");
+
+    assert_eq!(files.lock().unwrap().keys().filter(is_temp).count(), 1);
+
+    conn.close().await?;
+
+    // the temporary file was deleted when it was closed
+    assert_eq!(files.lock().unwrap().keys().collect::<Vec<_>>(), ["vfs.db"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_a_named_in_memory_database() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::memory_shared("sqlx-test-shared");
+
+    let mut conn = options.clone().connect().await?;
+    conn.execute("CREATE TABLE shared (id INTEGER); INSERT INTO shared VALUES (1);")
+        .await?;
+    conn.close().await?;
+
+    // the database outlives its connections
+    let pool = SqlitePoolOptions::new()
+        .min_connections(2)
+        .connect_with(options)
+        .await?;
+
+    let (a, b): (i64, i64) = sqlx::query_as("SELECT (SELECT id FROM shared), COUNT(*) FROM shared")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!((a, b), (1, 1));
+
+    pool.close().await;
+
+    // the database is deleted with the options
+    let options = SqliteConnectOptions::memory_shared("sqlx-test-dropped");
+    let mut conn = options.clone().connect().await?;
+    conn.execute("CREATE TABLE dropped (id INTEGER)").await?;
+    conn.close().await?;
+    drop(options);
+
+    let mut conn = SqliteConnectOptions::memory_shared("sqlx-test-dropped")
+        .connect()
+        .await?;
+    let tables: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'dropped'")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(tables, 0);
+
+    // characters with a meaning in URIs are part of the name
+    let mut conn = SqliteConnectOptions::memory_shared("sqlx-test?a#b&c")
+        .connect()
+        .await?;
+    conn.execute("CREATE TABLE escaped (id INTEGER)").await?;
+
+    let mut other = SqliteConnectOptions::memory_shared("sqlx-test?a")
+        .connect()
+        .await?;
+    let tables: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'escaped'")
+            .fetch_one(&mut other)
+            .await?;
+    assert_eq!(tables, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_deletes_a_temporary_database() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::temporary();
+    let path = options.get_filename().to_owned();
+
+    let mut conn = options.clone().connect().await?;
+    conn.execute("CREATE TABLE temporary (id INTEGER)").await?;
+    assert!(path.exists());
+
+    drop(options);
+    assert!(path.exists());
+
+    conn.close().await?;
+    assert!(!path.exists());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_query_stats() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE stats (id INTEGER PRIMARY KEY, v INTEGER);
+         WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100)
+         INSERT INTO stats SELECT i, i % 10 FROM n;",
+    )
+    .await?;
+
+    let scan = sqlx::query("UPDATE stats SET v = 0 WHERE v = ?")
+        .bind(5)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(scan.stats().fullscan_steps(), 99);
+
+    // the counters start over for each execution of the cached statement
+    let scan = sqlx::query("UPDATE stats SET v = 0 WHERE v = ?")
+        .bind(6)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(scan.stats().fullscan_steps(), 99);
+
+    let lookup = sqlx::query("UPDATE stats SET v = 0 WHERE id = ?")
+        .bind(5)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(lookup.stats().fullscan_steps(), 0);
+    assert!(lookup.stats().vm_steps() > 0);
+    assert!(lookup.stats().memory_used() > 0);
+
+    let sorted = sqlx::query("SELECT * FROM stats ORDER BY v")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(sorted.stats().sorts(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_values_of_dropped_rows() -> anyhow::Result<()> {
+    use sqlx::{Value, ValueRef};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query("SELECT 'text', x'0102', 42, 1.5, NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let values: Vec<_> = (0..5)
+        .map(|i| row.try_get_raw(i).map(|value| value.to_owned()))
+        .collect::<Result<_, _>>()?;
+    drop(row);
+
+    assert_eq!(values[0].try_decode::<String>()?, "text");
+    assert_eq!(values[1].try_decode::<Vec<u8>>()?, [1, 2]);
+    assert_eq!(values[2].try_decode::<i64>()?, 42);
+    assert_eq!(values[3].try_decode::<f64>()?, 1.5);
+    assert!(values[4].is_null());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_preallocates_rows_with_a_size_hint() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let rows = sqlx::query("SELECT 1 UNION ALL SELECT 2")
+        .size_hint(100)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 2);
+    assert!(rows.capacity() >= 100);
+
+    let values: Vec<i32> = sqlx::query_scalar("SELECT 1 UNION ALL SELECT 2")
+        .size_hint(50)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(values, [1, 2]);
+    assert!(values.capacity() >= 50);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_into_a_reused_buffer() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut values: Vec<(i32, String)> = Vec::with_capacity(16);
+    let capacity = values.capacity();
+
+    for n in [3, 2] {
+        let count = sqlx::query_as(
+            "WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < ?)
+             SELECT n, 'x' || n FROM s",
+        )
+        .bind(n)
+        .fetch_into(&mut conn, &mut values)
+        .await?;
+
+        assert_eq!(count, n as usize);
+        assert_eq!(values.len(), n as usize);
+        assert_eq!(values[0], (1, "x1".to_owned()));
+    }
+
+    assert_eq!(values.capacity(), capacity);
+
+    let mut ids: Vec<i32> = vec![0];
+    let count = sqlx::query_scalar("SELECT 1 UNION ALL SELECT 2")
+        .extend_into(&mut conn, &mut ids)
+        .await?;
+    assert_eq!(count, 2);
+    assert_eq!(ids, [0, 1, 2]);
+
+    let mut rows = Vec::new();
+    sqlx::query("SELECT 1 UNION ALL SELECT 2")
+        .fetch_into(&mut conn, &mut rows)
+        .await?;
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_in_parallel_from_a_read_pool() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::temporary();
+
+    let mut writer = options
+        .clone()
+        .journal_mode(SqliteJournalMode::Wal)
+        .connect()
+        .await?;
+    writer
+        .execute(
+            "CREATE TABLE numbers (n INTEGER);
+             WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 100)
+             INSERT INTO numbers SELECT n FROM s;",
+        )
+        .await?;
+
+    let pool = SqliteReadPool::connect_with(options.clone(), 2).await?;
+
+    let results = pool
+        .par_fetch_all(
+            (0..4).map(|part| sqlx::query("SELECT n FROM numbers WHERE n % 4 = ?").bind(part)),
+        )
+        .await?;
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|rows| rows.len() == 25));
+
+    let first: i64 = results[1][0].try_get(0)?;
+    assert_eq!(first, 1);
+
+    // connections are read-only
+    assert!(pool.pool().execute("DELETE FROM numbers").await.is_err());
+
+    pool.close().await;
+    writer.close().await?;
+
+    // readers of a database in rollback journal mode would block writers
+    let options = SqliteConnectOptions::temporary();
+    options
+        .clone()
+        .journal_mode(SqliteJournalMode::Delete)
+        .connect()
+        .await?
+        .close()
+        .await?;
+
+    assert!(SqliteReadPool::connect_with(options, 2).await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_serializes_writes_of_cloned_writers() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::temporary();
+    let writer = SqliteSerializedWriter::connect_with(&options).await?;
+
+    writer
+        .execute(sqlx::query("CREATE TABLE events (id INTEGER)"))
+        .await?;
+
+    let writes = (0..20).map(|id| {
+        let writer = writer.clone();
+
+        async move {
+            writer
+                .execute(sqlx::query("INSERT INTO events VALUES (?)").bind(id))
+                .await
+        }
+    });
+    futures::future::try_join_all(writes).await?;
+
+    {
+        let mut conn = writer.lock().await;
+        let mut tx = conn.begin().await?;
+        tx.execute("DELETE FROM events WHERE id >= 10").await?;
+        tx.commit().await?;
+    }
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM events ORDER BY rowid")
+        .fetch_all(&mut *writer.lock().await)
+        .await?;
+    assert_eq!(ids, (0..10).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checkpoints_on_shutdown() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::temporary().journal_mode(SqliteJournalMode::Wal);
+    let mut wal = options.get_filename().as_os_str().to_owned();
+    wal.push("-wal");
+
+    let mut conn = options.clone().connect().await?;
+    conn.execute("CREATE TABLE log (id INTEGER); INSERT INTO log VALUES (1), (2)")
+        .await?;
+
+    // keeps the log open, so closing the other connection does not remove it
+    let mut reader = options.clone().connect().await?;
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM log")
+        .fetch_one(&mut reader)
+        .await?;
+    assert_eq!(count, 2);
+    assert!(std::fs::metadata(&wal)?.len() > 0);
+
+    conn.shutdown(std::time::Duration::from_secs(5)).await?;
+    assert_eq!(std::fs::metadata(&wal)?.len(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fails_shutdown_if_checkpoint_is_busy() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::temporary()
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(std::time::Duration::ZERO);
+
+    let mut conn = options.clone().connect().await?;
+    conn.execute("CREATE TABLE log (id INTEGER); INSERT INTO log VALUES (1), (2)")
+        .await?;
+
+    // an open read transaction keeps the log from being truncated
+    let mut reader = options.clone().connect().await?;
+    reader.execute("BEGIN; SELECT count(*) FROM log").await?;
+
+    let err = conn
+        .shutdown(std::time::Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("5")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_counts_changes_of_triggers() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute(
+        "CREATE TABLE items (id INTEGER PRIMARY KEY);
+         CREATE TABLE audit (item INTEGER);
+         CREATE TRIGGER audit_items AFTER INSERT ON items
+         BEGIN INSERT INTO audit VALUES (new.id); END;",
+    )
+    .await?;
+
+    let result = conn.execute("INSERT INTO items VALUES (1), (2)").await?;
+    assert_eq!(result.rows_affected(), 2);
+    assert_eq!(result.total_changes(), 4);
+
+    let result = conn
+        .execute("INSERT INTO items VALUES (3); DELETE FROM audit")
+        .await?;
+    assert_eq!(result.rows_affected(), 4);
+    assert_eq!(result.total_changes(), 5);
+
+    let mut handle = conn.lock_handle().await?;
+    assert_eq!(handle.changes(), 3);
+    assert_eq!(handle.total_changes(), 9);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_the_last_insert_rowid_of_the_connection() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    assert_eq!(conn.last_insert_rowid().await?, 0);
+
+    let mut results = conn.execute_many(
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT);
+         INSERT INTO items (name) VALUES ('a'), ('b');
+         INSERT INTO items (id, name) VALUES (10, 'c');",
+    );
+    while results.try_next().await?.is_some() {}
+    drop(results);
+
+    assert_eq!(conn.last_insert_rowid().await?, 10);
+
+    conn.execute("DELETE FROM items").await?;
+    assert_eq!(conn.lock_handle().await?.last_insert_rowid(), 10);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_intercepts_queries() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .query_interceptor(|sql| {
+            // leave the definition of the view alone
+            if sql.starts_with("CREATE") {
+                return None;
+            }
+
+            let sql = sql
+                .replace("FROM documents", "FROM active_documents")
+                .replace(
+                    "UPDATE documents SET ",
+                    "UPDATE documents SET revision = revision + 1, ",
+                );
+
+            Some(sql)
+        })
+        .connect()
+        .await?;
+
+    conn.execute(
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY, title TEXT, revision INTEGER DEFAULT 0, deleted INTEGER DEFAULT 0);
+         CREATE VIEW active_documents AS SELECT * FROM documents WHERE NOT deleted;
+         INSERT INTO documents (id, title, deleted) VALUES (1, 'draft', 0), (2, 'old', 1);",
+    )
+    .await?;
+
+    let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM documents ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(titles, ["draft"]);
+
+    sqlx::query("UPDATE documents SET title = ? WHERE id = ?")
+        .bind("final")
+        .bind(1)
+        .execute(&mut conn)
+        .await?;
+
+    let (title, revision): (String, i64) =
+        sqlx::query_as("SELECT title, revision FROM documents WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!((&*title, revision), ("final", 1));
+
+    conn.set_query_interceptor(None);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM documents")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rewrites_queries_by_their_arguments() -> anyhow::Result<()> {
+    use sqlx::interceptor::QueryRewriter;
+    use sqlx::sqlite::{SqliteArgumentValue, SqliteArguments};
+    use std::str::FromStr;
+
+    struct TenantShards;
+
+    impl QueryRewriter<Sqlite> for TenantShards {
+        fn rewrite(&self, sql: &str, arguments: Option<&SqliteArguments<'_>>) -> Option<String> {
+            let tenant = match arguments?.values().first()? {
+                SqliteArgumentValue::Int64(tenant) => *tenant,
+                _ => return None,
+            };
+
+            Some(sql.replace("{tenant}.", &format!("tenant_{tenant}.")))
+        }
+    }
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .query_rewriter(TenantShards)
+        .connect()
+        .await?;
+
+    for tenant in [1, 2] {
+        conn.execute(&*format!(
+            "ATTACH DATABASE ':memory:' AS tenant_{tenant};
+             CREATE TABLE tenant_{tenant}.notes (tenant INTEGER, body TEXT);"
+        ))
+        .await?;
+    }
+
+    for (tenant, body) in [(1_i64, "one"), (2, "two"), (2, "three")] {
+        sqlx::query("INSERT INTO {tenant}.notes (tenant, body) VALUES (?, ?)")
+            .bind(tenant)
+            .bind(body)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM {tenant}.notes WHERE tenant = ?")
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 2);
+
+    let bodies: Vec<String> = sqlx::query_scalar("SELECT body FROM tenant_1.notes")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(bodies, ["one"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_intercepts_prepared_statements_once() -> anyhow::Result<()> {
+    use sqlx::interceptor;
+    use std::borrow::Cow;
+
+    let limit = |sql: &str| sql.starts_with("SELECT").then(|| format!("{sql} LIMIT 1"));
+
+    let interceptor = interceptor::query_interceptor::<Sqlite, _>(limit);
+    assert_eq!(
+        interceptor::apply(Some(&interceptor), "SELECT 1", None),
+        "SELECT 1 LIMIT 1"
+    );
+    assert!(matches!(
+        interceptor::apply(Some(&interceptor), "DELETE FROM t", None),
+        Cow::Borrowed("DELETE FROM t")
+    ));
+    assert!(matches!(
+        interceptor::apply::<Sqlite>(None, "SELECT 1", None),
+        Cow::Borrowed("SELECT 1")
+    ));
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .query_interceptor(limit)
+        .connect()
+        .await?;
+
+    let sql = "SELECT x FROM (SELECT 1 AS x UNION ALL SELECT 2) ORDER BY x";
+    let statement = conn.prepare(sql).await?;
+    assert_eq!(statement.sql(), format!("{sql} LIMIT 1"));
+
+    // intercepting again when executing would append a second `LIMIT`
+    for _ in 0..2 {
+        let xs: Vec<i64> = statement.query_scalar().fetch_all(&mut conn).await?;
+        assert_eq!(xs, [1]);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_manages_a_database_per_tenant() -> anyhow::Result<()> {
+    use sqlx::migrate::Migrator;
+    use sqlx::sqlite::SqliteTenantManager;
+    use std::path::Path;
+
+    let dir = tempdir::TempDir::new("sqlx_tenants")?;
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    let tenants = SqliteTenantManager::new(dir.path(), SqliteConnectOptions::new())
+        .max_open(2)
+        .migrator(migrator);
+
+    for tenant in ["a", "b", "c"] {
+        tenants
+            .get(tenant)
+            .await?
+            .execute(sqlx::query("INSERT INTO migrations_simple_test VALUES (2, ?)").bind(tenant))
+            .await?;
+    }
+
+    // `a` is the least recently used tenant
+    assert_eq!(tenants.open_tenants().await, ["b", "c"]);
+    assert!(dir.path().join("a.db").exists());
+
+    // reopening does not apply the migrations again
+    let a = tenants.get("a").await?;
+    let payloads: Vec<String> =
+        sqlx::query_scalar("SELECT some_payload FROM migrations_simple_test ORDER BY some_id")
+            .fetch_all(&mut *a.lock().await)
+            .await?;
+    assert_eq!(payloads, ["110_suffix", "a"]);
+    assert_eq!(tenants.open_tenants().await, ["c", "a"]);
+
+    // tenant IDs are case-insensitive, like some filesystems
+    tenants.get("C").await?;
+    assert_eq!(tenants.open_tenants().await, ["a", "c"]);
+    assert_eq!(tenants.path("C")?, tenants.path("c")?);
+
+    assert!(tenants.close("A").await);
+    assert!(!tenants.close("a").await);
+
+    assert!(tenants.get("../a").await.is_err());
+    assert!(tenants.get("").await.is_err());
+
+    tenants.close_all().await;
+    assert!(tenants.open_tenants().await.is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_opens_tenants_concurrently() -> anyhow::Result<()> {
+    use sqlx::migrate::Migrator;
+    use sqlx::sqlite::SqliteTenantManager;
+    use std::path::Path;
+
+    let dir = tempdir::TempDir::new("sqlx_tenants")?;
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    // opening `b` must not close `a` while it is being opened
+    let tenants = SqliteTenantManager::new(dir.path(), SqliteConnectOptions::new())
+        .max_open(1)
+        .migrator(migrator);
+
+    // keep the migrations of `a` waiting until `b` is open
+    let mut blocker = SqliteConnectOptions::new()
+        .filename(tenants.path("a")?)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+    blocker.execute("BEGIN EXCLUSIVE").await?;
+
+    let (a, b) = futures::try_join!(tenants.get("a"), async {
+        let b = tenants.get("b").await?;
+        assert_eq!(tenants.open_tenants().await, ["a", "b"]);
+        blocker.execute("COMMIT").await?;
+        Ok(b)
+    })?;
+
+    for writer in [a, b] {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM migrations_simple_test")
+            .fetch_one(&mut *writer.lock().await)
+            .await?;
+        assert_eq!(count, 1);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_health_of_the_database_file() -> anyhow::Result<()> {
+    let dir = tempdir::TempDir::new("sqlx_health")?;
+    let path = dir.path().join("health.db");
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true)
+        .connect()
+        .await?;
+    conn.execute("CREATE TABLE t (n INTEGER UNIQUE); INSERT INTO t VALUES (1), (2);")
+        .await?;
+
+    conn.ping().await?;
+    conn.check_health().await?;
+    assert!(conn.integrity_check(false).await?.is_empty());
+    assert!(conn.integrity_check(true).await?.is_empty());
+
+    // open files cannot be renamed on Windows
+    #[cfg(unix)]
+    {
+        std::fs::rename(&path, dir.path().join("rotated.db"))?;
+
+        let err = conn.ping().await.unwrap_err();
+        assert!(
+            matches!(&err, sqlx::Error::Io(e) if e.kind() == std::io::ErrorKind::NotFound),
+            "{err:?}"
+        );
+    }
+
+    // in-memory databases have no file to move
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_pings_without_waiting_for_locks() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let dir = tempdir::TempDir::new("sqlx_ping")?;
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("locked.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete)
+        .busy_timeout(Duration::from_millis(50));
+
+    let mut writer = options.connect().await?;
+    let mut conn = options.connect().await?;
+
+    writer.execute("CREATE TABLE t (n INTEGER)").await?;
+    writer.execute("BEGIN EXCLUSIVE").await?;
+
+    conn.ping().await?;
+    assert!(conn.check_health().await.is_err());
+
+    writer.execute("COMMIT").await?;
+    conn.check_health().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_maintenance_in_the_background() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteAutoVacuum, SqliteMaintenancePlan};
+    use std::time::Duration;
+
+    let options = SqliteConnectOptions::temporary().auto_vacuum(SqliteAutoVacuum::Incremental);
+
+    let mut conn = options.connect().await?;
+    conn.execute(
+        "CREATE TABLE blobs (data BLOB);
+         WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 100)
+         INSERT INTO blobs SELECT randomblob(4096) FROM s;
+         DELETE FROM blobs;",
+    )
+    .await?;
+    conn.optimize().await?;
+
+    let free_pages = || sqlx::query_scalar::<_, i64>("PRAGMA freelist_count");
+    assert!(free_pages().fetch_one(&mut conn).await? > 0);
+
+    let maintenance = SqliteMaintenancePlan::new(Duration::from_millis(10))
+        .incremental_vacuum(true, None)
+        .wal_checkpoint(true)
+        .spawn(options.connect().await?);
+
+    sqlx_core::rt::sleep(Duration::from_millis(200)).await;
+    maintenance.stop();
+
+    assert_eq!(free_pages().fetch_one(&mut conn).await?, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_and_defers_foreign_key_violations() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteForeignKeys;
+    use std::str::FromStr;
+
+    let schema = "CREATE TABLE parent (id INTEGER PRIMARY KEY);
+                  CREATE TABLE child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES parent);";
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .foreign_keys(false)
+        .connect()
+        .await?;
+    conn.execute(schema).await?;
+    conn.execute("INSERT INTO parent VALUES (1); INSERT INTO child VALUES (1, 1), (2, 2);")
+        .await?;
+
+    let violations = conn.foreign_key_check().await?;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].table, "child");
+    assert_eq!(violations[0].rowid, Some(2));
+    assert_eq!(violations[0].parent, "parent");
+    assert_eq!(violations[0].foreign_key_index, 0);
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .foreign_keys(SqliteForeignKeys::Deferred)
+        .connect()
+        .await?;
+    conn.execute(schema).await?;
+
+    // SQLite resets the deferral at the end of each transaction
+    for id in [1, 2] {
+        let mut tx = conn.begin().await?;
+        sqlx::query("INSERT INTO child VALUES (?1, ?1)")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("INSERT INTO parent VALUES (?)")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+
+    let mut tx = conn.begin().await?;
+    tx.execute("INSERT INTO child VALUES (3, 3)").await?;
+    assert!(tx.commit().await.is_err());
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    conn.execute(schema).await?;
+
+    let mut tx = conn.begin().await?;
+    assert!(tx.execute("INSERT INTO child VALUES (1, 1)").await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_test_transactions() -> anyhow::Result<()> {
+    use sqlx::testing::with_test_transaction;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    conn.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('alice');")
+        .await?;
+
+    let count = "SELECT COUNT(*) FROM users";
+
+    let inside: i64 = with_test_transaction(&mut conn, |tx| {
+        Box::pin(async move {
+            tx.execute("DELETE FROM users").await?;
+            sqlx::query_scalar(count).fetch_one(&mut **tx).await
+        })
+    })
+    .await??;
+    assert_eq!(inside, 0);
+
+    let after: i64 = sqlx::query_scalar(count).fetch_one(&mut conn).await?;
+    assert_eq!(after, 1);
+
+    // a savepoint within an open transaction
+    let mut tx = conn.begin().await?;
+    tx.execute("INSERT INTO users VALUES ('bob')").await?;
+
+    with_test_transaction(&mut *tx, |tx| {
+        Box::pin(async move { tx.execute("DELETE FROM users").await.map(drop) })
+    })
+    .await??;
+
+    let after: i64 = sqlx::query_scalar(count).fetch_one(&mut *tx).await?;
+    assert_eq!(after, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_expires_cached_statements() -> anyhow::Result<()> {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .statement_cache_capacity(1)
+        .connect()
+        .await?;
+    conn.execute("CREATE TABLE t (a INTEGER); INSERT INTO t VALUES (1);")
+        .await?;
+
+    for _ in 0..3 {
+        sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    }
+    sqlx::query("SELECT a FROM t").fetch_one(&mut conn).await?;
+
+    let stats = conn.statement_cache_stats().await?;
+    assert_eq!((stats.hits, stats.misses), (2, 2));
+    assert_eq!((stats.evictions, stats.expirations), (1, 0));
+
+    let mut conn = SqliteConnectOptions::from_str("sqlite::memory:")?
+        .statement_cache_ttl(Duration::ZERO)
+        .connect()
+        .await?;
+    conn.execute("CREATE TABLE t (a INTEGER); INSERT INTO t VALUES (1);")
+        .await?;
+
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.columns().len(), 1);
+
+    conn.execute("ALTER TABLE t ADD COLUMN b TEXT").await?;
+
+    // prepared again, with the new column
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.columns().len(), 2);
+
+    let stats = conn.statement_cache_stats().await?;
+    assert_eq!((stats.hits, stats.misses, stats.expirations), (0, 2, 1));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_refreshes_cached_statements_after_schema_changes() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    conn.execute("CREATE TABLE t (a INTEGER); INSERT INTO t VALUES (1);")
+        .await?;
+
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.columns().len(), 1);
+
+    conn.execute("ALTER TABLE t ADD COLUMN b TEXT DEFAULT 'x'")
+        .await?;
+
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.columns().len(), 2);
+    assert_eq!(row.try_get::<String, _>("b")?, "x");
+
+    conn.execute("ALTER TABLE t RENAME COLUMN a TO c").await?;
+
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i64, _>("c")?, 1);
+
+    // same names, other declared types
+    conn.execute(
+        "DROP TABLE t; CREATE TABLE t (c TEXT, b BLOB); INSERT INTO t VALUES ('y', x'01');",
+    )
+    .await?;
+
+    let row = sqlx::query("SELECT * FROM t").fetch_one(&mut conn).await?;
+    assert_eq!(row.column(0).type_info().name(), "TEXT");
+    assert_eq!(row.column(1).type_info().name(), "BLOB");
+    assert_eq!(row.try_get::<String, _>("c")?, "y");
+
+    let stats = conn.statement_cache_stats().await?;
+    assert_eq!((stats.hits, stats.misses), (3, 1));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_parameter_that_failed_to_bind() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    {
+        let mut handle = conn.lock_handle().await?;
+        unsafe {
+            libsqlite3_sys::sqlite3_limit(
+                handle.as_raw_handle().as_ptr(),
+                libsqlite3_sys::SQLITE_LIMIT_LENGTH,
+                16,
+            );
+        }
+    }
+
+    let err = sqlx::query("SELECT ?, ?")
+        .bind(1_i64)
+        .bind("a value longer than the limit")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    match err {
+        sqlx::Error::ArgumentBind {
+            index, type_name, ..
+        } => {
+            assert_eq!(index, 2);
+            assert_eq!(type_name, "&str");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_running_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let token = conn.cancellation_token();
+
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        token.cancel()
+    });
+
+    let err = sqlx::query_scalar::<_, i64>(
+        "WITH RECURSIVE forever(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM forever) \
+         SELECT count(*) FROM forever",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(canceller.join().unwrap());
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("9")
+    );
+
+    // later statements are not affected
+    let n: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(n, 1);
+
+    let token = conn.cancellation_token();
+    conn.close().await?;
+    assert!(!token.cancel());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_looks_up_column_indexes_by_name() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let rows = conn
+        .fetch_all("SELECT 1 AS a, 2 AS b UNION ALL SELECT 3, 4")
+        .await?;
+
+    let b = rows[0].column_index("b")?;
+    assert_eq!(b, 1);
+
+    let values: Vec<i32> = rows.iter().map(|row| row.get(b)).collect();
+    assert_eq!(values, [2, 4]);
+
+    assert!(matches!(
+        rows[0].column_index("c"),
+        Err(sqlx::Error::ColumnNotFound(name)) if name == "c"
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_capabilities_of_the_driver() -> anyhow::Result<()> {
+    let capabilities = Sqlite::capabilities();
+
+    assert!(capabilities.get_savepoints());
+    assert!(capabilities.get_multiple_statements_with_arguments());
+    assert_eq!(
+        capabilities.get_placeholder_style(),
+        PlaceholderStyle::QuestionMark
+    );
+    assert_eq!(
+        capabilities.get_max_bind_parameters(),
+        Sqlite::MAX_BIND_PARAMETERS
+    );
+
+    let mut conn = new::<Sqlite>().await?;
+
+    if capabilities.get_returning() {
+        conn.execute("CREATE TEMPORARY TABLE capability_test (id INTEGER PRIMARY KEY, name TEXT)")
+            .await?;
+
+        let id: i64 =
+            sqlx::query_scalar("INSERT INTO capability_test (name) VALUES (?) RETURNING id")
+                .bind("first")
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(id, 1);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_journals_and_replays_writes() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteArgumentValue, SqliteJournalEntry, SqliteJournaledConnection};
+
+    let dir = tempdir::TempDir::new("sqlx_journal")?;
+    let path = dir.path().join("writes.journal");
+
+    let conn = SqliteConnection::connect("sqlite::memory:").await?;
+    let mut conn = SqliteJournaledConnection::open(conn, &path)?
+        .redact(|_, i, _| (i == 2).then(|| SqliteArgumentValue::Text("<redacted>".into())));
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, token TEXT, email TEXT)")
+        .await?;
+    conn.execute(
+        sqlx::query("INSERT INTO users (name, token, email) VALUES (?, ?, ?)")
+            .bind("alice")
+            .bind_sensitive("secret")
+            .bind("alice@example.com"),
+    )
+    .await?;
+    conn.execute(sqlx::query("SELECT * FROM users")).await?;
+
+    let entries = SqliteJournalEntry::read_all(&path)?;
+
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].arguments().is_empty());
+    assert!(matches!(
+        entries[1].arguments(),
+        [
+            SqliteArgumentValue::Text(name),
+            SqliteArgumentValue::Null,
+            SqliteArgumentValue::Text(email),
+        ] if name == "alice" && email == "<redacted>"
+    ));
+
+    // reopening appends to the journal
+    let mut conn = SqliteJournaledConnection::open(conn.into_inner(), &path)?;
+    conn.execute(sqlx::query("DELETE FROM users WHERE id = ?").bind(2_i64))
+        .await?;
+
+    let mut replica = SqliteConnection::connect("sqlite::memory:").await?;
+    assert_eq!(replica.replay_journal(&path).await?, 3);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM users")
+        .fetch_one(&mut replica)
+        .await?;
+    assert_eq!(name, "alice");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_journals_rolled_back_transactions() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteJournalEntry, SqliteJournaledConnection};
+
+    let dir = tempdir::TempDir::new("sqlx_journal")?;
+    let path = dir.path().join("writes.journal");
+
+    let conn = SqliteConnection::connect("sqlite::memory:").await?;
+    let mut conn = SqliteJournaledConnection::open(conn, &path)?;
+
+    conn.execute("CREATE TABLE users (name TEXT)").await?;
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO users (name) VALUES ('alice')")
+        .await?;
+    conn.execute("ROLLBACK").await?;
+    conn.execute("SAVEPOINT a").await?;
+    conn.execute("INSERT INTO users (name) VALUES ('bob')")
+        .await?;
+    conn.execute("RELEASE a").await?;
+
+    let sql: Vec<String> = SqliteJournalEntry::read_all(&path)?
+        .iter()
+        .map(|entry| entry.sql().to_owned())
+        .collect();
+
+    assert_eq!(sql.len(), 7);
+    assert_eq!(sql[1], "BEGIN");
+    assert_eq!(sql[3], "ROLLBACK");
+
+    let mut replica = SqliteConnection::connect("sqlite::memory:").await?;
+    replica.replay_journal(&path).await?;
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM users")
+        .fetch_all(&mut replica)
+        .await?;
+    assert_eq!(names, ["bob"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_ignores_a_torn_final_journal_entry() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteJournalEntry, SqliteJournaledConnection};
+
+    let dir = tempdir::TempDir::new("sqlx_journal")?;
+    let path = dir.path().join("writes.journal");
+
+    let conn = SqliteConnection::connect("sqlite::memory:").await?;
+    let mut conn = SqliteJournaledConnection::open(conn, &path)?;
+
+    conn.execute("CREATE TABLE users (name TEXT)").await?;
+    conn.execute(sqlx::query("INSERT INTO users (name) VALUES (?)").bind("alice"))
+        .await?;
+
+    // as if the process crashed while writing the last entry
+    let len = std::fs::metadata(&path)?.len();
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&path)?
+        .set_len(len - 3)?;
+
+    assert_eq!(SqliteJournalEntry::read_all(&path)?.len(), 1);
+
+    // the torn entry is overwritten by the next one
+    let mut conn = SqliteJournaledConnection::open(conn.into_inner(), &path)?;
+    conn.execute("DELETE FROM users").await?;
+
+    let entries = SqliteJournalEntry::read_all(&path)?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].sql(), "DELETE FROM users");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_restores_to_a_point_in_time() -> anyhow::Result<()> {
+    use sqlx::sqlite::recovery::SqliteRecovery;
+    use sqlx::sqlite::SqliteJournaledConnection;
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempdir::TempDir::new("sqlx_recovery")?;
+    let journal = dir.path().join("data.journal");
+    let recovery = SqliteRecovery::new(dir.path().join("backups"), &journal);
+
+    let conn = SqliteConnectOptions::new()
+        .filename(dir.path().join("data.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .connect()
+        .await?;
+    let mut conn = SqliteJournaledConnection::open(conn, &journal)?;
+
+    conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("INSERT INTO items (id) VALUES (1)").await?;
+
+    let before_backup = SystemTime::now() - Duration::from_secs(1);
+    recovery.backup(&mut conn).await?;
+
+    conn.execute("INSERT INTO items (id) VALUES (2)").await?;
+    sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+    let time = SystemTime::now();
+    sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+    conn.execute("INSERT INTO items (id) VALUES (3)").await?;
+
+    assert_eq!(recovery.backups()?.len(), 1);
+    assert!(recovery
+        .restore_to(dir.path().join("early.db"), before_backup)
+        .await
+        .is_err());
+
+    let target = dir.path().join("restored.db");
+    assert_eq!(recovery.restore_to(&target, time).await?, 1);
+
+    let mut restored = SqliteConnectOptions::new()
+        .filename(&target)
+        .connect()
+        .await?;
+    let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM items ORDER BY id")
+        .fetch_all(&mut restored)
+        .await?;
+    assert_eq!(ids, [1, 2]);
+
+    // a failed restore leaves no file behind
+    let failed = dir.path().join("failed.db");
+    std::fs::remove_file(&journal)?;
+    assert!(recovery.restore_to(&failed, time).await.is_err());
+    assert!(!failed.exists());
+    assert!(!dir.path().join("failed.db.tmp").exists());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_journals_with_corrupt_lengths() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalEntry;
+
+    let dir = tempdir::TempDir::new("sqlx_journal")?;
+    let path = dir.path().join("corrupt.journal");
+
+    // an entry whose SQL claims to be 4 GiB long
+    let mut journal = b"SQLXJRN1".to_vec();
+    journal.extend_from_slice(&0_u64.to_le_bytes());
+    journal.extend_from_slice(&u32::MAX.to_le_bytes());
+    journal.extend_from_slice(b"SELECT 1");
+    std::fs::write(&path, &journal)?;
+
+    assert!(SqliteJournalEntry::read_all(&path)?.is_empty());
+
+    // an entry that claims to have 4 billion arguments
+    let sql = b"SELECT 1";
+    let mut journal = b"SQLXJRN1".to_vec();
+    journal.extend_from_slice(&0_u64.to_le_bytes());
+    journal.extend_from_slice(&(sql.len() as u32).to_le_bytes());
+    journal.extend_from_slice(sql);
+    journal.extend_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&path, &journal)?;
+
+    assert!(SqliteJournalEntry::read_all(&path)?.is_empty());
+
+    std::fs::write(&path, b"SQLXJRN")?;
+    assert!(SqliteJournalEntry::read_all(&path).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_registered_types_by_declared_type() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeRegistry};
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    let registry = SqliteTypeRegistry::new().register::<Point, _, _>(
+        "point",
+        |value| {
+            let text = <&str as sqlx::Decode<Sqlite>>::decode(value)?;
+            let (x, y) = text.split_once(',').ok_or("not a point")?;
+            Ok(Point {
+                x: x.parse()?,
+                y: y.parse()?,
+            })
+        },
+        |point| SqliteArgumentValue::Text(format!("{},{}", point.x, point.y).into()),
+    );
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .type_registry(registry.clone())
+        .connect()
+        .await?;
+
+    conn.execute("CREATE TABLE shapes (origin POINT, label TEXT)")
+        .await?;
+
+    sqlx::query("INSERT INTO shapes (origin, label) VALUES (?, 'a'), (NULL, 'b')")
+        .bind(registry.encode(&Point { x: 1.5, y: -2.0 })?)
+        .execute(&mut conn)
+        .await?;
+
+    let rows = sqlx::query("SELECT origin, label, origin || '' AS text FROM shapes ORDER BY label")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows[0].column(0).declared_type(), Some("POINT"));
+    assert_eq!(
+        rows[0].try_get_registered::<Point, _>("origin")?,
+        Point { x: 1.5, y: -2.0 }
+    );
+    assert_eq!(rows[1].try_get_registered_opt::<Point, _>(0)?, None);
+    assert!(rows[1].try_get_registered::<Point, _>(0).is_err());
+
+    // a column of another declared type, and an expression without one
+    assert!(rows[0].try_get_registered::<Point, _>("label").is_err());
+    assert!(rows[0].try_get_registered::<Point, _>("text").is_err());
+
+    assert!(registry.encode(&1_i32).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_clears_tables() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    // no `sqlite_sequence` table yet
+    conn.execute("CREATE TABLE kv (k TEXT PRIMARY KEY, v TEXT); INSERT INTO kv VALUES ('a', 'b')")
+        .await?;
+    sqlx::ops::clear_table(&mut conn, "kv", true).await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM kv")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .await?;
+
+    let insert = "INSERT INTO users (name) VALUES ('alice'), ('bob') RETURNING id";
+
+    let ids: Vec<i64> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [1, 2]);
+
+    sqlx::ops::clear_table(&mut conn, "users", false).await?;
+
+    let ids: Vec<i64> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [3, 4]);
+
+    sqlx::ops::clear_table(&mut conn, "users", true).await?;
+
+    let ids: Vec<i64> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [1, 2]);
+
+    // quoted and qualified names reset the sequence as well
+    sqlx::ops::clear_table(&mut conn, "main.\"Users\"", true).await?;
+
+    let ids: Vec<i64> = sqlx::query_scalar(insert).fetch_all(&mut conn).await?;
+    assert_eq!(ids, [1, 2]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_returning() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+        .await?;
+
+    let (result, users): (_, Vec<(i64, String)>) = sqlx::query_as(
+        "INSERT INTO users (email) VALUES ('alice@example.com'), ('bob@example.com') \
+         RETURNING id, email",
+    )
+    .execute_returning(&mut conn)
+    .await?;
+
+    assert_eq!(result.rows_affected(), 2);
+    assert_eq!(result.last_insert_rowid(), 2);
+    assert_eq!(
+        users,
+        [
+            (1, "alice@example.com".to_owned()),
+            (2, "bob@example.com".to_owned())
+        ]
+    );
+
+    let (result, ids): (_, Vec<i64>) =
+        sqlx::query_scalar("UPDATE users SET email = upper(email) WHERE id > ?1 RETURNING id")
+            .bind(1_i64)
+            .execute_returning(&mut conn)
+            .await?;
+
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(ids, [2]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_upserts_with_query_builder() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, visits INTEGER)",
+    )
+    .await?;
+    conn.execute("INSERT INTO users (id, email, visits) VALUES (1, 'old@example.com', 5)")
+        .await?;
+
+    let users = [(1, "alice@example.com"), (2, "bob@example.com")];
+
+    for update_columns in [&[][..], &["email"]] {
+        let mut builder = sqlx::QueryBuilder::<Sqlite>::new("");
+        builder.push_upsert(
+            "users",
+            &["id", "email"],
+            &["id"],
+            update_columns,
+            users,
+            |mut b, (id, email)| {
+                b.push_bind(id).push_bind(email);
+            },
+        );
+        builder.build().execute(&mut conn).await?;
+
+        let emails: Vec<(i64, String, Option<i64>)> =
+            sqlx::query_as("SELECT id, email, visits FROM users ORDER BY id")
+                .fetch_all(&mut conn)
+                .await?;
+
+        let expected = if update_columns.is_empty() {
+            "old@example.com"
+        } else {
+            "alice@example.com"
+        };
+
+        assert_eq!(
+            emails,
+            [
+                (1, expected.to_owned(), Some(5)),
+                (2, "bob@example.com".to_owned(), None)
+            ]
+        );
+    }
+
+    // `DO UPDATE` needs a conflict target
+    let mut builder = sqlx::QueryBuilder::<Sqlite>::new("");
+    builder.push_upsert(
+        "users",
+        &["id", "email"],
+        &[],
+        &["email"],
+        [(1, "carol@example.com")],
+        |mut b, (id, email)| {
+            b.push_bind(id).push_bind(email);
+        },
+    );
+    assert_eq!(
+        builder.sql(),
+        "INSERT INTO users (id, email) VALUES (?, ?) ON CONFLICT DO NOTHING"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_casts_result_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut builder = sqlx::QueryBuilder::<Sqlite>::new("SELECT ");
+    builder
+        .push_cast::<f64>("SUM(x)")
+        .push(" AS total, ")
+        .push_cast::<String>("MAX(x)")
+        .push(" AS max FROM (SELECT 1 AS x UNION ALL SELECT 2)");
+
+    assert_eq!(
+        builder.sql(),
+        "SELECT CAST(SUM(x) AS REAL) AS total, CAST(MAX(x) AS TEXT) AS max \
+         FROM (SELECT 1 AS x UNION ALL SELECT 2)"
+    );
+
+    let (total, max): (f64, String) = builder.build_query_as().fetch_one(&mut conn).await?;
+
+    assert_eq!(total, 3.0);
+    assert_eq!(max, "2");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_the_numeric_overflow_policy() -> anyhow::Result<()> {
+    use sqlx::types::NumericOverflow;
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .connect()
+        .await?;
+
+    let sql = "SELECT 1099511627776, 1e300";
+
+    // by default `i32` and `f32` are cast like before, other types fail
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, 0);
+    assert_eq!(row.try_get::<f32, _>(1)?, f32::INFINITY);
+    assert!(row.try_get::<i16, _>(0).is_err());
+    assert!(row.try_get::<u32, _>(0).is_err());
+    assert_eq!(row.try_get::<i64, _>(0)?, 1 << 40);
+
+    let row = sqlx::query(sql)
+        .numeric_overflow(NumericOverflow::Error)
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(row.try_get::<i32, _>(0).is_err());
+    assert!(row.try_get::<f32, _>(1).is_err());
+
+    let max: u8 = sqlx::query_scalar(sql)
+        .numeric_overflow(NumericOverflow::Saturate)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(max, u8::MAX);
+
+    conn.lock_handle()
+        .await?
+        .set_numeric_overflow(NumericOverflow::Saturate);
+
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, i32::MAX);
+    assert_eq!(row.try_get::<u8, _>(0)?, u8::MAX);
+    assert_eq!(row.try_get::<f32, _>(1)?, f32::MAX);
+
+    // the override only applies to its own query
+    let row = sqlx::query(sql)
+        .numeric_overflow(NumericOverflow::Error)
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(row.try_get::<u8, _>(0).is_err());
+
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<u8, _>(0)?, u8::MAX);
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .numeric_overflow(NumericOverflow::Lossy)
+        .connect()
+        .await?;
+
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<i16, _>(0)?, 0);
+    assert_eq!(row.try_get::<f32, _>(1)?, f32::INFINITY);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_pragma_profiles() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteProfile, SqliteSynchronous};
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .optimize_for(SqliteProfile::InMemoryTest)
+        .synchronous(SqliteSynchronous::Normal)
+        .connect()
+        .await?;
+
+    let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+        .fetch_one(&mut conn)
+        .await?;
+    let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+        .fetch_one(&mut conn)
+        .await?;
+    let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+        .fetch_one(&mut conn)
+        .await?;
+    let temp_store: i64 = sqlx::query_scalar("PRAGMA temp_store")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(journal_mode, "memory");
+    assert_eq!(synchronous, 1);
+    assert_eq!(cache_size, -16384);
+    assert_eq!(temp_store, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_typed_storage_settings() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteAutoVacuum, SqliteCacheSize};
+
+    let options = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .page_size(8192)
+        .cache_size(SqliteCacheSize::Kibibytes(4096))
+        .auto_vacuum(SqliteAutoVacuum::Incremental);
+
+    let settings = options.connect().await?.storage_settings().await?;
+    assert_eq!(settings.page_size, 8192);
+    assert_eq!(settings.cache_size, SqliteCacheSize::Kibibytes(4096));
+    assert_eq!(settings.auto_vacuum, SqliteAutoVacuum::Incremental);
+
+    let settings = options
+        .cache_size(SqliteCacheSize::Pages(100))
+        .connect()
+        .await?
+        .storage_settings()
+        .await?;
+    assert_eq!(settings.cache_size, SqliteCacheSize::Pages(100));
+
+    assert!(matches!(
+        "sqlite::memory:"
+            .parse::<SqliteConnectOptions>()?
+            .page_size(1000)
+            .connect()
+            .await,
+        Err(sqlx::Error::Configuration(_))
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_enforces_declared_types_in_strict_mode() -> anyhow::Result<()> {
+    use sqlx::migrate::Migrate;
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .strict(true)
+        .connect()
+        .await?;
+
+    conn.ensure_migrations_table().await?;
+
+    let strict: bool =
+        sqlx::query_scalar("SELECT strict FROM pragma_table_list WHERE name = '_sqlx_migrations'")
+            .fetch_one(&mut conn)
+            .await?;
+    assert!(strict);
+
+    conn.execute("CREATE TABLE legacy (id INTEGER, name TEXT, price MONEY)")
+        .await?;
+    conn.execute("INSERT INTO legacy (id, name, price) VALUES (1, 'one', 1), ('two', 2, 2)")
+        .await?;
+
+    let rows = conn.fetch_all("SELECT id, name FROM legacy").await?;
+    assert_eq!(rows[0].try_get::<i64, _>("id")?, 1);
+    assert_eq!(rows[0].try_get::<String, _>("name")?, "one");
+    assert!(rows[1].try_get::<String, _>("id").is_err());
+    assert_eq!(rows[1].try_get::<String, _>("name")?, "2");
+
+    assert!(matches!(
+        conn.describe("SELECT price FROM legacy").await,
+        Err(sqlx::Error::TypeNotFound { type_name }) if type_name == "MONEY"
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_configured_coercions() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteCoercions;
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .connect()
+        .await?;
+
+    let sql = "SELECT ' TRUE ', 'off', ' 42 ', '0.5', 7, 0.25";
+
+    let row = conn.fetch_one(sql).await?;
+    assert!(row.try_get::<bool, _>(0).is_err());
+    assert!(row.try_get::<i32, _>(2).is_err());
+    assert!(row.try_get::<f64, _>(3).is_err());
+    assert!(row.try_get::<String, _>(4).is_err());
+    assert!(row.try_get::<bool, _>(4)?);
+
+    conn.lock_handle()
+        .await?
+        .set_coercions(SqliteCoercions::new().text_to_bool(true));
+
+    let row = conn.fetch_one(sql).await?;
+    assert!(row.try_get::<bool, _>(0)?);
+    assert!(!row.try_get::<bool, _>(1)?);
+    assert!(row.try_get::<bool, _>(2).is_err());
+    assert!(row.try_get::<i32, _>(2).is_err());
+
+    let mut conn = "sqlite::memory:"
+        .parse::<SqliteConnectOptions>()?
+        .coercions(SqliteCoercions::lenient())
+        .connect()
+        .await?;
+
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(row.try_get::<i32, _>(2)?, 42);
+    assert_eq!(row.try_get::<f64, _>(3)?, 0.5);
+    assert_eq!(row.try_get::<String, _>(4)?, "7");
+    assert_eq!(row.try_get::<&str, _>(5)?, "0.25");
+    assert!(row.try_get::<i64, _>(3).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_through_dyn_executor() -> anyhow::Result<()> {
+    use sqlx::DynExecutor;
+
+    async fn insert_and_count(
+        executor: &mut dyn DynExecutor<Sqlite>,
+        text: &str,
+    ) -> sqlx::Result<i64> {
+        sqlx::query("INSERT INTO dyn_executor (text) VALUES (?)")
+            .bind(text)
+            .execute(&mut *executor)
+            .await?;
+
+        sqlx::query_scalar("SELECT COUNT(*) FROM dyn_executor")
+            .fetch_one(executor)
+            .await
+    }
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    sqlx::query("CREATE TABLE dyn_executor (text TEXT NOT NULL)")
+        .execute(&mut *conn)
+        .await?;
+
+    assert_eq!(insert_and_count(&mut *conn, "conn").await?, 1);
+
+    let mut tx = conn.begin().await?;
+    assert_eq!(insert_and_count(&mut *tx, "tx").await?, 2);
+    tx.rollback().await?;
+
+    drop(conn);
+
+    assert_eq!(insert_and_count(&mut pool.clone(), "pool").await?, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_query_options_through_dyn_executor() -> anyhow::Result<()> {
+    use sqlx::DynExecutor;
+
+    let mut conn = new::<Sqlite>().await?;
+    conn.clear_cached_statements().await?;
+
+    {
+        let executor: &mut dyn DynExecutor<Sqlite> = &mut conn;
+
+        let value: i32 = sqlx::query_scalar("SELECT ?")
+            .bind(1_i32)
+            .persistent(false)
+            .fetch_one(&mut *executor)
+            .await?;
+        assert_eq!(value, 1);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    {
+        let executor: &mut dyn DynExecutor<Sqlite> = &mut conn;
+
+        sqlx::query("SELECT ?")
+            .bind(1_i32)
+            .fetch_one(&mut *executor)
+            .await?;
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_builds_rows_from_values() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteValue;
+    use sqlx::FromRow;
+
+    #[derive(FromRow)]
+    struct Account {
+        id: i64,
+        name: String,
+        score: f64,
+        email: Option<String>,
+        verified: bool,
+        avatar: Vec<u8>,
+    }
+
+    let row = SqliteRow::from_values([
+        ("id", SqliteValue::from_encodable(7_i64)),
+        ("name", SqliteValue::from_encodable("Alice")),
+        ("score", SqliteValue::from_encodable(0.5_f64)),
+        ("email", SqliteValue::from_encodable(None::<String>)),
+        ("verified", SqliteValue::from_encodable(true)),
+        ("avatar", SqliteValue::from_encodable(vec![1_u8, 2, 3])),
+    ]);
+
+    assert_eq!(row.columns().len(), 6);
+    assert_eq!(row.column(1).name(), "name");
+    assert_eq!(row.column(4).type_info().name(), "BOOLEAN");
+
+    let account = Account::from_row(&row)?;
+
+    assert_eq!(account.id, 7);
+    assert_eq!(account.name, "Alice");
+    assert_eq!(account.score, 0.5);
+    assert_eq!(account.email, None);
+    assert!(account.verified);
+    assert_eq!(account.avatar, [1, 2, 3]);
+
+    assert!(row.try_get::<String, _>("id").is_err());
+    assert!(matches!(
+        row.try_get::<i64, _>("missing"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
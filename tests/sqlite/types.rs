@@ -1,5 +1,6 @@
 extern crate time_ as time;
 
+use sqlx::sqlite::types::Iso8601;
 use sqlx::sqlite::{Sqlite, SqliteRow};
 use sqlx_core::executor::Executor;
 use sqlx_core::row::Row;
@@ -184,6 +185,121 @@ mod git2 {
     ));
 }
 
+// POINT(1 2)
+#[cfg(feature = "geometry")]
+test_type!(geometry<sqlx::types::Geometry>(Sqlite,
+    "x'0101000000000000000000f03f0000000000000040'"
+        == sqlx::types::Geometry::from_wkb(
+            b"\x01\x01\0\0\0\0\0\0\0\0\0\xf0\x3f\0\0\0\0\0\0\0\x40".to_vec()
+        ).unwrap()
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(Sqlite,
+    "'127.0.0.1/32'"
+        == "127.0.0.1"
+            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+            .unwrap(),
+    "'8.8.8.8/24'"
+        == "8.8.8.8/24"
+            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+            .unwrap(),
+    "'2001:4f8:3:ba::/64'"
+        == "2001:4f8:3:ba::/64"
+            .parse::<sqlx::types::ipnetwork::IpNetwork>()
+            .unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipaddr<std::net::IpAddr>(Sqlite,
+    "'127.0.0.1'" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'::ffff:1.2.3.0'" == "::ffff:1.2.3.0".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+#[sqlx_macros::test]
+async fn it_rejects_networks_as_ipaddr() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query("SELECT '10.0.0.0/8', '10.0.0.1/32', 'not an address'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.try_get::<std::net::IpAddr, _>(0).is_err());
+    assert_eq!(
+        row.try_get::<std::net::IpAddr, _>(1)?,
+        "10.0.0.1".parse::<std::net::IpAddr>()?
+    );
+    assert!(row.try_get::<std::net::IpAddr, _>(2).is_err());
+    assert!(row
+        .try_get::<sqlx::types::ipnetwork::IpNetwork, _>(2)
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "mac_address")]
+test_type!(mac_address<sqlx::types::mac_address::MacAddress>(Sqlite,
+    "'00:01:02:03:04:05'"
+        == "00:01:02:03:04:05"
+            .parse::<sqlx::types::mac_address::MacAddress>()
+            .unwrap()
+));
+
+test_type!(duration<std::time::Duration>(Sqlite,
+    "0" == std::time::Duration::ZERO,
+    "1500000" == std::time::Duration::from_millis(1_500),
+));
+
+#[cfg(feature = "chrono")]
+test_type!(chrono_duration<sqlx::types::chrono::Duration>(Sqlite,
+    "-3600000000" == sqlx::types::chrono::Duration::hours(-1),
+));
+
+test_type!(iso8601_duration<Iso8601<std::time::Duration>>(Sqlite,
+    "'PT0S'" == Iso8601(std::time::Duration::ZERO),
+    "'PT1H30M'" == Iso8601(std::time::Duration::from_secs(5_400)),
+    "'PT25H0.000001S'" == Iso8601(std::time::Duration::new(90_000, 1_000)),
+));
+
+#[cfg(feature = "chrono")]
+test_type!(iso8601_chrono_duration<Iso8601<sqlx::types::chrono::Duration>>(Sqlite,
+    "'-PT1M30S'" == Iso8601(sqlx::types::chrono::Duration::seconds(-90)),
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_durations_from_integers_and_iso8601() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    for (sql, expected) in [
+        ("SELECT 1500000", Duration::from_millis(1_500)),
+        ("SELECT 'PT1.5S'", Duration::from_millis(1_500)),
+        ("SELECT 'P1DT2H'", Duration::from_secs(26 * 3_600)),
+        ("SELECT 'P1W'", Duration::from_secs(7 * 86_400)),
+        ("SELECT 'PT0,25S'", Duration::from_millis(250)),
+    ] {
+        let duration: Duration = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+        assert_eq!(duration, expected, "{sql}");
+    }
+
+    for sql in [
+        "SELECT 'P1M'",
+        "SELECT 'P1Y'",
+        "SELECT 'PT'",
+        "SELECT 'PT1'",
+        "SELECT 'P1H'",
+        "SELECT '1H'",
+        "SELECT -1",
+    ] {
+        let result: Result<Duration, _> = sqlx::query_scalar(sql).fetch_one(&mut conn).await;
+        assert!(result.is_err(), "{sql}");
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "uuid")]
 test_type!(uuid<sqlx::types::Uuid>(Sqlite,
     "x'b731678f636f4135bc6f19440c13bd19'"
@@ -192,6 +308,33 @@ test_type!(uuid<sqlx::types::Uuid>(Sqlite,
         == sqlx::types::Uuid::parse_str("00000000-0000-0000-0000-000000000000").unwrap()
 ));
 
+#[cfg(feature = "uuid")]
+#[sqlx_macros::test]
+async fn it_stores_uuids_as_16_byte_blobs() -> anyhow::Result<()> {
+    use sqlx::types::Uuid;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id UUID PRIMARY KEY NOT NULL)")
+        .await?;
+
+    let id = Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19")?;
+    sqlx::query("INSERT INTO users VALUES (?)")
+        .bind(id)
+        .execute(&mut conn)
+        .await?;
+
+    let (stored, kind, len): (Uuid, String, i64) =
+        sqlx::query_as("SELECT id, typeof(id), length(id) FROM users")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(stored, id);
+    assert_eq!(kind, "blob");
+    assert_eq!(len, 16);
+
+    Ok(())
+}
+
 #[cfg(feature = "uuid")]
 test_type!(uuid_hyphenated<sqlx::types::uuid::fmt::Hyphenated>(Sqlite,
     "'b731678f-636f-4135-bc6f-19440c13bd19'"
@@ -250,3 +393,124 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+#[cfg(all(feature = "zstd", feature = "lz4"))]
+#[sqlx_macros::test]
+async fn test_compressed_adapter() -> anyhow::Result<()> {
+    use sqlx::types::{Compressed, Lz4, Zstd};
+
+    #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+    struct Event {
+        payload: Compressed<String, Zstd>,
+        trace: Compressed<Vec<u8>, Lz4>,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE events (payload BLOB NOT NULL, trace BLOB NOT NULL)")
+        .await?;
+
+    let payload = r#"{"level":"info","message":"user logged in"}"#.repeat(100);
+    let trace = b"frame ".repeat(100);
+
+    sqlx::query("INSERT INTO events (payload, trace) VALUES (?, ?)")
+        .bind(Compressed::<_, Zstd>::new(payload.clone()))
+        .bind(Compressed::<_, Lz4>::new(trace.clone()))
+        .execute(&mut conn)
+        .await?;
+
+    let event: Event = sqlx::query_as("SELECT payload, trace FROM events")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(*event.payload, payload);
+    assert_eq!(*event.trace, trace);
+
+    let stored: i64 = sqlx::query_scalar("SELECT length(payload) FROM events")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(stored < payload.len() as i64 / 10);
+
+    Ok(())
+}
+
+#[cfg(feature = "encryption")]
+#[sqlx_macros::test]
+async fn test_encrypted_adapter() -> anyhow::Result<()> {
+    use sqlx::types::{Encrypted, KeyProvider};
+
+    struct TestKey;
+
+    impl KeyProvider for TestKey {
+        fn key() -> [u8; 32] {
+            *b"an example very very secret key."
+        }
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, email BLOB NOT NULL)")
+        .await?;
+
+    let email = "alice@example.com".to_owned();
+
+    for id in [1_i64, 2] {
+        sqlx::query("INSERT INTO users (id, email) VALUES (?, ?)")
+            .bind(id)
+            .bind(Encrypted::<_, TestKey>::seal(&email, "users.email", id.to_be_bytes()).unwrap())
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let stored: Vec<u8> = sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(!stored
+        .windows(email.len())
+        .any(|window| window == email.as_bytes()));
+
+    let (id, decrypted): (i64, Encrypted<String, TestKey>) =
+        sqlx::query_as("SELECT id, email FROM users WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(
+        decrypted.open("users.email", id.to_be_bytes()).unwrap(),
+        email
+    );
+
+    // a ciphertext copied into another row does not open
+    conn.execute("UPDATE users SET email = (SELECT email FROM users WHERE id = 1) WHERE id = 2")
+        .await?;
+
+    let copied: Encrypted<String, TestKey> =
+        sqlx::query_scalar("SELECT email FROM users WHERE id = 2")
+            .fetch_one(&mut conn)
+            .await?;
+    assert!(copied.open("users.email", 2_i64.to_be_bytes()).is_err());
+
+    struct OtherKey;
+
+    impl KeyProvider for OtherKey {
+        fn key() -> [u8; 32] {
+            *b"another key, just as secret too."
+        }
+    }
+
+    let wrong_key: Encrypted<String, OtherKey> =
+        sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert!(wrong_key.open("users.email", 1_i64.to_be_bytes()).is_err());
+
+    // flip a bit of the ciphertext
+    let mut tampered = stored;
+    *tampered.last_mut().unwrap() ^= 1;
+
+    let tampered: Encrypted<String, TestKey> = sqlx::query_scalar("SELECT ?")
+        .bind(tampered)
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(tampered.open("users.email", 1_i64.to_be_bytes()).is_err());
+
+    Ok(())
+}